@@ -1,12 +1,35 @@
 use eframe::egui;
 use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::Duration;
 
-use dafer_utils::data_loader;
+use dafer_utils::analysis::{self, ColumnRole};
+use dafer_utils::config::AppConfig;
+use dafer_utils::data_loader::{self, ColumnStats};
+use dafer_utils::datasource::DataSource;
+use dafer_utils::display;
+use dafer_utils::operations::Operation;
+use dafer_utils::persistence;
 use dafer_utils::query_engine;
-use polars::prelude::AnyValue;
+use polars::prelude::{AnyValue, DataFrame};
 
-use crate::state::AppState;
+use crate::state::{AppState, StatsResult};
+use crate::ui::crash::{crash_report, crash_ui};
 use crate::ui::main_ui::main_ui;
+use crate::ui::visualize;
+
+/// Minimum interval between session autosaves (debounce).
+const AUTOSAVE_INTERVAL_SECS: f64 = 5.0;
+
+/// Cached table strings are cut to this many chars (plus "…"). Full values are
+/// read from the preview DataFrame on demand (`AppState::full_cell`).
+pub(crate) const CELL_CACHE_MAX_CHARS: usize = 256;
+
+/// Read-only viewer build (`viewer` feature): files can be opened, browsed and
+/// plotted, but nothing is exported or saved to disk.
+pub(crate) const VIEWER: bool = cfg!(feature = "viewer");
 
 pub struct MyApp {
     pub state: AppState,
@@ -14,9 +37,11 @@ pub struct MyApp {
 
 impl Default for MyApp {
     fn default() -> Self {
-        Self {
-            state: AppState::default(),
-        }
+        let mut state = AppState::default();
+        state.config = AppConfig::load();
+        // A leftover recovery file means the previous session didn't exit cleanly
+        state.pending_recovery = persistence::load_recovery().filter(|w| w.state.source.is_some());
+        Self { state }
     }
 }
 
@@ -27,42 +52,99 @@ impl eframe::App for MyApp {
         // On-demand repaint: only repaint when UI interaction happens.
         // This eliminates the continuous 90fps loop that made tables slow.
 
-        // Recompute preview when pipeline changes (once per dirty flag)
-        if self.state.preview_dirty {
-            self.recompute_preview();
+        // After a caught panic, show the error screen until the user retries
+        if self.state.crash.is_some() {
+            crash_ui(ctx, &mut self.state);
+            return;
         }
 
-        // Rebuild table string cache when sort or data changes
-        if self.state.table_cache_dirty {
-            self.rebuild_table_cache();
+        let engine = self
+            .state
+            .pipeline_engine
+            .unwrap_or(self.state.config.performance.engine);
+        query_engine::set_engine(engine);
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            // Recompute preview when pipeline changes (once per dirty flag)
+            if self.state.preview_dirty {
+                self.recompute_preview(ctx);
+            }
+            self.poll_stats_job();
+
+            // Rebuild table string cache when sort or data changes
+            if self.state.table_cache_dirty {
+                self.rebuild_table_cache();
+            }
+
+            main_ui(ctx, &mut self.state);
+        }));
+        if let Err(payload) = result {
+            self.state.crash = Some(crash_report(&self.state, &*payload));
+            ctx.request_repaint();
+            return;
         }
 
-        main_ui(ctx, &mut self.state);
+        let view = self.state.view_settings();
+        let now = ctx.input(|i| i.time);
+        self.state.view_history.track(&view, now);
+
+        // Clean exit removes the recovery file; otherwise keep it up to date
+        if ctx.input(|i| i.viewport().close_requested()) {
+            persistence::clear_recovery();
+        } else {
+            self.autosave(ctx);
+        }
     }
 }
 
 impl MyApp {
+    /// Debounced autosave of the session to the recovery file.
+    /// Only writes when the encoded session differs from the last write.
+    fn autosave(&mut self, ctx: &egui::Context) {
+        let state = &mut self.state;
+        // Don't overwrite a pending recovery before the user decided on it
+        if state.pending_recovery.is_some() || state.source.is_none() {
+            return;
+        }
+
+        let now = ctx.input(|i| i.time);
+        if now - state.last_autosave_time < AUTOSAVE_INTERVAL_SECS {
+            ctx.request_repaint_after(Duration::from_secs_f64(AUTOSAVE_INTERVAL_SECS));
+            return;
+        }
+        state.last_autosave_time = now;
+
+        let Ok(bytes) = state.workspace().to_bytes() else {
+            return;
+        };
+        if bytes != state.last_autosave_bytes && persistence::write_recovery(&bytes).is_ok() {
+            state.last_autosave_bytes = bytes;
+        }
+    }
+
     /// Recompute the preview DataFrame from the current source + operations.
-    fn recompute_preview(&mut self) {
+    /// The first rows are shown right away; statistics over the full result
+    /// follow from a background job (see `spawn_stats_job`).
+    fn recompute_preview(&mut self, ctx: &egui::Context) {
         let state = &mut self.state;
 
         if let Some(source) = &state.source {
             match query_engine::preview(source, &state.operations, state.preview_rows) {
                 Ok(df) => {
-                    // Auto-detect numeric String columns on first load
+                    // Auto-detect numeric String columns on first load and
+                    // ask before casting (IDs with leading zeros must stay text)
                     if !state.auto_cast_detected {
-                        let numeric_cols = data_loader::detect_numeric_string_columns(&df);
-                        if !numeric_cols.is_empty() {
-                            if let Some(ref mut src) = state.source {
-                                src.auto_numeric_cols = numeric_cols;
-                            }
-                            state.auto_cast_detected = true;
-                            // Re-collect with casts applied next frame
-                            state.preview_dirty = true;
-                            state.status = "Auto-detected numeric columns, re-loading...".into();
-                            return;
-                        }
                         state.auto_cast_detected = true;
+                        state.pending_auto_cast = data_loader::detect_numeric_string_columns(&df)
+                            .into_iter()
+                            .filter(|(name, _)| !source.auto_cast_declined.contains(name))
+                            .map(|(name, kind)| (name, Some(kind)))
+                            .collect();
+                        if !state.pending_auto_cast.is_empty() {
+                            state
+                                .notifications
+                                .info("Numeric-looking text columns detected");
+                        }
                     }
 
                     // Cache column metadata
@@ -74,45 +156,104 @@ impl MyApp {
                     state.column_dtypes =
                         df.dtypes().iter().map(|d| format!("{}", d)).collect();
                     state.row_count = Some(df.height());
+                    state.source_schema =
+                        query_engine::schema_info(source, &[]).unwrap_or_default();
 
-                    // Compute stats and plot data from the FULL dataset
-                    match query_engine::execute(source, &state.operations) {
-                        Ok(full) => {
-                            state.column_stats = data_loader::column_stats(&full);
-                            // Format min/max floats to 4 decimal places for display
-                            for stat in &mut state.column_stats {
-                                stat.min = stat.min.take().map(|s| format_stat_float(&s));
-                                stat.max = stat.max.take().map(|s| format_stat_float(&s));
-                            }
-                            state.full_df = Some(full);
-                        }
-                        Err(_) => {
-                            state.column_stats = data_loader::column_stats(&df);
-                            for stat in &mut state.column_stats {
-                                stat.min = stat.min.take().map(|s| format_stat_float(&s));
-                                stat.max = stat.max.take().map(|s| format_stat_float(&s));
-                            }
-                            state.full_df = None;
-                        }
+                    // Stats of columns whose lineage did not change since an
+                    // earlier run are reused instead of recomputed
+                    let fingerprints = data_loader::stats_fingerprints(
+                        source,
+                        &state.operations,
+                        &state.column_names,
+                    );
+                    let cached: HashMap<String, ColumnStats> = fingerprints
+                        .iter()
+                        .filter_map(|(name, fingerprint)| {
+                            let stats = state.stats_cache.get(*fingerprint, name)?;
+                            Some((name.clone(), stats.clone()))
+                        })
+                        .collect();
+
+                    // Provisional stats (cached, or from the preview rows) until
+                    // the job over the FULL dataset (or a sample in "fast stats"
+                    // mode) reports
+                    state.column_stats = if cached.len() == state.column_names.len() {
+                        state
+                            .column_names
+                            .iter()
+                            .filter_map(|name| cached.get(name).cloned())
+                            .collect()
+                    } else {
+                        data_loader::column_stats(&df)
+                    };
+                    state.column_roles = analysis::infer_roles(&df);
+                    state.full_df = None;
+                    finish_stats(
+                        &mut state.column_stats,
+                        &mut state.column_roles,
+                        &state.role_overrides,
+                    );
+                    state.stats_job = Some(spawn_stats_job(
+                        ctx.clone(),
+                        source.clone(),
+                        state.operations.clone(),
+                        cached,
+                        fingerprints,
+                        state.time_steps,
+                    ));
+
+                    state.scan_report = Some(
+                        query_engine::scan_report(source, &state.operations)
+                            .map_err(|e| e.to_string()),
+                    );
+
+                    #[cfg(feature = "ipc-server")]
+                    if let Some(server) = &state.ipc_server {
+                        server.publish(source, &state.operations);
                     }
 
+                    // Per-step timing badges follow from the stats job
+                    state.step_timings = None;
+
                     if state.select_checks.len() != state.column_names.len() {
                         state.select_checks = vec![true; state.column_names.len()];
                     }
 
-                    let total_rows = state.full_df.as_ref().map(|f| f.height()).unwrap_or(df.height());
                     state.preview_df = Some(df);
                     state.plot_dirty = true;
+                    state.dashboard_dirty = true;
+                    state.pivot_computed = None;
                     state.table_cache_dirty = true;
-                    state.status = format!(
-                        "Showing {} of {} rows x {} columns",
+                    state.preview_error = None;
+                    state.step_failure = None;
+                    state.notifications.quiet(format!(
+                        "Showing {} rows x {} columns, computing statistics...",
                         state.row_count.unwrap_or(0),
-                        total_rows,
                         state.column_names.len()
-                    );
+                    ));
                 }
                 Err(e) => {
-                    state.status = format!("Preview error: {}", e);
+                    // Trace the error back to the step (and column) that caused it
+                    state.step_failure = query_engine::diagnose_failure(
+                        source,
+                        &state.operations,
+                        state.preview_rows,
+                    );
+                    match &state.step_failure {
+                        Some(failure) => {
+                            state.notifications.error(failure.to_string());
+                            let hint = failure
+                                .suggestion
+                                .as_ref()
+                                .map(|s| format!("\nSuggestion: {}", s))
+                                .unwrap_or_default();
+                            state.preview_error = Some(format!("{}{}\n\n{:?}", failure, hint, e));
+                        }
+                        None => {
+                            state.notifications.error(format!("Preview error: {}", e));
+                            state.preview_error = Some(format!("{:?}", e));
+                        }
+                    }
                     state.preview_df = None;
                     state.cached_cell_strings.clear();
                     state.cached_header_names.clear();
@@ -121,16 +262,79 @@ impl MyApp {
         } else {
             state.preview_df = None;
             state.full_df = None;
+            state.stats_job = None;
             state.column_names.clear();
             state.column_dtypes.clear();
             state.column_stats.clear();
             state.row_count = None;
             state.cached_cell_strings.clear();
             state.cached_header_names.clear();
-            state.status = "No file loaded".to_string();
+            state.notifications.warn("No file loaded");
         }
 
         state.preview_dirty = false;
+        state.filter_observed = None;
+        state.dry_run_rows = None;
+        state.dry_run = None;
+        state.cast_check = None;
+        state.measure_memory();
+    }
+
+    /// Apply the background statistics once they arrive.
+    fn poll_stats_job(&mut self) {
+        let state = &mut self.state;
+        let Some(job) = &state.stats_job else {
+            return;
+        };
+        let result = match job.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                state.stats_job = None;
+                state
+                    .notifications
+                    .error("Statistics job stopped unexpectedly");
+                return;
+            }
+        };
+        state.stats_job = None;
+        state.step_timings = result.timings;
+
+        let Some(full) = result.full else {
+            // Keep the provisional stats from the preview rows
+            state.stats_sample = None;
+            return;
+        };
+        for stats in &result.stats {
+            if let Some(fingerprint) = result.fingerprints.get(&stats.name) {
+                state.stats_cache.insert(*fingerprint, stats.clone());
+            }
+        }
+        state.stats_sample = result.sample;
+        state.column_stats = result.stats;
+        state.column_roles = result.roles;
+        let total_rows = match result.sample {
+            Some(info) => info.total_rows,
+            None => full.height(),
+        };
+        state.full_df = Some(full);
+        state.measure_memory();
+        finish_stats(
+            &mut state.column_stats,
+            &mut state.column_roles,
+            &state.role_overrides,
+        );
+        if state.plot_x.is_empty() && state.plot_y_columns.is_empty() {
+            visualize::suggest_plot_defaults(state);
+        }
+        state.plot_dirty = true;
+        state.dashboard_dirty = true;
+        state.notifications.quiet(format!(
+            "Showing {} of {} rows x {} columns",
+            state.row_count.unwrap_or(0),
+            total_rows,
+            state.column_names.len()
+        ));
     }
 
     /// Build the pre-computed string grid from the preview DataFrame.
@@ -142,12 +346,29 @@ impl MyApp {
         let Some(ref df) = state.preview_df else {
             state.cached_cell_strings.clear();
             state.cached_header_names.clear();
+            state.cached_column_ranges.clear();
+            state.cached_row_order.clear();
             return;
         };
 
-        let n_rows = df.height();
         let n_cols = df.width();
 
+        // Quick filters narrow the preview rows (DataFrame indices) shown
+        let quick_filters = state.quick_filter_ops();
+        state.quick_filter_error = None;
+        let rows: Vec<usize> = if quick_filters.is_empty() {
+            (0..df.height()).collect()
+        } else {
+            match quick_filter_rows(df, &quick_filters) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    state.quick_filter_error = Some(e.to_string());
+                    (0..df.height()).collect()
+                }
+            }
+        };
+        let n_rows = rows.len();
+
         // Build header names
         state.cached_header_names = df
             .get_column_names()
@@ -155,22 +376,35 @@ impl MyApp {
             .map(|s| s.to_string())
             .collect();
 
-        // Build cell strings [row][col]
+        // Build cell strings [row][col]; ID columns never use exponent notation
         let columns = df.get_columns();
+        let is_id: Vec<bool> = state
+            .cached_header_names
+            .iter()
+            .map(|name| state.column_roles.get(name) == Some(&ColumnRole::Id))
+            .collect();
         let mut grid: Vec<Vec<String>> = Vec::with_capacity(n_rows);
-        for row_idx in 0..n_rows {
+        for &row_idx in &rows {
             let mut row_strs = Vec::with_capacity(n_cols);
-            for col_s in columns {
-                let val = col_s
-                    .get(row_idx)
-                    .map(|v| format_cell_value(&v))
-                    .unwrap_or_default();
-                row_strs.push(val);
+            for (col_idx, col_s) in columns.iter().enumerate() {
+                let format = if is_id[col_idx] {
+                    format_id_value
+                } else {
+                    format_cell_value
+                };
+                let val = col_s.get(row_idx).map(|v| format(&v)).unwrap_or_default();
+                row_strs.push(truncate_cell(val));
             }
             grid.push(row_strs);
         }
 
-        // Apply visual sort if active
+        // Numeric range per column (used by color-scale formatting)
+        state.cached_column_ranges = (0..n_cols)
+            .map(|col_idx| numeric_range(grid.iter().map(|r| r[col_idx].as_str())))
+            .collect();
+
+        // Apply visual sort if active (keeping track of each row's DataFrame index)
+        let mut order: Vec<usize> = (0..n_rows).collect();
         if let Some(ref sort_col) = state.sort_column {
             if let Some(col_idx) = state
                 .cached_header_names
@@ -178,32 +412,58 @@ impl MyApp {
                 .position(|n| n == sort_col)
             {
                 let descending = state.sort_descending;
-                grid.sort_by(|a, b| {
-                    let ord = natural_cmp(&a[col_idx], &b[col_idx]);
+                order.sort_by(|&a, &b| {
+                    let ord = natural_cmp(&grid[a][col_idx], &grid[b][col_idx]);
                     if descending {
                         ord.reverse()
                     } else {
                         ord
                     }
                 });
+                let mut unsorted: Vec<Option<Vec<String>>> = grid.into_iter().map(Some).collect();
+                grid = order.iter().filter_map(|&i| unsorted[i].take()).collect();
             }
         }
 
         state.cached_cell_strings = grid;
+        state.cached_row_order = order.into_iter().map(|i| rows[i]).collect();
     }
 }
 
+/// Name of the temporary row index column used to apply quick filters.
+const QUICK_FILTER_ROW: &str = "__quick_filter_row";
+
+/// Rows (DataFrame indices) of the preview passing the quick filters.
+fn quick_filter_rows(df: &DataFrame, filters: &[Operation]) -> anyhow::Result<Vec<usize>> {
+    let indexed = df
+        .with_row_index(QUICK_FILTER_ROW.into(), None)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let kept = query_engine::apply_to_frame(&indexed, filters)?;
+    let index = kept
+        .column(QUICK_FILTER_ROW)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok((0..index.len())
+        .filter_map(|i| index.get(i).ok()?.extract::<u64>())
+        .map(|i| i as usize)
+        .collect())
+}
+
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
+/// Bytes shown in the hex preview of a binary cell.
+const BINARY_PREVIEW_BYTES: usize = 16;
+
 /// Format an AnyValue for table display.
-/// Null → empty, NaN → "NaN", strings → unquoted.
-fn format_cell_value(v: &AnyValue) -> String {
+/// Null → empty, NaN → "NaN", strings → unquoted, binary → hex preview.
+pub(crate) fn format_cell_value(v: &AnyValue) -> String {
     match v {
         AnyValue::Null => String::new(),
         AnyValue::Float64(f) if f.is_nan() => "NaN".to_string(),
         AnyValue::Float32(f) if f.is_nan() => "NaN".to_string(),
         AnyValue::String(s) => s.to_string(),
         AnyValue::StringOwned(s) => s.to_string(),
+        AnyValue::Binary(b) => display::hex_preview(b, BINARY_PREVIEW_BYTES),
+        AnyValue::BinaryOwned(b) => display::hex_preview(b, BINARY_PREVIEW_BYTES),
         other => {
             let s = other.to_string();
             // Strip Polars quote artifacts
@@ -216,6 +476,25 @@ fn format_cell_value(v: &AnyValue) -> String {
     }
 }
 
+/// Format an ID cell: whole floats (IDs auto-cast to Float64) without
+/// decimals or exponent notation.
+fn format_id_value(v: &AnyValue) -> String {
+    match v {
+        AnyValue::Float64(f) if f.fract() == 0.0 => format!("{f:.0}"),
+        AnyValue::Float32(f) if f.fract() == 0.0 => format!("{f:.0}"),
+        other => format_cell_value(other),
+    }
+}
+
+/// Cap a cell string at `CELL_CACHE_MAX_CHARS`, marking the cut with "…".
+fn truncate_cell(mut s: String) -> String {
+    if let Some((idx, _)) = s.char_indices().nth(CELL_CACHE_MAX_CHARS) {
+        s.truncate(idx);
+        s.push('…');
+    }
+    s
+}
+
 /// Numeric-aware string comparison for natural sorting.
 fn natural_cmp(a: &str, b: &str) -> Ordering {
     match (a.parse::<f64>(), b.parse::<f64>()) {
@@ -224,6 +503,102 @@ fn natural_cmp(a: &str, b: &str) -> Ordering {
     }
 }
 
+/// Compute the (min, max) of the values that parse as finite numbers.
+fn numeric_range<'a>(values: impl Iterator<Item = &'a str>) -> Option<(f64, f64)> {
+    values
+        .filter_map(|s| s.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+        .fold(None, |acc, v| match acc {
+            None => Some((v, v)),
+            Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+        })
+}
+
+// ─── Background Statistics ────────────────────────────────────────────────────
+
+/// Collect the full pipeline result (or a sample) and its statistics on a
+/// background thread, skipping the columns in `cached`, then time each step
+/// if `time_steps` is set (it re-runs every pipeline prefix). A result for a
+/// superseded preview is dropped: its receiver is replaced, so the send fails.
+fn spawn_stats_job(
+    ctx: egui::Context,
+    source: DataSource,
+    operations: Vec<Operation>,
+    cached: HashMap<String, ColumnStats>,
+    fingerprints: HashMap<String, u64>,
+    time_steps: bool,
+) -> Receiver<StatsResult> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        // Guarded as a whole: the stats themselves run Polars on the full result
+        let result = query_engine::guarded(|| {
+            let (full, sample) = match source.stats_sample {
+                Some(n) => query_engine::sample(&source, &operations, n)
+                    .map(|(sample, info)| (sample, Some(info)))?,
+                None => (query_engine::execute(&source, &operations)?, None),
+            };
+            Ok(StatsResult {
+                stats: data_loader::column_stats_with(&full, cached),
+                roles: analysis::infer_roles(&full),
+                full: Some(full),
+                sample,
+                fingerprints,
+                timings: None,
+            })
+        });
+        let mut result = match result {
+            Ok(result) => result,
+            Err(_) => StatsResult {
+                full: None,
+                sample: None,
+                stats: Vec::new(),
+                roles: BTreeMap::new(),
+                fingerprints: HashMap::new(),
+                timings: None,
+            },
+        };
+        if time_steps {
+            result.timings = query_engine::time_steps(&source, &operations).ok();
+        }
+        if tx.send(result).is_ok() {
+            ctx.request_repaint();
+        }
+    });
+    rx
+}
+
+/// Apply role overrides and format min/max of freshly computed stats.
+fn finish_stats(
+    stats: &mut [ColumnStats],
+    roles: &mut BTreeMap<String, ColumnRole>,
+    overrides: &BTreeMap<String, ColumnRole>,
+) {
+    for (name, role) in overrides {
+        if let Some(current) = roles.get_mut(name) {
+            *current = *role;
+        }
+    }
+    // Format min/max floats to 4 decimal places for display
+    // (IDs as whole numbers)
+    for stat in stats {
+        let format = if roles.get(&stat.name) == Some(&ColumnRole::Id) {
+            format_id_float
+        } else {
+            format_stat_float
+        };
+        stat.min = stat.min.take().map(|s| format(&s));
+        stat.max = stat.max.take().map(|s| format(&s));
+    }
+}
+
+/// Format an ID stat value as a whole number.
+fn format_id_float(s: &str) -> String {
+    match s.parse::<f64>() {
+        Ok(f) if f.fract() == 0.0 => format!("{f:.0}"),
+        _ => s.to_string(),
+    }
+}
+
 /// Format a stat value: if it parses as a float, display with 4 decimal places.
 /// Otherwise return as-is (integers, strings, dates, etc.).
 fn format_stat_float(s: &str) -> String {
@@ -8,6 +8,10 @@ pub enum MainTab {
     LoadPreview,
     Modify,
     Visualize,
+    Split,
+    Dock,
+    Dashboard,
+    Explore,
 }
 
 impl MainTab {
@@ -16,11 +20,23 @@ impl MainTab {
             MainTab::LoadPreview => "📂",
             MainTab::Modify => "⛭",
             MainTab::Visualize => "📊",
+            MainTab::Split => "◫",
+            MainTab::Dock => "▦",
+            MainTab::Dashboard => "⊞",
+            MainTab::Explore => "🔍",
         }
     }
 
-    pub fn all() -> [MainTab; 3] {
-        [MainTab::LoadPreview, MainTab::Modify, MainTab::Visualize]
+    pub fn all() -> [MainTab; 7] {
+        [
+            MainTab::LoadPreview,
+            MainTab::Modify,
+            MainTab::Visualize,
+            MainTab::Split,
+            MainTab::Dock,
+            MainTab::Dashboard,
+            MainTab::Explore,
+        ]
     }
 }
 
@@ -44,37 +60,89 @@ impl fmt::Display for FileType {
 
 // ─── Plot Type ────────────────────────────────────────────────────────────────
 
+pub use dafer_utils::plot::PlotType;
+
+// ─── Plot Annotation Kind ─────────────────────────────────────────────────────
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum PlotType {
+pub enum AnnotationKind {
     #[default]
-    Scatter,
-    Line,
-    Bar,
-    Histogram,
+    HLine,
+    VLine,
+    Text,
+}
+
+impl AnnotationKind {
+    pub fn all() -> &'static [AnnotationKind] {
+        &[
+            AnnotationKind::HLine,
+            AnnotationKind::VLine,
+            AnnotationKind::Text,
+        ]
+    }
 }
 
-impl PlotType {
-    pub fn all() -> &'static [PlotType] {
-        &[PlotType::Scatter, PlotType::Line, PlotType::Bar, PlotType::Histogram]
+impl fmt::Display for AnnotationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnnotationKind::HLine => write!(f, "Horizontal line"),
+            AnnotationKind::VLine => write!(f, "Vertical line"),
+            AnnotationKind::Text => write!(f, "Text"),
+        }
     }
+}
+
+// ─── Plot Analysis ────────────────────────────────────────────────────────────
+
+/// Transform applied to each plotted series (Visualize tab only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlotAnalysis {
+    #[default]
+    None,
+    /// FFT power spectrum over frequency.
+    Spectrum,
+    /// Trend, seasonal and residual series.
+    Decomposition,
+    /// Mean of Y per X bin, with a spread band.
+    Binned,
+}
 
-    /// Returns true if this plot type needs a Y column.
-    pub fn needs_y(&self) -> bool {
-        !matches!(self, PlotType::Histogram)
+impl PlotAnalysis {
+    pub fn all() -> &'static [PlotAnalysis] {
+        &[
+            PlotAnalysis::None,
+            PlotAnalysis::Spectrum,
+            PlotAnalysis::Decomposition,
+            PlotAnalysis::Binned,
+        ]
     }
 }
 
-impl fmt::Display for PlotType {
+impl fmt::Display for PlotAnalysis {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            PlotType::Scatter => write!(f, "Scatter"),
-            PlotType::Line => write!(f, "Line"),
-            PlotType::Bar => write!(f, "Bar"),
-            PlotType::Histogram => write!(f, "Histogram"),
+            PlotAnalysis::None => write!(f, "None"),
+            PlotAnalysis::Spectrum => write!(f, "Power spectrum"),
+            PlotAnalysis::Decomposition => write!(f, "Decomposition"),
+            PlotAnalysis::Binned => write!(f, "Binned mean"),
         }
     }
 }
 
+// ─── Pipeline Edit ────────────────────────────────────────────────────────────
+
+/// A destructive pipeline change. Confirmed in a dialog while the pipeline is locked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineEdit {
+    /// Remove the operation at this index.
+    Remove(usize),
+    Clear,
+    /// Restore the source and pipeline of the last save/load.
+    Revert,
+    /// Replace the pipeline with the snapshot at this index.
+    Snapshot(usize),
+}
+
 // ─── Export Format ────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -82,6 +150,8 @@ pub enum ExportFormat {
     #[default]
     Csv,
     Parquet,
+    /// Both files from one pipeline run.
+    CsvAndParquet,
 }
 
 impl fmt::Display for ExportFormat {
@@ -89,6 +159,7 @@ impl fmt::Display for ExportFormat {
         match self {
             ExportFormat::Csv => write!(f, "CSV"),
             ExportFormat::Parquet => write!(f, "Parquet"),
+            ExportFormat::CsvAndParquet => write!(f, "CSV + Parquet"),
         }
     }
 }
@@ -0,0 +1,96 @@
+use dafer_utils::display::DisplayConfig;
+use dafer_utils::plot::PlotConfig;
+
+// ─── Undo History ─────────────────────────────────────────────────────────────
+//
+// Undo for state that is edited in many places (plot setup, display formats,
+// table sort). Instead of every widget recording a command, the app compares a
+// snapshot once per frame and records the previous value when it changed.
+
+/// Changes closer together than this merge into one undo step (typing, dragging).
+const COALESCE_SECS: f64 = 1.0;
+
+/// Undo/redo stacks of snapshots of some piece of state.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    /// Last value seen by `track`.
+    current: Option<T>,
+    last_change: f64,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            current: None,
+            last_change: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    /// Record `value` if it differs from the last tracked value.
+    /// `time` is in seconds and only used to merge rapid changes.
+    pub fn track(&mut self, value: &T, time: f64) {
+        let Some(current) = &self.current else {
+            self.current = Some(value.clone());
+            return;
+        };
+        if current == value {
+            return;
+        }
+        if time - self.last_change > COALESCE_SECS {
+            self.undo.push(current.clone());
+        }
+        self.redo.clear();
+        self.current = Some(value.clone());
+        self.last_change = time;
+    }
+
+    /// Step back: returns the value to restore, if any.
+    pub fn undo(&mut self) -> Option<T> {
+        let previous = self.undo.pop()?;
+        if let Some(current) = self.current.replace(previous.clone()) {
+            self.redo.push(current);
+        }
+        self.last_change = f64::NEG_INFINITY;
+        Some(previous)
+    }
+
+    /// Step forward again: returns the value to restore, if any.
+    pub fn redo(&mut self) -> Option<T> {
+        let next = self.redo.pop()?;
+        if let Some(current) = self.current.replace(next.clone()) {
+            self.undo.push(current);
+        }
+        self.last_change = f64::NEG_INFINITY;
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all steps, e.g. when a new file is opened.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ─── View Settings ────────────────────────────────────────────────────────────
+
+/// The undoable view state outside the pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewSettings {
+    pub plot: PlotConfig,
+    pub display: DisplayConfig,
+    pub sort_column: Option<String>,
+    pub sort_descending: bool,
+}
@@ -1,12 +1,23 @@
 mod app;
 mod enums;
+mod history;
+mod notifications;
 mod state;
 mod ui;
 
 fn main() -> Result<(), eframe::Error> {
+    ui::crash::install_panic_hook();
+    let config = dafer_utils::config::AppConfig::load();
+    // SAFETY: no other thread has been started yet
+    unsafe { config.performance.apply_env() };
     let options = eframe::NativeOptions::default();
+    let title = if app::VIEWER {
+        "Data handling utils (viewer)"
+    } else {
+        "Data handling utils"
+    };
     eframe::run_native(
-        "Data handling utils",
+        title,
         options,
         Box::new(|_cc| Ok(Box::new(app::MyApp::default()))),
     )
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+// ─── Notifications ────────────────────────────────────────────────────────────
+//
+// Every status message becomes a notification: shown as a toast for a few
+// seconds, kept in a history drawer, and the latest one stays in the status bar.
+
+/// Toasts disappear after this long (errors stay twice as long).
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Oldest entries are dropped beyond this many.
+const MAX_HISTORY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub level: Level,
+    pub message: String,
+    pub created: Instant,
+    pub time: DateTime<Local>,
+    /// False for routine messages that only go to the status bar and history.
+    pub toast: bool,
+}
+
+impl Notification {
+    /// Still shown as a toast.
+    pub fn is_active(&self) -> bool {
+        if !self.toast {
+            return false;
+        }
+        let duration = match self.level {
+            Level::Error => TOAST_DURATION * 2,
+            Level::Info | Level::Warn => TOAST_DURATION,
+        };
+        self.created.elapsed() < duration
+    }
+}
+
+/// Notification history, newest last.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    history: VecDeque<Notification>,
+    /// Toasts closed by the user before they expired (by creation time).
+    dismissed: Vec<Instant>,
+}
+
+impl Notifications {
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(Level::Info, message.into());
+    }
+
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.push(Level::Warn, message.into());
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(Level::Error, message.into());
+    }
+
+    /// Info for the status bar and history only (no toast).
+    pub fn quiet(&mut self, message: impl Into<String>) {
+        self.add(Level::Info, message.into(), false);
+    }
+
+    pub fn push(&mut self, level: Level, message: String) {
+        self.add(level, message, true);
+    }
+
+    fn add(&mut self, level: Level, message: String, toast: bool) {
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(Notification {
+            level,
+            message,
+            created: Instant::now(),
+            time: Local::now(),
+            toast,
+        });
+    }
+
+    /// Most recent notification, shown in the status bar.
+    pub fn latest(&self) -> Option<&Notification> {
+        self.history.back()
+    }
+
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    /// Notifications currently shown as toasts, oldest first.
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.history
+            .iter()
+            .filter(|n| n.is_active() && !self.dismissed.contains(&n.created))
+    }
+
+    pub fn dismiss(&mut self, created: Instant) {
+        self.dismissed.retain(|d| d.elapsed() < TOAST_DURATION * 2);
+        self.dismissed.push(created);
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.dismissed.clear();
+    }
+}
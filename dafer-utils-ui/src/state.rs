@@ -1,10 +1,90 @@
+use chrono::NaiveDate;
 use polars::prelude::DataFrame;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
 
-use dafer_utils::data_loader::ColumnStats;
-use dafer_utils::datasource::DataSource;
-use dafer_utils::operations::{DTypeTag, FillNullStrategy, FilterOp, Operation, OperationType};
+use dafer_utils::action_log::{Action, ActionLog, LogEntry};
+use dafer_utils::analysis::{
+    BinSpread, ColumnRole, CrosstabAgg, LinearFit, MixedTypeReport, PivotSpec,
+};
+use dafer_utils::bundle::BundleOptions;
+use dafer_utils::config::AppConfig;
+use dafer_utils::data_loader::{ColumnStats, NumericKind, StatsCache};
+use dafer_utils::datasource::{CsvOptions, DataSource};
+use dafer_utils::display::DisplayConfig;
+use dafer_utils::execution::{ExportOptions, Provenance, SourceStatus};
+use dafer_utils::fixture::{Fixture, FixtureReport};
+use dafer_utils::memory::MemoryUsage;
+use dafer_utils::operations::{
+    self, DTypeTag, FillNullStrategy, FilterOp, FilterOptions, LiteralKind, NormalizeMethod,
+    Operation, OperationType, RoundMode, WindowFunction,
+};
+use dafer_utils::persistence::{
+    LastExport, PersistentState, PipelineSnapshot, SchemaDrift, UiLayout, Workspace,
+    WorkspaceSelection,
+};
+use dafer_utils::plot::{DashboardPlot, PlotAnnotation, PlotConfig};
+use dafer_utils::query_engine::{
+    self, CastRisk, CollectEngine, SampleInfo, ScanReport, StepFailure, StepTimings,
+};
+use dafer_utils::snapshot::{ResultSnapshot, SnapshotDiff};
+use dafer_utils::sql::SqlDialect;
 
-use crate::enums::{ExportFormat, MainTab, PlotType, Theme};
+use crate::enums::{
+    AnnotationKind, ExportFormat, MainTab, PipelineEdit, PlotAnalysis, PlotType, Theme,
+};
+use crate::history::{History, ViewSettings};
+use crate::notifications::Notifications;
+
+/// Render-ready data of one dashboard plot, rebuilt when the pipeline changes.
+#[derive(Default)]
+pub struct DashboardData {
+    /// Pipeline result with the plot's own filters applied.
+    pub df: Option<DataFrame>,
+    pub series: Vec<(String, Vec<[f64; 2]>)>,
+    pub x_is_datetime: bool,
+    pub error: Option<String>,
+}
+
+/// A saved pipeline waiting to be applied to the current source, with the
+/// user's mapping for the columns the source lacks.
+pub struct PendingMapping {
+    pub operations: Vec<Operation>,
+    /// (pipeline column, new column); empty target = leave unmapped.
+    pub mapping: Vec<(String, String)>,
+    pub available: Vec<String>,
+}
+
+/// Statistics over the full pipeline result (or a sample), computed on a
+/// background thread after the first rows are shown.
+pub struct StatsResult {
+    /// `None` if collecting failed; the stats then stay on the preview rows.
+    pub full: Option<DataFrame>,
+    pub sample: Option<SampleInfo>,
+    pub stats: Vec<ColumnStats>,
+    pub roles: BTreeMap<String, ColumnRole>,
+    /// Fingerprint of each column's stats, for `AppState::stats_cache`.
+    pub fingerprints: HashMap<String, u64>,
+    /// Per-step timings, if step timing is on.
+    pub timings: Option<StepTimings>,
+}
+
+/// Draft import options for the Raw Preview window, applied to the source on "Apply".
+pub struct RawPreview {
+    /// First lines of the file, unparsed.
+    pub lines: Vec<String>,
+    pub options: CsvOptions,
+}
+
+/// Rows behind a reported problem, from a transient (non-pipeline) query.
+pub struct DrillDown {
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Total matching rows (`rows` holds at most the first few hundred).
+    pub total: usize,
+}
 
 /// Central application state.
 ///
@@ -15,34 +95,106 @@ pub struct AppState {
     // ── Theme ──
     pub theme: Theme,
 
+    // ── Per-user settings (dock layout, ...) ──
+    pub config: AppConfig,
+
     // ── Navigation ──
     pub selected_tab: MainTab,
+    pub split_vertical: bool,
 
     // ── Data Core ──
     pub source: Option<DataSource>,
     pub operations: Vec<Operation>,
     pub redo_stack: Vec<Operation>,
+    /// Undo steps of plot setup, display formats and table sort.
+    pub view_history: History<ViewSettings>,
+    /// Named copies of the pipeline, saved with the workspace.
+    pub snapshots: Vec<PipelineSnapshot>,
+    pub snapshot_name: String,
+    /// Last export of the pipeline, saved with the workspace.
+    pub last_export: Option<LastExport>,
+    /// Source columns (name, dtype) before any operation, recorded in saved state.
+    pub source_schema: Vec<(String, String)>,
+
+    // ── Pipeline protection ──
+    pub pipeline_locked: bool,
+    pub pending_pipeline_edit: Option<PipelineEdit>,
+    /// Source + pipeline as of the last state/workspace save or load.
+    pub last_saved: Option<PersistentState>,
+
+    // ── Expected Output (fixture) ──
+    /// Expected output pinned to the pipeline; saved with it.
+    pub fixture: Option<Fixture>,
+    /// Rows pinned by "Pin first N rows".
+    pub fixture_rows: usize,
+    /// Result of the last verification, shown until closed.
+    pub fixture_report: Option<FixtureReport>,
+
+    // ── Result snapshots (A/B of pipeline edits) ──
+    /// Collected results kept under a name; not saved with the state.
+    pub result_snapshots: Vec<ResultSnapshot>,
+    pub result_snapshot_name: String,
+    /// Keep new snapshots in a temporary Parquet file instead of memory.
+    pub result_snapshot_spill: bool,
+    /// Last comparison (snapshot name, differences), shown until closed.
+    pub snapshot_diff: Option<(String, SnapshotDiff)>,
+
+    // ── Macros (grouped steps) ──
+    pub pipeline_selection: Vec<bool>,
+    pub macro_name: String,
+
+    // ── Pipeline step timing ──
+    pub time_steps: bool,
+    pub step_timings: Option<StepTimings>,
+    /// `Err` holds why the optimized plan could not be read.
+    pub scan_report: Option<Result<ScanReport, String>>,
 
     // ── Preview Cache ──
     pub preview_df: Option<DataFrame>,
     pub full_df: Option<DataFrame>,
+    /// Taken after each collect (see `measure_memory`).
+    pub memory: MemoryUsage,
     pub preview_rows: u32,
     pub preview_dirty: bool,
 
     // ── Auto-cast detection ──
     pub auto_cast_detected: bool,
+    /// Detected numeric String columns awaiting confirmation, with the chosen
+    /// target type (`None` = keep as text).
+    pub pending_auto_cast: Vec<(String, Option<NumericKind>)>,
 
     // ── Schema Info ──
     pub column_names: Vec<String>,
+    /// Role per result column: inferred, then `role_overrides` applied
+    /// (IDs are kept out of numeric defaults).
+    pub column_roles: BTreeMap<String, ColumnRole>,
+    /// Roles tagged by the user, saved with the workspace.
+    pub role_overrides: BTreeMap<String, ColumnRole>,
     pub column_dtypes: Vec<String>,
     pub column_stats: Vec<ColumnStats>,
     pub row_count: Option<usize>,
+    /// Set when stats/plots were computed on a sample ("fast stats" mode).
+    pub stats_sample: Option<SampleInfo>,
+    pub stats_sample_rows: usize,
+    /// Pending background statistics (see `StatsResult`).
+    pub stats_job: Option<Receiver<StatsResult>>,
+    pub stats_cache: StatsCache,
 
     // ── Table String Cache (performance: pre-computed, no DataFrame access during render) ──
     pub cached_cell_strings: Vec<Vec<String>>,
     pub cached_header_names: Vec<String>,
+    pub cached_column_ranges: Vec<Option<(f64, f64)>>,
+    /// Preview DataFrame row index of each (visually sorted) table row.
+    pub cached_row_order: Vec<usize>,
+    /// Table cell (visual row, column) open in the cell viewer.
+    pub cell_viewer: Option<(usize, usize)>,
     pub table_cache_dirty: bool,
 
+    // ── Display Config (conditional formatting, not part of pipeline) ──
+    pub display_config: DisplayConfig,
+    pub format_range_min: f64,
+    pub format_range_max: f64,
+
     // ── Cell/Row/Column Selection ──
     pub selected_cell: Option<(usize, usize)>,
     pub selected_row: Option<usize>,
@@ -52,25 +204,72 @@ pub struct AppState {
     pub sort_column: Option<String>,
     pub sort_descending: bool,
 
+    // ── Quick filters (table filter row over the preview rows, not the pipeline) ──
+    pub show_quick_filters: bool,
+    /// Filter text per column (see `query_engine::quick_filter`).
+    pub quick_filters: BTreeMap<String, String>,
+    /// Why the quick filters could not be applied (all rows are shown).
+    pub quick_filter_error: Option<String>,
+
     // ── Modify Tab: Operation Builder ──
     pub selected_op: OperationType,
     pub filter_column: String,
     pub filter_op: FilterOp,
     pub filter_value: String,
+    pub filter_options: FilterOptions,
+    /// Distinct values of a filter column (`None` when it has too many to list),
+    /// computed when the column is picked and dropped when the pipeline changes.
+    pub filter_observed: Option<(String, Option<Vec<String>>)>,
+    /// Row count of the pipeline result and of the last candidate step shown
+    /// next to an Apply button (candidate, its count); dropped when the
+    /// pipeline changes.
+    pub dry_run_rows: Option<usize>,
+    pub dry_run: Option<(String, Option<usize>)>,
+    /// Values the Cast builder's cast would lose, keyed by column and type.
+    pub cast_check: Option<(String, Option<CastRisk>)>,
     pub rename_from: String,
     pub rename_to: String,
     pub drop_column: String,
     pub select_checks: Vec<bool>,
     pub cast_column: String,
     pub cast_dtype: DTypeTag,
+    pub cast_quarantine: String,
     pub fill_column: String,
     pub fill_strategy: FillNullStrategy,
     pub fill_value: String,
+    pub fill_over: Vec<String>,
     pub sort_op_column: String,
     pub sort_op_descending: bool,
+    pub sort_op_stable: bool,
     pub limit_n: u32,
+    pub skip_n: u32,
+    pub tail_n: u32,
     pub datetime_column: String,
     pub datetime_format: String,
+    pub json_column: String,
+    pub json_path: String,
+    pub json_output: String,
+    pub json_dtype: DTypeTag,
+    pub round_column: String,
+    pub round_decimals: u32,
+    pub round_mode: RoundMode,
+    pub normalize_column: String,
+    pub normalize_method: NormalizeMethod,
+    pub normalize_output: String,
+    pub window_column: String,
+    pub window_function: WindowFunction,
+    pub window_over: Vec<String>,
+    pub window_output: String,
+    /// Coalesce inputs, in priority order.
+    pub coalesce_columns: Vec<String>,
+    pub coalesce_output: String,
+    pub date_range_column: String,
+    pub date_range_use_start: bool,
+    pub date_range_start: NaiveDate,
+    pub date_range_start_time: String,
+    pub date_range_use_end: bool,
+    pub date_range_end: NaiveDate,
+    pub date_range_end_time: String,
 
     // ── Visualize Tab ──
     pub plot_type: PlotType,
@@ -80,43 +279,211 @@ pub struct AppState {
     pub plot_dirty: bool,
     pub histogram_bins: usize,
     pub plot_x_is_datetime: bool,
+    pub plot_analysis: PlotAnalysis,
+    /// Season length in points for `PlotAnalysis::Decomposition`.
+    pub plot_period: usize,
+    /// Band of `PlotAnalysis::Binned` (bins: `histogram_bins`).
+    pub plot_spread: BinSpread,
+    /// X axis title replacing the X column name (e.g. frequency for spectra).
+    pub plot_x_label: Option<String>,
+    /// Fit a regression line to each series of a scatter plot.
+    pub plot_fit: bool,
+    pub plot_fits: Vec<(String, LinearFit)>,
+    /// Name typed in the plot Presets menu.
+    pub plot_preset_name: String,
+
+    // ── Plot Annotations ──
+    pub plot_annotations: Vec<PlotAnnotation>,
+    pub annotation_kind: AnnotationKind,
+    pub annotation_x: String,
+    pub annotation_y: String,
+    pub annotation_label: String,
 
     // ── Plot Reset Zoom ──
     pub plot_reset_counter: u64,
 
+    // ── Dashboard Tab ──
+    pub dashboard: Vec<DashboardPlot>,
+    pub dashboard_data: Vec<DashboardData>,
+    pub dashboard_dirty: bool,
+    pub dashboard_grid_columns: usize,
+    pub dashboard_link_x: bool,
+    pub dashboard_filter_column: String,
+    pub dashboard_filter_op: FilterOp,
+    pub dashboard_filter_value: String,
+    pub dashboard_filter_options: FilterOptions,
+
     // ── Export ──
     pub export_format: ExportFormat,
+    pub export_options: ExportOptions,
+    /// Name of the redaction profile applied to exports (see `config.redaction_profiles`).
+    pub redaction_profile: Option<String>,
+    pub show_redaction_profiles: bool,
+    pub show_bookmarks: bool,
+    pub show_performance: bool,
+    /// Collect engine of this pipeline, overriding `config.performance.engine`.
+    pub pipeline_engine: Option<CollectEngine>,
+    /// Query of the Quick Open window (open while `Some`).
+    pub quick_open: Option<String>,
+    /// Profile selected in the Redaction Profiles editor.
+    pub redaction_edit: usize,
+    /// CSV export held back: what the file would lose, per column.
+    pub pending_lossy_export: Option<Vec<String>>,
+    /// Export target that already exists (after adding the extension).
+    pub pending_overwrite: Option<PathBuf>,
+    /// Table written by "Export to DuckDB" (`duckdb` feature).
+    pub duckdb_table: String,
+    pub show_sql_export: bool,
+    /// Arrow IPC server publishing the pipeline (`ipc-server` feature).
+    #[cfg(feature = "ipc-server")]
+    pub ipc_server: Option<dafer_utils::ipc_server::IpcServer>,
+    pub sql_dialect: SqlDialect,
+    /// Generate a dbt model instead of a plain SELECT.
+    pub sql_dbt: bool,
+    /// Rows copied by "Copy as Markdown/HTML".
+    pub copy_table_rows: u32,
+
+    // ── Workspace ──
+    pub notes: String,
+    pub show_notes: bool,
+    pub pending_workspace: Option<Workspace>,
+    pub workspace_selection: WorkspaceSelection,
+    pub show_relocate: bool,
+    pub pending_mapping: Option<PendingMapping>,
+    /// Text of the Paste Pipeline window (open while `Some`).
+    pub paste_pipeline: Option<String>,
+    /// Options of the Export Bundle window (open while `Some`).
+    pub bundle: Option<BundleOptions>,
+    /// Loaded state held back because its source schema changed since it was saved.
+    pub pending_drift: Option<(PersistentState, SchemaDrift)>,
+    /// Provenance found in an opened export, offered for restoring its source and pipeline.
+    pub pending_provenance: Option<(Provenance, SourceStatus)>,
+    /// Drop/rename held back because plots or the table sort use the column,
+    /// with a description of each use.
+    pub pending_column_change: Option<(Operation, Vec<String>)>,
+    /// Delimiter typed in the CSV import options, applied with "Use".
+    pub custom_delimiter: String,
+    pub raw_preview: Option<RawPreview>,
+
+    // ── Session Autosave / Recovery ──
+    pub pending_recovery: Option<Workspace>,
+    pub last_autosave_time: f64,
+    pub last_autosave_bytes: Vec<u8>,
+
+    // ── Guided Tour ──
+    /// Current step of the guided tour (None = not running).
+    pub tour_step: Option<usize>,
+
+    // ── Crosstab ──
+    pub show_crosstab: bool,
+    pub crosstab_row: String,
+    pub crosstab_col: String,
+    pub crosstab_value: String,
+    pub crosstab_agg: CrosstabAgg,
+    /// (headers, rows) of the last computed crosstab, formatted for display.
+    pub crosstab_result: Option<(Vec<String>, Vec<Vec<String>>)>,
+
+    // ── Explore (pivot) ──
+    pub pivot: PivotSpec,
+    /// Spec of `pivot_result`; the pivot is recomputed when `pivot` differs.
+    pub pivot_computed: Option<PivotSpec>,
+    /// (headers, rows) of the last computed pivot, formatted for display.
+    pub pivot_result: Option<(Vec<String>, Vec<Vec<String>>)>,
+
+    // ── Mixed-Type Columns ──
+    pub show_mixed_types: bool,
+    pub mixed_types: Vec<MixedTypeReport>,
+    pub drill_down: Option<DrillDown>,
+
+    // ── Quick Calc ──
+    pub quick_calc_input: String,
+    /// (query, result), most recent last.
+    pub quick_calc_history: Vec<(String, String)>,
 
     // ── Status ──
-    pub status: String,
+    pub notifications: Notifications,
+    /// Diagnostics of a panic caught while updating the UI (see `ui::crash`).
+    pub crash: Option<String>,
+    /// Appends to the persistent action log (see `action_log`).
+    pub action_log: ActionLog,
+    pub show_action_log: bool,
+    /// Entries shown in the Action History window, loaded when it opens.
+    pub action_log_entries: Vec<LogEntry>,
+    pub show_notifications: bool,
+    /// Full error of the last failed preview, shown in a banner until it succeeds.
+    pub preview_error: Option<String>,
+    /// Pipeline step the failed preview traces back to, marked in the pipeline list.
+    pub step_failure: Option<StepFailure>,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         Self {
             theme: Theme::default(),
+
+            config: AppConfig::default(),
             selected_tab: MainTab::default(),
+            split_vertical: false,
 
             source: None,
             operations: Vec::new(),
             redo_stack: Vec::new(),
+            view_history: History::default(),
+            snapshots: Vec::new(),
+            snapshot_name: String::new(),
+            last_export: None,
+            source_schema: Vec::new(),
+
+            pipeline_locked: false,
+            pending_pipeline_edit: None,
+            last_saved: None,
+            fixture: None,
+            fixture_rows: 100,
+            fixture_report: None,
+
+            result_snapshots: Vec::new(),
+            result_snapshot_name: String::new(),
+            result_snapshot_spill: false,
+            snapshot_diff: None,
+
+            pipeline_selection: Vec::new(),
+            macro_name: String::new(),
+
+            time_steps: false,
+            step_timings: None,
+            scan_report: None,
 
             preview_df: None,
             full_df: None,
+            memory: MemoryUsage::default(),
             preview_rows: 200,
             preview_dirty: false,
 
             auto_cast_detected: false,
+            pending_auto_cast: Vec::new(),
 
             column_names: Vec::new(),
+            column_roles: BTreeMap::new(),
+            role_overrides: BTreeMap::new(),
             column_dtypes: Vec::new(),
             column_stats: Vec::new(),
             row_count: None,
+            stats_sample: None,
+            stats_sample_rows: 100_000,
+            stats_job: None,
+            stats_cache: StatsCache::default(),
 
             cached_cell_strings: Vec::new(),
             cached_header_names: Vec::new(),
+            cached_column_ranges: Vec::new(),
+            cached_row_order: Vec::new(),
+            cell_viewer: None,
             table_cache_dirty: false,
 
+            display_config: DisplayConfig::default(),
+            format_range_min: 0.0,
+            format_range_max: 100.0,
+
             selected_cell: None,
             selected_row: None,
             selected_col: None,
@@ -124,24 +491,61 @@ impl Default for AppState {
             sort_column: None,
             sort_descending: false,
 
+            show_quick_filters: false,
+            quick_filters: BTreeMap::new(),
+            quick_filter_error: None,
+
             selected_op: OperationType::default(),
             filter_column: String::new(),
             filter_op: FilterOp::default(),
             filter_value: String::new(),
+            filter_observed: None,
+            dry_run_rows: None,
+            dry_run: None,
+            cast_check: None,
+            filter_options: FilterOptions::default(),
             rename_from: String::new(),
             rename_to: String::new(),
             drop_column: String::new(),
             select_checks: Vec::new(),
             cast_column: String::new(),
             cast_dtype: DTypeTag::default(),
+            cast_quarantine: String::new(),
             fill_column: String::new(),
             fill_strategy: FillNullStrategy::default(),
             fill_value: String::new(),
+            fill_over: Vec::new(),
             sort_op_column: String::new(),
             sort_op_descending: false,
+            sort_op_stable: true,
             limit_n: 1000,
+            skip_n: 1,
+            tail_n: 100,
             datetime_column: String::new(),
             datetime_format: "%Y-%m-%d %H:%M:%S".to_string(),
+            json_column: String::new(),
+            json_path: "$.".to_string(),
+            json_output: String::new(),
+            json_dtype: DTypeTag::Utf8String,
+            round_column: String::new(),
+            round_decimals: 2,
+            round_mode: RoundMode::default(),
+            normalize_column: String::new(),
+            normalize_method: NormalizeMethod::default(),
+            normalize_output: String::new(),
+            window_column: String::new(),
+            window_function: WindowFunction::default(),
+            window_over: Vec::new(),
+            window_output: String::new(),
+            coalesce_columns: Vec::new(),
+            coalesce_output: String::new(),
+            date_range_column: String::new(),
+            date_range_use_start: true,
+            date_range_start: chrono::Local::now().date_naive(),
+            date_range_start_time: "00:00:00".to_string(),
+            date_range_use_end: true,
+            date_range_end: chrono::Local::now().date_naive(),
+            date_range_end_time: "23:59:59".to_string(),
 
             plot_type: PlotType::default(),
             plot_x: String::new(),
@@ -150,12 +554,532 @@ impl Default for AppState {
             plot_dirty: true,
             histogram_bins: 30,
             plot_x_is_datetime: false,
+            plot_analysis: PlotAnalysis::default(),
+            plot_period: 12,
+            plot_spread: BinSpread::default(),
+            plot_x_label: None,
+            plot_fit: false,
+            plot_fits: Vec::new(),
+            plot_preset_name: String::new(),
+
+            plot_annotations: Vec::new(),
+            annotation_kind: AnnotationKind::default(),
+            annotation_x: String::new(),
+            annotation_y: String::new(),
+            annotation_label: String::new(),
 
             plot_reset_counter: 0,
 
+            dashboard: Vec::new(),
+            dashboard_data: Vec::new(),
+            dashboard_dirty: true,
+            dashboard_grid_columns: 2,
+            dashboard_link_x: false,
+            dashboard_filter_column: String::new(),
+            dashboard_filter_op: FilterOp::default(),
+            dashboard_filter_value: String::new(),
+            dashboard_filter_options: FilterOptions::default(),
+
             export_format: ExportFormat::default(),
+            export_options: ExportOptions {
+                atomic: true,
+                ..Default::default()
+            },
+            redaction_profile: None,
+            show_redaction_profiles: false,
+            show_bookmarks: false,
+            show_performance: false,
+            pipeline_engine: None,
+            quick_open: None,
+            redaction_edit: 0,
+            pending_lossy_export: None,
+            pending_overwrite: None,
+            duckdb_table: "result".to_string(),
+            show_sql_export: false,
+            #[cfg(feature = "ipc-server")]
+            ipc_server: None,
+            sql_dialect: SqlDialect::default(),
+            sql_dbt: false,
+            copy_table_rows: 20,
+
+            notes: String::new(),
+            show_notes: false,
+            pending_workspace: None,
+            workspace_selection: WorkspaceSelection::default(),
+            show_relocate: false,
+            pending_mapping: None,
+            paste_pipeline: None,
+            bundle: None,
+            pending_drift: None,
+            pending_provenance: None,
+            pending_column_change: None,
+            custom_delimiter: String::new(),
+            raw_preview: None,
+
+            pending_recovery: None,
+            last_autosave_time: 0.0,
+            last_autosave_bytes: Vec::new(),
+
+            tour_step: None,
+            show_crosstab: false,
+            crosstab_row: String::new(),
+            crosstab_col: String::new(),
+            crosstab_value: String::new(),
+            crosstab_agg: CrosstabAgg::default(),
+            crosstab_result: None,
+            pivot: PivotSpec::default(),
+            pivot_computed: None,
+            pivot_result: None,
+
+            show_mixed_types: false,
+            mixed_types: Vec::new(),
+            drill_down: None,
+
+            quick_calc_input: String::new(),
+            quick_calc_history: Vec::new(),
+
+            notifications: Notifications::default(),
+            crash: None,
+            action_log: ActionLog::default(),
+            show_action_log: false,
+            action_log_entries: Vec::new(),
+            show_notifications: false,
+            preview_error: None,
+            step_failure: None,
+        }
+    }
+}
+
+impl AppState {
+    /// Snapshot of the serializable pipeline state (source + operations).
+    pub fn persistent_state(&self) -> PersistentState {
+        PersistentState {
+            source: self.source.clone(),
+            operations: self.operations.clone(),
+            source_relative: None,
+            expected_schema: self.source_schema.clone(),
+            fixture: self.fixture.clone(),
+            engine: self.pipeline_engine,
+            source_rows: None,
+        }
+    }
+
+    /// Remember the current source + pipeline as the last saved state.
+    pub fn mark_saved(&mut self) {
+        self.last_saved = Some(self.persistent_state());
+    }
+
+    /// Update `memory` from the process RSS and the preview and full DataFrames.
+    pub fn measure_memory(&mut self) {
+        self.memory = MemoryUsage::measure(self.preview_df.iter().chain(&self.full_df));
+    }
+
+    /// Request a destructive pipeline change: applied directly, or held for
+    /// confirmation while the pipeline is locked.
+    pub fn request_pipeline_edit(&mut self, edit: PipelineEdit) {
+        if self.pipeline_locked {
+            self.pending_pipeline_edit = Some(edit);
+        } else {
+            self.apply_pipeline_edit(edit);
+        }
+    }
+
+    /// Operations that a pipeline edit would discard (for the confirmation preview).
+    pub fn edit_discards(&self, edit: PipelineEdit) -> Vec<&Operation> {
+        match edit {
+            PipelineEdit::Remove(idx) => self.operations.get(idx).into_iter().collect(),
+            PipelineEdit::Clear => self.operations.iter().collect(),
+            PipelineEdit::Revert => {
+                let saved = self
+                    .last_saved
+                    .as_ref()
+                    .map(|s| s.operations.as_slice())
+                    .unwrap_or_default();
+                self.discarded_by(saved)
+            }
+            PipelineEdit::Snapshot(idx) => {
+                let target = self
+                    .snapshots
+                    .get(idx)
+                    .map(|s| s.operations.as_slice())
+                    .unwrap_or_default();
+                self.discarded_by(target)
+            }
+        }
+    }
+
+    /// Current steps not shared (as a common prefix) with `target`.
+    fn discarded_by(&self, target: &[Operation]) -> Vec<&Operation> {
+        let common = self
+            .operations
+            .iter()
+            .zip(target)
+            .take_while(|(a, b)| a.to_string() == b.to_string())
+            .count();
+        self.operations[common..].iter().collect()
+    }
+
+    /// Record in the action log that the pipeline was replaced as a whole.
+    pub fn log_pipeline_replaced(&mut self, reason: &str) {
+        self.action_log.record(Action::PipelineReplaced {
+            reason: reason.to_string(),
+            operations: self.operations.clone(),
+        });
+    }
+
+    pub fn apply_pipeline_edit(&mut self, edit: PipelineEdit) {
+        match edit {
+            PipelineEdit::Remove(idx) => {
+                if idx < self.operations.len() {
+                    let operation = self.operations.remove(idx);
+                    self.action_log.record(Action::OperationRemoved {
+                        index: idx,
+                        operation,
+                    });
+                    self.notifications.info("Operation removed");
+                }
+            }
+            PipelineEdit::Clear => {
+                self.operations.clear();
+                self.log_pipeline_replaced("cleared");
+                self.notifications.info("Pipeline cleared");
+            }
+            PipelineEdit::Revert => {
+                let Some(saved) = self.last_saved.clone() else {
+                    return;
+                };
+                self.source = saved.source;
+                self.operations = saved.operations;
+                self.check_source_exists();
+                self.log_pipeline_replaced("reverted to last save");
+                self.notifications.info("Reverted to last saved state");
+            }
+            PipelineEdit::Snapshot(idx) => {
+                let Some(snapshot) = self.snapshots.get(idx) else {
+                    return;
+                };
+                let reason = format!("set to snapshot \"{}\"", snapshot.name);
+                self.operations = snapshot.operations.clone();
+                self.notifications
+                    .info(format!("Jumped to snapshot \"{}\"", snapshot.name));
+                self.log_pipeline_replaced(&reason);
+            }
+        }
+        self.redo_stack.clear();
+        self.preview_dirty = true;
+    }
 
-            status: "Ready".to_string(),
+    /// Selected pipeline steps as a range, if they are consecutive.
+    pub fn selected_range(&self) -> Option<std::ops::Range<usize>> {
+        let first = self.pipeline_selection.iter().position(|s| *s)?;
+        let len = self.pipeline_selection[first..]
+            .iter()
+            .take_while(|s| **s)
+            .count();
+        let rest_empty = !self.pipeline_selection[first + len..].iter().any(|s| *s);
+        rest_empty.then_some(first..first + len)
+    }
+
+    /// Collapse consecutive steps into one named macro.
+    pub fn group_operations(&mut self, range: std::ops::Range<usize>, name: String) {
+        let start = range.start;
+        let operations: Vec<Operation> = self.operations.drain(range).collect();
+        let n = operations.len();
+        self.operations
+            .insert(start, Operation::Macro { name, operations });
+        self.pipeline_selection.clear();
+        self.redo_stack.clear();
+        // Re-run so step timings line up with the new entries
+        self.preview_dirty = true;
+        self.notifications
+            .info(format!("Grouped {} steps into a macro", n));
+    }
+
+    /// Expand a macro back into its steps.
+    pub fn ungroup_operation(&mut self, idx: usize) {
+        if !matches!(self.operations.get(idx), Some(Operation::Macro { .. })) {
+            return;
+        }
+        if let Operation::Macro { operations, .. } = self.operations.remove(idx) {
+            self.operations.splice(idx..idx, operations);
+            self.pipeline_selection.clear();
+            self.redo_stack.clear();
+            self.preview_dirty = true;
+            self.notifications.info("Macro expanded");
+        }
+    }
+
+    /// Pin the current pipeline output as the expected output.
+    pub fn pin_fixture(&mut self, rows: Option<usize>) {
+        let Some(source) = &self.source else {
+            return;
+        };
+        match Fixture::pin(source, &self.operations, rows) {
+            Ok(fixture) => {
+                self.notifications.info(format!(
+                    "Pinned expected output ({} rows x {} columns)",
+                    fixture.row_count,
+                    fixture.columns.len()
+                ));
+                self.fixture = Some(fixture);
+            }
+            Err(e) => self.notifications.error(format!("Pin error: {}", e)),
+        }
+    }
+
+    /// Re-run the pipeline against its fixture and show the report.
+    pub fn verify_fixture(&mut self) {
+        let (Some(source), Some(fixture)) = (&self.source, &self.fixture) else {
+            return;
+        };
+        match fixture.verify(source, &self.operations) {
+            Ok(report) => {
+                if report.passed() {
+                    self.notifications
+                        .info("Pipeline output matches the expected output");
+                } else {
+                    self.notifications.warn(format!(
+                        "Pipeline output differs from the expected output ({} differences)",
+                        report.differences.len()
+                    ));
+                }
+                self.fixture_report = Some(report);
+            }
+            Err(e) => self.notifications.error(format!("Verify error: {}", e)),
+        }
+    }
+
+    /// Collect the current pipeline result and keep it under `name`
+    /// (replacing a snapshot of the same name).
+    pub fn take_result_snapshot(&mut self, name: &str) {
+        let Some(source) = &self.source else {
+            return;
+        };
+        match ResultSnapshot::take(name, source, &self.operations, self.result_snapshot_spill) {
+            Ok(snapshot) => {
+                self.notifications.info(format!(
+                    "Result snapshot \"{}\" taken ({} rows)",
+                    name, snapshot.row_count
+                ));
+                self.result_snapshots.retain(|s| s.name != name);
+                self.result_snapshots.push(snapshot);
+            }
+            Err(e) => self.notifications.error(format!("Snapshot error: {}", e)),
+        }
+    }
+
+    /// Compare the live pipeline result with result snapshot `idx`.
+    pub fn compare_result_snapshot(&mut self, idx: usize) {
+        let (Some(source), Some(snapshot)) = (&self.source, self.result_snapshots.get(idx)) else {
+            return;
+        };
+        let diff = query_engine::execute(source, &self.operations)
+            .and_then(|current| snapshot.compare(&current));
+        match diff {
+            Ok(diff) => self.snapshot_diff = Some((snapshot.name.clone(), diff)),
+            Err(e) => self.notifications.error(format!("Compare error: {}", e)),
+        }
+    }
+
+    /// Prompt to relocate the source if it can't be found on disk.
+    pub fn check_source_exists(&mut self) {
+        self.show_relocate = self.source.as_ref().is_some_and(|s| !s.path.exists());
+    }
+
+    /// Full value of a table cell (visual row, column), read from the preview
+    /// DataFrame rather than the string cache.
+    pub fn cell_value(&self, visual_row: usize, col: usize) -> Option<String> {
+        let df = self.preview_df.as_ref()?;
+        let row = *self.cached_row_order.get(visual_row)?;
+        let value = df.get_columns().get(col)?.get(row).ok()?;
+        Some(crate::app::format_cell_value(&value))
+    }
+
+    /// Full text of a table cell: the cached string, or the DataFrame value if
+    /// the cached string was truncated.
+    pub fn full_cell(&self, visual_row: usize, col: usize) -> Option<String> {
+        let cached = self.cached_cell_strings.get(visual_row)?.get(col)?;
+        if cached.chars().count() > crate::app::CELL_CACHE_MAX_CHARS {
+            self.cell_value(visual_row, col)
+        } else {
+            Some(cached.clone())
+        }
+    }
+
+    /// The cached table with truncated cells replaced by their full values
+    /// (for exporting or copying the view).
+    pub fn full_cell_strings(&self) -> Vec<Vec<String>> {
+        (0..self.cached_cell_strings.len())
+            .map(|row| {
+                (0..self.cached_header_names.len())
+                    .map(|col| self.full_cell(row, col).unwrap_or_default())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Units of the pipeline output columns (source units carried through renames).
+    pub fn column_units(&self) -> BTreeMap<String, String> {
+        self.source
+            .as_ref()
+            .map(|source| operations::propagate_units(&source.units, &self.operations))
+            .unwrap_or_default()
+    }
+
+    /// Current plot setup of the Visualize tab.
+    pub fn plot_config(&self) -> PlotConfig {
+        PlotConfig {
+            plot_type: self.plot_type,
+            x: self.plot_x.clone(),
+            y_columns: self.plot_y_columns.clone(),
+            histogram_bins: self.histogram_bins,
+            annotations: self.plot_annotations.clone(),
+        }
+    }
+
+    /// Replace the Visualize tab's plot setup.
+    pub fn apply_plot_config(&mut self, config: PlotConfig) {
+        self.plot_type = config.plot_type;
+        self.plot_x = config.x;
+        self.plot_y_columns = config.y_columns;
+        self.histogram_bins = config.histogram_bins;
+        self.plot_annotations = config.annotations;
+        self.plot_dirty = true;
+    }
+
+    /// Dtype of a column of the current pipeline result, as Polars displays it.
+    pub fn column_dtype(&self, column: &str) -> Option<&str> {
+        let idx = self.column_names.iter().position(|c| c == column)?;
+        self.column_dtypes.get(idx).map(String::as_str)
+    }
+
+    /// Input widget and validation for values typed for `column`.
+    pub fn column_kind(&self, column: &str) -> LiteralKind {
+        self.column_dtype(column)
+            .map(LiteralKind::of_dtype)
+            .unwrap_or_default()
+    }
+
+    /// The table's quick filters as Filter operations (empty cells skipped;
+    /// none while the filter row is hidden).
+    pub fn quick_filter_ops(&self) -> Vec<Operation> {
+        if !self.show_quick_filters {
+            return Vec::new();
+        }
+        self.quick_filters
+            .iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(column, text)| {
+                let is_text = self.column_kind(column) == LiteralKind::Text;
+                query_engine::quick_filter(column, text, is_text)
+            })
+            .collect()
+    }
+
+    /// Views that use `column` and break if it is dropped or renamed.
+    pub fn column_dependents(&self, column: &str) -> Vec<String> {
+        let mut dependents = Vec::new();
+        if self.plot_config().reads(column) {
+            dependents.push("Visualize plot".to_string());
+        }
+        for plot in self.dashboard.iter().filter(|p| p.reads(column)) {
+            dependents.push(format!("Dashboard plot \"{}\"", plot.title));
+        }
+        if self.sort_column.as_deref() == Some(column) {
+            dependents.push("Table sort".to_string());
+        }
+        dependents
+    }
+
+    /// Point views at a renamed column (`to`), or remove their uses of a
+    /// dropped one (`None`).
+    pub fn retarget_column(&mut self, from: &str, to: Option<&str>) {
+        let mut config = self.plot_config();
+        config.retarget(from, to);
+        self.apply_plot_config(config);
+        for plot in &mut self.dashboard {
+            plot.retarget(from, to);
+        }
+        if self.sort_column.as_deref() == Some(from) {
+            self.sort_column = to.map(str::to_string);
+        }
+        self.dashboard_dirty = true;
+        self.table_cache_dirty = true;
+    }
+
+    /// Current undoable view state.
+    pub fn view_settings(&self) -> ViewSettings {
+        ViewSettings {
+            plot: self.plot_config(),
+            display: self.display_config.clone(),
+            sort_column: self.sort_column.clone(),
+            sort_descending: self.sort_descending,
+        }
+    }
+
+    /// Restore a view state from the undo history.
+    pub fn apply_view_settings(&mut self, view: ViewSettings) {
+        self.apply_plot_config(view.plot);
+        self.display_config = view.display;
+        self.sort_column = view.sort_column;
+        self.sort_descending = view.sort_descending;
+        self.table_cache_dirty = true;
+    }
+
+    /// Snapshot of the whole analysis view (pipeline, layout, plot, notes).
+    pub fn workspace(&self) -> Workspace {
+        Workspace {
+            state: self.persistent_state(),
+            layout: UiLayout {
+                tab_index: MainTab::all()
+                    .iter()
+                    .position(|t| *t == self.selected_tab)
+                    .unwrap_or(0),
+                display: self.display_config.clone(),
+            },
+            plot: self.plot_config(),
+            notes: self.notes.clone(),
+            dashboard: self.dashboard.clone(),
+            snapshots: self.snapshots.clone(),
+            column_roles: self.role_overrides.clone(),
+            last_export: self.last_export.clone(),
+        }
+    }
+
+    /// Apply the selected parts of a workspace.
+    pub fn apply_workspace(&mut self, workspace: Workspace, selection: WorkspaceSelection) {
+        if selection.source {
+            self.source = workspace.state.source;
+            self.last_export = workspace.last_export;
+            self.check_source_exists();
+        }
+        if selection.operations {
+            self.operations = workspace.state.operations;
+            self.log_pipeline_replaced("loaded from workspace");
+            self.snapshots = workspace.snapshots;
+            self.role_overrides = workspace.column_roles;
+            self.fixture = workspace.state.fixture;
+            self.pipeline_engine = workspace.state.engine;
+            self.redo_stack.clear();
+        }
+        if selection.layout {
+            self.display_config = workspace.layout.display;
+            self.selected_tab = MainTab::all()
+                .get(workspace.layout.tab_index)
+                .copied()
+                .unwrap_or_default();
+        }
+        if selection.plot {
+            self.apply_plot_config(workspace.plot);
+            self.dashboard = workspace.dashboard;
+            self.dashboard_dirty = true;
+        }
+        if selection.notes {
+            self.notes = workspace.notes;
+        }
+        self.preview_dirty = true;
+        if selection.operations && self.fixture.is_some() {
+            self.verify_fixture();
         }
     }
 }
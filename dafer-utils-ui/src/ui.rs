@@ -1,5 +1,22 @@
+pub mod action_log;
+pub mod analysis;
+pub mod bookmarks;
+pub mod crash;
+pub mod dashboard;
+pub mod dock;
+pub mod explore;
+pub mod fixture;
+pub mod formatting;
 pub mod load_preview;
 pub mod main_ui;
 pub mod modify;
 pub mod palette;
+pub mod performance;
+pub mod redaction;
+pub mod snapshot;
+pub mod split_view;
+pub mod sql_export;
+pub mod table;
+pub mod toasts;
+pub mod tour;
 pub mod visualize;
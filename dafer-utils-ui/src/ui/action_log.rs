@@ -0,0 +1,82 @@
+use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::action_log::{self, Action};
+use eframe::egui::{self, RichText};
+
+/// Most entries loaded into the Action History window.
+const MAX_ENTRIES: usize = 1000;
+
+/// Open the Action History window with the latest entries of the log file.
+pub fn open_action_history(state: &mut AppState) {
+    match action_log::read_recent(MAX_ENTRIES) {
+        Ok(entries) => {
+            state.action_log_entries = entries;
+            state.show_action_log = true;
+        }
+        Err(e) => state
+            .notifications
+            .error(format!("Action log error: {}", e)),
+    }
+}
+
+/// Action History window: the persistent action log, newest first. Steps of
+/// pipeline changes and exports can be expanded to see the full operations.
+pub fn action_history_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = true;
+    let mut refresh = false;
+    egui::Window::new("Action History")
+        .open(&mut open)
+        .default_size([520.0, 420.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(action_log::log_path().display().to_string())
+                        .small()
+                        .weak(),
+                );
+                if ui.small_button("Refresh").clicked() {
+                    refresh = true;
+                }
+            });
+            if let Some(e) = &state.action_log.write_error {
+                ui.colored_label(
+                    GruvboxMaterial::red(255),
+                    format!("Log write failed: {}", e),
+                );
+            }
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (i, entry) in state.action_log_entries.iter().enumerate().rev() {
+                    let title = format!("{}  {}", entry.time, entry.action);
+                    let operations = match &entry.action {
+                        Action::PipelineReplaced { operations, .. }
+                        | Action::Exported { operations, .. } => operations,
+                        _ => {
+                            ui.label(title);
+                            continue;
+                        }
+                    };
+                    egui::CollapsingHeader::new(title)
+                        .id_salt(("action_log", i))
+                        .show(ui, |ui| {
+                            if let Action::Exported {
+                                source: Some(source),
+                                ..
+                            } = &entry.action
+                            {
+                                ui.label(format!("Source: {}", source));
+                            }
+                            for (step, op) in operations.iter().enumerate() {
+                                ui.monospace(format!("{}. {}", step + 1, op));
+                            }
+                        });
+                }
+            });
+        });
+    if !open {
+        state.show_action_log = false;
+    }
+    if refresh {
+        open_action_history(state);
+    }
+}
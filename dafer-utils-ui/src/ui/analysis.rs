@@ -0,0 +1,368 @@
+use crate::app::{VIEWER, format_cell_value};
+use crate::state::{AppState, DrillDown};
+use eframe::egui::{self, RichText};
+
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+
+use crate::ui::modify::apply_op;
+
+use dafer_utils::analysis::{self, CrosstabAgg};
+use dafer_utils::execution;
+use dafer_utils::operations::{DTypeTag, FilterOp, FilterOptions, Operation};
+use dafer_utils::query_engine;
+use polars::prelude::DataFrame;
+
+// ─── Crosstab ─────────────────────────────────────────────────────────────────
+
+/// Crosstab dialog: pick row/column categories and an aggregate, then view or
+/// export the resulting contingency table. Computed on the full result when
+/// available, otherwise on the preview.
+pub fn crosstab_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_crosstab;
+    egui::Window::new("Crosstab")
+        .open(&mut open)
+        .default_size([520.0, 360.0])
+        .show(ctx, |ui| {
+            egui::Grid::new("crosstab_inputs")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Rows:");
+                    column_combo(
+                        ui,
+                        "crosstab_row",
+                        &mut state.crosstab_row,
+                        &state.column_names,
+                    );
+                    ui.end_row();
+                    ui.label("Columns:");
+                    column_combo(
+                        ui,
+                        "crosstab_col",
+                        &mut state.crosstab_col,
+                        &state.column_names,
+                    );
+                    ui.end_row();
+                    ui.label("Aggregate:");
+                    egui::ComboBox::from_id_salt("crosstab_agg")
+                        .selected_text(state.crosstab_agg.to_string())
+                        .show_ui(ui, |ui| {
+                            for agg in CrosstabAgg::all() {
+                                ui.selectable_value(&mut state.crosstab_agg, *agg, agg.to_string());
+                            }
+                        });
+                    ui.end_row();
+                    if state.crosstab_agg.needs_value() {
+                        ui.label("Value:");
+                        column_combo(
+                            ui,
+                            "crosstab_value",
+                            &mut state.crosstab_value,
+                            &state.column_names,
+                        );
+                        ui.end_row();
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                if ui.button("Compute").clicked() {
+                    compute_crosstab(state);
+                }
+                let has_result = state.crosstab_result.is_some();
+                if !VIEWER
+                    && ui
+                        .add_enabled(has_result, egui::Button::new("Export CSV..."))
+                        .clicked()
+                {
+                    export_crosstab(state);
+                }
+            });
+
+            ui.separator();
+            let Some((headers, rows)) = &state.crosstab_result else {
+                ui.label(
+                    RichText::new("Pick two columns and press Compute")
+                        .color(GruvboxMaterial::fg3(200)),
+                );
+                return;
+            };
+            egui::ScrollArea::both().show(ui, |ui| {
+                egui::Grid::new("crosstab_result")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in headers {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for row in rows {
+                            for (i, cell) in row.iter().enumerate() {
+                                if i == 0 {
+                                    ui.label(RichText::new(cell).strong());
+                                } else {
+                                    ui.label(cell);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+    state.show_crosstab = open;
+}
+
+fn column_combo(ui: &mut egui::Ui, id_salt: &str, selected: &mut String, columns: &[String]) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected.as_str())
+        .show_ui(ui, |ui| {
+            for name in columns {
+                ui.selectable_value(selected, name.clone(), name);
+            }
+        });
+}
+
+fn compute_crosstab(state: &mut AppState) {
+    let Some(df) = state.full_df.as_ref().or(state.preview_df.as_ref()) else {
+        state.notifications.warn("No data to crosstab");
+        return;
+    };
+    let value = state
+        .crosstab_agg
+        .needs_value()
+        .then_some(state.crosstab_value.as_str());
+    match analysis::crosstab(
+        df,
+        &state.crosstab_row,
+        &state.crosstab_col,
+        value,
+        state.crosstab_agg,
+    ) {
+        Ok(table) => {
+            let (headers, rows) = frame_grid(&table);
+            state.notifications.info(format!(
+                "Crosstab: {} × {}",
+                table.height(),
+                table.width().saturating_sub(1)
+            ));
+            state.crosstab_result = Some((headers, rows));
+        }
+        Err(e) => state.notifications.error(format!("Crosstab error: {}", e)),
+    }
+}
+
+/// Formatted (headers, rows) of a small result frame for display/export.
+pub(crate) fn frame_grid(df: &DataFrame) -> (Vec<String>, Vec<Vec<String>>) {
+    let headers = df
+        .get_column_names()
+        .iter()
+        .map(|n| n.to_string())
+        .collect();
+    let rows = (0..df.height())
+        .map(|r| {
+            df.get_columns()
+                .iter()
+                .map(|c| c.get(r).map(|v| format_cell_value(&v)).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    (headers, rows)
+}
+
+fn export_crosstab(state: &mut AppState) {
+    let Some((headers, rows)) = &state.crosstab_result else {
+        return;
+    };
+    if let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV file", &["csv"])
+        .save_file()
+    {
+        match execution::export_grid_csv(headers, rows, &path) {
+            Ok(()) => state
+                .notifications
+                .info(format!("Crosstab exported to {}", path.display())),
+            Err(e) => state.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+}
+
+// ─── Mixed-Type Columns ───────────────────────────────────────────────────────
+
+/// Values sampled per column when scanning for mixed types.
+const MIXED_TYPE_SAMPLES: usize = 10_000;
+
+/// Scan the current result for mixed-type String columns and open the report.
+pub fn scan_mixed_types(state: &mut AppState) {
+    let Some(df) = state.full_df.as_ref().or(state.preview_df.as_ref()) else {
+        state.notifications.warn("No data to scan");
+        return;
+    };
+    state.mixed_types = analysis::detect_mixed_type_columns(df, MIXED_TYPE_SAMPLES);
+    state
+        .notifications
+        .info(format!("{} mixed-type column(s)", state.mixed_types.len()));
+    state.show_mixed_types = true;
+}
+
+/// Mixed-type report: proportions and examples per column, with a one-click
+/// cast to the majority type that moves the other values to a quarantine column.
+pub fn mixed_types_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = state.show_mixed_types;
+    let mut action: Option<Operation> = None;
+    let mut drill: Option<(String, DTypeTag)> = None;
+    egui::Window::new("Mixed-Type Columns")
+        .open(&mut open)
+        .default_size([480.0, 320.0])
+        .show(ctx, |ui| {
+            if state.mixed_types.is_empty() {
+                ui.label(
+                    RichText::new("No String columns mix numbers, dates, and text")
+                        .color(GruvboxMaterial::fg3(200)),
+                );
+            }
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for report in &state.mixed_types {
+                    ui.label(RichText::new(&report.column).strong());
+                    let shares: Vec<String> = report
+                        .counts
+                        .iter()
+                        .map(|(kind, _)| format!("{:.0}% {}", report.fraction(*kind) * 100.0, kind))
+                        .collect();
+                    ui.label(format!(
+                        "{} (of {} sampled)",
+                        shares.join(" · "),
+                        report.total()
+                    ));
+                    for (kind, examples) in &report.samples {
+                        ui.label(
+                            RichText::new(format!("{}: {}", kind, examples.join(", ")))
+                                .small()
+                                .color(GruvboxMaterial::fg3(200)),
+                        );
+                    }
+                    let majority = report.majority();
+                    if let Some(dtype) = majority.dtype() {
+                        let quarantine = format!("{}_quarantine", report.column);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(format!("Cast to {} (rest → {})", majority, quarantine))
+                                .clicked()
+                            {
+                                action = Some(Operation::CastColumn {
+                                    column: report.column.clone(),
+                                    dtype: dtype.clone(),
+                                    quarantine: Some(quarantine),
+                                });
+                            }
+                            if ui.button("Show rows").clicked() {
+                                drill = Some((report.column.clone(), dtype));
+                            }
+                        });
+                    } else {
+                        ui.label(RichText::new("Mostly text: left as String").small());
+                    }
+                    ui.separator();
+                }
+            });
+        });
+    state.show_mixed_types = open;
+
+    if let Some(op) = action {
+        if let Operation::CastColumn { column, .. } = &op {
+            state.mixed_types.retain(|r| r.column != *column);
+        }
+        apply_op(state, op);
+    }
+    if let Some((column, dtype)) = drill {
+        let title = format!("{}: values that are not {}", column, dtype);
+        show_rows(state, title, cast_failures(&column, dtype));
+    }
+}
+
+/// Transient steps keeping only rows whose `column` fails a cast to `dtype`
+/// (the column itself is left untouched).
+fn cast_failures(column: &str, dtype: DTypeTag) -> Vec<Operation> {
+    const CHECK: &str = "__cast_check";
+    const FAILED: &str = "__cast_failed";
+    vec![
+        Operation::Coalesce {
+            columns: vec![column.to_string()],
+            output: CHECK.to_string(),
+        },
+        Operation::CastColumn {
+            column: CHECK.to_string(),
+            dtype,
+            quarantine: Some(FAILED.to_string()),
+        },
+        Operation::Filter {
+            column: FAILED.to_string(),
+            op: FilterOp::IsNotNull,
+            value: String::new(),
+            options: FilterOptions::default(),
+        },
+        Operation::DropColumn(CHECK.to_string()),
+        Operation::DropColumn(FAILED.to_string()),
+    ]
+}
+
+// ─── Drill-Down ───────────────────────────────────────────────────────────────
+
+/// Rows fetched for the drill-down window.
+const DRILL_DOWN_ROWS: u32 = 500;
+
+/// Run the pipeline plus transient `extra` steps and show the matching rows.
+/// The pipeline is not modified.
+pub fn show_rows(state: &mut AppState, title: String, extra: Vec<Operation>) {
+    let Some(source) = &state.source else {
+        return;
+    };
+    match query_engine::drill_down(source, &state.operations, &extra, DRILL_DOWN_ROWS) {
+        Ok((df, total)) => {
+            let (headers, rows) = frame_grid(&df);
+            state.drill_down = Some(DrillDown {
+                title,
+                headers,
+                rows,
+                total,
+            });
+        }
+        Err(e) => state.notifications.error(format!("Show rows error: {}", e)),
+    }
+}
+
+/// Window listing the rows behind a reported problem.
+pub fn drill_down_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(drill) = &state.drill_down else {
+        return;
+    };
+    let mut open = true;
+    egui::Window::new(drill.title.as_str())
+        .id(egui::Id::new("drill_down"))
+        .open(&mut open)
+        .default_size([600.0, 360.0])
+        .show(ctx, |ui| {
+            let shown = drill.rows.len();
+            if shown < drill.total {
+                ui.label(format!("{} rows (showing first {})", drill.total, shown));
+            } else {
+                ui.label(format!("{} rows", drill.total));
+            }
+            ui.separator();
+            egui::ScrollArea::both().show(ui, |ui| {
+                egui::Grid::new("drill_down_rows")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in &drill.headers {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for row in &drill.rows {
+                            for cell in row {
+                                ui.label(cell);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+    if !open {
+        state.drill_down = None;
+    }
+}
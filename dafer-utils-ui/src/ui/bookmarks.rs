@@ -0,0 +1,223 @@
+use crate::state::AppState;
+use crate::ui::main_ui::open_file;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::config::Bookmark;
+use eframe::egui::{self, RichText};
+
+// ─── Bookmarks ────────────────────────────────────────────────────────────────
+//
+// Starred datasets live in the app config. They are managed in the Bookmarks
+// window, listed on the welcome screen and searched from Quick Open (Ctrl+P).
+
+/// Star the open file (no-op if it is already bookmarked).
+pub fn bookmark_current(state: &mut AppState) {
+    let Some(source) = &state.source else {
+        return;
+    };
+    let path = source.path.clone();
+    if state.config.bookmarks.iter().any(|b| b.path == path) {
+        state.notifications.quiet("Already bookmarked");
+        return;
+    }
+    state.config.bookmarks.push(Bookmark::new(path));
+    save_bookmarks(state);
+}
+
+fn save_bookmarks(state: &mut AppState) {
+    match state.config.save() {
+        Ok(()) => state.notifications.quiet("Bookmarks saved"),
+        Err(e) => state
+            .notifications
+            .error(format!("Config save error: {}", e)),
+    }
+}
+
+/// Open a bookmarked file, warning instead if it has been moved or deleted.
+fn open_bookmark(state: &mut AppState, index: usize) {
+    let Some(bookmark) = state.config.bookmarks.get(index) else {
+        return;
+    };
+    let path = bookmark.path.clone();
+    if !path.exists() {
+        state.notifications.warn(format!(
+            "Bookmark \"{}\": {} not found",
+            bookmark.label,
+            path.display()
+        ));
+        return;
+    }
+    open_file(state, path);
+}
+
+/// Bookmarks as open buttons (welcome screen).
+pub fn bookmark_list(ui: &mut egui::Ui, state: &mut AppState) {
+    if state.config.bookmarks.is_empty() {
+        return;
+    }
+    let mut open = None;
+    ui.add_space(8.0);
+    ui.label("Bookmarks:");
+    for (i, bookmark) in state.config.bookmarks.iter().enumerate() {
+        let tags: Vec<&str> = bookmark.tags().collect();
+        let text = if tags.is_empty() {
+            format!("★ {}", bookmark.label)
+        } else {
+            format!("★ {}  #{}", bookmark.label, tags.join(" #"))
+        };
+        if ui
+            .button(text)
+            .on_hover_text(bookmark.path.display().to_string())
+            .clicked()
+        {
+            open = Some(i);
+        }
+    }
+    if let Some(i) = open {
+        open_bookmark(state, i);
+    }
+}
+
+/// Editor for the bookmarks. Changes are saved to the config on close.
+pub fn bookmarks_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = true;
+    let mut save = false;
+    let mut open_index = None;
+    let mut delete = None;
+    let mut add_current = false;
+    egui::Window::new("Bookmarks")
+        .open(&mut open)
+        .default_size([520.0, 320.0])
+        .show(ctx, |ui| {
+            if ui
+                .add_enabled(state.source.is_some(), egui::Button::new("★ Bookmark Current File"))
+                .clicked()
+            {
+                add_current = true;
+            }
+            ui.separator();
+            if state.config.bookmarks.is_empty() {
+                ui.weak("No bookmarks yet");
+            }
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("bookmarks_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (i, bookmark) in state.config.bookmarks.iter_mut().enumerate() {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut bookmark.label)
+                                    .hint_text("Label")
+                                    .desired_width(140.0),
+                            );
+                            ui.add(
+                                egui::TextEdit::singleline(&mut bookmark.tags)
+                                    .hint_text("tags, comma separated")
+                                    .desired_width(140.0),
+                            );
+                            let path = bookmark.path.display().to_string();
+                            if bookmark.path.exists() {
+                                ui.monospace(path);
+                            } else {
+                                ui.colored_label(GruvboxMaterial::red(255), path)
+                                    .on_hover_text("File not found");
+                            }
+                            ui.horizontal(|ui| {
+                                if ui.small_button("Open").clicked() {
+                                    open_index = Some(i);
+                                }
+                                if ui
+                                    .small_button("✖")
+                                    .on_hover_text("Remove bookmark")
+                                    .clicked()
+                                {
+                                    delete = Some(i);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.label(
+                RichText::new("Ctrl+P searches bookmarks by label, path or #tag.")
+                    .small()
+                    .weak(),
+            );
+            if ui.button("Save").clicked() {
+                save = true;
+            }
+        });
+    if add_current {
+        bookmark_current(state);
+    }
+    if let Some(i) = delete {
+        state.config.bookmarks.remove(i);
+        save = true;
+    }
+    if let Some(i) = open_index {
+        open_bookmark(state, i);
+    }
+    if !open {
+        state.show_bookmarks = false;
+        save = true;
+    }
+    if save {
+        save_bookmarks(state);
+    }
+}
+
+/// Quick Open (Ctrl+P): type to filter the bookmarks, Enter opens the first match.
+pub fn quick_open_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(query) = &mut state.quick_open else {
+        return;
+    };
+    let mut open = true;
+    let mut chosen = None;
+    egui::Window::new("Quick Open")
+        .open(&mut open)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(query)
+                    .hint_text("Search bookmarks (label, path, #tag)")
+                    .desired_width(f32::INFINITY),
+            );
+            response.request_focus();
+            let matches: Vec<usize> = state
+                .config
+                .bookmarks
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.matches(query))
+                .map(|(i, _)| i)
+                .collect();
+            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                chosen = matches.first().copied();
+            }
+            if state.config.bookmarks.is_empty() {
+                ui.weak("No bookmarks yet: use File > Bookmarks... to add some");
+            } else if matches.is_empty() {
+                ui.weak("No match");
+            }
+            for i in matches {
+                let bookmark = &state.config.bookmarks[i];
+                if ui
+                    .selectable_label(false, &bookmark.label)
+                    .on_hover_text(bookmark.path.display().to_string())
+                    .clicked()
+                {
+                    chosen = Some(i);
+                }
+            }
+        });
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        open = false;
+    }
+    if let Some(i) = chosen {
+        state.quick_open = None;
+        open_bookmark(state, i);
+    } else if !open {
+        state.quick_open = None;
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+
+use crate::state::AppState;
+use crate::ui::main_ui::undo;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::query_engine::panic_message;
+use eframe::egui::{self, RichText};
+
+// ─── Error Boundary ───────────────────────────────────────────────────────────
+//
+// A panic while updating the UI is caught in `MyApp::update` and replaces the
+// window content with this screen instead of closing the app. The hook keeps
+// the location and backtrace of the last panic for the diagnostics.
+
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Record the location and backtrace of every panic (still printed as usual).
+pub fn install_panic_hook() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let location = info.location().map(|l| l.to_string()).unwrap_or_default();
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        if let Ok(mut last) = LAST_PANIC.lock() {
+            *last = Some(format!("at {}\n\n{}", location, backtrace));
+        }
+        default(info);
+    }));
+}
+
+/// Diagnostics for a caught panic: message, where it happened, and what was
+/// loaded at the time.
+pub fn crash_report(state: &AppState, payload: &(dyn std::any::Any + Send)) -> String {
+    let details = LAST_PANIC
+        .lock()
+        .ok()
+        .and_then(|mut last| last.take())
+        .unwrap_or_default();
+    let mut report = format!(
+        "dafer-utils {} ({} {})\n\nPanic: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        panic_message(payload)
+    );
+    if let Some(source) = &state.source {
+        report.push_str(&format!(
+            "\nSource: {} ({})\n",
+            source.path.display(),
+            source.source_type
+        ));
+    }
+    report.push_str(&format!("\nPipeline ({} steps):\n", state.operations.len()));
+    for (i, op) in state.operations.iter().enumerate() {
+        report.push_str(&format!("  {}. {}\n", i + 1, op));
+    }
+    report.push_str(&format!("\n{}\n", details));
+    report
+}
+
+/// Shown in place of the UI after a caught panic.
+pub fn crash_ui(ctx: &egui::Context, state: &mut AppState) {
+    let Some(report) = state.crash.clone() else {
+        return;
+    };
+    egui::CentralPanel::default().show(ctx, |ui| {
+        ui.add_space(20.0);
+        ui.heading(RichText::new("Something went wrong").color(GruvboxMaterial::red(255)));
+        ui.label(
+            "An internal error stopped the view. The pipeline is kept; undo the \
+             last step if it caused the error, or try again.",
+        );
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            if ui.button("Copy diagnostics").clicked() {
+                ctx.copy_text(report.clone());
+                state.notifications.info("Diagnostics copied");
+            }
+            if ui
+                .add_enabled(
+                    !state.operations.is_empty(),
+                    egui::Button::new("Undo last step"),
+                )
+                .clicked()
+            {
+                undo(state);
+                state.crash = None;
+            }
+            if ui.button("Try again").clicked() {
+                state.crash = None;
+            }
+        });
+        ui.separator();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.label(RichText::new(&report).monospace().small());
+        });
+    });
+}
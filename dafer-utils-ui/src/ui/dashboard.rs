@@ -0,0 +1,340 @@
+use crate::enums::PlotType;
+use crate::state::{AppState, DashboardData};
+use crate::ui::modify::{filter_op_combo, filter_options_inputs, filter_value_inputs};
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use crate::ui::visualize::{PlotView, extract_series, render_histogram, render_xy_plot};
+use dafer_utils::analysis::ColumnRole;
+use dafer_utils::operations::Operation;
+use dafer_utils::plot::{DashboardPlot, PlotConfig};
+use dafer_utils::query_engine;
+use eframe::egui;
+
+/// Height of each plot in the dashboard grid.
+const DASHBOARD_PLOT_HEIGHT: f32 = 240.0;
+
+/// egui_plot link group shared by dashboard plots when X axes are linked;
+/// egui_plot keeps the shared bounds for the group in memory.
+const DASHBOARD_LINK_GROUP: &str = "dashboard_x_link";
+
+/// Dashboard tab: several plots in a grid, each with its own type, X/Y columns
+/// and filters. Filters are applied on top of the pipeline result, and all
+/// plots refresh whenever the pipeline changes. Saved in workspace files.
+pub fn dashboard_ui(ui: &mut egui::Ui, state: &mut AppState) {
+    if state.source.is_none() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(40.0);
+            ui.heading("No data loaded");
+            ui.label("Load a file in the Load & Preview tab first.");
+        });
+        return;
+    }
+
+    if state.preview_df.is_none() {
+        ui.label("Preview not available. Load data first.");
+        return;
+    }
+
+    if state.dashboard_dirty {
+        recompute_dashboard(state);
+    }
+
+    // ── Toolbar ──
+    ui.horizontal(|ui| {
+        if ui
+            .button("+ Add current plot")
+            .on_hover_text("Copy the plot setup of the Visualize tab")
+            .clicked()
+        {
+            let config = state.plot_config();
+            add_plot(state, config);
+        }
+        if ui.button("+ Add empty plot").clicked() {
+            add_plot(state, PlotConfig::default());
+        }
+        ui.separator();
+        ui.label("Columns:");
+        ui.add(egui::DragValue::new(&mut state.dashboard_grid_columns).range(1..=4));
+        ui.checkbox(&mut state.dashboard_link_x, "Link X axes")
+            .on_hover_text("Zoom and pan all X/Y plots together along X");
+        if ui.button("Reset Zoom").clicked() {
+            state.plot_reset_counter += 1;
+        }
+    });
+
+    ui.separator();
+
+    if state.dashboard.is_empty() {
+        ui.label("No plots yet. Add the current plot from the Visualize tab or an empty one.");
+        return;
+    }
+
+    // ── Plot Grid ──
+    let n_cols = state.dashboard_grid_columns.max(1);
+    let col_names = state.column_names.clone();
+    let mut to_remove: Option<usize> = None;
+    egui::ScrollArea::vertical()
+        .id_salt("dashboard_scroll")
+        .show(ui, |ui| {
+            for row_start in (0..state.dashboard.len()).step_by(n_cols) {
+                ui.columns(n_cols, |cols| {
+                    for (offset, col_ui) in cols.iter_mut().enumerate() {
+                        let idx = row_start + offset;
+                        if idx >= state.dashboard.len() {
+                            break;
+                        }
+                        col_ui.group(|ui| {
+                            if plot_card(ui, state, idx, &col_names) {
+                                to_remove = Some(idx);
+                            }
+                        });
+                    }
+                });
+                ui.add_space(4.0);
+            }
+        });
+
+    if let Some(idx) = to_remove {
+        state.dashboard.remove(idx);
+        state.dashboard_dirty = true;
+    }
+}
+
+fn add_plot(state: &mut AppState, config: PlotConfig) {
+    state.dashboard.push(DashboardPlot {
+        title: format!("Plot {}", state.dashboard.len() + 1),
+        config,
+        filters: Vec::new(),
+    });
+    state.dashboard_dirty = true;
+}
+
+/// One dashboard cell: title, compact controls and the plot.
+/// Returns true if the plot should be removed.
+fn plot_card(ui: &mut egui::Ui, state: &mut AppState, idx: usize, col_names: &[String]) -> bool {
+    let mut remove = false;
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut state.dashboard[idx].title).desired_width(140.0));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("✖").on_hover_text("Remove plot").clicked() {
+                remove = true;
+            }
+        });
+    });
+
+    // ── Plot setup ──
+    ui.horizontal_wrapped(|ui| {
+        let config = &mut state.dashboard[idx].config;
+        egui::ComboBox::from_id_salt(("dashboard_type", idx))
+            .selected_text(config.plot_type.to_string())
+            .show_ui(ui, |ui| {
+                for pt in PlotType::all() {
+                    changed |= ui
+                        .selectable_value(&mut config.plot_type, *pt, pt.to_string())
+                        .changed();
+                }
+            });
+
+        egui::ComboBox::from_id_salt(("dashboard_x", idx))
+            .selected_text(if config.x.is_empty() {
+                "X: (select)".to_string()
+            } else {
+                format!("X: {}", config.x)
+            })
+            .show_ui(ui, |ui| {
+                for name in col_names {
+                    changed |= ui
+                        .selectable_value(&mut config.x, name.clone(), name)
+                        .changed();
+                }
+            });
+
+        ui.menu_button(format!("Y ({})", config.y_columns.len()), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for name in col_names {
+                        let mut on = config.y_columns.contains(name);
+                        if !on && state.column_roles.get(name) == Some(&ColumnRole::Id) {
+                            continue;
+                        }
+                        if ui.checkbox(&mut on, name).changed() {
+                            if on {
+                                config.y_columns.push(name.clone());
+                            } else {
+                                config.y_columns.retain(|c| c != name);
+                            }
+                            changed = true;
+                        }
+                    }
+                });
+        });
+
+        if config.plot_type == PlotType::Histogram {
+            ui.label("Bins:");
+            changed |= ui
+                .add(egui::DragValue::new(&mut config.histogram_bins).range(5..=200))
+                .changed();
+        }
+
+        changed |= filters_menu(ui, state, idx, col_names);
+    });
+
+    if changed {
+        state.dashboard_dirty = true;
+    }
+
+    // ── Plot ──
+    let plot = &state.dashboard[idx];
+    let id = format!("dashboard_{idx}_{}", state.plot_reset_counter);
+    let units = state.column_units();
+    let mut view = PlotView {
+        height: DASHBOARD_PLOT_HEIGHT,
+        link_x: None,
+        units: &units,
+        fits: &[],
+    };
+    match state.dashboard_data.get(idx) {
+        Some(DashboardData {
+            error: Some(err), ..
+        }) => {
+            ui.colored_label(GruvboxMaterial::red(255), err);
+        }
+        Some(data) if plot.config.plot_type == PlotType::Histogram => match &data.df {
+            Some(df) => render_histogram(ui, &id, df, &plot.config, &view),
+            None => {
+                ui.label("No data.");
+            }
+        },
+        Some(data) if data.series.is_empty() => {
+            ui.label("Select valid X and Y columns (must be numeric) to plot.");
+        }
+        Some(data) => {
+            // Histograms have a value axis, so only X/Y plots take part in linking
+            view.link_x = state.dashboard_link_x.then_some(DASHBOARD_LINK_GROUP);
+            render_xy_plot(
+                ui,
+                &id,
+                &plot.config,
+                &data.series,
+                data.x_is_datetime,
+                &view,
+            );
+        }
+        None => {
+            ui.label("Refreshing...");
+        }
+    }
+
+    remove
+}
+
+/// Per-plot filter list with an inline builder. Returns true if filters changed.
+fn filters_menu(ui: &mut egui::Ui, state: &mut AppState, idx: usize, col_names: &[String]) -> bool {
+    let mut changed = false;
+    let n_filters = state.dashboard[idx].filters.len();
+    ui.menu_button(format!("Filters ({n_filters})"), |ui| {
+        let mut to_remove: Option<usize> = None;
+        for (i, filter) in state.dashboard[idx].filters.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(filter.to_string());
+                if ui.small_button("✖").clicked() {
+                    to_remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = to_remove {
+            state.dashboard[idx].filters.remove(i);
+            changed = true;
+        }
+        if n_filters > 0 {
+            ui.separator();
+        }
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt(("dashboard_filter_col", idx))
+                .selected_text(if state.dashboard_filter_column.is_empty() {
+                    "(column)"
+                } else {
+                    state.dashboard_filter_column.as_str()
+                })
+                .show_ui(ui, |ui| {
+                    for name in col_names {
+                        ui.selectable_value(&mut state.dashboard_filter_column, name.clone(), name);
+                    }
+                });
+            filter_op_combo(
+                ui,
+                egui::ComboBox::from_id_salt(("dashboard_filter_op", idx)),
+                &mut state.dashboard_filter_op,
+            );
+            filter_options_inputs(ui, &mut state.dashboard_filter_options);
+            let kind = state.column_kind(&state.dashboard_filter_column);
+            let invalid = filter_value_inputs(
+                ui,
+                "dashboard_filter_value",
+                &mut state.dashboard_filter_op,
+                &mut state.dashboard_filter_value,
+                state.dashboard_filter_options.value_is_column,
+                col_names,
+                kind,
+            );
+            if let Some(reason) = &invalid {
+                ui.colored_label(GruvboxMaterial::red(255), reason);
+            }
+            let can_add = !state.dashboard_filter_column.is_empty() && invalid.is_none();
+            if ui.add_enabled(can_add, egui::Button::new("Add")).clicked() {
+                state.dashboard[idx].filters.push(Operation::Filter {
+                    column: state.dashboard_filter_column.clone(),
+                    op: state.dashboard_filter_op.clone(),
+                    value: state.dashboard_filter_value.clone(),
+                    options: state.dashboard_filter_options,
+                });
+                changed = true;
+            }
+        });
+    });
+    changed
+}
+
+/// Rebuild the data of every dashboard plot from the pipeline result.
+fn recompute_dashboard(state: &mut AppState) {
+    state.dashboard_dirty = false;
+
+    // Use full dataset for plotting (fall back to preview if unavailable)
+    let base = state.full_df.as_ref().or(state.preview_df.as_ref());
+    state.dashboard_data = state
+        .dashboard
+        .iter()
+        .map(|plot| {
+            let Some(base) = base else {
+                return DashboardData::default();
+            };
+            let df = if plot.filters.is_empty() {
+                Ok(base.clone())
+            } else {
+                query_engine::apply_to_frame(base, &plot.filters)
+            };
+            match df {
+                Ok(df) => {
+                    let (series, x_is_datetime) = if plot.config.plot_type == PlotType::Histogram {
+                        (Vec::new(), false)
+                    } else {
+                        extract_series(&df, &plot.config.x, &plot.config.y_columns)
+                    };
+                    DashboardData {
+                        df: Some(df),
+                        series,
+                        x_is_datetime,
+                        error: None,
+                    }
+                }
+                Err(e) => DashboardData {
+                    error: Some(format!("Filter error: {}", e)),
+                    ..Default::default()
+                },
+            }
+        })
+        .collect();
+}
@@ -0,0 +1,134 @@
+use crate::state::AppState;
+use crate::ui::load_preview::stats_table;
+use crate::ui::modify::{builder_panel, export_panel, pipeline_panel};
+use crate::ui::table::data_table;
+use crate::ui::visualize::{plot_controls, plot_panel};
+use dafer_utils::display::{DockPanel, DockRegion};
+use eframe::egui;
+
+/// Dock tab: a user-arranged workbench of panels.
+///
+/// Each panel (pipeline, builder, export, table, stats, plot) is assigned to a
+/// region (left/right/top/bottom/center or hidden). Side regions are resizable
+/// panels; panels sharing a region are stacked and split its height evenly.
+/// The arrangement is saved in the app config.
+pub fn dock_ui(ui: &mut egui::Ui, state: &mut AppState) {
+    if state.source.is_none() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(40.0);
+            ui.heading("No data loaded");
+            ui.label("Load a file in the Load & Preview tab first.");
+        });
+        return;
+    }
+
+    layout_editor(ui, state);
+    ui.separator();
+
+    let layout = state.config.dock.clone();
+    let left = layout.panels_in(DockRegion::Left);
+    let right = layout.panels_in(DockRegion::Right);
+    let top = layout.panels_in(DockRegion::Top);
+    let bottom = layout.panels_in(DockRegion::Bottom);
+    let center = layout.panels_in(DockRegion::Center);
+
+    if !top.is_empty() {
+        egui::TopBottomPanel::top("dock_top")
+            .resizable(true)
+            .default_height(200.0)
+            .show_inside(ui, |ui| render_region(ui, state, &top));
+    }
+    if !bottom.is_empty() {
+        egui::TopBottomPanel::bottom("dock_bottom")
+            .resizable(true)
+            .default_height(200.0)
+            .show_inside(ui, |ui| render_region(ui, state, &bottom));
+    }
+    if !left.is_empty() {
+        egui::SidePanel::left("dock_left")
+            .resizable(true)
+            .default_width(320.0)
+            .show_inside(ui, |ui| render_region(ui, state, &left));
+    }
+    if !right.is_empty() {
+        egui::SidePanel::right("dock_right")
+            .resizable(true)
+            .default_width(420.0)
+            .show_inside(ui, |ui| render_region(ui, state, &right));
+    }
+    egui::CentralPanel::default().show_inside(ui, |ui| render_region(ui, state, &center));
+}
+
+/// Region selector per panel. Changes are saved to the app config immediately.
+fn layout_editor(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal_wrapped(|ui| {
+        for panel in DockPanel::all() {
+            let mut region = state.config.dock.region(*panel);
+            egui::ComboBox::from_id_salt(("dock_region", *panel))
+                .selected_text(format!("{}: {}", panel, region))
+                .show_ui(ui, |ui| {
+                    for r in DockRegion::all() {
+                        ui.selectable_value(&mut region, *r, r.to_string());
+                    }
+                });
+            if region != state.config.dock.region(*panel) {
+                state.config.dock.set_region(*panel, region);
+                if let Err(e) = state.config.save() {
+                    state
+                        .notifications
+                        .error(format!("Config save error: {}", e));
+                }
+            }
+        }
+        if ui.button("Reset layout").clicked() {
+            state.config.dock = Default::default();
+            if let Err(e) = state.config.save() {
+                state
+                    .notifications
+                    .error(format!("Config save error: {}", e));
+            }
+        }
+    });
+}
+
+/// Stack the panels of a region vertically, splitting the height evenly.
+fn render_region(ui: &mut egui::Ui, state: &mut AppState, panels: &[DockPanel]) {
+    if panels.is_empty() {
+        return;
+    }
+    let spacing = ui.spacing().item_spacing.y;
+    let height = (ui.available_height() / panels.len() as f32 - spacing).max(80.0);
+    for panel in panels {
+        let width = ui.available_width();
+        ui.allocate_ui(egui::vec2(width, height), |ui| {
+            ui.set_min_height(height);
+            render_panel(ui, state, *panel);
+        });
+    }
+}
+
+fn render_panel(ui: &mut egui::Ui, state: &mut AppState, panel: DockPanel) {
+    match panel {
+        DockPanel::Pipeline => pipeline_panel(ui, state),
+        DockPanel::Builder => builder_panel(ui, state),
+        DockPanel::Export => export_panel(ui, state),
+        DockPanel::Table => {
+            let height = ui.available_height();
+            data_table(ui, state, "dock", height);
+        }
+        DockPanel::Stats => {
+            ui.strong("Column Statistics");
+            ui.separator();
+            stats_table(ui, state, "dock");
+        }
+        DockPanel::Plot => {
+            if state.preview_df.is_none() {
+                ui.label("Preview not available.");
+                return;
+            }
+            plot_controls(ui, state);
+            ui.separator();
+            plot_panel(ui, state);
+        }
+    }
+}
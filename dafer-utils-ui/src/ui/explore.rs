@@ -0,0 +1,233 @@
+use crate::app::VIEWER;
+use crate::state::AppState;
+use crate::ui::analysis::frame_grid;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use crate::ui::table::grid_table;
+use dafer_utils::analysis::{self, CrosstabAgg, PivotSpec};
+use dafer_utils::execution;
+use dafer_utils::query_engine;
+use eframe::egui::{self, RichText};
+
+/// Area of the pivot a field is dropped into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PivotArea {
+    Rows,
+    Columns,
+    Values,
+}
+
+impl PivotArea {
+    fn all() -> [PivotArea; 3] {
+        [PivotArea::Rows, PivotArea::Columns, PivotArea::Values]
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            PivotArea::Rows => "Rows",
+            PivotArea::Columns => "Columns",
+            PivotArea::Values => "Values",
+        }
+    }
+
+    fn fields(self, spec: &mut PivotSpec) -> &mut Vec<String> {
+        match self {
+            PivotArea::Rows => &mut spec.rows,
+            PivotArea::Columns => &mut spec.columns,
+            PivotArea::Values => &mut spec.values,
+        }
+    }
+}
+
+/// Explore tab: pivot table builder.
+///
+/// Fields are dragged from the list into Rows, Columns and Values (or added
+/// from their + menu); a field sits in one area at a time. The pivot is
+/// computed over the full pipeline result whenever the layout changes.
+pub fn explore_ui(ui: &mut egui::Ui, state: &mut AppState) {
+    if state.source.is_none() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(40.0);
+            ui.heading("No data loaded");
+            ui.label("Load a file in the Load & Preview tab first.");
+        });
+        return;
+    }
+
+    egui::SidePanel::left("explore_fields")
+        .resizable(true)
+        .default_width(160.0)
+        .show_inside(ui, |ui| {
+            ui.strong("Fields");
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for name in state.column_names.clone() {
+                    field_source(ui, state, &name);
+                }
+            });
+        });
+
+    egui::CentralPanel::default().show_inside(ui, |ui| {
+        ui.horizontal_top(|ui| {
+            for area in PivotArea::all() {
+                drop_area(ui, state, area);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Aggregate:");
+            egui::ComboBox::from_id_salt("pivot_agg")
+                .selected_text(state.pivot.agg.to_string())
+                .show_ui(ui, |ui| {
+                    for agg in CrosstabAgg::all() {
+                        ui.selectable_value(&mut state.pivot.agg, *agg, agg.to_string());
+                    }
+                });
+            if ui.button("Refresh").clicked() {
+                state.pivot_computed = None;
+            }
+            if !VIEWER
+                && ui
+                    .add_enabled(
+                        state.pivot_result.is_some(),
+                        egui::Button::new("Export CSV..."),
+                    )
+                    .clicked()
+            {
+                export_pivot(state);
+            }
+            if ui.button("Clear").clicked() {
+                state.pivot = PivotSpec::default();
+            }
+        });
+        ui.separator();
+
+        if state.pivot_computed.as_ref() != Some(&state.pivot) {
+            compute_pivot(state);
+        }
+        match &state.pivot_result {
+            Some((headers, rows)) => {
+                ui.label(
+                    RichText::new(format!("{} rows × {} columns", rows.len(), headers.len()))
+                        .small()
+                        .color(GruvboxMaterial::fg3(200)),
+                );
+                grid_table(ui, "pivot", headers, rows, state.pivot.rows.len());
+            }
+            None => {
+                ui.label(
+                    RichText::new("Drag fields into Rows, Columns or Values")
+                        .color(GruvboxMaterial::fg3(200)),
+                );
+            }
+        }
+    });
+}
+
+/// A draggable field in the list, with a menu to add it without dragging.
+fn field_source(ui: &mut egui::Ui, state: &mut AppState, name: &str) {
+    ui.horizontal(|ui| {
+        ui.menu_button("+", |ui| {
+            for area in PivotArea::all() {
+                if ui.button(format!("Add to {}", area.label())).clicked() {
+                    place(&mut state.pivot, area, name);
+                    ui.close();
+                }
+            }
+        });
+        ui.dnd_drag_source(
+            egui::Id::new(("pivot_field", name)),
+            name.to_string(),
+            |ui| {
+                ui.label(name);
+            },
+        );
+    });
+}
+
+/// One pivot area: its fields (draggable to another area, removable) and a
+/// drop zone for new ones.
+fn drop_area(ui: &mut egui::Ui, state: &mut AppState, area: PivotArea) {
+    let mut removed = None;
+    let frame = egui::Frame::group(ui.style());
+    let (_, dropped) = ui.dnd_drop_zone::<String, ()>(frame, |ui| {
+        ui.set_min_size(egui::vec2(160.0, 80.0));
+        ui.vertical(|ui| {
+            ui.strong(area.label());
+            let fields = area.fields(&mut state.pivot).clone();
+            if fields.is_empty() {
+                ui.label(RichText::new("drop fields here").small().weak());
+            }
+            for field in fields {
+                ui.horizontal(|ui| {
+                    ui.dnd_drag_source(
+                        egui::Id::new(("pivot_chip", area.label(), &field)),
+                        field.clone(),
+                        |ui| {
+                            ui.label(&field);
+                        },
+                    );
+                    if ui.small_button("X").clicked() {
+                        removed = Some(field.clone());
+                    }
+                });
+            }
+            if area == PivotArea::Values && !state.pivot.agg.needs_value() {
+                ui.label(RichText::new("(not used by Count)").small().weak());
+            }
+        });
+    });
+    if let Some(field) = removed {
+        area.fields(&mut state.pivot).retain(|f| *f != field);
+    }
+    if let Some(field) = dropped {
+        place(&mut state.pivot, area, &field);
+    }
+}
+
+/// Move `field` into `area` (out of any other area).
+fn place(spec: &mut PivotSpec, area: PivotArea, field: &str) {
+    for other in PivotArea::all() {
+        other.fields(spec).retain(|f| f != field);
+    }
+    area.fields(spec).push(field.to_string());
+}
+
+/// Recompute the pivot over the full pipeline result.
+fn compute_pivot(state: &mut AppState) {
+    state.pivot_computed = Some(state.pivot.clone());
+    let spec = &state.pivot;
+    let ready = !spec.rows.is_empty() || !spec.columns.is_empty() || !spec.values.is_empty();
+    let Some(source) = state.source.as_ref().filter(|_| ready) else {
+        state.pivot_result = None;
+        return;
+    };
+    if spec.agg.needs_value() && spec.values.is_empty() {
+        state.pivot_result = None;
+        return;
+    }
+    let result = query_engine::build_lazy(source, &state.operations)
+        .and_then(|lf| analysis::pivot(lf, spec));
+    match result {
+        Ok(table) => state.pivot_result = Some(frame_grid(&table)),
+        Err(e) => {
+            state.pivot_result = None;
+            state.notifications.error(format!("Pivot error: {}", e));
+        }
+    }
+}
+
+fn export_pivot(state: &mut AppState) {
+    let Some((headers, rows)) = &state.pivot_result else {
+        return;
+    };
+    if let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV file", &["csv"])
+        .save_file()
+    {
+        match execution::export_grid_csv(headers, rows, &path) {
+            Ok(()) => state
+                .notifications
+                .info(format!("Pivot exported to {}", path.display())),
+            Err(e) => state.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::fixture::GOLDEN_ROWS;
+use eframe::egui::{self, RichText};
+
+// ─── Expected Output ──────────────────────────────────────────────────────────
+//
+// Pin the pipeline's current output, save it with the state file, and verify
+// later runs against it (automatically when the state is loaded).
+
+/// Edit > Expected Output submenu.
+pub fn fixture_menu(ui: &mut egui::Ui, state: &mut AppState) {
+    let has_source = state.source.is_some();
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::DragValue::new(&mut state.fixture_rows)
+                .range(1..=100_000)
+                .suffix(" rows"),
+        );
+        if ui
+            .add_enabled(has_source, egui::Button::new("Pin First Rows"))
+            .clicked()
+        {
+            state.pin_fixture(Some(state.fixture_rows));
+            ui.close();
+        }
+    });
+    if ui
+        .add_enabled(has_source, egui::Button::new("Pin Full Result"))
+        .on_hover_text(format!(
+            "Hash of every row; the first {} rows are kept to show differences",
+            GOLDEN_ROWS
+        ))
+        .clicked()
+    {
+        state.pin_fixture(None);
+        ui.close();
+    }
+    ui.separator();
+
+    let Some(fixture) = &state.fixture else {
+        ui.label(RichText::new("No expected output pinned").weak());
+        return;
+    };
+    let covered = match fixture.rows {
+        Some(n) => format!("first {} rows", n),
+        None => "full result".to_string(),
+    };
+    ui.label(
+        RichText::new(format!(
+            "Pinned {} ({}), {}",
+            covered, fixture.row_count, fixture.pinned_at
+        ))
+        .small()
+        .weak(),
+    );
+    if ui.button("Verify").clicked() {
+        state.verify_fixture();
+        ui.close();
+    }
+    if ui.button("Remove Expected Output").clicked() {
+        state.fixture = None;
+        state.fixture_report = None;
+        ui.close();
+    }
+}
+
+/// Result of the last verification.
+pub fn fixture_report_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(report) = &state.fixture_report else {
+        return;
+    };
+    let mut open = true;
+    egui::Window::new("Expected Output")
+        .open(&mut open)
+        .default_size([480.0, 300.0])
+        .show(ctx, |ui| {
+            if report.passed() {
+                ui.colored_label(
+                    GruvboxMaterial::green(255),
+                    "✔ The pipeline output matches the expected output.",
+                );
+                return;
+            }
+            ui.colored_label(
+                GruvboxMaterial::red(255),
+                "The pipeline output differs from the expected output:",
+            );
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for difference in &report.differences {
+                    ui.monospace(difference);
+                }
+            });
+        });
+    if !open {
+        state.fixture_report = None;
+    }
+}
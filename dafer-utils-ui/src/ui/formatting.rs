@@ -0,0 +1,124 @@
+use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::display::FormatRule;
+use eframe::egui::{self, Color32};
+
+/// Compute the background highlight for a cell from its column's formatting rules.
+/// The first matching rule wins. `range` is the numeric (min, max) of the column.
+pub fn cell_highlight(
+    rules: &[FormatRule],
+    text: &str,
+    range: Option<(f64, f64)>,
+) -> Option<Color32> {
+    let value = text.trim().parse::<f64>().ok().filter(|v| v.is_finite());
+    for rule in rules {
+        match rule {
+            FormatRule::HighlightNulls => {
+                if text.is_empty() {
+                    return Some(GruvboxMaterial::yellow(70));
+                }
+            }
+            FormatRule::OutsideRange { min, max } => {
+                if let Some(v) = value {
+                    if v < *min || v > *max {
+                        return Some(GruvboxMaterial::red(80));
+                    }
+                }
+            }
+            FormatRule::ColorScale => {
+                if let (Some(v), Some((lo, hi))) = (value, range) {
+                    let t = if hi > lo {
+                        ((v - lo) / (hi - lo)) as f32
+                    } else {
+                        0.5
+                    };
+                    return Some(lerp_color(
+                        GruvboxMaterial::blue(70),
+                        GruvboxMaterial::red(70),
+                        t.clamp(0.0, 1.0),
+                    ));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Paint the conditional-formatting background of a table cell, if a rule matches.
+pub fn paint_cell_highlight(ui: &egui::Ui, state: &AppState, col_idx: usize, text: &str) {
+    let Some(name) = state.cached_header_names.get(col_idx) else {
+        return;
+    };
+    let rules = state.display_config.rules(name);
+    if rules.is_empty() {
+        return;
+    }
+    let range = state.cached_column_ranges.get(col_idx).copied().flatten();
+    if let Some(color) = cell_highlight(rules, text, range) {
+        ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+    }
+}
+
+/// Linear interpolation between two colors (per channel, unmultiplied).
+fn lerp_color(a: Color32, b: Color32, t: f32) -> Color32 {
+    let mix = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+    Color32::from_rgba_unmultiplied(
+        mix(a.r(), b.r()),
+        mix(a.g(), b.g()),
+        mix(a.b(), b.b()),
+        mix(a.a(), b.a()),
+    )
+}
+
+/// Header context menu content for configuring a column's formatting rules.
+pub fn column_format_menu(ui: &mut egui::Ui, state: &mut AppState, column: &str) {
+    ui.strong(format!("Format: {}", column));
+    ui.separator();
+
+    let mut color_scale = state
+        .display_config
+        .has_rule(column, &FormatRule::ColorScale);
+    if ui.checkbox(&mut color_scale, "Color scale").changed() {
+        toggle_rule(state, column, FormatRule::ColorScale, color_scale);
+    }
+
+    let mut nulls = state
+        .display_config
+        .has_rule(column, &FormatRule::HighlightNulls);
+    if ui.checkbox(&mut nulls, "Highlight nulls").changed() {
+        toggle_rule(state, column, FormatRule::HighlightNulls, nulls);
+    }
+
+    ui.separator();
+    ui.label("Highlight outside range:");
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new(&mut state.format_range_min).prefix("min "));
+        ui.add(egui::DragValue::new(&mut state.format_range_max).prefix("max "));
+    });
+    let range_rule = FormatRule::OutsideRange {
+        min: state.format_range_min,
+        max: state.format_range_max,
+    };
+    ui.horizontal(|ui| {
+        if ui.button("Set range").clicked() {
+            state.display_config.set_rule(column, range_rule.clone());
+        }
+        if state.display_config.has_rule(column, &range_rule) && ui.button("Remove").clicked() {
+            state.display_config.remove_rule(column, &range_rule);
+        }
+    });
+
+    ui.separator();
+    if ui.button("Clear formatting").clicked() {
+        state.display_config.clear_column(column);
+        ui.close();
+    }
+}
+
+fn toggle_rule(state: &mut AppState, column: &str, rule: FormatRule, enabled: bool) {
+    if enabled {
+        state.display_config.set_rule(column, rule);
+    } else {
+        state.display_config.remove_rule(column, &rule);
+    }
+}
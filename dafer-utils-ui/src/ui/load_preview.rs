@@ -1,9 +1,266 @@
-use crate::state::AppState;
+use crate::state::{AppState, RawPreview};
+use crate::ui::analysis::show_rows;
+use crate::ui::bookmarks::bookmark_list;
+use crate::ui::main_ui::open_file;
 use crate::ui::palette::gruvbox_material::GruvboxMaterial;
-use dafer_utils::datasource::DataSource;
+use crate::ui::table::data_table;
+use crate::ui::tour::{TourTarget, highlight, open_sample, start_tour};
+use crate::ui::visualize::with_unit;
+use dafer_utils::analysis::ColumnRole;
+use dafer_utils::data_loader::{self, ColumnStats, DISTINCT_IS_APPROX};
+use dafer_utils::datasource::{DEFAULT_CHUNK_SIZE, DEFAULT_INFER_SCHEMA_LENGTH, DataSourceType};
+use dafer_utils::display;
+use dafer_utils::operations::{self, FilterOp, FilterOptions, Operation};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 
+/// Delimiter of the loaded CSV (detected on open), with a manual override.
+fn csv_import_options(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some(source) = &mut state.source else {
+        return;
+    };
+    if source.source_type != DataSourceType::Csv {
+        return;
+    }
+    let before = source.csv.clone();
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label("Delimiter")
+            .selected_text(data_loader::delimiter_name(&source.csv.delimiter))
+            .show_ui(ui, |ui| {
+                for d in data_loader::DELIMITER_CANDIDATES {
+                    ui.selectable_value(
+                        &mut source.csv.delimiter,
+                        d.to_string(),
+                        data_loader::delimiter_name(d),
+                    );
+                }
+            });
+        ui.add(
+            egui::TextEdit::singleline(&mut state.custom_delimiter)
+                .hint_text("custom, e.g. ~|~")
+                .desired_width(80.0),
+        );
+        if ui
+            .add_enabled(
+                !state.custom_delimiter.is_empty(),
+                egui::Button::new("Use").small(),
+            )
+            .on_hover_text("Delimiters longer than one character use a slower line reader")
+            .clicked()
+        {
+            source.csv.delimiter = state.custom_delimiter.clone();
+        }
+        if ui
+            .small_button("Detect")
+            .on_hover_text("Guess the delimiter from the first KB of the file")
+            .clicked()
+        {
+            match data_loader::sniff_delimiter(&source.path) {
+                Ok(d) => source.csv.delimiter = d,
+                Err(e) => state
+                    .notifications
+                    .error(format!("Delimiter detection failed: {}", e)),
+            }
+        }
+        if ui
+            .small_button("Raw Preview...")
+            .on_hover_text("Show the first lines as plain text to set header and skipped rows")
+            .clicked()
+        {
+            match data_loader::read_raw_lines(&source.path, RAW_PREVIEW_LINES) {
+                Ok(lines) => {
+                    state.raw_preview = Some(RawPreview {
+                        lines,
+                        options: source.csv.clone(),
+                    })
+                }
+                Err(e) => state.notifications.error(format!("Read error: {}", e)),
+            }
+        }
+        if !source.csv.has_header || source.csv.skip_rows > 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "header: {}, skip: {}",
+                    if source.csv.has_header { "yes" } else { "no" },
+                    source.csv.skip_rows
+                ))
+                .small()
+                .weak(),
+            );
+        }
+    });
+    ui.collapsing("Scan options", |ui| {
+        let mut full_inference = source.csv.infer_schema_length.is_none();
+        let mut rows = source
+            .csv
+            .infer_schema_length
+            .unwrap_or(DEFAULT_INFER_SCHEMA_LENGTH);
+        ui.horizontal(|ui| {
+            ui.label("Infer types from");
+            ui.add_enabled(
+                !full_inference,
+                egui::DragValue::new(&mut rows)
+                    .range(100..=10_000_000)
+                    .speed(100.0)
+                    .suffix(" rows"),
+            );
+            ui.checkbox(&mut full_inference, "whole file")
+                .on_hover_text("Exact types, but reads the file twice");
+        });
+        source.csv.infer_schema_length = (!full_inference).then_some(rows);
+        ui.checkbox(&mut source.csv.low_memory, "Low memory")
+            .on_hover_text("Slower parsing with a smaller peak memory footprint");
+        ui.checkbox(&mut source.csv.rechunk, "Rechunk")
+            .on_hover_text("Merge parsed chunks into contiguous memory (faster later operations)");
+        ui.horizontal(|ui| {
+            ui.label("Chunk size");
+            ui.add(
+                egui::DragValue::new(&mut source.csv.chunk_size)
+                    .range(1_000..=10_000_000)
+                    .speed(1_000.0)
+                    .suffix(" rows"),
+            );
+            if ui.small_button("Defaults").clicked() {
+                source.csv.infer_schema_length = Some(DEFAULT_INFER_SCHEMA_LENGTH);
+                source.csv.low_memory = false;
+                source.csv.rechunk = false;
+                source.csv.chunk_size = DEFAULT_CHUNK_SIZE;
+            }
+        });
+        if data_loader::needs_tokenizer(&source.csv.delimiter) {
+            ui.label(
+                egui::RichText::new("Not used with multi-character delimiters")
+                    .small()
+                    .weak(),
+            );
+        }
+    });
+    if source.csv != before {
+        // Columns detected under the old options no longer apply
+        source.auto_numeric_cols.clear();
+        source.auto_integer_cols.clear();
+        state.preview_dirty = true;
+        state.auto_cast_detected = false;
+    }
+}
+
+/// Lines shown in the Raw Preview window.
+const RAW_PREVIEW_LINES: usize = 50;
+
+/// The first lines of the file as plain text, with draft import options. Rows
+/// below show how the draft splits them; nothing is re-scanned until Apply.
+pub fn raw_preview_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(raw) = &mut state.raw_preview else {
+        return;
+    };
+    let mut open = true;
+    let mut apply = false;
+    egui::Window::new("Raw Preview")
+        .open(&mut open)
+        .default_size([640.0, 460.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Delimiter");
+                ui.add(egui::TextEdit::singleline(&mut raw.options.delimiter).desired_width(40.0))
+                    .on_hover_text("Use \\t for tab");
+                ui.checkbox(&mut raw.options.has_header, "Header row");
+                ui.label("Skip rows");
+                ui.add(
+                    egui::DragValue::new(&mut raw.options.skip_rows).range(0..=RAW_PREVIEW_LINES),
+                );
+            });
+            if raw.options.delimiter == "\\t" {
+                raw.options.delimiter = "\t".to_string();
+            }
+            ui.separator();
+
+            ui.strong("File");
+            egui::ScrollArea::both()
+                .id_salt("raw_lines")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for (i, line) in raw.lines.iter().enumerate() {
+                        let text =
+                            egui::RichText::new(format!("{:>3}  {}", i + 1, line)).monospace();
+                        let text = if i < raw.options.skip_rows {
+                            text.weak().strikethrough()
+                        } else if i == raw.options.skip_rows && raw.options.has_header {
+                            text.strong()
+                        } else {
+                            text
+                        };
+                        ui.label(text);
+                    }
+                });
+            ui.separator();
+
+            ui.strong("Parsed");
+            if raw.options.delimiter.is_empty() {
+                ui.label("Enter a delimiter.");
+            } else {
+                let rows: Vec<Vec<&str>> = raw
+                    .lines
+                    .iter()
+                    .skip(raw.options.skip_rows)
+                    .filter(|l| !l.is_empty())
+                    .take(10)
+                    .map(|l| data_loader::split_fields(l, &raw.options.delimiter))
+                    .collect();
+                let n_cols = rows.first().map_or(0, |r| r.len());
+                egui::ScrollArea::horizontal()
+                    .id_salt("raw_parsed")
+                    .show(ui, |ui| {
+                        egui::Grid::new("raw_parsed_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (i, row) in rows.iter().enumerate() {
+                                    for j in 0..n_cols {
+                                        let cell = row.get(j).copied().unwrap_or_default();
+                                        if i == 0 && raw.options.has_header {
+                                            ui.strong(cell);
+                                        } else {
+                                            ui.label(cell);
+                                        }
+                                    }
+                                    if row.len() > n_cols {
+                                        ui.colored_label(
+                                            GruvboxMaterial::red(255),
+                                            format!("+{} fields", row.len() - n_cols),
+                                        );
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.label(format!("{} columns", n_cols));
+            }
+            ui.add_space(4.0);
+            if ui
+                .add_enabled(
+                    !raw.options.delimiter.is_empty(),
+                    egui::Button::new("Apply"),
+                )
+                .clicked()
+            {
+                apply = true;
+            }
+        });
+
+    if apply {
+        if let (Some(raw), Some(source)) = (state.raw_preview.take(), &mut state.source) {
+            if source.csv != raw.options {
+                source.csv = raw.options;
+                source.auto_numeric_cols.clear();
+                source.auto_integer_cols.clear();
+                state.preview_dirty = true;
+                state.auto_cast_detected = false;
+            }
+        }
+    } else if !open {
+        state.raw_preview = None;
+    }
+}
+
 /// Data Loading & Preview tab.
 ///
 /// High-performance rendering: uses a pre-computed string cache (built in app.rs)
@@ -12,11 +269,9 @@ use egui_extras::{Column, TableBuilder};
 ///
 /// Features:
 /// - File picker (browse for CSV/Parquet)
-/// - Virtualized table with pre-computed strings
-/// - Clickable column headers for visual sorting
-/// - Cell/row/column selection + Ctrl+C copy
+/// - Virtualized table with pre-computed strings (see `ui::table`)
 /// - Alternate row striping
-/// - Column statistics and file metadata
+/// - Column statistics (with distribution sparklines) and file metadata
 pub fn load_preview_tab(ui: &mut egui::Ui, state: &mut AppState) {
     // ── File Picker ──
     ui.horizontal(|ui| {
@@ -33,169 +288,55 @@ pub fn load_preview_tab(ui: &mut egui::Ui, state: &mut AppState) {
                     .add_filter("Data files", &["csv", "tsv", "parquet", "pq"])
                     .pick_file()
                 {
-                    if let Some(ds) = DataSource::from_path(file.clone()) {
-                        state.source = Some(ds);
-                        state.operations.clear();
-                        state.redo_stack.clear();
-                        state.preview_dirty = true;
-                        state.sort_column = None;
-                        state.sort_descending = false;
-                        state.auto_cast_detected = false;
-                        state.selected_cell = None;
-                        state.selected_row = None;
-                        state.selected_col = None;
-                        state.status = format!("Loaded: {}", file.display());
-                    } else {
-                        state.status = format!("Unsupported: {}", file.display());
-                    }
+                    open_file(state, file);
                 }
             }
         });
     });
+    csv_import_options(ui, state);
 
     ui.separator();
 
-    // ── Handle Ctrl+C Copy ──
-    if ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::C)) {
-        copy_selection_to_clipboard(ui, state);
-    }
-
     // ── Preview Table (from pre-computed string cache) ──
     if !state.cached_cell_strings.is_empty() && !state.cached_header_names.is_empty() {
         let n_rows = state.cached_cell_strings.len();
         let n_cols = state.cached_header_names.len();
 
-        ui.label(format!("Preview ({n_rows} rows x {n_cols} cols)"));
+        ui.horizontal(|ui| {
+            ui.label(format!("Preview ({n_rows} rows x {n_cols} cols)"));
+            spread_preview_toggle(ui, state);
+        });
 
-        let text_height = ui.text_style_height(&egui::TextStyle::Body);
-        let row_height = text_height + 2.0;
-        let available = ui.available_size();
         // Limit table to ~50% so column stats remain visible
-        let table_height = (available.y * 0.5).max(150.0);
-
-        let header_names: Vec<String> = state.cached_header_names.clone();
-
-        egui::ScrollArea::horizontal()
-            .id_salt("preview_hscroll")
-            .show(ui, |ui| {
-                TableBuilder::new(ui)
-                    .id_salt("preview_data_table")
-                    .striped(true)
-                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                    .columns(Column::initial(100.0).at_least(60.0).clip(true).resizable(true), n_cols)
-                    .max_scroll_height(table_height)
-                    .header(row_height + 4.0, |mut header| {
-                        for (col_idx, name) in header_names.iter().enumerate() {
-                            header.col(|ui| {
-                                let is_sorted =
-                                    state.sort_column.as_deref() == Some(name.as_str());
-                                let label = if is_sorted {
-                                    if state.sort_descending {
-                                        format!("{} v", name)
-                                    } else {
-                                        format!("{} ^", name)
-                                    }
-                                } else {
-                                    name.clone()
-                                };
-
-                                let response = ui.add(
-                                    egui::Label::new(
-                                        egui::RichText::new(&label)
-                                            .strong()
-                                            .color(GruvboxMaterial::fg(255)),
-                                    )
-                                    .sense(egui::Sense::click()),
-                                );
-
-                                if response.clicked() {
-                                    if is_sorted {
-                                        state.sort_descending = !state.sort_descending;
-                                    } else {
-                                        state.sort_column = Some(name.clone());
-                                        state.sort_descending = false;
-                                    }
-                                    state.table_cache_dirty = true;
-                                }
-
-                                if response.secondary_clicked() {
-                                    state.selected_col = Some(col_idx);
-                                    state.selected_cell = None;
-                                    state.selected_row = None;
-                                }
-                            });
-                        }
-                    })
-                    .body(|body| {
-                        let has_selection = state.selected_cell.is_some()
-                            || state.selected_row.is_some()
-                            || state.selected_col.is_some();
-                        body.rows(row_height, n_rows, |mut row| {
-                            let visual_row = row.index();
-                            for col_idx in 0..n_cols {
-                                row.col(|ui| {
-                                    // Only render content for visible cells
-                                    if !ui.is_rect_visible(ui.max_rect()) {
-                                        return;
-                                    }
-                                    if has_selection {
-                                        let is_selected =
-                                            state.selected_cell == Some((visual_row, col_idx))
-                                                || state.selected_row == Some(visual_row)
-                                                || state.selected_col == Some(col_idx);
-                                        if is_selected {
-                                            ui.painter().rect_filled(
-                                                ui.max_rect(),
-                                                0.0,
-                                                GruvboxMaterial::blue(50),
-                                            );
-                                        }
-                                    }
-                                    ui.label(
-                                        state.cached_cell_strings[visual_row][col_idx].as_str(),
-                                    );
-                                });
-                            }
-                        });
-                    });
-            });
+        let table_height = (ui.available_height() * 0.5).max(150.0);
+        data_table(ui, state, "preview", table_height);
 
         ui.add_space(4.0);
 
         // ── Column Statistics (always visible) ──
         if !state.column_stats.is_empty() {
-            ui.strong("Column Statistics");
+            ui.horizontal(|ui| {
+                ui.strong("Column Statistics");
+                ui.separator();
+                fast_stats_controls(ui, state);
+                ui.separator();
+                units_menu(ui, state);
+                if state.stats_job.is_some() {
+                    ui.separator();
+                    ui.spinner();
+                    ui.label(
+                        egui::RichText::new("Preview rows only; computing statistics...")
+                            .small()
+                            .weak(),
+                    );
+                }
+            });
             ui.separator();
-            let stats_height = ui.available_height().max(80.0);
-            egui::ScrollArea::horizontal()
-                .id_salt("stats_hscroll")
-                .show(ui, |ui| {
-                    TableBuilder::new(ui)
-                        .id_salt("preview_stats_table")
-                        .striped(true)
-                        .cell_layout(egui::Layout::left_to_right(egui::Align::Min))
-                        .columns(Column::initial(80.0).at_least(60.0).clip(true).resizable(true), 6)
-                        .max_scroll_height(stats_height)
-                        .header(18.0, |mut header| {
-                            for label in &["Name", "Type", "Min", "Max", "Nulls", "Errors"] {
-                                header.col(|ui| {
-                                    ui.strong(*label);
-                                });
-                            }
-                        })
-                        .body(|body| {
-                            let stats = &state.column_stats;
-                            body.rows(16.0, stats.len(), |mut row| {
-                                let stat = &stats[row.index()];
-                                row.col(|ui| { ui.label(&stat.name); });
-                                row.col(|ui| { ui.label(&stat.dtype); });
-                                row.col(|ui| { ui.label(stat.min.as_deref().unwrap_or("-")); });
-                                row.col(|ui| { ui.label(stat.max.as_deref().unwrap_or("-")); });
-                                row.col(|ui| { ui.label(stat.null_count.to_string()); });
-                                row.col(|ui| { ui.label(stat.error_count.to_string()); });
-                            });
-                        });
-                });
+            let rect = ui
+                .scope(|ui| stats_table(ui, state, "preview"))
+                .response
+                .rect;
+            highlight(ui, state, TourTarget::Stats, rect);
         }
     } else if state.source.is_some() {
         ui.label("Loading preview...");
@@ -206,6 +347,17 @@ pub fn load_preview_tab(ui: &mut egui::Ui, state: &mut AppState) {
             ui.add_space(8.0);
             ui.label("Click 'Browse File' to load a CSV or Parquet file.");
             ui.label("Or use File > Open from the menu bar.");
+            ui.add_space(8.0);
+            ui.label("New here? Try the bundled Iris sample:");
+            ui.horizontal(|ui| {
+                if ui.button("Open Sample Dataset").clicked() {
+                    open_sample(state);
+                }
+                if ui.button("Start Guided Tour").clicked() {
+                    start_tour(state);
+                }
+            });
+            bookmark_list(ui, state);
         });
     }
 
@@ -214,16 +366,7 @@ pub fn load_preview_tab(ui: &mut egui::Ui, state: &mut AppState) {
         ui.add_space(4.0);
         ui.separator();
         if let Ok(meta) = std::fs::metadata(&source.path) {
-            let size = meta.len();
-            let size_str = if size > 1_000_000_000 {
-                format!("{:.2} GB", size as f64 / 1_000_000_000.0)
-            } else if size > 1_000_000 {
-                format!("{:.2} MB", size as f64 / 1_000_000.0)
-            } else if size > 1_000 {
-                format!("{:.1} KB", size as f64 / 1_000.0)
-            } else {
-                format!("{} bytes", size)
-            };
+            let size_str = display::format_bytes(meta.len());
             ui.label(format!(
                 "File: {} | Size: {} | {} | Pipeline: {} ops",
                 source.path.display(),
@@ -232,31 +375,395 @@ pub fn load_preview_tab(ui: &mut egui::Ui, state: &mut AppState) {
                 state.operations.len()
             ));
         }
+        if let Some(stat) = state.column_stats.iter().find(|s| s.span.is_some()) {
+            ui.label(format!(
+                "Time span ({}): {} → {} ({})",
+                stat.name,
+                stat.min.as_deref().unwrap_or("-"),
+                stat.max.as_deref().unwrap_or("-"),
+                stat.span.as_deref().unwrap_or("-")
+            ));
+        }
+        if let Some(last) = &state.last_export {
+            let rows = last
+                .rows
+                .map(|n| format!("{} rows", n))
+                .unwrap_or_else(|| "rows unknown".to_string());
+            ui.label(format!(
+                "Last export: {} ({}, {}) at {}",
+                last.output,
+                last.format,
+                rows,
+                last.time()
+            ));
+            if last.source_is_newer(&source.path) {
+                ui.colored_label(
+                    GruvboxMaterial::orange(255),
+                    "⚠ The source file changed after the last export; the output may be stale",
+                );
+            } else if last.operations != state.operations {
+                ui.colored_label(
+                    GruvboxMaterial::orange(255),
+                    "⚠ The pipeline changed after the last export",
+                );
+            }
+        }
     }
 }
 
-/// Copy the current selection (cell, row, or column) to clipboard.
-fn copy_selection_to_clipboard(ui: &egui::Ui, state: &AppState) {
-    let text = if let Some((row, col)) = state.selected_cell {
-        state
-            .cached_cell_strings
-            .get(row)
-            .and_then(|r| r.get(col))
-            .cloned()
-    } else if let Some(row) = state.selected_row {
-        state.cached_cell_strings.get(row).map(|r| r.join("\t"))
-    } else if let Some(col) = state.selected_col {
-        let vals: Vec<&str> = state
-            .cached_cell_strings
-            .iter()
-            .filter_map(|r| r.get(col).map(|s| s.as_str()))
-            .collect();
-        Some(vals.join("\n"))
-    } else {
-        None
+/// Banner while a big file is shown with sampled statistics, with a button to
+/// collect every row instead.
+pub fn big_file_banner(ctx: &egui::Context, state: &mut AppState) {
+    let Some(source) = state.source.as_mut() else {
+        return;
+    };
+    let Some(info) = state.stats_sample else {
+        return;
     };
+    if !source.big_file || source.stats_sample.is_none() {
+        return;
+    }
+    let size_gb = std::fs::metadata(&source.path)
+        .map(|m| m.len() as f64 / 1e9)
+        .unwrap_or(0.0);
+    egui::TopBottomPanel::top("big_file_banner")
+        .frame(
+            egui::Frame::new()
+                .fill(GruvboxMaterial::yellow(30))
+                .inner_margin(4.0),
+        )
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Big file ({:.1} GB): statistics, distributions and plots use a sample \
+                         of {} / {} rows and are approximate. The table and exports are exact.",
+                        size_gb, info.sample_rows, info.total_rows
+                    ))
+                    .color(GruvboxMaterial::yellow(255)),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button("Compute exact")
+                        .on_hover_text(
+                            "Collect every row; may take a long time and a lot of memory",
+                        )
+                        .clicked()
+                    {
+                        source.stats_sample = None;
+                        state.preview_dirty = true;
+                    }
+                });
+            });
+        });
+}
 
-    if let Some(text) = text {
-        ui.ctx().copy_text(text);
+/// Parquet only: take the preview from row groups across the file instead of
+/// its head, so late-file data shows up too.
+fn spread_preview_toggle(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some(source) = state.source.as_mut() else {
+        return;
+    };
+    if source.source_type != DataSourceType::Parquet {
+        return;
+    }
+    if ui
+        .checkbox(&mut source.spread_preview, "Spread across file")
+        .on_hover_text(format!(
+            "Preview rows from up to {} row groups across the file instead of the first rows",
+            data_loader::SPREAD_SLICES
+        ))
+        .changed()
+    {
+        state.preview_dirty = true;
+    }
+}
+
+/// "Fast stats" toggle for the current dataset plus the sample annotation.
+/// The sample size is stored on the data source, so it is saved with the pipeline.
+fn fast_stats_controls(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some(source) = state.source.as_mut() else {
+        return;
+    };
+    if let Some(n) = source.stats_sample {
+        state.stats_sample_rows = n;
+    }
+    let mut enabled = source.stats_sample.is_some();
+    let toggled = ui
+        .checkbox(&mut enabled, "Fast stats")
+        .on_hover_text("Compute statistics and plots on a random sample instead of all rows")
+        .changed();
+    let resized = ui
+        .add_enabled(
+            enabled,
+            egui::DragValue::new(&mut state.stats_sample_rows)
+                .range(1_000..=10_000_000)
+                .speed(1_000.0)
+                .suffix(" rows"),
+        )
+        .changed();
+    if toggled || (enabled && resized) {
+        source.stats_sample = enabled.then_some(state.stats_sample_rows);
+        state.preview_dirty = true;
+    }
+    let mut seeded = source.seed.is_some();
+    let mut seed = source.seed.unwrap_or(0);
+    let seed_toggled = ui
+        .add_enabled(enabled, egui::Checkbox::new(&mut seeded, "Seed"))
+        .on_hover_text("Sample the same rows on every run and machine (saved with the pipeline)")
+        .changed();
+    let seed_changed = ui
+        .add_enabled(enabled && seeded, egui::DragValue::new(&mut seed))
+        .changed();
+    if seed_toggled || seed_changed {
+        source.seed = seeded.then_some(seed);
+        state.preview_dirty = true;
+    }
+
+    if let Some(info) = state.stats_sample {
+        ui.label(
+            egui::RichText::new(format!(
+                "Sample of {} / {} rows (±{:.1}% at 95% for proportions; min/max may miss extremes)",
+                info.sample_rows,
+                info.total_rows,
+                info.margin_of_error() * 100.0
+            ))
+            .color(GruvboxMaterial::yellow(255)),
+        );
+    }
+}
+
+/// Editor for the physical unit of each column. Units are stored on the data
+/// source under the source column name, so they follow later renames.
+fn units_menu(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some(source) = state.source.as_mut() else {
+        return;
+    };
+    ui.menu_button("Units", |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("units_grid").num_columns(2).show(ui, |ui| {
+                    for name in &state.column_names {
+                        ui.label(name);
+                        match operations::source_column(name, &state.operations) {
+                            Some(source_name) => {
+                                let mut unit =
+                                    source.units.get(&source_name).cloned().unwrap_or_default();
+                                let edit = ui
+                                    .add(egui::TextEdit::singleline(&mut unit).desired_width(60.0));
+                                if edit.changed() {
+                                    if unit.is_empty() {
+                                        source.units.remove(&source_name);
+                                    } else {
+                                        source.units.insert(source_name, unit);
+                                    }
+                                }
+                            }
+                            None => {
+                                ui.weak("(derived)");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    });
+}
+
+/// Column statistics table (one row per column).
+/// `id` keeps scroll/column-width state separate per view.
+/// Non-zero null counts are links that show the null rows.
+pub fn stats_table(ui: &mut egui::Ui, state: &mut AppState, id: &str) {
+    let stats_height = ui.available_height().max(80.0);
+    let mut show_nulls: Option<String> = None;
+    // (column, role); `None` returns the column to its inferred role
+    let mut set_role: Option<(String, Option<ColumnRole>)> = None;
+    egui::ScrollArea::horizontal()
+        .id_salt((id, "stats_hscroll"))
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt((id, "stats_table"))
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Min))
+                .columns(
+                    Column::initial(80.0)
+                        .at_least(60.0)
+                        .clip(true)
+                        .resizable(true),
+                    9,
+                )
+                .column(
+                    Column::initial(110.0)
+                        .at_least(60.0)
+                        .clip(true)
+                        .resizable(true),
+                )
+                .max_scroll_height(stats_height)
+                .header(18.0, |mut header| {
+                    for label in &[
+                        "Name",
+                        "Type",
+                        "Role",
+                        "Min",
+                        "Max",
+                        "Nulls",
+                        "Distinct",
+                        "Errors",
+                        "Details",
+                        "Distribution",
+                    ] {
+                        header.col(|ui| {
+                            ui.strong(*label);
+                        });
+                    }
+                })
+                .body(|body| {
+                    let stats = &state.column_stats;
+                    let units = state.column_units();
+                    let roles = &state.column_roles;
+                    let overrides = &state.role_overrides;
+                    body.rows(16.0, stats.len(), |mut row| {
+                        let stat = &stats[row.index()];
+                        let role = roles.get(&stat.name).copied();
+                        row.col(|ui| {
+                            ui.label(with_unit(&stat.name, &units));
+                        });
+                        row.col(|ui| {
+                            ui.label(&stat.dtype);
+                        });
+                        row.col(|ui| {
+                            let tagged = overrides.contains_key(&stat.name);
+                            let text = match role {
+                                Some(r) if tagged => format!("{r} ✎"),
+                                Some(r) => r.to_string(),
+                                None => "-".to_string(),
+                            };
+                            egui::ComboBox::from_id_salt((id, "role", row.index()))
+                                .selected_text(text)
+                                .width(80.0)
+                                .show_ui(ui, |ui| {
+                                    if ui.selectable_label(!tagged, "Auto").clicked() {
+                                        set_role = Some((stat.name.clone(), None));
+                                    }
+                                    for r in ColumnRole::all() {
+                                        if ui
+                                            .selectable_label(
+                                                tagged && role == Some(*r),
+                                                r.to_string(),
+                                            )
+                                            .clicked()
+                                        {
+                                            set_role = Some((stat.name.clone(), Some(*r)));
+                                        }
+                                    }
+                                });
+                        });
+                        row.col(|ui| {
+                            ui.label(stat.min.as_deref().unwrap_or("-"));
+                        });
+                        row.col(|ui| {
+                            ui.label(stat.max.as_deref().unwrap_or("-"));
+                        });
+                        row.col(|ui| {
+                            if stat.null_count == 0 {
+                                ui.label("0");
+                            } else if ui
+                                .link(stat.null_count.to_string())
+                                .on_hover_text("Show rows")
+                                .clicked()
+                            {
+                                show_nulls = Some(stat.name.clone());
+                            }
+                        });
+                        row.col(|ui| {
+                            let response = ui.label(format_distinct(stat.distinct));
+                            if DISTINCT_IS_APPROX {
+                                response.on_hover_text("Approximate (HyperLogLog estimate)");
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(stat.error_count.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(stat_details(stat));
+                        });
+                        row.col(|ui| {
+                            // The distribution of identifiers says nothing
+                            if stat.sparkline.is_empty() || role == Some(ColumnRole::Id) {
+                                ui.label("-");
+                            } else {
+                                sparkline(ui, &stat.sparkline);
+                            }
+                        });
+                    });
+                });
+        });
+
+    if let Some((column, role)) = set_role {
+        match role {
+            Some(role) => state.role_overrides.insert(column, role),
+            None => state.role_overrides.remove(&column),
+        };
+        state.preview_dirty = true;
+    }
+
+    if let Some(column) = show_nulls {
+        let title = format!("Nulls in {}", column);
+        let extra = vec![Operation::Filter {
+            column,
+            op: FilterOp::IsNull,
+            value: String::new(),
+            options: FilterOptions::default(),
+        }];
+        show_rows(state, title, extra);
+    }
+}
+
+/// Distinct count for the stats table, marked "≈" when it is an estimate.
+fn format_distinct(distinct: Option<usize>) -> String {
+    match distinct {
+        Some(n) if DISTINCT_IS_APPROX => format!("≈{n}"),
+        Some(n) => n.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// One-line type-specific details for the stats table (span, mode, counts).
+fn stat_details(stat: &ColumnStats) -> String {
+    if let Some(span) = &stat.span {
+        return match &stat.timezone {
+            Some(tz) => format!("{span} ({tz})"),
+            None => span.clone(),
+        };
+    }
+    if let Some((trues, falses)) = stat.bool_counts {
+        return format!("true: {trues} | false: {falses}");
+    }
+    match (&stat.mode, stat.avg_length) {
+        (Some(mode), Some(avg)) => format!("mode: {mode} | avg len: {avg:.1}"),
+        (Some(mode), None) => format!("mode: {mode}"),
+        _ => "-".to_string(),
+    }
+}
+
+/// Draw a tiny inline histogram from pre-computed bin counts.
+fn sparkline(ui: &mut egui::Ui, bins: &[u32]) {
+    let size = egui::vec2(ui.available_width().clamp(40.0, 120.0), 12.0);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let max = bins.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let bar_width = rect.width() / bins.len() as f32;
+    let painter = ui.painter();
+    for (i, &count) in bins.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let height = (count as f32 / max) * rect.height();
+        let x0 = rect.left() + i as f32 * bar_width;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(x0, rect.bottom() - height),
+            egui::pos2(x0 + (bar_width - 1.0).max(1.0), rect.bottom()),
+        );
+        painter.rect_filled(bar, 0.0, GruvboxMaterial::aqua(200));
     }
 }
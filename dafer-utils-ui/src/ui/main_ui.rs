@@ -1,16 +1,53 @@
-use crate::enums::MainTab;
-use crate::state::AppState;
+use crate::app::VIEWER;
+use crate::enums::{ExportFormat, MainTab, PipelineEdit};
+use crate::state::{AppState, PendingMapping};
 use eframe::egui::{self, Frame, RichText};
 
-use crate::ui::load_preview::load_preview_tab;
-use crate::ui::modify::modify_tab_ui;
+use crate::ui::action_log::{action_history_window, open_action_history};
+use crate::ui::analysis::{
+    crosstab_window, drill_down_window, mixed_types_window, scan_mixed_types,
+};
+use crate::ui::bookmarks::{bookmarks_window, quick_open_window};
+use crate::ui::dashboard::dashboard_ui;
+use crate::ui::dock::dock_ui;
+use crate::ui::explore::explore_ui;
+use crate::ui::fixture::{fixture_menu, fixture_report_window};
+use crate::ui::load_preview::{big_file_banner, load_preview_tab, raw_preview_window};
+use crate::ui::modify::{apply_op, export_result, export_to, modify_tab_ui};
 use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use crate::ui::performance::performance_window;
+use crate::ui::redaction::redaction_profiles_window;
+use crate::ui::snapshot::{result_snapshots_menu, snapshot_diff_window};
+use crate::ui::split_view::split_view_ui;
+use crate::ui::sql_export::sql_export_window;
+use crate::ui::table::cell_viewer;
+use crate::ui::toasts::{notifications_drawer, preview_error_banner, status_message, toasts};
+use crate::ui::tour::{open_sample, start_tour, tour_window};
 use crate::ui::visualize::visualize_tab_ui;
 
-use dafer_utils::persistence::PersistentState;
+use dafer_utils::action_log::Action;
+use dafer_utils::bundle::{self, BundleOptions};
+use dafer_utils::data_loader::NumericKind;
+use dafer_utils::datasource::{DataSource, DataSourceType};
+use dafer_utils::display;
+use dafer_utils::execution::{self, SourceStatus};
+use dafer_utils::i18n::{Language, tr};
+use dafer_utils::mapping;
+use dafer_utils::operations::Operation;
+use dafer_utils::persistence::{
+    self, PersistentState, PipelineSnapshot, Workspace, WorkspaceSelection,
+};
+use dafer_utils::query_engine;
+use std::collections::BTreeMap;
 
 /// Main UI layout: menu bar + vertical tab bar + central panel + status bar.
 pub fn main_ui(ctx: &egui::Context, state: &mut AppState) {
+    let lang = state.config.language;
+
+    if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::P)) {
+        state.quick_open = Some(String::new());
+    }
+
     // ── Top Menu Bar ──
     egui::TopBottomPanel::top("menu_bar")
         .frame(
@@ -21,8 +58,8 @@ pub fn main_ui(ctx: &egui::Context, state: &mut AppState) {
         .show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
                 ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                    ui.menu_button("File", |ui| {
-                        if ui.button(RichText::new("Open...")).clicked() {
+                    ui.menu_button(tr(lang, "File"), |ui| {
+                        if ui.button(RichText::new(tr(lang, "Open..."))).clicked() {
                             if let Some(file) = rfd::FileDialog::new()
                                 .add_filter("Data files", &["csv", "tsv", "parquet", "pq"])
                                 .pick_file()
@@ -31,69 +68,296 @@ pub fn main_ui(ctx: &egui::Context, state: &mut AppState) {
                             }
                             ui.close();
                         }
+                        if ui
+                            .add(
+                                egui::Button::new(tr(lang, "Quick Open..."))
+                                    .shortcut_text("Ctrl+P"),
+                            )
+                            .clicked()
+                        {
+                            state.quick_open = Some(String::new());
+                            ui.close();
+                        }
+                        if ui.button(RichText::new(tr(lang, "Bookmarks..."))).clicked() {
+                            state.show_bookmarks = true;
+                            ui.close();
+                        }
                         ui.separator();
-                        if ui.button(RichText::new("Save State...")).clicked() {
+                        if !VIEWER
+                            && ui
+                                .button(RichText::new(tr(lang, "Save State...")))
+                                .clicked()
+                        {
                             if let Some(path) = rfd::FileDialog::new()
                                 .add_filter("State file", &["dfr"])
                                 .save_file()
                             {
-                                let persistent = PersistentState {
-                                    source: state.source.clone(),
-                                    operations: state.operations.clone(),
-                                };
+                                let mut persistent = state.persistent_state();
+                                persistent.source_rows = source_rows(&persistent);
                                 match persistent.save(&path) {
-                                    Ok(()) => state.status = "State saved".to_string(),
+                                    Ok(()) => {
+                                        state.mark_saved();
+                                        state.notifications.info("State saved");
+                                    }
                                     Err(e) => {
-                                        state.status = format!("Save error: {}", e)
+                                        state.notifications.error(format!("Save error: {}", e))
                                     }
                                 }
                             }
                             ui.close();
                         }
-                        if ui.button(RichText::new("Load State...")).clicked() {
+                        if ui
+                            .button(RichText::new(tr(lang, "Load State...")))
+                            .clicked()
+                        {
                             if let Some(path) = rfd::FileDialog::new()
                                 .add_filter("State file", &["dfr"])
                                 .pick_file()
                             {
                                 match PersistentState::load(&path) {
-                                    Ok(persistent) => {
-                                        state.source = persistent.source;
-                                        state.operations = persistent.operations;
-                                        state.redo_stack.clear();
-                                        state.preview_dirty = true;
-                                        state.status = "State loaded".to_string();
+                                    Ok(persistent) => load_state(state, persistent),
+                                    Err(e) => {
+                                        state.notifications.error(format!("Load error: {}", e))
+                                    }
+                                }
+                            }
+                            ui.close();
+                        }
+                        if ui
+                            .button(RichText::new(tr(lang, "Apply Pipeline from State...")))
+                            .on_hover_text(
+                                "Run a saved pipeline on the current file, mapping renamed columns",
+                            )
+                            .clicked()
+                        {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("State file", &["dfr"])
+                                .pick_file()
+                            {
+                                match PersistentState::load(&path) {
+                                    Ok(persistent) => apply_pipeline(state, persistent.operations),
+                                    Err(e) => {
+                                        state.notifications.error(format!("Load error: {}", e))
+                                    }
+                                }
+                            }
+                            ui.close();
+                        }
+                        for (label, base64) in
+                            [("Copy Pipeline", false), ("Copy Pipeline as Base64", true)]
+                        {
+                            if ui
+                                .add_enabled(
+                                    !state.operations.is_empty(),
+                                    egui::Button::new(tr(lang, label)),
+                                )
+                                .on_hover_text("Share the pipeline as text, e.g. over chat")
+                                .clicked()
+                            {
+                                copy_pipeline(ui.ctx(), state, base64);
+                                ui.close();
+                            }
+                        }
+                        if ui
+                            .button(RichText::new(tr(lang, "Paste Pipeline...")))
+                            .clicked()
+                        {
+                            state.paste_pipeline = Some(String::new());
+                            ui.close();
+                        }
+                        if !VIEWER
+                            && ui
+                                .add_enabled(
+                                    state.source.is_some(),
+                                    egui::Button::new(tr(lang, "Export Bundle...")),
+                                )
+                                .on_hover_text(
+                                    "Zip the pipeline, parameters, provenance and a report",
+                                )
+                                .clicked()
+                        {
+                            state.bundle = Some(BundleOptions::default());
+                            ui.close();
+                        }
+                        ui.separator();
+                        if !VIEWER
+                            && ui
+                                .button(RichText::new(tr(lang, "Save Workspace...")))
+                                .clicked()
+                        {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("Workspace", &["dafer"])
+                                .save_file()
+                            {
+                                match state.workspace().save(&path) {
+                                    Ok(()) => {
+                                        state.mark_saved();
+                                        state.notifications.info("Workspace saved");
+                                    }
+                                    Err(e) => {
+                                        state.notifications.error(format!("Save error: {}", e))
+                                    }
+                                }
+                            }
+                            ui.close();
+                        }
+                        if ui
+                            .button(RichText::new(tr(lang, "Open Workspace...")))
+                            .clicked()
+                        {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("Workspace", &["dafer"])
+                                .pick_file()
+                            {
+                                match Workspace::load(&path) {
+                                    Ok(workspace) => {
+                                        state.pending_workspace = Some(workspace);
+                                        state.workspace_selection = WorkspaceSelection::default();
                                     }
                                     Err(e) => {
-                                        state.status = format!("Load error: {}", e)
+                                        state.notifications.error(format!("Load error: {}", e))
                                     }
                                 }
                             }
                             ui.close();
                         }
+                        if ui
+                            .button(RichText::new(tr(lang, "Action History...")))
+                            .clicked()
+                        {
+                            open_action_history(state);
+                            ui.close();
+                        }
+                        if ui.button(RichText::new(tr(lang, "Notes..."))).clicked() {
+                            state.show_notes = true;
+                            ui.close();
+                        }
+                        #[cfg(feature = "ipc-server")]
+                        ipc_server_toggle(ui, state);
                         ui.separator();
-                        if ui.button(RichText::new("Exit")).clicked() {
+                        if ui.button(RichText::new(tr(lang, "Exit"))).clicked() {
                             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                         }
                     });
-                    ui.menu_button("Edit", |ui| {
-                        if ui.button(RichText::new("Undo")).clicked() {
+                    ui.menu_button(tr(lang, "Edit"), |ui| {
+                        if ui.button(RichText::new(tr(lang, "Undo"))).clicked() {
                             undo(state);
                             ui.close();
                         }
-                        if ui.button(RichText::new("Redo")).clicked() {
+                        if ui.button(RichText::new(tr(lang, "Redo"))).clicked() {
                             redo(state);
                             ui.close();
                         }
+                        if ui
+                            .add_enabled(
+                                state.view_history.can_undo(),
+                                egui::Button::new(tr(lang, "Undo View Change")),
+                            )
+                            .on_hover_text("Plot setup, display formats and table sort")
+                            .clicked()
+                        {
+                            undo_view(state);
+                            ui.close();
+                        }
+                        if ui
+                            .add_enabled(
+                                state.view_history.can_redo(),
+                                egui::Button::new(tr(lang, "Redo View Change")),
+                            )
+                            .clicked()
+                        {
+                            redo_view(state);
+                            ui.close();
+                        }
+                        ui.separator();
+                        if ui
+                            .button(RichText::new(tr(lang, "Clear Pipeline")))
+                            .clicked()
+                        {
+                            state.request_pipeline_edit(PipelineEdit::Clear);
+                            ui.close();
+                        }
+                        if ui
+                            .add_enabled(
+                                state.last_saved.is_some(),
+                                egui::Button::new(tr(lang, "Revert to Last Saved")),
+                            )
+                            .clicked()
+                        {
+                            state.request_pipeline_edit(PipelineEdit::Revert);
+                            ui.close();
+                        }
+                        ui.menu_button(tr(lang, "Snapshots"), |ui| snapshots_menu(ui, state));
+                        ui.menu_button(tr(lang, "Result Snapshots"), |ui| {
+                            result_snapshots_menu(ui, state)
+                        });
+                        ui.menu_button(tr(lang, "Expected Output"), |ui| fixture_menu(ui, state));
                         ui.separator();
-                        if ui.button(RichText::new("Clear Pipeline")).clicked() {
-                            state.operations.clear();
-                            state.redo_stack.clear();
+                        ui.checkbox(&mut state.pipeline_locked, tr(lang, "Lock Pipeline"));
+                        if ui
+                            .add_enabled(
+                                state.source.is_some(),
+                                egui::Button::new(tr(lang, "Reset Auto-Cast Choices")),
+                            )
+                            .clicked()
+                        {
+                            if let Some(source) = &mut state.source {
+                                source.auto_numeric_cols.clear();
+                                source.auto_integer_cols.clear();
+                                source.auto_cast_declined.clear();
+                            }
+                            state.auto_cast_detected = false;
                             state.preview_dirty = true;
-                            state.status = "Pipeline cleared".to_string();
                             ui.close();
                         }
+                        ui.separator();
+                        if ui.button(RichText::new(tr(lang, "Performance..."))).clicked() {
+                            state.show_performance = true;
+                            ui.close();
+                        }
+                        ui.menu_button(tr(lang, "Language"), |ui| {
+                            for language in Language::all() {
+                                if ui
+                                    .selectable_value(
+                                        &mut state.config.language,
+                                        *language,
+                                        language.to_string(),
+                                    )
+                                    .clicked()
+                                {
+                                    if let Err(e) = state.config.save() {
+                                        state
+                                            .notifications
+                                            .error(format!("Config save error: {}", e));
+                                    }
+                                    ui.close();
+                                }
+                            }
+                        });
                     });
-                    ui.menu_button("About", |ui| {
+                    ui.menu_button(tr(lang, "Analysis"), |ui| {
+                        if ui.button(RichText::new(tr(lang, "Crosstab..."))).clicked() {
+                            state.show_crosstab = true;
+                            ui.close();
+                        }
+                        if ui
+                            .button(RichText::new(tr(lang, "Mixed-Type Columns...")))
+                            .clicked()
+                        {
+                            scan_mixed_types(state);
+                            ui.close();
+                        }
+                    });
+                    ui.menu_button(tr(lang, "About"), |ui| {
+                        if ui.button(RichText::new("Open Sample Dataset")).clicked() {
+                            open_sample(state);
+                            ui.close();
+                        }
+                        if ui.button(RichText::new("Guided Tour")).clicked() {
+                            start_tour(state);
+                            ui.close();
+                        }
+                        ui.separator();
                         let _ = ui.button(RichText::new("dafer-utils v0.1.0"));
                         let _ = ui.button(RichText::new("Rust Data Science Desktop App"));
                     });
@@ -110,11 +374,7 @@ pub fn main_ui(ctx: &egui::Context, state: &mut AppState) {
         )
         .show(ctx, |ui| {
             ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                ui.label(
-                    egui::RichText::new(&state.status)
-                        .small()
-                        .color(GruvboxMaterial::fg(255)),
-                );
+                status_message(ui, state);
                 // Show pipeline ops count on the right
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if !state.operations.is_empty() {
@@ -127,10 +387,17 @@ pub fn main_ui(ctx: &egui::Context, state: &mut AppState) {
                             .color(GruvboxMaterial::fg3(200)),
                         );
                     }
+                    memory_usage(ui, state);
+                    quick_calc(ui, state);
                 });
             });
         });
 
+    // ── Preview Error / Big File Banners, Notification History ──
+    preview_error_banner(ctx, state);
+    big_file_banner(ctx, state);
+    notifications_drawer(ctx, state);
+
     // ── Left Tab Bar (vertical) ──
     egui::SidePanel::left("tab_bar")
         .frame(
@@ -152,22 +419,211 @@ pub fn main_ui(ctx: &egui::Context, state: &mut AppState) {
             });
         });
 
+    // ── Crash Recovery Prompt ──
+    if state.pending_recovery.is_some() {
+        recovery_dialog(ctx, state);
+    }
+
+    // ── Restore From Export Provenance ──
+    if state.pending_provenance.is_some() {
+        provenance_dialog(ctx, state);
+    }
+    if state.pending_column_change.is_some() {
+        column_change_dialog(ctx, state);
+    }
+    if state.pending_lossy_export.is_some() {
+        lossy_export_dialog(ctx, state);
+    }
+    if state.pending_overwrite.is_some() {
+        overwrite_dialog(ctx, state);
+    }
+
+    // ── Auto-Cast Confirmation ──
+    if !state.pending_auto_cast.is_empty() {
+        auto_cast_dialog(ctx, state);
+    }
+
+    // ── Workspace Load (selective) ──
+    if state.pending_workspace.is_some() {
+        workspace_load_dialog(ctx, state);
+    }
+
+    // ── Destructive Pipeline Edit (locked pipeline) ──
+    if state.pending_pipeline_edit.is_some() {
+        pipeline_edit_dialog(ctx, state);
+    }
+
+    // ── Schema Drift (loaded state) ──
+    if state.pending_drift.is_some() {
+        schema_drift_dialog(ctx, state);
+    }
+
+    // ── Column Mapping (saved pipeline on a different file) ──
+    if state.pending_mapping.is_some() {
+        column_mapping_dialog(ctx, state);
+    }
+
+    // ── Paste Pipeline ──
+    if state.paste_pipeline.is_some() {
+        paste_pipeline_window(ctx, state);
+    }
+
+    // ── Export Bundle ──
+    if state.bundle.is_some() {
+        bundle_window(ctx, state);
+    }
+
+    // ── Missing Source ──
+    if state.show_relocate {
+        relocate_dialog(ctx, state);
+    }
+
+    // ── Cell Viewer ──
+    cell_viewer(ctx, state);
+
+    // ── Crosstab ──
+    if state.show_crosstab {
+        crosstab_window(ctx, state);
+    }
+
+    // ── Mixed-Type Columns ──
+    if state.show_mixed_types {
+        mixed_types_window(ctx, state);
+    }
+
+    // ── Drill-Down (problem rows) ──
+    drill_down_window(ctx, state);
+
+    // ── Raw Preview (import options) ──
+    if state.raw_preview.is_some() {
+        raw_preview_window(ctx, state);
+    }
+
+    // ── Expected Output Report ──
+    if state.fixture_report.is_some() {
+        fixture_report_window(ctx, state);
+    }
+
+    // ── Result Snapshot Comparison ──
+    if state.snapshot_diff.is_some() {
+        snapshot_diff_window(ctx, state);
+    }
+
+    // ── Redaction Profiles ──
+    if state.show_redaction_profiles {
+        redaction_profiles_window(ctx, state);
+    }
+
+    // ── Performance Settings ──
+    if state.show_performance {
+        performance_window(ctx, state);
+    }
+
+    // ── Bookmarks ──
+    if state.show_bookmarks {
+        bookmarks_window(ctx, state);
+    }
+    if state.quick_open.is_some() {
+        quick_open_window(ctx, state);
+    }
+
+    // ── Pipeline as SQL ──
+    if state.show_sql_export {
+        sql_export_window(ctx, state);
+    }
+
+    // ── Action History ──
+    if state.show_action_log {
+        action_history_window(ctx, state);
+    }
+
+    // ── Guided Tour ──
+    tour_window(ctx, state);
+
+    // ── Toasts ──
+    toasts(ctx, state);
+
+    // ── Notes ──
+    if state.show_notes {
+        egui::Window::new("Notes")
+            .open(&mut state.show_notes)
+            .default_size([360.0, 240.0])
+            .show(ctx, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut state.notes)
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(10),
+                );
+            });
+    }
+
     // ── Central Panel (tab content) ──
     egui::CentralPanel::default().show(ctx, |ui| match state.selected_tab {
         MainTab::LoadPreview => load_preview_tab(ui, state),
         MainTab::Modify => modify_tab_ui(ui, state),
         MainTab::Visualize => visualize_tab_ui(ui, state),
+        MainTab::Split => split_view_ui(ui, state),
+        MainTab::Dock => dock_ui(ui, state),
+        MainTab::Dashboard => dashboard_ui(ui, state),
+        MainTab::Explore => explore_ui(ui, state),
     });
 }
 
+/// File menu entry starting/stopping the Arrow IPC server.
+#[cfg(feature = "ipc-server")]
+fn ipc_server_toggle(ui: &mut egui::Ui, state: &mut AppState) {
+    use dafer_utils::ipc_server::{DEFAULT_PORT, IpcServer};
+
+    let mut serving = state.ipc_server.is_some();
+    let label = match &state.ipc_server {
+        Some(server) => format!("Serving Result on {}", server.addr()),
+        None => "Serve Result (Arrow IPC)".to_string(),
+    };
+    if !ui
+        .checkbox(&mut serving, label)
+        .on_hover_text("Each connection receives the full result as an Arrow IPC stream")
+        .changed()
+    {
+        return;
+    }
+    if !serving {
+        state.ipc_server = None;
+        state.notifications.info("Arrow IPC server stopped");
+        return;
+    }
+    match IpcServer::start(DEFAULT_PORT) {
+        Ok(server) => {
+            if let Some(source) = &state.source {
+                server.publish(source, &state.operations);
+            }
+            state
+                .notifications
+                .info(format!("Serving the pipeline result on {}", server.addr()));
+            state.ipc_server = Some(server);
+        }
+        Err(e) => state
+            .notifications
+            .error(format!("Arrow IPC server error: {}", e)),
+    }
+}
+
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
 /// Open a data file, set it as the source, and trigger preview.
-fn open_file(state: &mut AppState, path: std::path::PathBuf) {
+pub(crate) fn open_file(state: &mut AppState, path: std::path::PathBuf) {
     if let Some(ds) = dafer_utils::datasource::DataSource::from_path(path.clone()) {
+        state.action_log.record(Action::FileOpened {
+            path: path.display().to_string(),
+        });
         state.source = Some(ds);
         state.operations.clear();
         state.redo_stack.clear();
+        state.view_history.clear();
+        state.role_overrides.clear();
+        state.fixture = None;
+        state.fixture_report = None;
+        state.last_export = None;
+        state.quick_filters.clear();
         state.preview_dirty = true;
         state.sort_column = None;
         state.sort_descending = false;
@@ -177,26 +633,1037 @@ fn open_file(state: &mut AppState, path: std::path::PathBuf) {
         state.selected_col = None;
         state.plot_y_columns.clear();
         state.plot_multi_data.clear();
-        state.status = format!("Loaded: {}", path.display());
+        state
+            .notifications
+            .info(format!("Loaded: {}", path.display()));
+        match execution::read_provenance(&path) {
+            Ok(Some(provenance)) => {
+                let status = provenance.source_status();
+                state.pending_provenance = Some((provenance, status));
+            }
+            Ok(None) => {}
+            Err(e) => state
+                .notifications
+                .warn(format!("Unreadable provenance: {}", e)),
+        }
+    } else {
+        state
+            .notifications
+            .warn(format!("Unsupported file: {}", path.display()));
+    }
+}
+
+/// Offer to restore the autosaved session left by a crash or forced quit.
+fn recovery_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(recovery) = &state.pending_recovery else {
+        return;
+    };
+    let source = recovery
+        .state
+        .source
+        .as_ref()
+        .map(|s| s.path.display().to_string())
+        .unwrap_or_default();
+    let n_ops = recovery.state.operations.len();
+
+    let mut choice: Option<bool> = None;
+    egui::Window::new("Restore previous session?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("The previous session did not exit cleanly.");
+            ui.monospace(&source);
+            ui.label(format!("Pipeline: {} ops", n_ops));
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Restore").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Discard").clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    match choice {
+        Some(true) => {
+            if let Some(recovery) = state.pending_recovery.take() {
+                state.apply_workspace(recovery, WorkspaceSelection::default());
+                state.notifications.info("Previous session restored");
+            }
+        }
+        Some(false) => {
+            state.pending_recovery = None;
+            persistence::clear_recovery();
+            state.notifications.info("Previous session discarded");
+        }
+        None => {}
+    }
+}
+
+/// Offer to reopen the original source and pipeline of an exported file.
+fn provenance_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some((provenance, status)) = &state.pending_provenance else {
+        return;
+    };
+    let status = *status;
+
+    let mut choice: Option<bool> = None;
+    egui::Window::new("Restore pipeline from export?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "This file was exported by version {} on {} from:",
+                provenance.app_version, provenance.exported_at
+            ));
+            ui.monospace(&provenance.source);
+            ui.label(format!("Pipeline: {} ops", provenance.operations.len()));
+            match status {
+                SourceStatus::Unchanged => {
+                    ui.label("The source file is unchanged since the export.");
+                }
+                SourceStatus::Changed => {
+                    ui.colored_label(
+                        GruvboxMaterial::yellow(255),
+                        "⚠ The source file has changed since the export (SHA-256 differs).",
+                    );
+                }
+                SourceStatus::Missing => {
+                    ui.colored_label(
+                        GruvboxMaterial::red(255),
+                        "The source file is missing or unreadable.",
+                    );
+                }
+            }
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        status != SourceStatus::Missing,
+                        egui::Button::new("Restore Source + Pipeline"),
+                    )
+                    .clicked()
+                {
+                    choice = Some(true);
+                }
+                if ui.button("Keep This File").clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    match choice {
+        Some(true) => {
+            if let Some((provenance, _)) = state.pending_provenance.take() {
+                open_file(state, std::path::PathBuf::from(&provenance.source));
+                // The source may itself be an export; restoring one level is enough.
+                state.pending_provenance = None;
+                state.operations = provenance.operations;
+                state.log_pipeline_replaced("restored from provenance");
+                state.preview_dirty = true;
+                state.notifications.info(format!(
+                    "Restored {} ops on {}",
+                    state.operations.len(),
+                    provenance.source
+                ));
+            }
+        }
+        Some(false) => state.pending_provenance = None,
+        None => {}
+    }
+}
+
+/// Confirm a drop/rename of a column that plots or the table sort still use.
+fn column_change_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some((op, dependents)) = &state.pending_column_change else {
+        return;
+    };
+    let renamed = matches!(op, Operation::RenameColumn { .. });
+
+    // Some(true): apply and update the views, Some(false): apply only
+    let mut choice: Option<bool> = None;
+    let mut cancel = false;
+    egui::Window::new("Column in use")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("{} affects:", op));
+            for dependent in dependents {
+                ui.label(format!("  • {}", dependent));
+            }
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let update = if renamed {
+                    "Apply + Rename References"
+                } else {
+                    "Apply + Remove References"
+                };
+                if ui.button(update).clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Apply Only").clicked() {
+                    choice = Some(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if cancel {
+        state.pending_column_change = None;
+        return;
+    }
+    let Some(update) = choice else {
+        return;
+    };
+    let Some((op, _)) = state.pending_column_change.take() else {
+        return;
+    };
+    if update {
+        match &op {
+            Operation::RenameColumn { from, to } => state.retarget_column(from, Some(to)),
+            Operation::DropColumn(column) => state.retarget_column(column, None),
+            _ => {}
+        }
+    }
+    apply_op(state, op);
+}
+
+/// CSV export of columns CSV cannot represent exactly: offer Parquet instead.
+fn lossy_export_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(warnings) = &state.pending_lossy_export else {
+        return;
+    };
+
+    // Some(true): export as Parquet, Some(false): export CSV anyway
+    let mut choice: Option<bool> = None;
+    let mut cancel = false;
+    egui::Window::new("CSV export loses information")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            for warning in warnings {
+                ui.colored_label(GruvboxMaterial::yellow(255), format!("⚠ {}", warning));
+            }
+            ui.label("Parquet keeps types, time zones and full precision.");
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Export as Parquet").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Export CSV Anyway").clicked() {
+                    choice = Some(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if cancel || choice.is_some() {
+        state.pending_lossy_export = None;
+    }
+    if let Some(parquet) = choice {
+        if parquet {
+            state.export_format = ExportFormat::Parquet;
+        }
+        export_result(state);
+    }
+}
+
+/// The export target exists: overwrite it or export under a numbered name.
+fn overwrite_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(path) = &state.pending_overwrite else {
+        return;
+    };
+
+    let message = if state.export_options.split.is_some() {
+        "Numbered parts of this export already exist:"
+    } else {
+        "This file already exists:"
+    };
+
+    // Some(true): overwrite, Some(false): new name
+    let mut choice: Option<bool> = None;
+    let mut cancel = false;
+    egui::Window::new("File exists")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(message);
+            ui.monospace(path.display().to_string());
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Overwrite").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("New Name").clicked() {
+                    choice = Some(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if cancel {
+        state.pending_overwrite = None;
+        return;
+    }
+    let Some(overwrite) = choice else {
+        return;
+    };
+    let Some(path) = state.pending_overwrite.take() else {
+        return;
+    };
+    let path = if overwrite {
+        path
     } else {
-        state.status = format!("Unsupported file: {}", path.display());
+        execution::next_free_path(&path)
+    };
+    export_to(state, &path);
+}
+
+/// Process RSS and estimated DataFrame memory, as of the last collect.
+fn memory_usage(ui: &mut egui::Ui, state: &AppState) {
+    let memory = state.memory;
+    let rss = match memory.rss {
+        Some(rss) => format!("RSS {}", display::format_bytes(rss)),
+        None => "RSS n/a".to_string(),
+    };
+    ui.label(
+        RichText::new(format!(
+            "{} | data ~{}",
+            rss,
+            display::format_bytes(memory.frames as u64)
+        ))
+        .small()
+        .color(GruvboxMaterial::fg3(200)),
+    )
+    .on_hover_text(
+        "Process memory and the estimated size of the preview and statistics \
+         DataFrames, updated after each collect. If it keeps growing, add a \
+         Limit, select fewer columns or turn on fast stats.",
+    );
+}
+
+/// Let the user choose which parts of a loaded workspace to apply.
+fn workspace_load_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(workspace) = &state.pending_workspace else {
+        return;
+    };
+    let source = workspace
+        .state
+        .source
+        .as_ref()
+        .map(|s| s.path.display().to_string())
+        .unwrap_or_else(|| "(none)".to_string());
+    let n_ops = workspace.state.operations.len();
+
+    let mut choice: Option<bool> = None;
+    egui::Window::new("Open Workspace")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let sel = &mut state.workspace_selection;
+            ui.checkbox(&mut sel.source, format!("Source: {}", source));
+            ui.checkbox(&mut sel.operations, format!("Operations ({} ops)", n_ops));
+            ui.checkbox(&mut sel.layout, "Layout & display formatting");
+            ui.checkbox(&mut sel.plot, "Plot setup & dashboard");
+            ui.checkbox(&mut sel.notes, "Notes");
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Operations only").clicked() {
+                    state.workspace_selection = WorkspaceSelection::operations_only();
+                }
+                if ui.button("Apply").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    match choice {
+        Some(true) => {
+            if let Some(workspace) = state.pending_workspace.take() {
+                let selection = state.workspace_selection;
+                state.apply_workspace(workspace, selection);
+                state.mark_saved();
+                state.notifications.info("Workspace loaded");
+            }
+        }
+        Some(false) => state.pending_workspace = None,
+        None => {}
+    }
+}
+
+/// Quick calc box: one-off aggregates on the pipeline result, e.g.
+/// `mean(temp) where site == 'A'`. Results go to the status bar and a history menu.
+fn quick_calc(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some(source) = &state.source else {
+        return;
+    };
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut state.quick_calc_input)
+            .hint_text("quick calc: mean(col) where col2 == 'A'")
+            .desired_width(240.0)
+            .font(egui::TextStyle::Small),
+    );
+    let query = state.quick_calc_input.trim().to_string();
+    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && !query.is_empty() {
+        match query_engine::quick_calc(source, &state.operations, &query) {
+            Ok(result) => {
+                state.notifications.info(format!("{} = {}", query, result));
+                state.quick_calc_history.push((query, result));
+            }
+            Err(e) => state
+                .notifications
+                .error(format!("Quick calc error: {}", e)),
+        }
+    }
+    response.on_hover_text(format!(
+        "Functions: {}. Conditions: ==, !=, >, >=, <, <=, contains, joined with and",
+        query_engine::QUICK_CALC_FUNCTIONS.join(", ")
+    ));
+
+    if !state.quick_calc_history.is_empty() {
+        ui.menu_button(RichText::new("Σ").small(), |ui| {
+            for (query, result) in state.quick_calc_history.iter().rev() {
+                if ui
+                    .button(format!("{} = {}", query, result))
+                    .on_hover_text("Edit this query")
+                    .clicked()
+                {
+                    state.quick_calc_input = query.clone();
+                    ui.close();
+                }
+            }
+            ui.separator();
+            if ui.button("Clear history").clicked() {
+                state.quick_calc_history.clear();
+                ui.close();
+            }
+        });
+    }
+}
+
+/// Confirm the auto-cast of numeric-looking String columns, per column:
+/// keep as text, Float64, or Int64. Choices are stored on the DataSource.
+fn auto_cast_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let mut choice: Option<bool> = None;
+    egui::Window::new("Convert numeric text columns?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("These String columns look numeric. Keep ID-like columns as text");
+            ui.label("to preserve leading zeros.");
+            ui.add_space(4.0);
+            egui::Grid::new("auto_cast_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (name, kind) in &mut state.pending_auto_cast {
+                        ui.label(name.as_str());
+                        egui::ComboBox::from_id_salt(("auto_cast", name.as_str()))
+                            .selected_text(
+                                kind.map_or("Keep as text".to_string(), |k| k.to_string()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(kind, None, "Keep as text");
+                                ui.selectable_value(kind, Some(NumericKind::Float), "Float64");
+                                ui.selectable_value(kind, Some(NumericKind::Integer), "Int64");
+                            });
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Keep all as text").clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    let Some(apply) = choice else {
+        return;
+    };
+    let pending = std::mem::take(&mut state.pending_auto_cast);
+    let Some(source) = &mut state.source else {
+        return;
+    };
+    for (name, kind) in pending {
+        match kind.filter(|_| apply) {
+            Some(NumericKind::Float) => source.auto_numeric_cols.push(name),
+            Some(NumericKind::Integer) => source.auto_integer_cols.push(name),
+            None => source.auto_cast_declined.push(name),
+        }
+    }
+    state.preview_dirty = true;
+    state.notifications.info("Auto-cast choices saved");
+}
+
+/// Row count of the state's source, recorded on save so loading can tell when
+/// the file changed. Parquet only: the count comes from the file footer. A CSV
+/// count is a full scan on the UI thread, so CSV sources skip the row check.
+fn source_rows(persistent: &PersistentState) -> Option<usize> {
+    let source = persistent
+        .source
+        .as_ref()
+        .filter(|s| s.source_type == DataSourceType::Parquet)?;
+    query_engine::count_rows(source, &[], &[]).ok()
+}
+
+/// Load a saved state. Pre-flight: if its source schema or row count changed
+/// since it was saved, hold it back and show the drift summary instead of
+/// running the pipeline.
+fn load_state(state: &mut AppState, persistent: PersistentState) {
+    let drift = persistent.source.as_ref().and_then(|source| {
+        let actual = query_engine::schema_info(source, &[]).ok()?;
+        let rows = persistent.source_rows.and_then(|_| source_rows(&persistent));
+        persistent.schema_drift(&actual, rows)
+    });
+    match drift {
+        Some(drift) => state.pending_drift = Some((persistent, drift)),
+        None => apply_state(state, persistent),
     }
 }
 
+fn apply_state(state: &mut AppState, persistent: PersistentState) {
+    state.source = persistent.source;
+    state.operations = persistent.operations;
+    state.fixture = persistent.fixture;
+    state.pipeline_engine = persistent.engine;
+    state.redo_stack.clear();
+    state.preview_dirty = true;
+    state.check_source_exists();
+    state.mark_saved();
+    state.log_pipeline_replaced("loaded from state");
+    state.notifications.info("State loaded");
+    if state.fixture.is_some() && !state.show_relocate {
+        state.verify_fixture();
+    }
+}
+
+/// How to resolve a held-back state in the schema drift dialog.
+enum DriftChoice {
+    LoadAnyway,
+    /// Apply only the operations to the file that is open now.
+    OntoOpenFile,
+    Cancel,
+}
+
+/// Warn that the source changed since the state was saved, listing the row
+/// count and column changes and the operations that read affected columns.
+fn schema_drift_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some((persistent, drift)) = &state.pending_drift else {
+        return;
+    };
+    let open_file = state
+        .source
+        .as_ref()
+        .filter(|open| persistent.source.as_ref().is_none_or(|s| s.path != open.path))
+        .map(|open| open.path.clone());
+
+    let mut choice = None;
+    egui::Window::new("Source changed")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("The source no longer matches the one recorded with this pipeline.");
+            ui.add_space(4.0);
+            let mut summary = format!("{} column(s) changed", drift.columns_changed());
+            if let Some((saved, now)) = drift.rows {
+                summary += &format!(", rows {} → {}", saved, now);
+            }
+            ui.strong(summary);
+            for name in &drift.removed {
+                ui.colored_label(GruvboxMaterial::red(255), format!("− {} (missing)", name));
+            }
+            for (name, expected, actual) in &drift.retyped {
+                ui.colored_label(
+                    GruvboxMaterial::yellow(255),
+                    format!("~ {}: {} → {}", name, expected, actual),
+                );
+            }
+            for name in &drift.added {
+                ui.colored_label(GruvboxMaterial::green(255), format!("+ {} (new)", name));
+            }
+            if !drift.affected.is_empty() {
+                ui.add_space(4.0);
+                ui.strong("Affected operations:");
+                for &i in &drift.affected {
+                    ui.label(format!("{}. {}", i + 1, persistent.operations[i]));
+                }
+            }
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Load anyway").clicked() {
+                    choice = Some(DriftChoice::LoadAnyway);
+                }
+                if let Some(path) = &open_file {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    if ui
+                        .button(format!("Apply to {}", name))
+                        .on_hover_text("Load only the operations onto the file open now")
+                        .clicked()
+                    {
+                        choice = Some(DriftChoice::OntoOpenFile);
+                    }
+                }
+                if ui.button("Cancel").clicked() {
+                    choice = Some(DriftChoice::Cancel);
+                }
+            });
+        });
+
+    let Some(choice) = choice else {
+        return;
+    };
+    let Some((persistent, _)) = state.pending_drift.take() else {
+        return;
+    };
+    match choice {
+        DriftChoice::LoadAnyway => apply_state(state, persistent),
+        DriftChoice::OntoOpenFile => apply_pipeline(state, persistent.operations),
+        DriftChoice::Cancel => {}
+    }
+}
+
+/// Apply saved operations to the current source. Columns the source lacks go
+/// through the mapping dialog first, pre-filled with fuzzy-matched suggestions.
+fn apply_pipeline(state: &mut AppState, operations: Vec<Operation>) {
+    let Some(source) = &state.source else {
+        state.notifications.warn("Load a data file first");
+        return;
+    };
+    let available: Vec<String> = match query_engine::schema_info(source, &[]) {
+        Ok(schema) => schema.into_iter().map(|(name, _)| name).collect(),
+        Err(e) => {
+            state.notifications.error(format!("Schema error: {}", e));
+            return;
+        }
+    };
+    let missing = mapping::missing_columns(&operations, &available);
+    if missing.is_empty() {
+        set_pipeline(state, operations);
+        return;
+    }
+    let mapping = missing
+        .into_iter()
+        .map(|name| {
+            let suggestion = mapping::suggest_column(&name, &available).unwrap_or_default();
+            (name, suggestion)
+        })
+        .collect();
+    state.pending_mapping = Some(PendingMapping {
+        operations,
+        mapping,
+        available,
+    });
+}
+
+/// Copy the pipeline as a snippet (compact JSON, or base64).
+fn copy_pipeline(ctx: &egui::Context, state: &mut AppState, base64: bool) {
+    match persistence::pipeline_to_snippet(&state.operations, base64) {
+        Ok(snippet) => {
+            ctx.copy_text(snippet);
+            state.notifications.quiet(format!(
+                "Pipeline copied to clipboard ({} ops)",
+                state.operations.len()
+            ));
+        }
+        Err(e) => state.notifications.error(format!("Copy error: {}", e)),
+    }
+}
+
+/// Paste a pipeline snippet and apply it to the current file, mapping
+/// columns it lacks like a pipeline loaded from a state file.
+fn paste_pipeline_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(text) = &mut state.paste_pipeline else {
+        return;
+    };
+    let mut open = true;
+    let mut apply = false;
+    egui::Window::new("Paste Pipeline")
+        .open(&mut open)
+        .default_size([420.0, 240.0])
+        .show(ctx, |ui| {
+            ui.label("Paste a pipeline copied with File > Copy Pipeline (JSON or base64):");
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(text)
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(8),
+                    );
+                });
+            let parsed = persistence::pipeline_from_snippet(text);
+            match &parsed {
+                Ok(operations) => ui.weak(format!("{} operations", operations.len())),
+                Err(e) if !text.trim().is_empty() => {
+                    ui.colored_label(GruvboxMaterial::red(255), e.to_string())
+                }
+                Err(_) => ui.weak("Nothing pasted yet"),
+            };
+            apply = ui
+                .add_enabled(parsed.is_ok(), egui::Button::new("Replace Pipeline"))
+                .clicked();
+        });
+    if apply {
+        let operations = state
+            .paste_pipeline
+            .take()
+            .and_then(|text| persistence::pipeline_from_snippet(&text).ok())
+            .unwrap_or_default();
+        apply_pipeline(state, operations);
+    } else if !open {
+        state.paste_pipeline = None;
+    }
+}
+
+/// Export Bundle: pick what goes in, then where the zip is written.
+fn bundle_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(options) = &mut state.bundle else {
+        return;
+    };
+    let mut open = true;
+    let mut export = false;
+    egui::Window::new("Export Bundle")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label("pipeline.json, pipeline.dfr, parameters.json, provenance.json, report.html");
+            ui.horizontal(|ui| {
+                let mut sample = options.sample_rows.is_some();
+                if ui.checkbox(&mut sample, "Include input sample").changed() {
+                    options.sample_rows = sample.then_some(10_000);
+                }
+                if let Some(n) = &mut options.sample_rows {
+                    ui.add(
+                        egui::DragValue::new(n)
+                            .range(100..=1_000_000)
+                            .speed(100.0)
+                            .suffix(" rows"),
+                    );
+                }
+            });
+            export = ui.button("Export...").clicked();
+        });
+    if export {
+        let options = *options;
+        let stem = state
+            .source
+            .as_ref()
+            .and_then(|s| s.path.file_stem())
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Zip", &["zip"])
+            .set_file_name(format!("{}_bundle.zip", stem))
+            .save_file()
+        {
+            match bundle::export_bundle(&state.persistent_state(), &path, &options) {
+                Ok(files) => {
+                    state.bundle = None;
+                    state.notifications.info(format!(
+                        "Bundle exported to {} ({} files)",
+                        path.display(),
+                        files.len()
+                    ));
+                }
+                Err(e) => state.notifications.error(format!("Bundle error: {}", e)),
+            }
+        }
+    } else if !open {
+        state.bundle = None;
+    }
+}
+
+fn set_pipeline(state: &mut AppState, operations: Vec<Operation>) {
+    state.operations = operations;
+    state.redo_stack.clear();
+    state.preview_dirty = true;
+    state.log_pipeline_replaced("applied from state");
+    state
+        .notifications
+        .info(format!("Applied pipeline ({} ops)", state.operations.len()));
+}
+
+/// Map pipeline columns missing from the current file to its columns.
+fn column_mapping_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(pending) = &mut state.pending_mapping else {
+        return;
+    };
+
+    let mut choice: Option<bool> = None;
+    egui::Window::new("Map Columns")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("The pipeline uses columns that this file does not have:");
+            ui.add_space(4.0);
+            egui::Grid::new("column_mapping_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (i, (from, to)) in pending.mapping.iter_mut().enumerate() {
+                        ui.monospace(from.as_str());
+                        egui::ComboBox::from_id_salt(("column_mapping", i))
+                            .selected_text(if to.is_empty() {
+                                "(unmapped)"
+                            } else {
+                                to.as_str()
+                            })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(to, String::new(), "(unmapped)");
+                                for name in &pending.available {
+                                    ui.selectable_value(to, name.clone(), name);
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    match choice {
+        Some(true) => {
+            if let Some(mut pending) = state.pending_mapping.take() {
+                let mapping: BTreeMap<String, String> = pending
+                    .mapping
+                    .into_iter()
+                    .filter(|(_, to)| !to.is_empty())
+                    .collect();
+                mapping::remap_columns(&mut pending.operations, &mapping);
+                set_pipeline(state, pending.operations);
+            }
+        }
+        Some(false) => state.pending_mapping = None,
+        None => {}
+    }
+}
+
+/// Save the current pipeline under a name, or jump back to a saved one.
+fn snapshots_menu(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut state.snapshot_name)
+                .hint_text("Snapshot name")
+                .desired_width(140.0),
+        );
+        let name = state.snapshot_name.trim().to_string();
+        if ui
+            .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+            .clicked()
+        {
+            state.snapshots.retain(|s| s.name != name);
+            state.snapshots.push(PipelineSnapshot {
+                name: name.clone(),
+                operations: state.operations.clone(),
+            });
+            state.snapshot_name.clear();
+            state
+                .notifications
+                .info(format!("Snapshot \"{}\" saved", name));
+        }
+    });
+    if state.snapshots.is_empty() {
+        ui.weak("No snapshots");
+        return;
+    }
+    ui.separator();
+    let mut jump = None;
+    let mut delete = None;
+    for (i, snapshot) in state.snapshots.iter().enumerate() {
+        ui.horizontal(|ui| {
+            let label = format!("{} ({} ops)", snapshot.name, snapshot.operations.len());
+            if ui.button(label).clicked() {
+                jump = Some(i);
+            }
+            if ui
+                .small_button("✖")
+                .on_hover_text("Delete snapshot")
+                .clicked()
+            {
+                delete = Some(i);
+            }
+        });
+    }
+    if let Some(i) = jump {
+        state.request_pipeline_edit(PipelineEdit::Snapshot(i));
+        ui.close();
+    }
+    if let Some(i) = delete {
+        state.snapshots.remove(i);
+    }
+}
+
+/// Confirm a destructive edit of a locked pipeline, previewing the steps it discards.
+fn pipeline_edit_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let Some(edit) = state.pending_pipeline_edit else {
+        return;
+    };
+    let title = match edit {
+        PipelineEdit::Remove(_) => "Remove operation?",
+        PipelineEdit::Clear => "Clear pipeline?",
+        PipelineEdit::Revert => "Revert to last saved state?",
+        PipelineEdit::Snapshot(_) => "Jump to snapshot?",
+    };
+    let discarded: Vec<String> = state
+        .edit_discards(edit)
+        .iter()
+        .map(|op| op.to_string())
+        .collect();
+
+    let mut choice: Option<bool> = None;
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("The pipeline is locked. These steps will be discarded:");
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    if discarded.is_empty() {
+                        ui.weak("(none)");
+                    }
+                    for op in &discarded {
+                        ui.colored_label(GruvboxMaterial::red(255), format!("− {}", op));
+                    }
+                });
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Confirm").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    if let Some(confirmed) = choice {
+        state.pending_pipeline_edit = None;
+        if confirmed {
+            state.apply_pipeline_edit(edit);
+        }
+    }
+}
+
+/// Ask the user to locate a source file that no longer exists at its saved path.
+/// The pipeline and auto-cast columns are kept; only the path (and type) change.
+fn relocate_dialog(ctx: &egui::Context, state: &mut AppState) {
+    let missing = state
+        .source
+        .as_ref()
+        .map(|s| s.path.display().to_string())
+        .unwrap_or_default();
+
+    egui::Window::new("Source file not found")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("The data source could not be found:");
+            ui.monospace(&missing);
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Locate...").clicked() {
+                    if let Some(file) = rfd::FileDialog::new()
+                        .add_filter("Data files", &["csv", "tsv", "parquet", "pq"])
+                        .pick_file()
+                    {
+                        match DataSource::from_path(file.clone()) {
+                            Some(mut ds) => {
+                                if let Some(old) = &state.source {
+                                    ds.auto_numeric_cols = old.auto_numeric_cols.clone();
+                                    ds.auto_integer_cols = old.auto_integer_cols.clone();
+                                    ds.auto_cast_declined = old.auto_cast_declined.clone();
+                                    ds.stats_sample = old.stats_sample;
+                                    ds.seed = old.seed;
+                                }
+                                state.source = Some(ds);
+                                state.preview_dirty = true;
+                                state.show_relocate = false;
+                                state
+                                    .notifications
+                                    .info(format!("Source relocated to {}", file.display()));
+                            }
+                            None => state
+                                .notifications
+                                .warn(format!("Unsupported file: {}", file.display())),
+                        }
+                    }
+                }
+                if ui.button("Ignore").clicked() {
+                    state.show_relocate = false;
+                }
+            });
+        });
+}
+
 /// Undo: pop last operation and push it onto redo stack.
-fn undo(state: &mut AppState) {
+pub fn undo(state: &mut AppState) {
     if let Some(op) = state.operations.pop() {
+        state.action_log.record(Action::OperationRemoved {
+            index: state.operations.len(),
+            operation: op.clone(),
+        });
         state.redo_stack.push(op);
         state.preview_dirty = true;
-        state.status = "Undo".to_string();
+        state.notifications.info("Undo");
+    }
+}
+
+/// Undo the last plot / display / sort change.
+fn undo_view(state: &mut AppState) {
+    if let Some(view) = state.view_history.undo() {
+        state.apply_view_settings(view);
+        state.notifications.info("Undo view change");
+    }
+}
+
+/// Redo the last undone plot / display / sort change.
+fn redo_view(state: &mut AppState) {
+    if let Some(view) = state.view_history.redo() {
+        state.apply_view_settings(view);
+        state.notifications.info("Redo view change");
     }
 }
 
 /// Redo: pop from redo stack and push onto operations.
 fn redo(state: &mut AppState) {
     if let Some(op) = state.redo_stack.pop() {
+        state.action_log.record(Action::OperationAdded {
+            operation: op.clone(),
+        });
         state.operations.push(op);
         state.preview_dirty = true;
-        state.status = "Redo".to_string();
+        state.notifications.info("Redo");
     }
 }
@@ -1,14 +1,29 @@
-use crate::enums::ExportFormat;
+use crate::app::VIEWER;
+use crate::enums::{ExportFormat, PipelineEdit};
 use crate::state::AppState;
-use dafer_utils::execution;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use crate::ui::redaction::{redaction_picker, selected_profile};
+use crate::ui::table::data_table;
+use crate::ui::tour::{TourTarget, highlight};
+use chrono::{Datelike, Local, NaiveDate, NaiveTime};
+use dafer_utils::action_log::Action;
+use dafer_utils::datasource::DataSource;
+use dafer_utils::execution::{self, ExportOptions, SplitLimit};
+use dafer_utils::help;
+use dafer_utils::i18n::tr;
+use dafer_utils::lint;
 use dafer_utils::operations::*;
+use dafer_utils::persistence::LastExport;
+use dafer_utils::query_engine;
 use eframe::egui;
-use egui_extras::{Column, TableBuilder};
+use std::path::Path;
+use std::time::Duration;
 
 /// Data Modification tab.
 ///
 /// Layout:
 /// - Top: three-column toolbar (Pipeline | Operation Builder | Export)
+///   (Export covers both the full pipeline result and the current view as displayed)
 /// - Bottom: table preview of current pipeline result (uses cached strings)
 pub fn modify_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
     if state.source.is_none() {
@@ -20,273 +35,920 @@ pub fn modify_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
         return;
     }
 
-    let col_names = state.column_names.clone();
-
     // ── Top Toolbar: 3-column layout ──
     ui.columns(3, |cols| {
-        // Column 0: Pipeline
         cols[0].group(|ui| {
             ui.set_min_height(180.0);
-            ui.strong("Pipeline");
-            ui.separator();
-            egui::ScrollArea::vertical()
-                .id_salt("pipeline_scroll")
-                .max_height(120.0)
-                .show(ui, |ui| {
-                    if state.operations.is_empty() {
-                        ui.label("No operations yet.");
-                    } else {
-                        let mut remove_idx: Option<usize> = None;
-                        for (i, op) in state.operations.iter().enumerate() {
-                            ui.horizontal(|ui| {
-                                ui.label(format!("{}.", i + 1));
-                                ui.label(op.to_string());
-                                if ui.small_button("X").clicked() {
-                                    remove_idx = Some(i);
-                                }
-                            });
+            pipeline_panel(ui, state);
+        });
+        let builder = cols[1].group(|ui| {
+            ui.set_min_height(180.0);
+            builder_panel(ui, state);
+        });
+        highlight(&cols[1], state, TourTarget::Builder, builder.response.rect);
+        let export = cols[2].group(|ui| {
+            ui.set_min_height(180.0);
+            export_panel(ui, state);
+        });
+        highlight(&cols[2], state, TourTarget::Export, export.response.rect);
+    });
+
+    ui.separator();
+
+    // ── Table Preview (uses cached strings for performance) ──
+    if !state.cached_cell_strings.is_empty() && !state.cached_header_names.is_empty() {
+        let n_rows = state.cached_cell_strings.len();
+        let n_cols = state.cached_header_names.len();
+
+        ui.label(format!("Preview ({n_rows} rows x {n_cols} cols)"));
+
+        let table_height = (ui.available_height() - 10.0).max(100.0);
+        data_table(ui, state, "modify", table_height);
+    }
+}
+
+// ─── Panels ──────────────────────────────────────────────────────────────────
+//
+// The toolbar groups are standalone panels so other layouts (the dock) can reuse them.
+
+/// Pipeline panel: list of operations with remove, undo/redo and clear.
+pub fn pipeline_panel(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.strong(tr(state.config.language, "Pipeline"));
+    ui.separator();
+    egui::ScrollArea::vertical()
+        .id_salt("pipeline_scroll")
+        .max_height(120.0)
+        .show(ui, |ui| {
+            if state.operations.is_empty() {
+                ui.label("No operations yet.");
+            } else {
+                // Timings are only shown while they match the current pipeline
+                let timings = state
+                    .step_timings
+                    .as_ref()
+                    .filter(|t| t.steps.len() == state.operations.len());
+                if let Some(t) = timings {
+                    ui.weak(format!("Scan: {}", format_duration(t.scan)));
+                }
+                let slowest = timings.and_then(|t| t.steps.iter().max().copied());
+
+                state
+                    .pipeline_selection
+                    .resize(state.operations.len(), false);
+                let mut remove_idx: Option<usize> = None;
+                let mut ungroup_idx: Option<usize> = None;
+                for (i, op) in state.operations.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut state.pipeline_selection[i], "")
+                            .on_hover_text("Select consecutive steps to group into a macro");
+                        ui.label(format!("{}.", i + 1));
+                        if let Operation::Macro { operations, .. } = op {
+                            egui::CollapsingHeader::new(op.to_string())
+                                .id_salt(("macro", i))
+                                .show(ui, |ui| {
+                                    for inner in operations {
+                                        ui.weak(inner.to_string());
+                                    }
+                                });
+                            if ui
+                                .small_button("⤢")
+                                .on_hover_text("Expand macro into its steps")
+                                .clicked()
+                            {
+                                ungroup_idx = Some(i);
+                            }
+                        } else {
+                            ui.label(op.to_string());
                         }
-                        if let Some(idx) = remove_idx {
-                            state.operations.remove(idx);
-                            state.redo_stack.clear();
-                            state.preview_dirty = true;
-                            state.status = "Operation removed".to_string();
+                        if let Some(failure) = state
+                            .step_failure
+                            .as_ref()
+                            .filter(|f| f.index == i)
+                        {
+                            ui.colored_label(GruvboxMaterial::red(255), "✖")
+                                .on_hover_text(&failure.message);
                         }
-                    }
-                });
-            ui.horizontal(|ui| {
-                if ui.small_button("Undo").clicked() {
-                    if let Some(op) = state.operations.pop() {
-                        state.redo_stack.push(op);
-                        state.preview_dirty = true;
-                        state.status = "Undo".to_string();
-                    }
+                        if let Some(elapsed) = timings.and_then(|t| t.steps.get(i)) {
+                            let text = format!("⏱ {}", format_duration(*elapsed));
+                            if Some(*elapsed) == slowest && state.operations.len() > 1 {
+                                ui.colored_label(GruvboxMaterial::orange(255), text)
+                                    .on_hover_text("Slowest step");
+                            } else {
+                                ui.weak(text);
+                            }
+                        }
+                        if ui.small_button("X").clicked() {
+                            remove_idx = Some(i);
+                        }
+                    });
                 }
-                if ui.small_button("Redo").clicked() {
-                    if let Some(op) = state.redo_stack.pop() {
-                        state.operations.push(op);
-                        state.preview_dirty = true;
-                        state.status = "Redo".to_string();
-                    }
+                if let Some(idx) = remove_idx {
+                    state.request_pipeline_edit(PipelineEdit::Remove(idx));
                 }
-                if ui.small_button("Clear").clicked() {
-                    state.operations.clear();
-                    state.redo_stack.clear();
-                    state.preview_dirty = true;
-                    state.status = "Pipeline cleared".to_string();
+                if let Some(idx) = ungroup_idx {
+                    state.ungroup_operation(idx);
                 }
+            }
+        });
+    ui.horizontal(|ui| {
+        if ui.small_button("Undo").clicked() {
+            if let Some(op) = state.operations.pop() {
+                state.action_log.record(Action::OperationRemoved {
+                    index: state.operations.len(),
+                    operation: op.clone(),
+                });
+                state.redo_stack.push(op);
+                state.preview_dirty = true;
+                state.notifications.info("Undo");
+            }
+        }
+        if ui.small_button("Redo").clicked() {
+            if let Some(op) = state.redo_stack.pop() {
+                state.action_log.record(Action::OperationAdded {
+                    operation: op.clone(),
+                });
+                state.operations.push(op);
+                state.preview_dirty = true;
+                state.notifications.info("Redo");
+            }
+        }
+        if ui.small_button("Clear").clicked() {
+            state.request_pipeline_edit(PipelineEdit::Clear);
+        }
+        ui.checkbox(&mut state.pipeline_locked, "🔒")
+            .on_hover_text("Lock pipeline: confirm before removing or clearing operations");
+        if ui
+            .checkbox(&mut state.time_steps, "Timing")
+            .on_hover_text("Time each step by re-running the pipeline prefixes")
+            .changed()
+        {
+            state.preview_dirty = true;
+        }
+    });
+
+    // ── What the optimized plan reads from the source ──
+    match &state.scan_report {
+        Some(Ok(report)) => {
+            let (read, total) = report.columns_read;
+            let pushed = if report.predicate_pushed {
+                "filter pushed to scan"
+            } else {
+                "no filter at scan"
+            };
+            ui.weak(format!("Reads {read}/{total} columns · {pushed}"))
+                .on_hover_text(
+                    "From the optimized query plan. Filters and column selections placed \
+                     early let the scan skip data (Parquet row groups, unused columns).",
+                );
+        }
+        Some(Err(e)) => {
+            ui.weak("Scan report unavailable")
+                .on_hover_text(format!("Could not read the optimized plan: {}", e));
+        }
+        None => {}
+    }
+
+    // ── Lint suggestions (one-click reorder/removal) ──
+    let mut fix: Option<lint::Fix> = None;
+    for suggestion in lint::lint(&state.operations) {
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                GruvboxMaterial::orange(255),
+                format!("⚠ {}", suggestion.message),
+            );
+            if ui.small_button("Apply").clicked() {
+                fix = Some(suggestion.fix);
+            }
+        });
+    }
+    if let Some(fix) = fix {
+        fix.apply(&mut state.operations);
+        state.pipeline_selection.clear();
+        state.redo_stack.clear();
+        state.preview_dirty = true;
+        state.notifications.info("Applied suggestion");
+    }
+
+    // ── Group selected steps into a macro ──
+    let selected = state.pipeline_selection.iter().filter(|s| **s).count();
+    if selected >= 2 {
+        ui.horizontal(|ui| {
+            ui.label("Macro:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.macro_name)
+                    .hint_text("e.g. sensor cleanup")
+                    .desired_width(120.0),
+            );
+            let range = state.selected_range();
+            let can_group = range.is_some() && !state.macro_name.trim().is_empty();
+            let response = ui
+                .add_enabled(can_group, egui::Button::new(format!("Group {selected}")))
+                .on_disabled_hover_text("Select consecutive steps and enter a name");
+            if let (true, Some(range)) = (response.clicked(), range) {
+                let name = state.macro_name.trim().to_string();
+                state.group_operations(range, name);
+                state.macro_name.clear();
+            }
+        });
+    }
+}
+
+/// Render the first rows of the pipeline result with `render` and copy it.
+fn copy_result_table(
+    ui: &egui::Ui,
+    state: &mut AppState,
+    render: fn(&DataSource, &[Operation], u32) -> anyhow::Result<String>,
+    label: &str,
+) {
+    let Some(source) = &state.source else {
+        return;
+    };
+    match render(source, &state.operations, state.copy_table_rows) {
+        Ok(text) => {
+            ui.ctx().copy_text(text);
+            state.notifications.info(format!(
+                "Copied {} rows as {} table",
+                state.copy_table_rows, label
+            ));
+        }
+        Err(e) => state.notifications.error(format!("Copy error: {}", e)),
+    }
+}
+
+/// Short wall-clock duration for timing badges (e.g. `12.3 ms`, `1.42 s`).
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2} s", secs)
+    } else {
+        format!("{:.1} ms", secs * 1000.0)
+    }
+}
+
+/// Operation builder panel: operation type selector plus its parameter form.
+pub fn builder_panel(ui: &mut egui::Ui, state: &mut AppState) {
+    let col_names = state.column_names.clone();
+
+    let lang = state.config.language;
+
+    ui.strong(tr(lang, "Add Operation"));
+    ui.separator();
+    egui::ScrollArea::vertical()
+        .id_salt("op_builder_scroll")
+        .max_height(150.0)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_label(tr(lang, "Operation"))
+                    .selected_text(tr(lang, &state.selected_op.to_string()).to_string())
+                    .show_ui(ui, |ui| {
+                        for op_type in OperationType::all() {
+                            let label = tr(lang, &op_type.to_string()).to_string();
+                            ui.selectable_value(&mut state.selected_op, *op_type, label);
+                        }
+                    });
+                ui.menu_button("?", |ui| operation_help_popover(ui, state.selected_op));
             });
+            ui.add_space(2.0);
+
+            match state.selected_op {
+                OperationType::Filter => render_filter_builder(ui, state, &col_names),
+                OperationType::Sort => render_sort_builder(ui, state, &col_names),
+                OperationType::DropColumn => render_drop_builder(ui, state, &col_names),
+                OperationType::RenameColumn => render_rename_builder(ui, state, &col_names),
+                OperationType::SelectColumns => render_select_builder(ui, state, &col_names),
+                OperationType::Limit => render_limit_builder(ui, state),
+                OperationType::Skip => render_skip_builder(ui, state),
+                OperationType::Tail => render_tail_builder(ui, state),
+                OperationType::FillNull => render_fill_null_builder(ui, state, &col_names),
+                OperationType::CastColumn => render_cast_builder(ui, state, &col_names),
+                OperationType::ParseDatetime => {
+                    render_parse_datetime_builder(ui, state, &col_names)
+                }
+                OperationType::JsonExtract => render_json_extract_builder(ui, state, &col_names),
+                OperationType::Round => render_round_builder(ui, state, &col_names),
+                OperationType::Normalize => render_normalize_builder(ui, state, &col_names),
+                OperationType::Window => render_window_builder(ui, state, &col_names),
+                OperationType::Coalesce => render_coalesce_builder(ui, state, &col_names),
+                OperationType::DateRange => render_date_range_builder(ui, state),
+            }
         });
+}
 
-        // Column 1: Operation Builder
-        cols[1].group(|ui| {
-            ui.set_min_height(180.0);
-            ui.strong("Add Operation");
-            ui.separator();
-            egui::ScrollArea::vertical()
-                .id_salt("op_builder_scroll")
-                .max_height(150.0)
+/// Parameter help of an operation builder, with its reference tables.
+fn operation_help_popover(ui: &mut egui::Ui, op: OperationType) {
+    let help = help::operation_help(op);
+    ui.set_max_width(360.0);
+    egui::ScrollArea::vertical()
+        .max_height(400.0)
+        .show(ui, |ui| {
+            ui.strong(op.to_string());
+            ui.label(help.summary);
+            ui.add_space(4.0);
+            egui::Grid::new("op_help_params")
+                .num_columns(2)
+                .spacing([8.0, 2.0])
                 .show(ui, |ui| {
-                    egui::ComboBox::from_label("Operation")
-                        .selected_text(state.selected_op.to_string())
-                        .show_ui(ui, |ui| {
-                            for op_type in OperationType::all() {
-                                ui.selectable_value(
-                                    &mut state.selected_op,
-                                    *op_type,
-                                    op_type.to_string(),
-                                );
+                    for (param, text) in help.params {
+                        ui.label(egui::RichText::new(*param).strong());
+                        ui.label(*text);
+                        ui.end_row();
+                    }
+                });
+            if !help.examples.is_empty() {
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new("Examples").strong());
+                for example in help.examples {
+                    ui.label(egui::RichText::new(*example).monospace());
+                }
+            }
+            for topic in help.topics {
+                ui.add_space(4.0);
+                ui.collapsing(topic.title(), |ui| {
+                    egui::Grid::new(("op_help_topic", topic.title()))
+                        .num_columns(2)
+                        .spacing([8.0, 2.0])
+                        .show(ui, |ui| {
+                            for (syntax, meaning) in topic.entries() {
+                                ui.label(egui::RichText::new(*syntax).monospace());
+                                ui.label(*meaning);
+                                ui.end_row();
                             }
                         });
-                    ui.add_space(2.0);
-
-                    match state.selected_op {
-                        OperationType::Filter => {
-                            render_filter_builder(ui, state, &col_names)
-                        }
-                        OperationType::Sort => {
-                            render_sort_builder(ui, state, &col_names)
-                        }
-                        OperationType::DropColumn => {
-                            render_drop_builder(ui, state, &col_names)
-                        }
-                        OperationType::RenameColumn => {
-                            render_rename_builder(ui, state, &col_names)
-                        }
-                        OperationType::SelectColumns => {
-                            render_select_builder(ui, state, &col_names)
-                        }
-                        OperationType::Limit => render_limit_builder(ui, state),
-                        OperationType::FillNull => {
-                            render_fill_null_builder(ui, state, &col_names)
-                        }
-                        OperationType::CastColumn => {
-                            render_cast_builder(ui, state, &col_names)
-                        }
-                        OperationType::ParseDatetime => {
-                            render_parse_datetime_builder(ui, state, &col_names)
-                        }
-                    }
                 });
+            }
         });
+}
 
-        // Column 2: Export
-        cols[2].group(|ui| {
-            ui.set_min_height(180.0);
-            ui.strong("Export");
-            ui.separator();
-
-            egui::ComboBox::from_label("Format")
-                .selected_text(state.export_format.to_string())
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(
-                        &mut state.export_format,
-                        ExportFormat::Csv,
-                        "CSV",
-                    );
-                    ui.selectable_value(
-                        &mut state.export_format,
-                        ExportFormat::Parquet,
-                        "Parquet",
-                    );
-                });
+/// Export panel: full pipeline export and export/copy of the current view.
+pub fn export_panel(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.strong("Export");
+    ui.separator();
+    if VIEWER {
+        ui.weak("This is a read-only viewer build: exporting is disabled.");
+        return;
+    }
 
-            ui.add_space(4.0);
+    egui::ComboBox::from_label("Format")
+        .selected_text(state.export_format.to_string())
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut state.export_format, ExportFormat::Csv, "CSV");
+            ui.selectable_value(&mut state.export_format, ExportFormat::Parquet, "Parquet");
+            ui.selectable_value(
+                &mut state.export_format,
+                ExportFormat::CsvAndParquet,
+                "CSV + Parquet",
+            )
+            .on_hover_text("Runs the pipeline once and writes both files");
+        });
+    ui.checkbox(&mut state.export_options.row_hash, "Row hash column")
+        .on_hover_text(format!(
+            "Append {} with the SHA-256 of each row",
+            execution::ROW_HASH_COLUMN
+        ));
+    ui.checkbox(&mut state.export_options.manifest, "Write manifest")
+        .on_hover_text("Sidecar .manifest.json with row count, schema and file SHA-256");
+    ui.add_enabled(
+        state.export_format != ExportFormat::Parquet,
+        egui::Checkbox::new(&mut state.export_options.provenance, "Write provenance"),
+    )
+    .on_hover_text(
+        "Sidecar .provenance.json with source path and SHA-256, operations, app version \
+         and time.",
+    )
+    .on_disabled_hover_text("Parquet exports always embed provenance in their metadata");
+    ui.checkbox(&mut state.export_options.summary, "Write summary")
+        .on_hover_text("Companion _summary.csv with the statistics of each exported column");
+    ui.checkbox(&mut state.export_options.atomic, "Atomic write")
+        .on_hover_text("Write to a .partial file and rename it when complete");
+    float_precision_option(ui, state);
+    split_options(ui, &mut state.export_options.split);
+    redaction_picker(ui, state);
 
-            if ui.button("Export...").clicked() {
-                let ext = match state.export_format {
-                    ExportFormat::Csv => "csv",
-                    ExportFormat::Parquet => "parquet",
-                };
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Export file", &[ext])
-                    .save_file()
-                {
-                    if let Some(source) = &state.source {
-                        let result = match state.export_format {
-                            ExportFormat::Csv => {
-                                execution::export_csv(source, &state.operations, &path)
-                            }
-                            ExportFormat::Parquet => {
-                                execution::export_parquet(
-                                    source,
-                                    &state.operations,
-                                    &path,
-                                )
-                            }
-                        };
-                        match result {
-                            Ok(()) => {
-                                state.status =
-                                    format!("Exported to {}", path.display());
-                            }
-                            Err(e) => {
-                                state.status = format!("Export error: {}", e);
-                            }
-                        }
-                    }
-                }
+    ui.add_space(4.0);
+
+    if ui.button("Export...").clicked() {
+        request_export(state);
+    }
+
+    #[cfg(feature = "duckdb")]
+    duckdb_export(ui, state);
+
+    if ui
+        .add_enabled(
+            state.source.is_some(),
+            egui::Button::new("Pipeline as SQL..."),
+        )
+        .on_hover_text("The pipeline as a DuckDB/PostgreSQL SELECT or a dbt model")
+        .clicked()
+    {
+        state.show_sql_export = true;
+    }
+    if ui
+        .add_enabled(
+            state.source.is_some(),
+            egui::Button::new("Open in External App"),
+        )
+        .on_hover_text(
+            "Write the full result to a temporary CSV and open it with the default application",
+        )
+        .clicked()
+    {
+        if let Some(source) = &state.source {
+            match execution::open_externally(source, &state.operations) {
+                Ok(path) => state
+                    .notifications
+                    .quiet(format!("Opened {}", path.display())),
+                Err(e) => state.notifications.error(format!("Open error: {}", e)),
             }
+        }
+    }
 
-            ui.add_space(8.0);
-            ui.label(
-                egui::RichText::new(format!(
-                    "{} operations in pipeline",
-                    state.operations.len()
-                ))
-                .small(),
+    ui.collapsing("Completion hook", |ui| {
+        let response = ui
+            .add(
+                egui::TextEdit::singleline(&mut state.config.completion_hook)
+                    .hint_text("shell command (empty = off)")
+                    .desired_width(f32::INFINITY),
+            )
+            .on_hover_text(
+                "Run after each export with a JSON summary on stdin and \
+                 DAFER_RUN_STATUS=success|failure. For a webhook: \
+                 curl -s -X POST -H 'Content-Type: application/json' -d @- <url>",
             );
-        });
+        if response.lost_focus() {
+            if let Err(e) = state.config.save() {
+                state
+                    .notifications
+                    .error(format!("Config save error: {}", e));
+            }
+        }
     });
 
-    ui.separator();
+    ui.add_space(4.0);
+    ui.label(egui::RichText::new("Current view (as displayed):").small());
+    ui.horizontal(|ui| {
+        if !VIEWER && ui.button("Export view...").clicked() {
+            export_view(state);
+        }
+        if ui.button("Copy view").clicked() {
+            match execution::grid_to_csv_string(
+                &state.cached_header_names,
+                &state.full_cell_strings(),
+            ) {
+                Ok(text) => {
+                    ui.ctx().copy_text(text);
+                    state.notifications.info("View copied to clipboard");
+                }
+                Err(e) => state.notifications.error(format!("Copy error: {}", e)),
+            }
+        }
+    });
 
-    // ── Table Preview (uses cached strings for performance) ──
-    if !state.cached_cell_strings.is_empty() && !state.cached_header_names.is_empty() {
-        let n_rows = state.cached_cell_strings.len();
-        let n_cols = state.cached_header_names.len();
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Result as table:").small());
+        ui.add(
+            egui::DragValue::new(&mut state.copy_table_rows)
+                .range(1..=1000)
+                .suffix(" rows"),
+        );
+    });
+    ui.horizontal(|ui| {
+        if ui
+            .button("Copy Markdown")
+            .on_hover_text("For GitHub issues and wikis")
+            .clicked()
+        {
+            copy_result_table(ui, state, execution::markdown_table, "Markdown");
+        }
+        if ui.button("Copy HTML").clicked() {
+            copy_result_table(ui, state, execution::html_table, "HTML");
+        }
+    });
 
-        ui.label(format!("Preview ({n_rows} rows x {n_cols} cols)"));
+    ui.add_space(8.0);
+    ui.label(
+        egui::RichText::new(format!("{} operations in pipeline", state.operations.len())).small(),
+    );
+}
 
-        let text_height = ui.text_style_height(&egui::TextStyle::Body);
-        let row_height = text_height + 4.0;
-        let available = ui.available_size();
-        let table_height = (available.y - 10.0).max(100.0);
-        let header_names: Vec<String> = state.cached_header_names.clone();
-
-        egui::ScrollArea::horizontal()
-            .id_salt("modify_hscroll")
-            .show(ui, |ui| {
-                TableBuilder::new(ui)
-                    .id_salt("modify_data_table")
-                    .striped(true)
-                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                    .columns(Column::initial(100.0).at_least(60.0).clip(true).resizable(true), n_cols)
-                    .max_scroll_height(table_height)
-                    .header(row_height + 2.0, |mut header| {
-                        for name in &header_names {
-                            header.col(|ui| {
-                                ui.strong(name.as_str());
-                            });
-                        }
-                    })
-                    .body(|body| {
-                        body.rows(row_height, n_rows, |mut row| {
-                            let visual_row = row.index();
-                            for col_idx in 0..n_cols {
-                                row.col(|ui| {
-                                    if !ui.is_rect_visible(ui.max_rect()) {
-                                        return;
-                                    }
-                                    ui.label(
-                                        state.cached_cell_strings[visual_row][col_idx].as_str(),
-                                    );
-                                });
-                            }
-                        });
-                    });
+/// Split the export into numbered files (`out_0001.csv`, ...) by rows or size.
+fn split_options(ui: &mut egui::Ui, split: &mut Option<SplitLimit>) {
+    ui.horizontal(|ui| {
+        let mut enabled = split.is_some();
+        if ui
+            .checkbox(&mut enabled, "Split into files of at most")
+            .on_hover_text("Numbered files out_0001, out_0002, ... for upload size limits")
+            .changed()
+        {
+            *split = enabled.then_some(SplitLimit::Rows(1_000_000));
+        }
+        let Some(limit) = split else {
+            return;
+        };
+        let (mut value, mut by_size) = match *limit {
+            SplitLimit::Rows(n) => (n, false),
+            SplitLimit::Megabytes(mb) => (mb, true),
+        };
+        ui.add(egui::DragValue::new(&mut value).range(1..=usize::MAX));
+        egui::ComboBox::from_id_salt("export_split_unit")
+            .width(60.0)
+            .selected_text(if by_size { "MB" } else { "rows" })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut by_size, false, "rows");
+                ui.selectable_value(&mut by_size, true, "MB")
+                    .on_hover_text("Approximate; Parquet parts come out smaller");
             });
+        *limit = if by_size {
+            SplitLimit::Megabytes(value)
+        } else {
+            SplitLimit::Rows(value)
+        };
+    });
+}
+
+/// CSV float rounding: off writes every float exactly.
+fn float_precision_option(ui: &mut egui::Ui, state: &mut AppState) {
+    let precision = &mut state.export_options.float_precision;
+    ui.add_enabled_ui(state.export_format != ExportFormat::Parquet, |ui| {
+        ui.horizontal(|ui| {
+            let mut fixed = precision.is_some();
+            if ui
+                .checkbox(&mut fixed, "Round floats")
+                .on_hover_text("Write CSV floats with a fixed number of decimals")
+                .changed()
+            {
+                *precision = fixed.then_some(6);
+            }
+            if let Some(p) = precision {
+                ui.add(egui::DragValue::new(p).range(0..=17).suffix(" dp"));
+            }
+        });
+    });
+}
+
+/// Export, first listing what a CSV file would lose (if anything) so the user
+/// can switch to Parquet.
+fn request_export(state: &mut AppState) {
+    if state.export_format == ExportFormat::Csv {
+        if let Some(source) = &state.source {
+            match execution::csv_export_warnings(
+                source,
+                &state.operations,
+                &state.export_options,
+            ) {
+                Ok(warnings) if !warnings.is_empty() => {
+                    state.pending_lossy_export = Some(warnings);
+                    return;
+                }
+                Ok(_) => {}
+                Err(e) => state
+                    .notifications
+                    .warn(format!("Could not check the CSV schema: {}", e)),
+            }
+        }
     }
+    export_result(state);
 }
 
-// ─── Operation Builders ───────────────────────────────────────────────────────
+/// Ask for the output file and export the full result in the selected format.
+pub(crate) fn export_result(state: &mut AppState) {
+    let ext = export_extension(state.export_format);
+    let Some(chosen) = rfd::FileDialog::new()
+        .add_filter("Export file", &[ext])
+        .save_file()
+    else {
+        return;
+    };
+    let path = execution::with_extension(&chosen, ext);
+    let mut targets = vec![path.clone()];
+    if state.export_format == ExportFormat::CsvAndParquet {
+        targets.push(path.with_extension("parquet"));
+    }
+    if state.export_options.split.is_some() {
+        targets = targets
+            .iter()
+            .map(|target| execution::part_path(target, 1))
+            .collect();
+    }
+    if state.export_options.summary {
+        targets.push(execution::summary_path(&path));
+    }
+    // The native dialog already confirmed overwriting the name it returned
+    if targets
+        .iter()
+        .any(|target| *target != chosen && target.exists())
+    {
+        state.pending_overwrite = Some(path);
+        return;
+    }
+    export_to(state, &path);
+}
 
-fn render_filter_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
-    column_combo(ui, "Column", &mut state.filter_column, col_names);
+fn export_extension(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Csv | ExportFormat::CsvAndParquet => "csv",
+        ExportFormat::Parquet => "parquet",
+    }
+}
 
-    egui::ComboBox::from_label("Operator")
-        .selected_text(state.filter_op.to_string())
-        .show_ui(ui, |ui| {
-            for op in FilterOp::all() {
-                ui.selectable_value(&mut state.filter_op, op.clone(), op.to_string());
+/// Export the full result to `path` in the selected format.
+pub(crate) fn export_to(state: &mut AppState, path: &Path) {
+    if VIEWER {
+        return;
+    }
+    let output = match state.export_format {
+        ExportFormat::CsvAndParquet => format!(
+            "{} and {}",
+            path.with_extension("csv").display(),
+            path.with_extension("parquet").display()
+        ),
+        _ => path.display().to_string(),
+    };
+    if let Some(source) = &state.source {
+        let started = std::time::Instant::now();
+        let options = ExportOptions {
+            redaction: selected_profile(state),
+            ..state.export_options.clone()
+        };
+        let result = match state.export_format {
+            ExportFormat::Csv => execution::export_csv(source, &state.operations, path, &options),
+            ExportFormat::Parquet => {
+                execution::export_parquet(source, &state.operations, path, &options)
+            }
+            ExportFormat::CsvAndParquet => {
+                execution::export_csv_and_parquet(source, &state.operations, path, &options)
+            }
+        };
+        let output = match result.as_deref() {
+            Ok([file]) => file.display().to_string(),
+            Ok([first, second]) => format!("{} and {}", first.display(), second.display()),
+            Ok(files @ [first, .., last]) => format!(
+                "{} ... {} ({} files)",
+                first.display(),
+                last.display(),
+                files.len()
+            ),
+            _ => output,
+        };
+        let manifest = match &result {
+            Ok(files) if files.len() == 1 => {
+                execution::manifest_path(&files[0]).display().to_string()
             }
+            _ => "next to each file".to_string(),
+        };
+        let summary = execution::RunSummary {
+            success: result.is_ok(),
+            output: output.clone(),
+            source: Some(source.path.display().to_string()),
+            operations: state.operations.len(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        state.action_log.record(Action::Exported {
+            path: output.clone(),
+            format: state.export_format.to_string(),
+            source: summary.source.clone(),
+            operations: state.operations.clone(),
+            success: summary.success,
         });
-
-    if state.filter_op.needs_value() {
-        ui.horizontal(|ui| {
-            ui.label("Value:");
-            ui.text_edit_singleline(&mut state.filter_value);
+        if result.is_ok() {
+            let rows = query_engine::count_rows(source, &state.operations, &[]).ok();
+            state.last_export = Some(LastExport::new(
+                &output,
+                &state.export_format.to_string(),
+                rows,
+                &state.operations,
+            ));
+        }
+        let result = result.map(|_| match &options.redaction {
+            Some(profile) => format!(" (redacted: {})", profile.name),
+            None => String::new(),
         });
+        match result {
+            Ok(redacted) if state.export_options.manifest => {
+                state.notifications.info(format!(
+                    "Exported to {}{} (manifest: {})",
+                    output, redacted, manifest
+                ));
+            }
+            Ok(redacted) => {
+                state
+                    .notifications
+                    .info(format!("Exported to {}{}", output, redacted));
+            }
+            Err(e) => {
+                state.notifications.error(format!("Export error: {}", e));
+            }
+        }
+        let hook = state.config.completion_hook.trim();
+        if !hook.is_empty() {
+            if let Err(e) = execution::run_hook(hook, &summary) {
+                state
+                    .notifications
+                    .error(format!("Completion hook failed: {}", e));
+            }
+        }
     }
+}
 
-    if ui.button("Apply Filter").clicked() && !state.filter_column.is_empty() {
-        let op = Operation::Filter {
-            column: state.filter_column.clone(),
-            op: state.filter_op.clone(),
-            value: state.filter_value.clone(),
+/// Create or replace a table in a DuckDB database file with the result.
+#[cfg(feature = "duckdb")]
+fn duckdb_export(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal(|ui| {
+        ui.label("DuckDB table");
+        ui.add(egui::TextEdit::singleline(&mut state.duckdb_table).desired_width(120.0));
+        let ready = state.source.is_some() && !state.duckdb_table.trim().is_empty();
+        if !ui
+            .add_enabled(ready, egui::Button::new("Export to DuckDB..."))
+            .on_hover_text("Creates or replaces the table; the database file is created if missing")
+            .clicked()
+        {
+            return;
+        }
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("DuckDB database", &["duckdb", "db"])
+            .save_file()
+        else {
+            return;
+        };
+        let Some(source) = &state.source else {
+            return;
         };
+        let table = state.duckdb_table.trim().to_string();
+        let options = ExportOptions {
+            redaction: selected_profile(state),
+            ..state.export_options.clone()
+        };
+        let result = execution::export_duckdb(source, &state.operations, &path, &table, &options);
+        state.action_log.record(Action::Exported {
+            path: format!("{}#{}", path.display(), table),
+            format: "DuckDB".to_string(),
+            source: Some(source.path.display().to_string()),
+            operations: state.operations.clone(),
+            success: result.is_ok(),
+        });
+        let output = format!("{}#{}", path.display(), table);
+        if result.is_ok() {
+            let rows = query_engine::count_rows(source, &state.operations, &[]).ok();
+            state.last_export = Some(LastExport::new(&output, "DuckDB", rows, &state.operations));
+        }
+        match result {
+            Ok(()) => state.notifications.info(format!(
+                "Exported to table {} in {}",
+                table,
+                path.display()
+            )),
+            Err(e) => state.notifications.error(format!("Export error: {}", e)),
+        }
+    });
+}
+
+// ─── Operation Builders ───────────────────────────────────────────────────────
+
+fn render_filter_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.filter_column, col_names);
+
+    filter_op_combo(
+        ui,
+        egui::ComboBox::from_label(tr(lang, "Operator")),
+        &mut state.filter_op,
+    );
+    filter_options_inputs(ui, &mut state.filter_options);
+    let kind = state.column_kind(&state.filter_column);
+    let invalid = filter_value_inputs(
+        ui,
+        "filter_value",
+        &mut state.filter_op,
+        &mut state.filter_value,
+        state.filter_options.value_is_column,
+        col_names,
+        kind,
+    );
+    observed_value_picker(ui, state);
+    if let Some(reason) = &invalid {
+        ui.colored_label(GruvboxMaterial::red(255), reason);
+    }
+
+    let can_apply = !state.filter_column.is_empty() && invalid.is_none();
+    let op = Operation::Filter {
+        column: state.filter_column.clone(),
+        op: state.filter_op.clone(),
+        value: state.filter_value.clone(),
+        options: state.filter_options,
+    };
+    let apply = ui
+        .horizontal(|ui| {
+            let clicked = ui
+                .add_enabled(can_apply, egui::Button::new("Apply Filter"))
+                .clicked();
+            if can_apply {
+                dry_run_label(ui, state, &op);
+            }
+            clicked
+        })
+        .inner;
+    if apply {
         apply_op(state, op);
     }
 }
 
+/// Rows the pipeline would keep with `candidate` appended, as a share of the
+/// current result. Counted (not collected) when the candidate changes.
+fn dry_run_label(ui: &mut egui::Ui, state: &mut AppState, candidate: &Operation) {
+    let Some(source) = &state.source else {
+        return;
+    };
+    let key = format!("{:?}", candidate);
+    if state.dry_run_rows.is_none() {
+        state.dry_run_rows = query_engine::count_rows(source, &state.operations, &[]).ok();
+    }
+    if state.dry_run.as_ref().is_none_or(|(k, _)| *k != key) {
+        let rows = query_engine::count_rows(source, &state.operations, &[candidate.clone()]).ok();
+        state.dry_run = Some((key, rows));
+    }
+    let (Some(total), Some((_, Some(rows)))) = (state.dry_run_rows, &state.dry_run) else {
+        return;
+    };
+    let share = if total == 0 {
+        0.0
+    } else {
+        *rows as f64 / total as f64 * 100.0
+    };
+    let text = format!("→ {} of {} rows ({:.2}%)", rows, total, share);
+    if *rows == 0 {
+        ui.colored_label(GruvboxMaterial::orange(255), text)
+            .on_hover_text("No row would be kept");
+    } else {
+        ui.weak(text);
+    }
+}
+
+/// Dropdown of the values a low-cardinality column actually holds, for `=`/`≠`
+/// filters, with a warning when the typed value matches no row.
+fn observed_value_picker(ui: &mut egui::Ui, state: &mut AppState) {
+    let wants_value = matches!(state.filter_op, FilterOp::Eq | FilterOp::Neq)
+        && !state.filter_options.value_is_column
+        && !state.filter_column.is_empty();
+    let Some(source) = state.source.as_ref().filter(|_| wants_value) else {
+        return;
+    };
+    if state
+        .filter_observed
+        .as_ref()
+        .is_none_or(|(column, _)| *column != state.filter_column)
+    {
+        let values = query_engine::observed_values(
+            source,
+            &state.operations,
+            &state.filter_column,
+            query_engine::OBSERVED_VALUES_CAP,
+        )
+        .unwrap_or(None);
+        state.filter_observed = Some((state.filter_column.clone(), values));
+    }
+    let Some((_, Some(values))) = &state.filter_observed else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label("Observed values")
+            .selected_text(format!("{} values", values.len()))
+            .show_ui(ui, |ui| {
+                for v in values {
+                    ui.selectable_value(&mut state.filter_value, v.clone(), v);
+                }
+            });
+    });
+    // Numbers match by value ("2" and "2.0"), text exactly or ignoring case
+    let typed = state.filter_value.trim();
+    let number = typed.parse::<f64>().ok();
+    let unmatched = !typed.is_empty()
+        && !values.iter().any(|v| {
+            v == typed
+                || (state.filter_options.case_insensitive && v.eq_ignore_ascii_case(typed))
+                || number.is_some() && v.parse::<f64>().ok() == number
+        });
+    if unmatched {
+        ui.colored_label(
+            GruvboxMaterial::orange(255),
+            format!("⚠ No row has the value \"{}\"", state.filter_value),
+        );
+    }
+}
+
 fn render_sort_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
-    column_combo(ui, "Column", &mut state.sort_op_column, col_names);
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.sort_op_column, col_names);
     ui.checkbox(&mut state.sort_op_descending, "Descending");
+    ui.checkbox(&mut state.sort_op_stable, "Stable")
+        .on_hover_text("Rows with equal values keep their current (input) order");
 
     if ui.button("Apply Sort").clicked() && !state.sort_op_column.is_empty() {
         let op = Operation::Sort {
             column: state.sort_op_column.clone(),
             descending: state.sort_op_descending,
+            stable: state.sort_op_stable,
         };
         apply_op(state, op);
     }
@@ -297,7 +959,7 @@ fn render_drop_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[Str
 
     if ui.button("Drop Column").clicked() && !state.drop_column.is_empty() {
         let op = Operation::DropColumn(state.drop_column.clone());
-        apply_op(state, op);
+        request_column_change(state, op);
     }
 }
 
@@ -316,7 +978,7 @@ fn render_rename_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[S
             from: state.rename_from.clone(),
             to: state.rename_to.clone(),
         };
-        apply_op(state, op);
+        request_column_change(state, op);
     }
 }
 
@@ -363,16 +1025,48 @@ fn render_limit_builder(ui: &mut egui::Ui, state: &mut AppState) {
         ui.add(egui::DragValue::new(&mut state.limit_n).range(1..=u32::MAX));
     });
 
-    if ui.button("Apply Limit").clicked() {
-        let op = Operation::Limit(state.limit_n);
+    let op = Operation::Limit(state.limit_n);
+    let apply = ui
+        .horizontal(|ui| {
+            let clicked = ui.button("Apply Limit").clicked();
+            dry_run_label(ui, state, &op);
+            clicked
+        })
+        .inner;
+    if apply {
+        apply_op(state, op);
+    }
+}
+
+fn render_skip_builder(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal(|ui| {
+        ui.label("Rows to skip:");
+        ui.add(egui::DragValue::new(&mut state.skip_n).range(1..=u32::MAX));
+    });
+
+    if ui.button("Apply Skip").clicked() {
+        let op = Operation::Skip(state.skip_n);
+        apply_op(state, op);
+    }
+}
+
+fn render_tail_builder(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal(|ui| {
+        ui.label("Last rows:");
+        ui.add(egui::DragValue::new(&mut state.tail_n).range(1..=u32::MAX));
+    });
+
+    if ui.button("Apply Tail").clicked() {
+        let op = Operation::Tail(state.tail_n);
         apply_op(state, op);
     }
 }
 
 fn render_fill_null_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
-    column_combo(ui, "Column", &mut state.fill_column, col_names);
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.fill_column, col_names);
 
-    egui::ComboBox::from_label("Strategy")
+    egui::ComboBox::from_label(tr(lang, "Strategy"))
         .selected_text(state.fill_strategy.to_string())
         .show_ui(ui, |ui| {
             for s in FillNullStrategy::all() {
@@ -380,14 +1074,25 @@ fn render_fill_null_builder(ui: &mut egui::Ui, state: &mut AppState, col_names:
             }
         });
 
+    let mut invalid = None;
     if state.fill_strategy.needs_value() {
+        let kind = state.column_kind(&state.fill_column);
         ui.horizontal(|ui| {
             ui.label("Fill value:");
-            ui.text_edit_singleline(&mut state.fill_value);
+            typed_value_field(ui, "fill_value", &mut state.fill_value, kind);
         });
+        invalid = kind.check(&state.fill_value);
+        if let Some(reason) = &invalid {
+            ui.colored_label(GruvboxMaterial::red(255), reason);
+        }
     }
+    group_columns_menu(ui, &mut state.fill_over, col_names);
 
-    if ui.button("Apply Fill Null").clicked() && !state.fill_column.is_empty() {
+    let can_apply = !state.fill_column.is_empty() && invalid.is_none();
+    if ui
+        .add_enabled(can_apply, egui::Button::new("Apply Fill Null"))
+        .clicked()
+    {
         let value = if state.fill_strategy.needs_value() {
             Some(state.fill_value.clone())
         } else {
@@ -397,15 +1102,17 @@ fn render_fill_null_builder(ui: &mut egui::Ui, state: &mut AppState, col_names:
             column: state.fill_column.clone(),
             strategy: state.fill_strategy.clone(),
             value,
+            over: state.fill_over.clone(),
         };
         apply_op(state, op);
     }
 }
 
 fn render_cast_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
-    column_combo(ui, "Column", &mut state.cast_column, col_names);
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.cast_column, col_names);
 
-    egui::ComboBox::from_label("Target type")
+    egui::ComboBox::from_label(tr(lang, "Target type"))
         .selected_text(state.cast_dtype.to_string())
         .show_ui(ui, |ui| {
             for dt in DTypeTag::all() {
@@ -413,21 +1120,63 @@ fn render_cast_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[Str
             }
         });
 
+    ui.horizontal(|ui| {
+        ui.label("Quarantine failures to:");
+        ui.add(
+            egui::TextEdit::singleline(&mut state.cast_quarantine).hint_text("(optional column)"),
+        );
+    });
+
+    cast_risk_warning(ui, state);
+
     if ui.button("Apply Cast").clicked() && !state.cast_column.is_empty() {
+        let quarantine = state.cast_quarantine.trim();
         let op = Operation::CastColumn {
             column: state.cast_column.clone(),
             dtype: state.cast_dtype.clone(),
+            quarantine: (!quarantine.is_empty()).then(|| quarantine.to_string()),
         };
         apply_op(state, op);
     }
 }
 
+/// Warn when the selected cast would null out or truncate values, checked
+/// against the whole column when the column or target type changes.
+fn cast_risk_warning(ui: &mut egui::Ui, state: &mut AppState) {
+    let (Some(source), false) = (&state.source, state.cast_column.is_empty()) else {
+        return;
+    };
+    let key = format!("{}:{}", state.cast_column, state.cast_dtype);
+    if state.cast_check.as_ref().is_none_or(|(k, _)| *k != key) {
+        let risk = query_engine::check_cast(
+            source,
+            &state.operations,
+            &state.cast_column,
+            &state.cast_dtype,
+        )
+        .unwrap_or(None);
+        state.cast_check = Some((key, risk));
+    }
+    if let Some((_, Some(risk))) = &state.cast_check {
+        ui.colored_label(GruvboxMaterial::orange(255), format!("⚠ {}", risk));
+        if risk.out_of_range > 0 && state.cast_quarantine.trim().is_empty() {
+            ui.weak("Set a quarantine column to keep the original values");
+        }
+    }
+}
+
 fn render_parse_datetime_builder(
     ui: &mut egui::Ui,
     state: &mut AppState,
     col_names: &[String],
 ) {
-    column_combo(ui, "Column", &mut state.datetime_column, col_names);
+    let lang = state.config.language;
+    column_combo(
+        ui,
+        tr(lang, "Column"),
+        &mut state.datetime_column,
+        col_names,
+    );
     ui.horizontal(|ui| {
         ui.label("Format:");
         ui.text_edit_singleline(&mut state.datetime_format);
@@ -445,8 +1194,458 @@ fn render_parse_datetime_builder(
     }
 }
 
+fn render_json_extract_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.json_column, col_names);
+    ui.horizontal(|ui| {
+        ui.label("Path:");
+        ui.text_edit_singleline(&mut state.json_path);
+    });
+    ui.label(
+        egui::RichText::new("e.g. $.user.id or $.items[0].name")
+            .small()
+            .weak(),
+    );
+    ui.horizontal(|ui| {
+        ui.label("Output:");
+        ui.text_edit_singleline(&mut state.json_output);
+    });
+    egui::ComboBox::from_label(tr(lang, "Output type"))
+        .selected_text(state.json_dtype.to_string())
+        .show_ui(ui, |ui| {
+            for dt in DTypeTag::all() {
+                ui.selectable_value(&mut state.json_dtype, dt.clone(), dt.to_string());
+            }
+        });
+
+    let can_apply = !state.json_column.is_empty()
+        && !state.json_path.is_empty()
+        && !state.json_output.is_empty();
+    if ui
+        .add_enabled(can_apply, egui::Button::new("Extract"))
+        .clicked()
+    {
+        let op = Operation::JsonExtract {
+            column: state.json_column.clone(),
+            json_path: state.json_path.clone(),
+            output: state.json_output.clone(),
+            dtype: state.json_dtype.clone(),
+        };
+        apply_op(state, op);
+    }
+}
+
+fn render_round_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.round_column, col_names);
+    ui.horizontal(|ui| {
+        ui.label("Decimals:");
+        ui.add(egui::DragValue::new(&mut state.round_decimals).range(0..=15));
+    });
+    egui::ComboBox::from_label(tr(lang, "Mode"))
+        .selected_text(state.round_mode.to_string())
+        .show_ui(ui, |ui| {
+            for mode in RoundMode::all() {
+                ui.selectable_value(&mut state.round_mode, *mode, mode.to_string());
+            }
+        });
+
+    if ui.button("Apply Round").clicked() && !state.round_column.is_empty() {
+        let op = Operation::Round {
+            column: state.round_column.clone(),
+            decimals: state.round_decimals,
+            mode: state.round_mode,
+        };
+        apply_op(state, op);
+    }
+}
+
+fn render_normalize_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
+    let lang = state.config.language;
+    let before = state.normalize_column.clone();
+    column_combo(
+        ui,
+        tr(lang, "Column"),
+        &mut state.normalize_column,
+        col_names,
+    );
+    // Suggest an output name when the column changes
+    if state.normalize_column != before
+        && (state.normalize_output.is_empty()
+            || state.normalize_output == format!("{before}_scaled"))
+    {
+        state.normalize_output = format!("{}_scaled", state.normalize_column);
+    }
+    egui::ComboBox::from_label(tr(lang, "Method"))
+        .selected_text(state.normalize_method.to_string())
+        .show_ui(ui, |ui| {
+            for method in NormalizeMethod::all() {
+                ui.selectable_value(&mut state.normalize_method, *method, method.to_string());
+            }
+        });
+    ui.horizontal(|ui| {
+        ui.label("Output:");
+        ui.text_edit_singleline(&mut state.normalize_output);
+    });
+
+    let can_apply = !state.normalize_column.is_empty() && !state.normalize_output.is_empty();
+    if ui
+        .add_enabled(can_apply, egui::Button::new("Apply Normalize"))
+        .clicked()
+    {
+        let op = Operation::Normalize {
+            column: state.normalize_column.clone(),
+            method: state.normalize_method,
+            output: state.normalize_output.clone(),
+        };
+        apply_op(state, op);
+    }
+}
+
+fn render_window_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
+    let lang = state.config.language;
+    column_combo(ui, tr(lang, "Column"), &mut state.window_column, col_names);
+    egui::ComboBox::from_label(tr(lang, "Function"))
+        .selected_text(state.window_function.to_string())
+        .show_ui(ui, |ui| {
+            for function in WindowFunction::all() {
+                ui.selectable_value(&mut state.window_function, *function, function.to_string());
+            }
+        });
+    group_columns_menu(ui, &mut state.window_over, col_names);
+    ui.horizontal(|ui| {
+        ui.label("Output:");
+        ui.text_edit_singleline(&mut state.window_output);
+    });
+
+    let can_apply = !state.window_column.is_empty() && !state.window_output.is_empty();
+    if ui
+        .add_enabled(can_apply, egui::Button::new("Apply Window"))
+        .clicked()
+    {
+        let op = Operation::Window {
+            column: state.window_column.clone(),
+            function: state.window_function,
+            over: state.window_over.clone(),
+            output: state.window_output.clone(),
+        };
+        apply_op(state, op);
+    }
+}
+
+fn render_coalesce_builder(ui: &mut egui::Ui, state: &mut AppState, col_names: &[String]) {
+    columns_menu(
+        ui,
+        "Columns (first non-null wins):",
+        "(select columns)",
+        &mut state.coalesce_columns,
+        col_names,
+    );
+    ui.horizontal(|ui| {
+        ui.label("Output:");
+        ui.text_edit_singleline(&mut state.coalesce_output);
+    });
+
+    let can_apply = !state.coalesce_columns.is_empty() && !state.coalesce_output.is_empty();
+    if ui
+        .add_enabled(can_apply, egui::Button::new("Apply Coalesce"))
+        .clicked()
+    {
+        let op = Operation::Coalesce {
+            columns: state.coalesce_columns.clone(),
+            output: state.coalesce_output.clone(),
+        };
+        apply_op(state, op);
+    }
+}
+
+/// Date-range filter on a Date/Datetime column: calendar pickers plus presets.
+/// Generates `Gte`/`Lte` filters; the query engine turns the values into typed
+/// datetime literals, so comparisons are chronological, not lexical.
+fn render_date_range_builder(ui: &mut egui::Ui, state: &mut AppState) {
+    let lang = state.config.language;
+    let temporal_cols: Vec<String> = state
+        .column_names
+        .iter()
+        .zip(&state.column_dtypes)
+        .filter(|(_, dtype)| dtype.starts_with("date"))
+        .map(|(name, _)| name.clone())
+        .collect();
+    if temporal_cols.is_empty() {
+        ui.label("No Date/Datetime columns (use Parse Datetime first).");
+        return;
+    }
+    column_combo(
+        ui,
+        tr(lang, "Column"),
+        &mut state.date_range_column,
+        &temporal_cols,
+    );
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut state.date_range_use_start, "From");
+        ui.add_enabled_ui(state.date_range_use_start, |ui| {
+            date_field(ui, &mut state.date_range_start, "date_range_start");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.date_range_start_time).desired_width(60.0),
+            );
+        });
+    });
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut state.date_range_use_end, "To");
+        ui.add_enabled_ui(state.date_range_use_end, |ui| {
+            date_field(ui, &mut state.date_range_end, "date_range_end");
+            ui.add(egui::TextEdit::singleline(&mut state.date_range_end_time).desired_width(60.0));
+        });
+    });
+
+    // ── Presets (relative to the local clock) ──
+    ui.horizontal(|ui| {
+        let now = Local::now().naive_local();
+        let last_day = ui.small_button("Last 24h").clicked();
+        let last_week = ui.small_button("Last 7 days").clicked();
+        let this_month = ui.small_button("This month").clicked();
+        let preset = if last_day {
+            Some(now - chrono::Duration::hours(24))
+        } else if last_week {
+            Some(now - chrono::Duration::days(7))
+        } else if this_month {
+            now.date().with_day(1).and_then(|d| d.and_hms_opt(0, 0, 0))
+        } else {
+            None
+        };
+        if let Some(start) = preset {
+            state.date_range_use_start = true;
+            state.date_range_use_end = true;
+            state.date_range_start = start.date();
+            state.date_range_start_time = start.format("%H:%M:%S").to_string();
+            state.date_range_end = now.date();
+            state.date_range_end_time = now.format("%H:%M:%S").to_string();
+        }
+    });
+
+    let can_apply = !state.date_range_column.is_empty()
+        && (state.date_range_use_start || state.date_range_use_end);
+    if ui
+        .add_enabled(can_apply, egui::Button::new("Apply Date Range"))
+        .clicked()
+    {
+        let bound = |date: NaiveDate, time: &str| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M:%S")
+                .map(|t| date.and_time(t).format("%Y-%m-%d %H:%M:%S").to_string())
+        };
+        let start = state
+            .date_range_use_start
+            .then(|| bound(state.date_range_start, &state.date_range_start_time));
+        let end = state
+            .date_range_use_end
+            .then(|| bound(state.date_range_end, &state.date_range_end_time));
+        match (start.transpose(), end.transpose()) {
+            (Ok(start), Ok(end)) => {
+                let column = state.date_range_column.clone();
+                for (op, value) in [(FilterOp::Gte, start), (FilterOp::Lte, end)] {
+                    if let Some(value) = value {
+                        let filter = Operation::Filter {
+                            column: column.clone(),
+                            op,
+                            value,
+                            options: FilterOptions::default(),
+                        };
+                        apply_op(state, filter);
+                    }
+                }
+            }
+            _ => state.notifications.warn("Invalid time: use HH:MM:SS"),
+        }
+    }
+}
+
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
+/// Filter operator combo box. Re-selecting `Between` keeps the typed bounds.
+pub fn filter_op_combo(ui: &mut egui::Ui, combo: egui::ComboBox, op: &mut FilterOp) {
+    combo.selected_text(op.to_string()).show_ui(ui, |ui| {
+        for candidate in FilterOp::all() {
+            let selected = op.same_kind(&candidate);
+            if ui
+                .selectable_label(selected, candidate.to_string())
+                .clicked()
+                && !selected
+            {
+                *op = candidate;
+            }
+        }
+    });
+}
+
+/// Value input(s) for a filter operator: one value, or low/high bounds for `Between`.
+/// With `as_column` the values are picked from `col_names` (column-to-column filter),
+/// otherwise the widget matches `kind` (the filtered column's type).
+/// Returns why the value(s) are invalid for that type, if they are.
+pub fn filter_value_inputs(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    op: &mut FilterOp,
+    value: &mut String,
+    as_column: bool,
+    col_names: &[String],
+    kind: LiteralKind,
+) -> Option<String> {
+    // `contains` matches text whatever the column type
+    let kind = if *op == FilterOp::Contains || as_column {
+        LiteralKind::Text
+    } else {
+        kind
+    };
+    if let FilterOp::Between {
+        low,
+        high,
+        inclusive,
+    } = op
+    {
+        ui.horizontal(|ui| {
+            ui.label("From:");
+            let salt = format!("{id_salt}_low");
+            filter_value_field(ui, &salt, low, as_column, col_names, kind);
+            ui.label("to");
+            let salt = format!("{id_salt}_high");
+            filter_value_field(ui, &salt, high, as_column, col_names, kind);
+            ui.checkbox(inclusive, "Inclusive");
+        });
+        kind.check(low).or_else(|| kind.check(high))
+    } else if op.needs_value() {
+        ui.horizontal(|ui| {
+            ui.label(if as_column { "Column:" } else { "Value:" });
+            let salt = format!("{id_salt}_value");
+            filter_value_field(ui, &salt, value, as_column, col_names, kind);
+        });
+        kind.check(value)
+    } else {
+        None
+    }
+}
+
+/// A literal input for `kind`, or a column picker when comparing against a column.
+fn filter_value_field(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    value: &mut String,
+    as_column: bool,
+    col_names: &[String],
+    kind: LiteralKind,
+) {
+    if !as_column {
+        typed_value_field(ui, id_salt, value, kind);
+        return;
+    }
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(if value.is_empty() {
+            "(select column)"
+        } else {
+            value.as_str()
+        })
+        .show_ui(ui, |ui| {
+            for name in col_names {
+                ui.selectable_value(value, name.clone(), name);
+            }
+        });
+}
+
+/// Input for a literal kept as text in the operation: a drag value for numbers,
+/// a checkbox for booleans, a calendar for dates (plus a time field for
+/// datetimes), free text otherwise. Values that do not fit the widget are
+/// replaced by its default when it is first shown.
+pub fn typed_value_field(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    value: &mut String,
+    kind: LiteralKind,
+) {
+    match kind {
+        // Drag values go through f64, which cannot hold every u64: typed instead
+        LiteralKind::Integer { max, .. } if max > i64::MAX.into() => {
+            ui.add(egui::TextEdit::singleline(value).desired_width(120.0));
+        }
+        LiteralKind::Integer { min, max } => {
+            let mut n: i64 = value.trim().parse().unwrap_or(0);
+            let range = min as i64..=max as i64;
+            let changed = ui.add(egui::DragValue::new(&mut n).range(range)).changed();
+            if changed || value.trim().parse::<i64>().is_err() {
+                *value = n.to_string();
+            }
+        }
+        LiteralKind::Float => {
+            let mut x: f64 = value.trim().parse().unwrap_or(0.0);
+            let changed = ui.add(egui::DragValue::new(&mut x).speed(0.1)).changed();
+            if changed || value.trim().parse::<f64>().is_err() {
+                *value = x.to_string();
+            }
+        }
+        LiteralKind::Boolean => {
+            let mut b = value.trim() == "true";
+            let changed = ui.checkbox(&mut b, "true").changed();
+            if changed || value.trim().parse::<bool>().is_err() {
+                *value = b.to_string();
+            }
+        }
+        LiteralKind::Date | LiteralKind::Datetime => {
+            let trimmed = value.trim();
+            let (date, time) = trimmed
+                .split_once([' ', 'T'])
+                .unwrap_or((trimmed, "00:00:00"));
+            let mut date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .unwrap_or_else(|_| Local::now().date_naive());
+            let mut time = time.to_string();
+            date_field(ui, &mut date, id_salt);
+            let date = date.format("%Y-%m-%d").to_string();
+            *value = if kind == LiteralKind::Datetime {
+                ui.add(egui::TextEdit::singleline(&mut time).desired_width(60.0));
+                format!("{} {}", date, time.trim())
+            } else {
+                date
+            };
+        }
+        LiteralKind::Text => {
+            ui.add(egui::TextEdit::singleline(value).desired_width(80.0));
+        }
+    }
+}
+
+/// Date typed as YYYY-MM-DD, applied once it parses. The text being typed is
+/// kept while the field has focus; an invalid entry reverts when it loses it.
+fn date_field(ui: &mut egui::Ui, date: &mut NaiveDate, id_salt: &str) {
+    let id = ui.id().with(id_salt);
+    let mut text = ui
+        .data(|d| d.get_temp::<String>(id))
+        .unwrap_or_else(|| date.format("%Y-%m-%d").to_string());
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .desired_width(80.0)
+            .hint_text("YYYY-MM-DD"),
+    );
+    if let Ok(parsed) = NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d") {
+        *date = parsed;
+    }
+    if response.has_focus() {
+        ui.data_mut(|d| d.insert_temp(id, text));
+    } else {
+        ui.data_mut(|d| d.remove::<String>(id));
+    }
+}
+
+/// Case / null handling modifiers for a filter, and whether the right-hand
+/// side is another column.
+pub fn filter_options_inputs(ui: &mut egui::Ui, options: &mut FilterOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut options.value_is_column, "Compare to column")
+            .on_hover_text("Compare against another column instead of a value (e.g. end < start)");
+        ui.checkbox(&mut options.case_insensitive, "Ignore case")
+            .on_hover_text("String =, ≠ and contains ignore upper/lower case");
+        ui.checkbox(&mut options.null_safe, "Null-safe")
+            .on_hover_text("= and ≠ treat null as a value (null ≠ x keeps null rows)");
+    });
+}
+
 /// Reusable column name combo box.
 fn column_combo(ui: &mut egui::Ui, label: &str, selected: &mut String, col_names: &[String]) {
     egui::ComboBox::from_label(label)
@@ -462,9 +1661,93 @@ fn column_combo(ui: &mut egui::Ui, label: &str, selected: &mut String, col_names
         });
 }
 
+/// Multi-select of group columns for window ("over") operations.
+fn group_columns_menu(ui: &mut egui::Ui, over: &mut Vec<String>, col_names: &[String]) {
+    columns_menu(ui, "Within groups:", "(whole column)", over, col_names);
+}
+
+/// Labelled multi-select of columns; keeps the order in which they were picked.
+fn columns_menu(
+    ui: &mut egui::Ui,
+    label: &str,
+    empty_text: &str,
+    selected: &mut Vec<String>,
+    col_names: &[String],
+) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let text = if selected.is_empty() {
+            empty_text.to_string()
+        } else {
+            selected.join(", ")
+        };
+        ui.menu_button(text, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for name in col_names {
+                        let mut on = selected.contains(name);
+                        if ui.checkbox(&mut on, name).changed() {
+                            if on {
+                                selected.push(name.clone());
+                            } else {
+                                selected.retain(|c| c != name);
+                            }
+                        }
+                    }
+                });
+        });
+    });
+}
+
+/// Export the cached preview grid (visual sort + display formatting) as CSV.
+fn export_view(state: &mut AppState) {
+    if state.cached_header_names.is_empty() {
+        state.notifications.warn("Nothing to export");
+        return;
+    }
+    if let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV file", &["csv"])
+        .save_file()
+    {
+        match execution::export_grid_csv(
+            &state.cached_header_names,
+            &state.full_cell_strings(),
+            &path,
+        ) {
+            Ok(()) => state
+                .notifications
+                .info(format!("View exported to {}", path.display())),
+            Err(e) => state.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+}
+
 /// Apply an operation: push to operations, clear redo, mark preview dirty.
-fn apply_op(state: &mut AppState, op: Operation) {
-    state.status = format!("Applied: {}", op);
+/// Apply a drop or rename, or hold it for confirmation if views use the column.
+fn request_column_change(state: &mut AppState, op: Operation) {
+    let column = match &op {
+        Operation::DropColumn(column) | Operation::RenameColumn { from: column, .. } => {
+            column.clone()
+        }
+        _ => {
+            apply_op(state, op);
+            return;
+        }
+    };
+    let dependents = state.column_dependents(&column);
+    if dependents.is_empty() {
+        apply_op(state, op);
+    } else {
+        state.pending_column_change = Some((op, dependents));
+    }
+}
+
+pub(crate) fn apply_op(state: &mut AppState, op: Operation) {
+    state.notifications.info(format!("Applied: {}", op));
+    state.action_log.record(Action::OperationAdded {
+        operation: op.clone(),
+    });
     state.operations.push(op);
     state.redo_stack.clear();
     state.preview_dirty = true;
@@ -0,0 +1,98 @@
+use crate::state::AppState;
+use dafer_utils::query_engine::CollectEngine;
+use eframe::egui::{self, RichText};
+
+// ─── Performance Settings ─────────────────────────────────────────────────────
+//
+// Advanced Polars settings: the collect engine (app-wide, with an optional
+// override saved with the pipeline), thread pool size and morsel size.
+
+/// Combo box over the engines; engines missing from this build are disabled.
+fn engine_combo(ui: &mut egui::Ui, id: &str, engine: &mut Option<CollectEngine>, none: &str) {
+    let selected = engine.map_or_else(|| none.to_string(), |e| e.to_string());
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            if !none.is_empty() {
+                ui.selectable_value(engine, None, none);
+            }
+            for &candidate in CollectEngine::all() {
+                ui.add_enabled_ui(candidate.available(), |ui| {
+                    ui.selectable_value(engine, Some(candidate), candidate.to_string())
+                        .on_disabled_hover_text("Build with the `streaming` feature");
+                });
+            }
+        });
+}
+
+/// Optional number setting: a checkbox enabling a drag value.
+fn optional_value(ui: &mut egui::Ui, label: &str, value: &mut Option<usize>, default: usize) {
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *value = enabled.then_some(default);
+    }
+    if let Some(v) = value {
+        ui.add(egui::DragValue::new(v).range(1..=usize::MAX));
+    } else {
+        ui.weak("Polars default");
+    }
+    ui.end_row();
+}
+
+/// Editor for the performance settings. Saved to the config on close.
+pub fn performance_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = true;
+    let before = (state.config.performance, state.pipeline_engine);
+    egui::Window::new("Performance")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("performance_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Engine");
+                    let mut engine = Some(state.config.performance.engine);
+                    engine_combo(ui, "performance_engine", &mut engine, "");
+                    state.config.performance.engine = engine.unwrap_or_default();
+                    ui.end_row();
+
+                    ui.label("This pipeline");
+                    engine_combo(
+                        ui,
+                        "pipeline_engine",
+                        &mut state.pipeline_engine,
+                        "Use app setting",
+                    );
+                    ui.end_row();
+
+                    let cores = std::thread::available_parallelism().map_or(4, |n| n.get());
+                    optional_value(ui, "Threads", &mut state.config.performance.threads, cores);
+                    optional_value(
+                        ui,
+                        "Morsel size",
+                        &mut state.config.performance.morsel_size,
+                        100_000,
+                    );
+                });
+            ui.label(
+                RichText::new(
+                    "The pipeline engine is saved with the state file. \
+                     Threads and morsel size apply after a restart.",
+                )
+                .small()
+                .weak(),
+            );
+        });
+    if (state.config.performance, state.pipeline_engine) != before {
+        state.preview_dirty = true;
+    }
+    if !open {
+        state.show_performance = false;
+        match state.config.save() {
+            Ok(()) => state.notifications.quiet("Performance settings saved"),
+            Err(e) => state
+                .notifications
+                .error(format!("Config save error: {}", e)),
+        }
+    }
+}
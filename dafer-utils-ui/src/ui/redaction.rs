@@ -0,0 +1,177 @@
+use crate::state::AppState;
+use dafer_utils::redaction::{RedactAction, RedactionProfile, RedactionRule};
+use eframe::egui::{self, RichText};
+
+// ─── Redaction Profiles ───────────────────────────────────────────────────────
+//
+// Profiles live in the app config. The export panel only picks one by name;
+// the profile is looked up again at export time so edits apply immediately.
+
+/// The selected profile, if it still exists.
+pub fn selected_profile(state: &AppState) -> Option<RedactionProfile> {
+    let name = state.redaction_profile.as_ref()?;
+    state
+        .config
+        .redaction_profiles
+        .iter()
+        .find(|p| &p.name == name)
+        .cloned()
+}
+
+/// Profile picker for the export panel, with a button opening the editor.
+pub fn redaction_picker(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal(|ui| {
+        let selected = state
+            .redaction_profile
+            .clone()
+            .unwrap_or_else(|| "None".to_string());
+        egui::ComboBox::from_label("Redaction")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut state.redaction_profile, None, "None");
+                for profile in &state.config.redaction_profiles {
+                    ui.selectable_value(
+                        &mut state.redaction_profile,
+                        Some(profile.name.clone()),
+                        &profile.name,
+                    );
+                }
+            })
+            .response
+            .on_hover_text("Drop, hash or mask columns in the exported file only");
+        if ui.small_button("Edit...").clicked() {
+            state.show_redaction_profiles = true;
+        }
+    });
+}
+
+/// Editor for the saved profiles. Changes are saved to the config on close.
+pub fn redaction_profiles_window(ctx: &egui::Context, state: &mut AppState) {
+    let mut open = true;
+    let mut save = false;
+    let columns = state.column_names.clone();
+    egui::Window::new("Redaction Profiles")
+        .open(&mut open)
+        .default_size([460.0, 360.0])
+        .show(ctx, |ui| {
+            let profiles = &mut state.config.redaction_profiles;
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("redaction_edit")
+                    .selected_text(
+                        profiles
+                            .get(state.redaction_edit)
+                            .map(|p| p.name.clone())
+                            .unwrap_or_default(),
+                    )
+                    .show_ui(ui, |ui| {
+                        for (i, profile) in profiles.iter().enumerate() {
+                            ui.selectable_value(&mut state.redaction_edit, i, &profile.name);
+                        }
+                    });
+                if ui.button("New").clicked() {
+                    profiles.push(RedactionProfile {
+                        name: format!("Profile {}", profiles.len() + 1),
+                        ..Default::default()
+                    });
+                    state.redaction_edit = profiles.len() - 1;
+                }
+                if ui
+                    .add_enabled(
+                        state.redaction_edit < profiles.len(),
+                        egui::Button::new("Delete"),
+                    )
+                    .clicked()
+                {
+                    let removed = profiles.remove(state.redaction_edit);
+                    if state.redaction_profile.as_ref() == Some(&removed.name) {
+                        state.redaction_profile = None;
+                    }
+                    state.redaction_edit = state.redaction_edit.saturating_sub(1);
+                }
+            });
+            ui.separator();
+
+            let Some(profile) = profiles.get_mut(state.redaction_edit) else {
+                ui.label("No profiles yet.");
+                return;
+            };
+            ui.horizontal(|ui| {
+                ui.label("Name");
+                let old_name = profile.name.clone();
+                if ui.text_edit_singleline(&mut profile.name).changed()
+                    && state.redaction_profile.as_ref() == Some(&old_name)
+                {
+                    state.redaction_profile = Some(profile.name.clone());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Hash salt");
+                ui.add(egui::TextEdit::singleline(&mut profile.salt).password(true))
+                    .on_hover_text("Prepended to values before hashing; keep it private");
+            });
+            ui.add_space(4.0);
+
+            let mut remove = None;
+            egui::Grid::new("redaction_rules")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, rule) in profile.rules.iter_mut().enumerate() {
+                        egui::ComboBox::from_id_salt(("redaction_column", i))
+                            .selected_text(&rule.column)
+                            .show_ui(ui, |ui| {
+                                for name in &columns {
+                                    ui.selectable_value(&mut rule.column, name.clone(), name);
+                                }
+                            });
+                        egui::ComboBox::from_id_salt(("redaction_action", i))
+                            .selected_text(rule.action.to_string())
+                            .show_ui(ui, |ui| {
+                                for action in RedactAction::all() {
+                                    ui.selectable_value(
+                                        &mut rule.action,
+                                        action,
+                                        action.to_string(),
+                                    );
+                                }
+                            });
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                profile.rules.remove(i);
+            }
+            if ui.button("+ Add Rule").clicked() {
+                profile.rules.push(RedactionRule {
+                    column: columns.first().cloned().unwrap_or_default(),
+                    action: RedactAction::default(),
+                });
+            }
+            ui.label(
+                RichText::new(
+                    "Columns missing from the result are skipped and listed in the provenance.",
+                )
+                .small()
+                .weak(),
+            );
+            ui.add_space(4.0);
+            if ui.button("Save").clicked() {
+                save = true;
+            }
+        });
+    if !open {
+        state.show_redaction_profiles = false;
+        save = true;
+    }
+    if save {
+        match state.config.save() {
+            Ok(()) => state.notifications.quiet("Redaction profiles saved"),
+            Err(e) => state
+                .notifications
+                .error(format!("Config save error: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use eframe::egui::{self, RichText};
+
+// ─── Result Snapshots ─────────────────────────────────────────────────────────
+//
+// Keep the collected result of the pipeline under a name, keep editing, then
+// compare the live result against it (row counts, column stats, first rows).
+
+/// Edit > Result Snapshots submenu.
+pub fn result_snapshots_menu(ui: &mut egui::Ui, state: &mut AppState) {
+    let has_source = state.source.is_some();
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut state.result_snapshot_name)
+                .hint_text("Snapshot name")
+                .desired_width(140.0),
+        );
+        let name = state.result_snapshot_name.trim().to_string();
+        if ui
+            .add_enabled(has_source && !name.is_empty(), egui::Button::new("Take"))
+            .on_hover_text("Run the whole pipeline and keep its result")
+            .clicked()
+        {
+            state.take_result_snapshot(&name);
+            state.result_snapshot_name.clear();
+        }
+    });
+    ui.checkbox(&mut state.result_snapshot_spill, "Keep on disk (temp Parquet)")
+        .on_hover_text("For large results: rows go to a temporary file instead of memory");
+    if state.result_snapshots.is_empty() {
+        ui.weak("No result snapshots");
+        return;
+    }
+    ui.separator();
+    let mut compare = None;
+    let mut delete = None;
+    for (i, snapshot) in state.result_snapshots.iter().enumerate() {
+        ui.horizontal(|ui| {
+            let label = format!(
+                "{} ({} rows, {} ops{})",
+                snapshot.name,
+                snapshot.row_count,
+                snapshot.operations.len(),
+                if snapshot.is_spilled() { ", on disk" } else { "" }
+            );
+            if ui
+                .button(label)
+                .on_hover_text(format!("Taken {}. Click to compare", snapshot.taken_at))
+                .clicked()
+            {
+                compare = Some(i);
+            }
+            if ui
+                .small_button("✖")
+                .on_hover_text("Delete snapshot")
+                .clicked()
+            {
+                delete = Some(i);
+            }
+        });
+    }
+    if let Some(i) = compare {
+        state.compare_result_snapshot(i);
+        ui.close();
+    }
+    if let Some(i) = delete {
+        state.result_snapshots.remove(i);
+    }
+}
+
+/// Differences between a result snapshot and the live result.
+pub fn snapshot_diff_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some((name, diff)) = &state.snapshot_diff else {
+        return;
+    };
+    let mut open = true;
+    egui::Window::new(format!("Compare with \"{}\"", name))
+        .id(egui::Id::new("snapshot_diff_window"))
+        .open(&mut open)
+        .default_size([520.0, 360.0])
+        .show(ctx, |ui| {
+            if diff.is_identical() {
+                ui.colored_label(
+                    GruvboxMaterial::green(255),
+                    format!(
+                        "✔ Same rows and statistics (first {} rows compared).",
+                        diff.rows_compared
+                    ),
+                );
+                return;
+            }
+            let (before, after) = diff.rows;
+            let delta = after as i64 - before as i64;
+            ui.label(format!("Rows: {} → {} ({:+})", before, after, delta));
+            if !diff.added_columns.is_empty() {
+                ui.label(format!("Added columns: {}", diff.added_columns.join(", ")));
+            }
+            if !diff.removed_columns.is_empty() {
+                ui.label(format!("Removed columns: {}", diff.removed_columns.join(", ")));
+            }
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                if !diff.stat_changes.is_empty() {
+                    ui.separator();
+                    ui.strong("Column statistics");
+                    egui::Grid::new("snapshot_stat_changes")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for change in &diff.stat_changes {
+                                ui.label(&change.column);
+                                ui.label(change.stat);
+                                ui.monospace(&change.before);
+                                ui.label("→");
+                                ui.monospace(&change.after);
+                                ui.end_row();
+                            }
+                        });
+                }
+                if !diff.cell_differences.is_empty() {
+                    ui.separator();
+                    ui.strong(format!(
+                        "Cell differences in the first {} rows",
+                        diff.rows_compared
+                    ));
+                    for difference in &diff.cell_differences {
+                        ui.monospace(difference);
+                    }
+                }
+            });
+            ui.label(
+                RichText::new("Rows are compared by position; a sort or filter shifts them.")
+                    .small()
+                    .weak(),
+            );
+        });
+    if !open {
+        state.snapshot_diff = None;
+    }
+}
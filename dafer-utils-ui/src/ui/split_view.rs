@@ -0,0 +1,55 @@
+use crate::state::AppState;
+use crate::ui::table::data_table;
+use crate::ui::visualize::{plot_controls, plot_panel};
+use eframe::egui;
+
+/// Split View tab: preview table and plot side by side.
+///
+/// Both halves render from the same cached state, so pipeline edits show up
+/// in the table and the plot at once. The divider between them is draggable;
+/// the orientation (left/right or top/bottom) can be toggled.
+pub fn split_view_ui(ui: &mut egui::Ui, state: &mut AppState) {
+    if state.source.is_none() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(40.0);
+            ui.heading("No data loaded");
+            ui.label("Load a file in the Load & Preview tab first.");
+        });
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Split:");
+        ui.selectable_value(&mut state.split_vertical, false, "Side by side");
+        ui.selectable_value(&mut state.split_vertical, true, "Stacked");
+    });
+    ui.separator();
+
+    if state.split_vertical {
+        egui::TopBottomPanel::top("split_table_panel")
+            .resizable(true)
+            .default_height(ui.available_height() * 0.5)
+            .show_inside(ui, |ui| {
+                let height = ui.available_height();
+                data_table(ui, state, "split", height);
+            });
+    } else {
+        egui::SidePanel::left("split_table_panel")
+            .resizable(true)
+            .default_width(ui.available_width() * 0.5)
+            .show_inside(ui, |ui| {
+                let height = ui.available_height();
+                data_table(ui, state, "split", height);
+            });
+    }
+
+    egui::CentralPanel::default().show_inside(ui, |ui| {
+        if state.preview_df.is_none() {
+            ui.label("Preview not available.");
+            return;
+        }
+        plot_controls(ui, state);
+        ui.separator();
+        plot_panel(ui, state);
+    });
+}
@@ -0,0 +1,105 @@
+use crate::app::VIEWER;
+use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::sql::{self, SqlDialect};
+use eframe::egui::{self, RichText};
+
+// ─── Pipeline as SQL ──────────────────────────────────────────────────────────
+//
+// The pipeline written as a SQL SELECT (or a dbt model) to move it into a
+// warehouse. Steps without a SQL equivalent are listed, not silently dropped.
+
+/// Generated SQL with dialect and dbt choices, and Copy / Save buttons.
+pub fn sql_export_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(source) = &state.source else {
+        state.show_sql_export = false;
+        return;
+    };
+    let columns = &state.source_schema;
+    let table = sql::table_name(source);
+    let model = if state.sql_dbt {
+        sql::to_dbt_model(&state.operations, columns, "raw", &table, state.sql_dialect)
+    } else {
+        let from = match state.sql_dialect {
+            SqlDialect::DuckDb => sql::duckdb_reader(source),
+            SqlDialect::Postgres => sql::ident(&table),
+        };
+        sql::to_sql(&state.operations, columns, &from, state.sql_dialect)
+    };
+
+    let mut open = true;
+    egui::Window::new("Pipeline as SQL")
+        .open(&mut open)
+        .default_size([620.0, 460.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_label("Dialect")
+                    .selected_text(state.sql_dialect.to_string())
+                    .show_ui(ui, |ui| {
+                        for dialect in SqlDialect::all() {
+                            ui.selectable_value(
+                                &mut state.sql_dialect,
+                                dialect,
+                                dialect.to_string(),
+                            );
+                        }
+                    });
+                ui.checkbox(&mut state.sql_dbt, "dbt model")
+                    .on_hover_text(format!(
+                        "Read from {{{{ source('raw', '{}') }}}} and materialize as a table",
+                        table
+                    ));
+            });
+
+            if !model.unsupported.is_empty() {
+                ui.colored_label(
+                    GruvboxMaterial::orange(255),
+                    format!(
+                        "{} step(s) have no SQL equivalent and were left out:",
+                        model.unsupported.len()
+                    ),
+                );
+                for (step, reason) in &model.unsupported {
+                    ui.label(
+                        RichText::new(format!("  {}. {}", step, reason))
+                            .small()
+                            .color(GruvboxMaterial::orange(255)),
+                    );
+                }
+            }
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(model.sql.clone());
+                    state.notifications.quiet("SQL copied to clipboard");
+                }
+                if !VIEWER && ui.button("Save...").clicked() {
+                    let file_name = format!("{}.sql", table);
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("SQL", &["sql"])
+                        .set_file_name(&file_name)
+                        .save_file()
+                    {
+                        match std::fs::write(&path, &model.sql) {
+                            Ok(()) => state
+                                .notifications
+                                .info(format!("SQL saved to {}", path.display())),
+                            Err(e) => state.notifications.error(format!("SQL save error: {}", e)),
+                        }
+                    }
+                }
+            });
+            egui::ScrollArea::both().show(ui, |ui| {
+                let mut text = model.sql.as_str();
+                ui.add(
+                    egui::TextEdit::multiline(&mut text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            });
+        });
+    if !open {
+        state.show_sql_export = false;
+    }
+}
@@ -0,0 +1,422 @@
+use crate::state::AppState;
+use crate::ui::formatting::{column_format_menu, paint_cell_highlight};
+use crate::ui::modify::apply_op;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+/// Syntax of a quick-filter cell (see `query_engine::quick_filter`).
+const QUICK_FILTER_HINT: &str = "Text columns: contains, ignoring case. Or an operator: \
+                                 >5, <=2024-01-01, !=x, =0";
+
+/// Cells longer than this get a hover popup with the full, wrapped value.
+const LONG_TEXT_CHARS: usize = 40;
+
+/// Shared preview data table, rendered from the pre-computed string cache.
+///
+/// Used by every view that shows the pipeline result (Load & Preview, Modify,
+/// Split View). `id` keeps scroll/column-width state separate per view.
+///
+/// - Clickable column headers for visual sorting
+/// - Column selection (right-click) + Ctrl+C copy
+/// - Per-column conditional formatting and hiding (header right-click menu)
+/// - Click selects a cell; double-click opens the cell viewer (see `cell_viewer`)
+/// - Optional quick-filter row under the header (see `quick_filter_bar`)
+pub fn data_table(ui: &mut egui::Ui, state: &mut AppState, id: &str, max_height: f32) {
+    // ── Handle Ctrl+C Copy ──
+    if ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::C)) {
+        copy_selection_to_clipboard(ui, state);
+    }
+
+    let n_rows = state.cached_cell_strings.len();
+    let n_cols = state.cached_header_names.len();
+    if (n_rows == 0 && state.quick_filter_ops().is_empty()) || n_cols == 0 {
+        return;
+    }
+    if state.show_quick_filters {
+        quick_filter_bar(ui, state, n_rows);
+    }
+
+    let text_height = ui.text_style_height(&egui::TextStyle::Body);
+    let row_height = text_height + 2.0;
+    let header_height = if state.show_quick_filters {
+        2.0 * row_height + 10.0
+    } else {
+        row_height + 4.0
+    };
+    let header_names: Vec<String> = state.cached_header_names.clone();
+
+    // ── Hidden columns (view only) ──
+    let visible: Vec<usize> = (0..n_cols)
+        .filter(|&i| !state.display_config.is_hidden(&header_names[i]))
+        .collect();
+    if visible.len() < n_cols {
+        hidden_columns_menu(ui, state, &header_names, n_cols - visible.len());
+    }
+    if visible.is_empty() {
+        return;
+    }
+
+    egui::ScrollArea::horizontal()
+        .id_salt((id, "hscroll"))
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt((id, "table"))
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .columns(
+                    Column::initial(100.0)
+                        .at_least(60.0)
+                        .clip(true)
+                        .resizable(true),
+                    visible.len(),
+                )
+                .max_scroll_height(max_height)
+                .header(header_height, |mut header| {
+                    for &col_idx in &visible {
+                        let name = &header_names[col_idx];
+                        header.col(|ui| {
+                            ui.vertical(|ui| {
+                                let is_sorted =
+                                    state.sort_column.as_deref() == Some(name.as_str());
+                                let label = if is_sorted {
+                                    if state.sort_descending {
+                                        format!("{} v", name)
+                                    } else {
+                                        format!("{} ^", name)
+                                    }
+                                } else {
+                                    name.clone()
+                                };
+
+                                let response = ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(&label)
+                                            .strong()
+                                            .color(GruvboxMaterial::fg(255)),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                );
+
+                                if response.clicked() {
+                                    if is_sorted {
+                                        state.sort_descending = !state.sort_descending;
+                                    } else {
+                                        state.sort_column = Some(name.clone());
+                                        state.sort_descending = false;
+                                    }
+                                    state.table_cache_dirty = true;
+                                }
+
+                                if response.secondary_clicked() {
+                                    state.selected_col = Some(col_idx);
+                                    state.selected_cell = None;
+                                    state.selected_row = None;
+                                }
+
+                                response.context_menu(|ui| {
+                                    if ui.button("Hide column").clicked() {
+                                        state.display_config.set_hidden(name, true);
+                                        state.selected_col = None;
+                                        ui.close();
+                                    }
+                                    if ui
+                                        .checkbox(&mut state.show_quick_filters, "Quick filter row")
+                                        .changed()
+                                    {
+                                        state.table_cache_dirty = true;
+                                    }
+                                    ui.separator();
+                                    column_format_menu(ui, state, name);
+                                });
+
+                                if state.show_quick_filters {
+                                    let text = state.quick_filters.entry(name.clone()).or_default();
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(text)
+                                                .id_salt((id, "quick_filter", name))
+                                                .hint_text("filter")
+                                                .desired_width(f32::INFINITY),
+                                        )
+                                        .on_hover_text(QUICK_FILTER_HINT)
+                                        .changed()
+                                    {
+                                        state.table_cache_dirty = true;
+                                    }
+                                }
+                            });
+                        });
+                    }
+                })
+                .body(|body| {
+                    let has_selection = state.selected_cell.is_some()
+                        || state.selected_row.is_some()
+                        || state.selected_col.is_some();
+                    body.rows(row_height, n_rows, |mut row| {
+                        let visual_row = row.index();
+                        for &col_idx in &visible {
+                            row.col(|ui| {
+                                // Only render content for visible cells
+                                if !ui.is_rect_visible(ui.max_rect()) {
+                                    return;
+                                }
+                                let text = state.cached_cell_strings[visual_row][col_idx].as_str();
+                                paint_cell_highlight(ui, state, col_idx, text);
+                                if has_selection {
+                                    let is_selected = state.selected_cell
+                                        == Some((visual_row, col_idx))
+                                        || state.selected_row == Some(visual_row)
+                                        || state.selected_col == Some(col_idx);
+                                    if is_selected {
+                                        ui.painter().rect_filled(
+                                            ui.max_rect(),
+                                            0.0,
+                                            GruvboxMaterial::blue(50),
+                                        );
+                                    }
+                                }
+                                let response =
+                                    ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                                if response.clicked() {
+                                    state.selected_cell = Some((visual_row, col_idx));
+                                    state.selected_row = None;
+                                    state.selected_col = None;
+                                }
+                                if response.double_clicked() {
+                                    state.cell_viewer = Some((visual_row, col_idx));
+                                }
+                                if text.chars().count() > LONG_TEXT_CHARS || text.contains('\n') {
+                                    response.on_hover_ui(|ui| {
+                                        ui.set_max_width(400.0);
+                                        let full = state.cell_value(visual_row, col_idx);
+                                        ui.label(full.as_deref().unwrap_or(text));
+                                        ui.weak("Double-click to open the cell viewer");
+                                    });
+                                }
+                            });
+                        }
+                    });
+                });
+        });
+}
+
+/// Read-only table for a result other than the pipeline preview (e.g. a pivot),
+/// styled like `data_table`. The first `key_columns` columns are shown bold.
+pub fn grid_table(
+    ui: &mut egui::Ui,
+    id: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+    key_columns: usize,
+) {
+    if headers.is_empty() {
+        return;
+    }
+    let row_height = ui.text_style_height(&egui::TextStyle::Body) + 2.0;
+    egui::ScrollArea::horizontal()
+        .id_salt((id, "hscroll"))
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt((id, "table"))
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .columns(
+                    Column::initial(100.0)
+                        .at_least(60.0)
+                        .clip(true)
+                        .resizable(true),
+                    headers.len(),
+                )
+                .header(row_height + 4.0, |mut header| {
+                    for name in headers {
+                        header.col(|ui| {
+                            ui.label(
+                                egui::RichText::new(name)
+                                    .strong()
+                                    .color(GruvboxMaterial::fg(255)),
+                            );
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(row_height, rows.len(), |mut row| {
+                        let cells = &rows[row.index()];
+                        for (i, cell) in cells.iter().enumerate() {
+                            row.col(|ui| {
+                                if i < key_columns {
+                                    ui.label(egui::RichText::new(cell).strong());
+                                } else {
+                                    ui.label(cell);
+                                }
+                            });
+                        }
+                    });
+                });
+        });
+}
+
+/// Summary above the table while the quick-filter row is shown: rows kept,
+/// and actions to promote the filters to pipeline steps or clear them.
+fn quick_filter_bar(ui: &mut egui::Ui, state: &mut AppState, n_shown: usize) {
+    let filters = state.quick_filter_ops();
+    ui.horizontal(|ui| {
+        let total = state.preview_df.as_ref().map_or(0, |df| df.height());
+        if let Some(error) = &state.quick_filter_error {
+            ui.colored_label(GruvboxMaterial::red(255), "Quick filters not applied")
+                .on_hover_text(error);
+        } else if filters.is_empty() {
+            ui.weak("Type in the row under the header to filter the preview");
+        } else {
+            ui.label(format!(
+                "{} filter(s): {} of {} preview rows",
+                filters.len(),
+                n_shown,
+                total
+            ))
+            .on_hover_text("Only the preview rows are filtered; the pipeline is unchanged");
+        }
+        let ready = !filters.is_empty() && state.quick_filter_error.is_none();
+        if ui
+            .add_enabled(ready, egui::Button::new("Promote to Pipeline"))
+            .on_hover_text("Add the filters as pipeline steps (they then apply to all rows)")
+            .clicked()
+        {
+            for op in filters.iter().cloned() {
+                apply_op(state, op);
+            }
+            state.quick_filters.clear();
+            state.table_cache_dirty = true;
+        }
+        if ui
+            .add_enabled(!filters.is_empty(), egui::Button::new("Clear"))
+            .clicked()
+        {
+            state.quick_filters.clear();
+            state.table_cache_dirty = true;
+        }
+        if ui
+            .small_button("✖")
+            .on_hover_text("Hide the filter row")
+            .clicked()
+        {
+            state.show_quick_filters = false;
+            state.quick_filters.clear();
+            state.table_cache_dirty = true;
+        }
+    });
+}
+
+/// "N hidden" menu above the table to show hidden columns again.
+fn hidden_columns_menu(ui: &mut egui::Ui, state: &mut AppState, names: &[String], n_hidden: usize) {
+    ui.menu_button(format!("{} hidden column(s)", n_hidden), |ui| {
+        for name in names {
+            if state.display_config.is_hidden(name) && ui.button(format!("Show {}", name)).clicked()
+            {
+                state.display_config.set_hidden(name, false);
+            }
+        }
+        ui.separator();
+        if ui.button("Show all").clicked() {
+            state.display_config.hidden_columns.clear();
+            ui.close();
+        }
+    });
+}
+
+/// Cell viewer window: the full value of a cell (wrapped, copyable) and the
+/// rest of its row. Values come from the preview DataFrame, not the string cache.
+pub fn cell_viewer(ctx: &egui::Context, state: &mut AppState) {
+    let Some((row, col)) = state.cell_viewer else {
+        return;
+    };
+    let Some(value) = state.cell_value(row, col) else {
+        state.cell_viewer = None;
+        return;
+    };
+    let column = state
+        .cached_header_names
+        .get(col)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut open = true;
+    let mut select_col: Option<usize> = None;
+    egui::Window::new(format!("Row {} · {}", row + 1, column))
+        .id(egui::Id::new("cell_viewer"))
+        .open(&mut open)
+        .default_size([420.0, 320.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} chars", value.chars().count()));
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(value.clone());
+                    state
+                        .notifications
+                        .info(format!("Copied value of {}", column));
+                }
+            });
+            egui::ScrollArea::vertical()
+                .id_salt("cell_viewer_value")
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    ui.add(
+                        // `&str` buffer: selectable and copyable, but read-only
+                        egui::TextEdit::multiline(&mut value.as_str()).desired_width(f32::INFINITY),
+                    );
+                });
+
+            ui.separator();
+            egui::CollapsingHeader::new("Row")
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .id_salt("cell_viewer_row")
+                        .show(ui, |ui| {
+                            egui::Grid::new("cell_viewer_row_grid")
+                                .num_columns(2)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (i, name) in state.cached_header_names.iter().enumerate() {
+                                        if ui.selectable_label(i == col, name).clicked() {
+                                            select_col = Some(i);
+                                        }
+                                        let text = state.cached_cell_strings[row][i].as_str();
+                                        ui.add(egui::Label::new(text).truncate());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                });
+        });
+
+    if !open {
+        state.cell_viewer = None;
+    } else if let Some(i) = select_col {
+        state.cell_viewer = Some((row, i));
+    }
+}
+
+/// Copy the current selection (cell, row, or column) to clipboard.
+/// Copies full values, even where the cached table strings are truncated.
+fn copy_selection_to_clipboard(ui: &egui::Ui, state: &AppState) {
+    let n_rows = state.cached_cell_strings.len();
+    let n_cols = state.cached_header_names.len();
+    let text = if let Some((row, col)) = state.selected_cell {
+        state.full_cell(row, col)
+    } else if let Some(row) = state.selected_row {
+        let vals: Option<Vec<String>> = (0..n_cols).map(|c| state.full_cell(row, c)).collect();
+        vals.map(|v| v.join("\t"))
+    } else if let Some(col) = state.selected_col {
+        let vals: Vec<String> = (0..n_rows)
+            .filter_map(|r| state.full_cell(r, col))
+            .collect();
+        Some(vals.join("\n"))
+    } else {
+        None
+    };
+
+    if let Some(text) = text {
+        ui.ctx().copy_text(text);
+    }
+}
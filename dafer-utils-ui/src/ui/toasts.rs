@@ -0,0 +1,158 @@
+use crate::notifications::{Level, Notification};
+use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use eframe::egui::{self, RichText};
+use std::time::Duration;
+
+// ─── Notifications UI ─────────────────────────────────────────────────────────
+//
+// Toasts (bottom right), the status bar message, the history drawer and the
+// preview error banner. Messages are pushed through `state.notifications`.
+
+fn level_color(level: Level) -> egui::Color32 {
+    match level {
+        Level::Info => GruvboxMaterial::fg(255),
+        Level::Warn => GruvboxMaterial::yellow(255),
+        Level::Error => GruvboxMaterial::red(255),
+    }
+}
+
+fn level_icon(level: Level) -> &'static str {
+    match level {
+        Level::Info => "ℹ",
+        Level::Warn => "⚠",
+        Level::Error => "✖",
+    }
+}
+
+/// Latest message in the status bar; clicking it opens the history drawer.
+pub fn status_message(ui: &mut egui::Ui, state: &mut AppState) {
+    let (text, color) = match state.notifications.latest() {
+        Some(n) => (n.message.clone(), level_color(n.level)),
+        None => ("Ready".to_string(), GruvboxMaterial::fg(255)),
+    };
+    if ui
+        .add(egui::Label::new(RichText::new(text).small().color(color)).sense(egui::Sense::click()))
+        .on_hover_text("Show notification history")
+        .clicked()
+    {
+        state.show_notifications = !state.show_notifications;
+    }
+}
+
+/// Transient toasts stacked above the status bar.
+pub fn toasts(ctx: &egui::Context, state: &mut AppState) {
+    let active: Vec<Notification> = state.notifications.toasts().cloned().collect();
+    if active.is_empty() {
+        return;
+    }
+    // Repaint so toasts disappear on time without user input
+    ctx.request_repaint_after(Duration::from_millis(500));
+
+    let mut dismiss = None;
+    egui::Area::new(egui::Id::new("toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -32.0])
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            for toast in active.iter().rev() {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(320.0);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(level_icon(toast.level)).color(level_color(toast.level)),
+                        );
+                        ui.label(&toast.message);
+                        if ui.small_button("✖").clicked() {
+                            dismiss = Some(toast.created);
+                        }
+                    });
+                });
+                ui.add_space(4.0);
+            }
+        });
+    if let Some(created) = dismiss {
+        state.notifications.dismiss(created);
+    }
+}
+
+/// Right-hand drawer listing all notifications, newest first.
+pub fn notifications_drawer(ctx: &egui::Context, state: &mut AppState) {
+    if !state.show_notifications {
+        return;
+    }
+    egui::SidePanel::right("notifications")
+        .default_width(300.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong("Notifications");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✖").on_hover_text("Close").clicked() {
+                        state.show_notifications = false;
+                    }
+                    if ui.small_button("Clear").clicked() {
+                        state.notifications.clear();
+                    }
+                });
+            });
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for n in state.notifications.history().rev() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(
+                            RichText::new(n.time.format("%H:%M:%S").to_string())
+                                .small()
+                                .weak(),
+                        );
+                        ui.label(RichText::new(level_icon(n.level)).color(level_color(n.level)));
+                        ui.label(&n.message);
+                    });
+                }
+            });
+        });
+}
+
+/// Banner under the menu bar while the preview is failing, with the full error.
+pub fn preview_error_banner(ctx: &egui::Context, state: &mut AppState) {
+    let Some(details) = &state.preview_error else {
+        return;
+    };
+    let summary = details.lines().next().unwrap_or_default().to_string();
+    let suggestion = state
+        .step_failure
+        .as_ref()
+        .and_then(|failure| failure.suggestion.clone());
+    let mut close = false;
+    egui::TopBottomPanel::top("preview_error_banner")
+        .frame(
+            egui::Frame::new()
+                .fill(GruvboxMaterial::red(40))
+                .inner_margin(4.0),
+        )
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("✖ Preview failed:").color(GruvboxMaterial::red(255)));
+                ui.label(&summary);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("Dismiss").clicked() {
+                        close = true;
+                    }
+                });
+            });
+            if let Some(suggestion) = &suggestion {
+                ui.label(RichText::new(format!("Suggestion: {}", suggestion)).small());
+            }
+            egui::CollapsingHeader::new("Details")
+                .id_salt("preview_error_details")
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut details.as_str())
+                            .code_editor()
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+        });
+    if close {
+        state.preview_error = None;
+    }
+}
@@ -0,0 +1,140 @@
+use crate::enums::MainTab;
+use crate::state::AppState;
+use crate::ui::main_ui::open_file;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use dafer_utils::data_loader;
+use eframe::egui;
+
+// ─── Guided Tour ──────────────────────────────────────────────────────────────
+//
+// A fixed script of steps. Each step switches to its tab and outlines one
+// panel; panels call `highlight` with their rect so the tour needs no layout
+// knowledge of its own.
+
+/// Panel a tour step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourTarget {
+    Stats,
+    Builder,
+    PlotControls,
+    Export,
+}
+
+struct TourStep {
+    tab: MainTab,
+    target: TourTarget,
+    title: &'static str,
+    text: &'static str,
+}
+
+const TOUR: &[TourStep] = &[
+    TourStep {
+        tab: MainTab::LoadPreview,
+        target: TourTarget::Stats,
+        title: "1. Load",
+        text: "The sample file is loaded. Above is a preview of the rows; the column \
+               statistics show types, ranges, nulls and distributions at a glance.",
+    },
+    TourStep {
+        tab: MainTab::Modify,
+        target: TourTarget::Builder,
+        title: "2. Filter",
+        text: "Operations build a pipeline that is re-run on the whole file. Pick \
+               Filter, choose SepalLengthCm, operator >, value 5.5 and click Apply Filter.",
+    },
+    TourStep {
+        tab: MainTab::Visualize,
+        target: TourTarget::PlotControls,
+        title: "3. Plot",
+        text: "Plots use the pipeline result. Choose Scatter, X = SepalLengthCm and \
+               add PetalLengthCm as a Y series.",
+    },
+    TourStep {
+        tab: MainTab::Modify,
+        target: TourTarget::Export,
+        title: "4. Export",
+        text: "Export writes the full pipeline result to CSV or Parquet. Save the \
+               workspace (File menu) to keep the pipeline, plot and notes together.",
+    },
+];
+
+/// Load the bundled sample dataset.
+pub fn open_sample(state: &mut AppState) {
+    match data_loader::write_sample_dataset() {
+        Ok(path) => open_file(state, path),
+        Err(e) => state
+            .notifications
+            .error(format!("Sample dataset error: {}", e)),
+    }
+}
+
+/// Load the sample dataset and start the tour at its first step.
+pub fn start_tour(state: &mut AppState) {
+    open_sample(state);
+    go_to_step(state, 0);
+}
+
+fn go_to_step(state: &mut AppState, step: usize) {
+    state.tour_step = Some(step);
+    state.selected_tab = TOUR[step].tab;
+}
+
+/// Outline `rect` if the current tour step points at `target`.
+pub fn highlight(ui: &egui::Ui, state: &AppState, target: TourTarget, rect: egui::Rect) {
+    let Some(step) = state.tour_step else {
+        return;
+    };
+    if TOUR[step].target == target && TOUR[step].tab == state.selected_tab {
+        ui.painter().rect_stroke(
+            rect.expand(3.0),
+            4.0,
+            egui::Stroke::new(2.0, GruvboxMaterial::yellow(255)),
+            egui::StrokeKind::Outside,
+        );
+    }
+}
+
+/// Floating window with the current step and Back / Next / End buttons.
+pub fn tour_window(ctx: &egui::Context, state: &mut AppState) {
+    let Some(step) = state.tour_step else {
+        return;
+    };
+    let mut next_step: Option<Option<usize>> = None;
+
+    egui::Window::new("Guided Tour")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -40.0])
+        .show(ctx, |ui| {
+            let current = &TOUR[step];
+            ui.set_max_width(300.0);
+            ui.strong(current.title);
+            ui.label(current.text);
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(step > 0, egui::Button::new("Back"))
+                    .clicked()
+                {
+                    next_step = Some(Some(step - 1));
+                }
+                if step + 1 < TOUR.len() {
+                    if ui.button("Next").clicked() {
+                        next_step = Some(Some(step + 1));
+                    }
+                    if ui.button("End Tour").clicked() {
+                        next_step = Some(None);
+                    }
+                } else if ui.button("Finish").clicked() {
+                    next_step = Some(None);
+                }
+                ui.label(format!("{}/{}", step + 1, TOUR.len()));
+            });
+        });
+
+    match next_step {
+        Some(Some(step)) => go_to_step(state, step),
+        Some(None) => state.tour_step = None,
+        None => {}
+    }
+}
@@ -1,9 +1,19 @@
-use crate::enums::PlotType;
+use crate::app::VIEWER;
+use crate::enums::{AnnotationKind, PlotAnalysis, PlotType};
 use crate::state::AppState;
+use crate::ui::palette::gruvbox_material::GruvboxMaterial;
+use crate::ui::tour::{TourTarget, highlight};
 use chrono::DateTime;
+use dafer_utils::analysis::{self, BinSpread, ColumnRole, LinearFit};
+use dafer_utils::execution;
+use dafer_utils::plot::{PlotAnnotation, PlotConfig, PlotPreset};
 use eframe::egui;
-use egui_plot::{Bar, BarChart, GridMark, Legend, Line, Plot, PlotPoints, Points};
+use egui_plot::{
+    Bar, BarChart, GridMark, HLine, Legend, Line, Plot, PlotPoint, PlotPoints, PlotUi, Points,
+    Text, VLine,
+};
 use polars::prelude::*;
+use std::collections::BTreeMap;
 
 /// Data Visualization tab.
 ///
@@ -11,6 +21,7 @@ use polars::prelude::*;
 /// - Supports Scatter, Line, Bar, Histogram plot types
 /// - Each Y column gets its own colored series
 /// - Data is extracted from the cached preview DataFrame
+/// - Controls and plot area are separate panels so other views can compose them
 pub fn visualize_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
     if state.source.is_none() {
         ui.vertical_centered(|ui| {
@@ -26,6 +37,14 @@ pub fn visualize_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
         return;
     }
 
+    let rect = ui.scope(|ui| plot_controls(ui, state)).response.rect;
+    highlight(ui, state, TourTarget::PlotControls, rect);
+    ui.separator();
+    plot_panel(ui, state);
+}
+
+/// Plot configuration controls: plot type, X column, bins and Y series.
+pub fn plot_controls(ui: &mut egui::Ui, state: &mut AppState) {
     // ── Plot Configuration ──
     ui.horizontal(|ui| {
         // Plot type
@@ -70,7 +89,18 @@ pub fn visualize_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
             {
                 state.plot_dirty = true;
             }
+        } else {
+            analysis_controls(ui, state);
         }
+        if state.plot_type == PlotType::Scatter
+            && ui
+                .checkbox(&mut state.plot_fit, "Fit line")
+                .on_hover_text("Least squares line per Y series, with slope, intercept and R²")
+                .changed()
+        {
+            state.plot_dirty = true;
+        }
+        preset_menu(ui, state);
     });
 
     // ── Y Column(s) selection ──
@@ -101,8 +131,10 @@ pub fn visualize_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
                 egui::ScrollArea::vertical()
                     .max_height(300.0)
                     .show(ui, |ui| {
+                        // ID columns are numbers but not quantities: not offered as Y
                         for name in &col_names {
-                            if !state.plot_y_columns.contains(name) {
+                            let is_id = state.column_roles.get(name) == Some(&ColumnRole::Id);
+                            if !state.plot_y_columns.contains(name) && !is_id {
                                 if ui.button(name).clicked() {
                                     state.plot_y_columns.push(name.clone());
                                     state.plot_dirty = true;
@@ -112,26 +144,364 @@ pub fn visualize_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
                         }
                     });
             });
+
+            if ui
+                .small_button("Suggest")
+                .on_hover_text("Timestamp on X, measures on Y (from column roles)")
+                .clicked()
+            {
+                suggest_plot_defaults(state);
+            }
         });
     }
 
-    ui.separator();
+    annotation_controls(ui, state);
+}
+
+/// Saved plot setups: save the current one under a name, apply or delete.
+fn preset_menu(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.menu_button("Presets", |ui| {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut state.plot_preset_name)
+                    .hint_text("Preset name")
+                    .desired_width(140.0),
+            );
+            let name = state.plot_preset_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+                .on_hover_text(
+                    "Type, axes, series, bins and annotations; replaces a preset of the same name",
+                )
+                .clicked()
+            {
+                let preset = PlotPreset {
+                    name: name.clone(),
+                    config: state.plot_config(),
+                };
+                let presets = &mut state.config.plot_presets;
+                match presets.iter_mut().find(|p| p.name == name) {
+                    Some(existing) => *existing = preset,
+                    None => presets.push(preset),
+                }
+                save_presets(state, format!("Plot preset \"{}\" saved", name));
+                ui.close();
+            }
+        });
+        ui.separator();
+        if state.config.plot_presets.is_empty() {
+            ui.label(egui::RichText::new("No presets yet").weak());
+        }
+        let mut apply = None;
+        let mut delete = None;
+        for (i, preset) in state.config.plot_presets.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .button(&preset.name)
+                    .on_hover_text(format!(
+                        "{} of {} by {}",
+                        preset.config.plot_type,
+                        preset.config.y_columns.join(", "),
+                        preset.config.x
+                    ))
+                    .clicked()
+                {
+                    apply = Some(i);
+                    ui.close();
+                }
+                if ui.small_button("X").clicked() {
+                    delete = Some(i);
+                }
+            });
+        }
+        if let Some(i) = apply {
+            apply_preset(state, i);
+        }
+        if let Some(i) = delete {
+            let removed = state.config.plot_presets.remove(i);
+            save_presets(state, format!("Plot preset \"{}\" deleted", removed.name));
+        }
+    });
+}
 
+/// Apply a saved preset to the loaded data, matching columns by name.
+fn apply_preset(state: &mut AppState, index: usize) {
+    let Some(preset) = state.config.plot_presets.get(index) else {
+        return;
+    };
+    let name = preset.name.clone();
+    let (config, missing) = preset.config_for(&state.column_names);
+    state.apply_plot_config(config);
+    if missing.is_empty() {
+        state
+            .notifications
+            .quiet(format!("Plot preset \"{}\" applied", name));
+    } else {
+        state.notifications.warn(format!(
+            "Plot preset \"{}\" applied without missing column(s): {}",
+            name,
+            missing.join(", ")
+        ));
+    }
+}
+
+fn save_presets(state: &mut AppState, message: String) {
+    match state.config.save() {
+        Ok(()) => state.notifications.quiet(message),
+        Err(e) => state
+            .notifications
+            .error(format!("Config save error: {}", e)),
+    }
+}
+
+/// Spectrum / decomposition of the Y series, with the decomposition period.
+fn analysis_controls(ui: &mut egui::Ui, state: &mut AppState) {
+    egui::ComboBox::from_label("Analysis")
+        .selected_text(state.plot_analysis.to_string())
+        .show_ui(ui, |ui| {
+            for analysis in PlotAnalysis::all() {
+                if ui
+                    .selectable_value(&mut state.plot_analysis, *analysis, analysis.to_string())
+                    .changed()
+                {
+                    state.plot_dirty = true;
+                }
+            }
+        })
+        .response
+        .on_hover_text(
+            "Power spectrum: FFT of each Y series over evenly spaced X (Hz for a time axis). \
+             Decomposition: moving-average trend, seasonal pattern and residual.",
+        );
+    if state.plot_analysis == PlotAnalysis::Decomposition {
+        ui.label("Period:");
+        if ui
+            .add(egui::DragValue::new(&mut state.plot_period).range(2..=10_000))
+            .on_hover_text("Season length in points (e.g. 24 for hourly data with a daily cycle)")
+            .changed()
+        {
+            state.plot_dirty = true;
+        }
+    }
+    if state.plot_analysis == PlotAnalysis::Binned {
+        ui.label("Bins:");
+        if ui
+            .add(egui::DragValue::new(&mut state.histogram_bins).range(5..=200))
+            .changed()
+        {
+            state.plot_dirty = true;
+        }
+        egui::ComboBox::from_id_salt("plot_spread")
+            .selected_text(state.plot_spread.to_string())
+            .show_ui(ui, |ui| {
+                for spread in BinSpread::all() {
+                    if ui
+                        .selectable_value(&mut state.plot_spread, *spread, spread.to_string())
+                        .changed()
+                    {
+                        state.plot_dirty = true;
+                    }
+                }
+            });
+    }
+}
+
+/// Most Y series picked by `suggest_plot_defaults`.
+const MAX_SUGGESTED_Y: usize = 3;
+
+/// Pick X and Y from the column roles: the first timestamp as X (else the
+/// current X) and the first few measures as Y.
+pub fn suggest_plot_defaults(state: &mut AppState) {
+    let roles = &state.column_roles;
+    let with_role = |role: ColumnRole| {
+        state
+            .column_names
+            .iter()
+            .filter(|name| roles.get(*name) == Some(&role))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let x = with_role(ColumnRole::Timestamp)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| state.plot_x.clone());
+    let y: Vec<String> = with_role(ColumnRole::Measure)
+        .into_iter()
+        .filter(|name| *name != x)
+        .take(MAX_SUGGESTED_Y)
+        .collect();
+    if x.is_empty() && y.is_empty() {
+        return;
+    }
+    state.plot_x = x;
+    state.plot_y_columns = y;
+    state.plot_dirty = true;
+}
+
+/// Annotation chips (with remove buttons) and a small form to add one.
+/// Values on a datetime X axis may be typed as `YYYY-MM-DD[ HH:MM:SS]`.
+fn annotation_controls(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Annotations:");
+
+        let mut to_remove: Option<usize> = None;
+        for (i, annotation) in state.plot_annotations.iter().enumerate() {
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(annotation.to_string());
+                    if ui.small_button("X").clicked() {
+                        to_remove = Some(i);
+                    }
+                });
+            });
+        }
+        if let Some(idx) = to_remove {
+            state.plot_annotations.remove(idx);
+        }
+
+        ui.menu_button("+ Add annotation", |ui| {
+            egui::ComboBox::from_id_salt("annotation_kind")
+                .selected_text(state.annotation_kind.to_string())
+                .show_ui(ui, |ui| {
+                    for kind in AnnotationKind::all() {
+                        ui.selectable_value(&mut state.annotation_kind, *kind, kind.to_string());
+                    }
+                });
+            let kind = state.annotation_kind;
+            if kind != AnnotationKind::HLine {
+                ui.horizontal(|ui| {
+                    ui.label("X:");
+                    ui.text_edit_singleline(&mut state.annotation_x);
+                });
+            }
+            if kind != AnnotationKind::VLine {
+                ui.horizontal(|ui| {
+                    ui.label("Y:");
+                    ui.text_edit_singleline(&mut state.annotation_y);
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.label(if kind == AnnotationKind::Text {
+                    "Text:"
+                } else {
+                    "Label:"
+                });
+                ui.text_edit_singleline(&mut state.annotation_label);
+            });
+
+            if ui.button("Add").clicked() {
+                let x = parse_axis_value(&state.annotation_x, state.plot_x_is_datetime);
+                let y = state.annotation_y.trim().parse::<f64>().ok();
+                let label = state.annotation_label.clone();
+                let annotation = match kind {
+                    AnnotationKind::HLine => y.map(|y| PlotAnnotation::HLine { y, label }),
+                    AnnotationKind::VLine => x.map(|x| PlotAnnotation::VLine { x, label }),
+                    AnnotationKind::Text => match (x, y) {
+                        (Some(x), Some(y)) => Some(PlotAnnotation::Text { x, y, text: label }),
+                        _ => None,
+                    },
+                };
+                match annotation {
+                    Some(annotation) => {
+                        state.plot_annotations.push(annotation);
+                        state.annotation_label.clear();
+                        ui.close();
+                    }
+                    None => state
+                        .notifications
+                        .warn("Annotation needs numeric coordinates"),
+                }
+            }
+        });
+    });
+}
+
+/// Parse a coordinate typed by the user. On a datetime axis, dates and
+/// datetimes are accepted and converted to seconds since the epoch.
+fn parse_axis_value(text: &str, is_datetime: bool) -> Option<f64> {
+    let text = text.trim();
+    if let Ok(v) = text.parse::<f64>() {
+        return Some(v);
+    }
+    if !is_datetime {
+        return None;
+    }
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S") {
+        return Some(dt.and_utc().timestamp() as f64);
+    }
+    chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc().timestamp() as f64)
+}
+
+/// Draw reference lines and text markers on top of a plot.
+pub fn draw_annotations(plot_ui: &mut PlotUi, annotations: &[PlotAnnotation]) {
+    for annotation in annotations {
+        match annotation {
+            PlotAnnotation::HLine { y, label } => {
+                plot_ui.hline(HLine::new(label.as_str(), *y).color(GruvboxMaterial::red(255)));
+            }
+            PlotAnnotation::VLine { x, label } => {
+                plot_ui.vline(VLine::new(label.as_str(), *x).color(GruvboxMaterial::orange(255)));
+            }
+            PlotAnnotation::Text { x, y, text } => {
+                plot_ui.text(Text::new(
+                    text.as_str(),
+                    PlotPoint::new(*x, *y),
+                    text.as_str(),
+                ));
+            }
+        }
+    }
+}
+
+/// Plot area: recomputes plot data when dirty and renders the configured plot
+/// into the remaining space.
+pub fn plot_panel(ui: &mut egui::Ui, state: &mut AppState) {
     // ── Recompute plot data if dirty ──
     if state.plot_dirty {
         recompute_plot_data(state);
     }
 
-    // ── Reset Zoom ──
-    if ui.button("Reset Zoom").clicked() {
-        state.plot_reset_counter += 1;
-    }
+    // ── Reset Zoom / Export ──
+    ui.horizontal(|ui| {
+        if ui.button("Reset Zoom").clicked() {
+            state.plot_reset_counter += 1;
+        }
+        if !VIEWER
+            && ui
+                .button("Export plot data...")
+                .on_hover_text("Save the plotted points or histogram bins as CSV")
+                .clicked()
+        {
+            export_plot_data(state);
+        }
+    });
 
-    let plot_height = (ui.available_height() - 10.0).max(200.0);
+    let units = state.column_units();
+    fit_summary(ui, state);
+    let view = PlotView {
+        height: (ui.available_height() - 10.0).max(200.0),
+        link_x: None,
+        units: &units,
+        fits: &state.plot_fits,
+    };
+    let rc = state.plot_reset_counter;
+    let mut config = state.plot_config();
+    if let Some(label) = &state.plot_x_label {
+        config.x = label.clone();
+    }
 
     // ── Render Plot ──
     if state.plot_type == PlotType::Histogram {
-        render_histogram(ui, state, plot_height);
+        // Use full dataset for histogram (fall back to preview if unavailable)
+        match state.full_df.as_ref().or(state.preview_df.as_ref()) {
+            Some(df) => render_histogram(ui, &format!("histogram_{rc}"), df, &config, &view),
+            None => {
+                ui.label("No data.");
+            }
+        }
         return;
     }
 
@@ -140,99 +510,156 @@ pub fn visualize_tab_ui(ui: &mut egui::Ui, state: &mut AppState) {
         return;
     }
 
+    render_xy_plot(
+        ui,
+        &format!("visualize_{rc}"),
+        &config,
+        &state.plot_multi_data,
+        state.plot_x_is_datetime,
+        &view,
+    );
+}
+
+/// Per-view plot settings that are not part of the saved `PlotConfig`.
+pub struct PlotView<'a> {
+    pub height: f32,
+    /// Plots sharing a link group zoom and pan together along X.
+    pub link_x: Option<&'a str>,
+    /// Column units, shown in axis labels and legends.
+    pub units: &'a BTreeMap<String, String>,
+    /// Regression lines drawn over scatter plots, by series name.
+    pub fits: &'a [(String, LinearFit)],
+}
+
+/// One line per fitted series: slope, intercept, R², residual std and n.
+fn fit_summary(ui: &mut egui::Ui, state: &AppState) {
+    for (name, fit) in &state.plot_fits {
+        ui.label(
+            egui::RichText::new(format!(
+                "{}: y = {:.4}·x {} {:.4}   R² = {:.4}   σ = {:.4}   n = {}",
+                name,
+                fit.slope,
+                if fit.intercept < 0.0 { "−" } else { "+" },
+                fit.intercept.abs(),
+                fit.r_squared,
+                fit.residual_std,
+                fit.n
+            ))
+            .small()
+            .monospace()
+            .color(GruvboxMaterial::fg3(220)),
+        );
+    }
+}
+
+/// Column name with its unit, e.g. "temp [°C]".
+pub fn with_unit(name: &str, units: &BTreeMap<String, String>) -> String {
+    match units.get(name) {
+        Some(unit) => format!("{name} [{unit}]"),
+        None => name.to_string(),
+    }
+}
+
+/// The unit shared by all `columns`, if every one has the same unit.
+fn common_unit<'a>(
+    columns: impl IntoIterator<Item = &'a String>,
+    units: &'a BTreeMap<String, String>,
+) -> Option<&'a str> {
+    let mut found: Option<&str> = None;
+    for name in columns {
+        let unit = units.get(name)?;
+        match found {
+            Some(prev) if prev != unit.as_str() => return None,
+            _ => found = Some(unit),
+        }
+    }
+    found
+}
+
+/// Render a scatter/line/bar plot of pre-extracted series.
+/// `id` must be unique per plot on screen (and change to reset zoom).
+pub fn render_xy_plot(
+    ui: &mut egui::Ui,
+    id: &str,
+    config: &PlotConfig,
+    data: &[(String, Vec<[f64; 2]>)],
+    x_is_datetime: bool,
+    view: &PlotView,
+) {
+    let plot_type = config.plot_type;
+    let mut plot = Plot::new(format!("{id}_{plot_type}"))
+        .height(view.height)
+        .x_axis_label(with_unit(&config.x, view.units))
+        .show_axes([true, true])
+        .show_grid([true, true])
+        .legend(Legend::default());
+    if let Some(unit) = common_unit(data.iter().map(|(name, _)| name), view.units) {
+        plot = plot.y_axis_label(unit);
+    }
+    if let Some(group) = view.link_x {
+        let group = egui::Id::new(group);
+        plot = plot
+            .link_axis(group, [true, false])
+            .link_cursor(group, [true, false]);
+    }
+    if x_is_datetime {
+        plot = plot.x_axis_formatter(datetime_axis_formatter);
+        plot = plot.label_formatter(datetime_label_formatter);
+    }
     // Compute data bounds for axis auto-fit
-    let x_label = state.plot_x.clone();
-    let bounds = compute_plot_bounds(&state.plot_multi_data);
-    let rc = state.plot_reset_counter;
-    let x_is_dt = state.plot_x_is_datetime;
-
-    match state.plot_type {
-        PlotType::Scatter => {
-            let mut plot = Plot::new(format!("scatter_{rc}"))
-                .height(plot_height)
-                .x_axis_label(&x_label)
-                .show_axes([true, true])
-                .show_grid([true, true])
-                .legend(Legend::default());
-            if x_is_dt {
-                plot = plot.x_axis_formatter(datetime_axis_formatter);
-                plot = plot.label_formatter(datetime_label_formatter);
-            }
-            if let Some((x0, x1, y0, y1)) = bounds {
-                let xm = (x1 - x0).abs().max(0.1) * 0.05;
-                let ym = (y1 - y0).abs().max(0.1) * 0.05;
-                plot = plot.include_x(x0 - xm).include_x(x1 + xm)
-                           .include_y(y0 - ym).include_y(y1 + ym);
-            }
-            plot.show(ui, |plot_ui| {
-                for (name, data) in &state.plot_multi_data {
-                    let points = Points::new(
-                        name.as_str(),
-                        PlotPoints::new(data.clone()),
-                    )
-                    .radius(3.0);
-                    plot_ui.points(points);
+    if let Some((x0, x1, y0, y1)) = compute_plot_bounds(data) {
+        let xm = (x1 - x0).abs().max(0.1) * 0.05;
+        let ym = (y1 - y0).abs().max(0.1) * 0.05;
+        plot = plot
+            .include_x(x0 - xm)
+            .include_x(x1 + xm)
+            .include_y(y0 - ym)
+            .include_y(y1 + ym);
+    }
+
+    plot.show(ui, |plot_ui| {
+        match plot_type {
+            PlotType::Scatter => {
+                for (name, points) in data {
+                    let series =
+                        Points::new(with_unit(name, view.units), PlotPoints::new(points.clone()))
+                            .radius(3.0);
+                    plot_ui.points(series);
+                    let fit = view.fits.iter().find(|(n, _)| n == name);
+                    let xs = points.iter().map(|p| p[0]).filter(|x| x.is_finite());
+                    let x0 = xs.clone().fold(f64::INFINITY, f64::min);
+                    let x1 = xs.fold(f64::NEG_INFINITY, f64::max);
+                    if let Some((_, fit)) = fit.filter(|_| x0 < x1) {
+                        let line = vec![[x0, fit.predict(x0)], [x1, fit.predict(x1)]];
+                        plot_ui.line(Line::new(format!("{name} fit"), PlotPoints::new(line)));
+                    }
                 }
-            });
-        }
-        PlotType::Line => {
-            let mut plot = Plot::new(format!("line_{rc}"))
-                .height(plot_height)
-                .x_axis_label(&x_label)
-                .show_axes([true, true])
-                .show_grid([true, true])
-                .legend(Legend::default());
-            if x_is_dt {
-                plot = plot.x_axis_formatter(datetime_axis_formatter);
-                plot = plot.label_formatter(datetime_label_formatter);
             }
-            if let Some((x0, x1, y0, y1)) = bounds {
-                let xm = (x1 - x0).abs().max(0.1) * 0.05;
-                let ym = (y1 - y0).abs().max(0.1) * 0.05;
-                plot = plot.include_x(x0 - xm).include_x(x1 + xm)
-                           .include_y(y0 - ym).include_y(y1 + ym);
-            }
-            plot.show(ui, |plot_ui| {
-                for (name, data) in &state.plot_multi_data {
-                    let mut sorted_data = data.clone();
+            PlotType::Line => {
+                for (name, points) in data {
+                    let mut sorted_data = points.clone();
                     sorted_data.sort_by(|a, b| {
                         a[0].partial_cmp(&b[0]).unwrap_or(std::cmp::Ordering::Equal)
                     });
-                    let line = Line::new(name.as_str(), PlotPoints::new(sorted_data));
+                    let line = Line::new(with_unit(name, view.units), PlotPoints::new(sorted_data));
                     plot_ui.line(line);
                 }
-            });
-        }
-        PlotType::Bar => {
-            let mut plot = Plot::new(format!("bar_{rc}"))
-                .height(plot_height)
-                .x_axis_label(&x_label)
-                .show_axes([true, true])
-                .show_grid([true, true])
-                .legend(Legend::default());
-            if x_is_dt {
-                plot = plot.x_axis_formatter(datetime_axis_formatter);
-                plot = plot.label_formatter(datetime_label_formatter);
-            }
-            if let Some((x0, x1, y0, y1)) = bounds {
-                let xm = (x1 - x0).abs().max(0.1) * 0.05;
-                let ym = (y1 - y0).abs().max(0.1) * 0.05;
-                plot = plot.include_x(x0 - xm).include_x(x1 + xm)
-                           .include_y(y0 - ym).include_y(y1 + ym);
             }
-            plot.show(ui, |plot_ui| {
-                for (name, data) in &state.plot_multi_data {
-                    let bars: Vec<Bar> = data
+            PlotType::Bar => {
+                for (name, points) in data {
+                    let bars: Vec<Bar> = points
                         .iter()
                         .map(|[x, y]| Bar::new(*x, *y).width(0.8))
                         .collect();
-                    plot_ui.bar_chart(BarChart::new(name.as_str(), bars));
+                    plot_ui.bar_chart(BarChart::new(with_unit(name, view.units), bars));
                 }
-            });
+            }
+            PlotType::Histogram => {} // rendered by `render_histogram`
         }
-        PlotType::Histogram => {} // handled above
-    }
+        draw_annotations(plot_ui, &config.annotations);
+    });
 }
+
 /// Compute data bounds across all plot series.
 fn compute_plot_bounds(data: &[(String, Vec<[f64; 2]>)]) -> Option<(f64, f64, f64, f64)> {
     let mut x_min = f64::INFINITY;
@@ -295,6 +722,8 @@ fn datetime_label_formatter(name: &str, point: &egui_plot::PlotPoint) -> String
 fn recompute_plot_data(state: &mut AppState) {
     state.plot_dirty = false;
     state.plot_x_is_datetime = false;
+    state.plot_x_label = None;
+    state.plot_fits.clear();
 
     if state.plot_type == PlotType::Histogram {
         // Histogram only needs Y/X data, handled inline
@@ -303,63 +732,161 @@ fn recompute_plot_data(state: &mut AppState) {
 
     state.plot_multi_data.clear();
 
-    if state.plot_x.is_empty() || state.plot_y_columns.is_empty() {
-        return;
-    }
-
     // Use full dataset for plotting (fall back to preview if unavailable)
     let plot_df = state.full_df.as_ref().or(state.preview_df.as_ref());
     if let Some(df) = plot_df {
-        // Check if X column is datetime/date type
-        let x_vals = if let Ok(series) = df.column(&state.plot_x) {
-            match series.dtype() {
-                DataType::Datetime(tu, _) => {
-                    state.plot_x_is_datetime = true;
-                    let divisor = match tu {
-                        TimeUnit::Nanoseconds => 1_000_000_000.0,
-                        TimeUnit::Microseconds => 1_000_000.0,
-                        TimeUnit::Milliseconds => 1_000.0,
-                    };
-                    if let Ok(casted) = series.cast(&DataType::Float64) {
-                        if let Ok(ca) = casted.f64() {
-                            ca.into_no_null_iter().map(|v| v / divisor).collect()
-                        } else {
-                            vec![]
-                        }
+        let (data, x_is_datetime) = if state.plot_analysis == PlotAnalysis::Binned {
+            binned_series(df, state)
+        } else {
+            extract_series(df, &state.plot_x, &state.plot_y_columns)
+        };
+        state.plot_multi_data = data;
+        state.plot_x_is_datetime = x_is_datetime;
+    }
+    apply_plot_analysis(state);
+    state.plot_fits = if state.plot_fit && state.plot_type == PlotType::Scatter {
+        state
+            .plot_multi_data
+            .iter()
+            .filter_map(|(name, points)| Some((name.clone(), LinearFit::from_points(points)?)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+}
+
+/// Mean, low and high series per Y column, binned over X (see
+/// `analysis::binned_stats`), and whether X is a date/time.
+fn binned_series(df: &DataFrame, state: &AppState) -> (Vec<(String, Vec<[f64; 2]>)>, bool) {
+    let x_is_datetime = matches!(
+        df.column(&state.plot_x).map(|c| c.dtype()),
+        Ok(DataType::Datetime(_, _) | DataType::Date)
+    );
+    let mut data = Vec::new();
+    if state.plot_x.is_empty() {
+        return (data, x_is_datetime);
+    }
+    for y in &state.plot_y_columns {
+        let bins = match analysis::binned_stats(
+            df.clone().lazy(),
+            &state.plot_x,
+            y,
+            state.histogram_bins,
+            state.plot_spread,
+        ) {
+            Ok(bins) => bins,
+            Err(_) => continue,
+        };
+        let series = |label: &str, value: fn(&analysis::BinStats) -> f64| {
+            let points = bins.iter().map(|b| [b.x, value(b)]).collect();
+            (format!("{y} {label}"), points)
+        };
+        data.push(series("mean", |b| b.mean));
+        data.push(series("low", |b| b.low));
+        data.push(series("high", |b| b.high));
+    }
+    (data, x_is_datetime)
+}
+
+/// Replace the extracted series by their spectrum or decomposition.
+fn apply_plot_analysis(state: &mut AppState) {
+    let data = std::mem::take(&mut state.plot_multi_data);
+    let data: Vec<(String, Vec<[f64; 2]>)> = match state.plot_analysis {
+        PlotAnalysis::None | PlotAnalysis::Binned => data,
+        PlotAnalysis::Spectrum => {
+            state.plot_x_label = Some(if state.plot_x_is_datetime {
+                "frequency [Hz]".to_string()
+            } else {
+                format!("frequency [1/{}]", state.plot_x)
+            });
+            state.plot_x_is_datetime = false;
+            data.into_iter()
+                .map(|(name, points)| (format!("{name} power"), analysis::power_spectrum(&points)))
+                .collect()
+        }
+        PlotAnalysis::Decomposition => data
+            .into_iter()
+            .flat_map(|(name, points)| {
+                let parts = analysis::decompose(&points, state.plot_period);
+                [
+                    (format!("{name} trend"), parts.trend),
+                    (format!("{name} seasonal"), parts.seasonal),
+                    (format!("{name} residual"), parts.residual),
+                ]
+            })
+            .collect(),
+    };
+    state.plot_multi_data = data
+        .into_iter()
+        .filter(|(_, points)| !points.is_empty())
+        .collect();
+}
+
+/// Extract one `[x, y]` series per Y column. Datetime/Date X columns are
+/// converted to seconds since the epoch; the returned flag tells whether
+/// that happened (so the axis can be formatted as dates).
+pub fn extract_series(
+    df: &DataFrame,
+    x: &str,
+    y_columns: &[String],
+) -> (Vec<(String, Vec<[f64; 2]>)>, bool) {
+    let mut x_is_datetime = false;
+    let mut series_data = Vec::new();
+
+    if x.is_empty() || y_columns.is_empty() {
+        return (series_data, x_is_datetime);
+    }
+
+    // Check if X column is datetime/date type
+    let x_vals = if let Ok(series) = df.column(x) {
+        match series.dtype() {
+            DataType::Datetime(tu, _) => {
+                x_is_datetime = true;
+                let divisor = match tu {
+                    TimeUnit::Nanoseconds => 1_000_000_000.0,
+                    TimeUnit::Microseconds => 1_000_000.0,
+                    TimeUnit::Milliseconds => 1_000.0,
+                };
+                if let Ok(casted) = series.cast(&DataType::Float64) {
+                    if let Ok(ca) = casted.f64() {
+                        ca.into_no_null_iter().map(|v| v / divisor).collect()
                     } else {
                         vec![]
                     }
+                } else {
+                    vec![]
                 }
-                DataType::Date => {
-                    state.plot_x_is_datetime = true;
-                    if let Ok(casted) = series.cast(&DataType::Float64) {
-                        if let Ok(ca) = casted.f64() {
-                            ca.into_no_null_iter().map(|v| v * 86400.0).collect()
-                        } else {
-                            vec![]
-                        }
+            }
+            DataType::Date => {
+                x_is_datetime = true;
+                if let Ok(casted) = series.cast(&DataType::Float64) {
+                    if let Ok(ca) = casted.f64() {
+                        ca.into_no_null_iter().map(|v| v * 86400.0).collect()
                     } else {
                         vec![]
                     }
+                } else {
+                    vec![]
                 }
-                _ => extract_f64_column(df, &state.plot_x),
             }
-        } else {
-            vec![]
-        };
+            _ => extract_f64_column(df, x),
+        }
+    } else {
+        vec![]
+    };
 
-        for y_col in &state.plot_y_columns.clone() {
-            let y_vals = extract_f64_column(df, y_col);
-            let data: Vec<[f64; 2]> = x_vals
-                .iter()
-                .zip(y_vals.iter())
-                .map(|(&x, &y)| [x, y])
-                .collect();
-            if !data.is_empty() {
-                state.plot_multi_data.push((y_col.clone(), data));
-            }
+    for y_col in y_columns {
+        let y_vals = extract_f64_column(df, y_col);
+        let data: Vec<[f64; 2]> = x_vals
+            .iter()
+            .zip(y_vals.iter())
+            .map(|(&x, &y)| [x, y])
+            .collect();
+        if !data.is_empty() {
+            series_data.push((y_col.clone(), data));
         }
     }
+    (series_data, x_is_datetime)
 }
 
 /// Extract a column as Vec<f64>, casting to float. Non-numeric/null values are skipped.
@@ -374,26 +901,30 @@ fn extract_f64_column(df: &DataFrame, col_name: &str) -> Vec<f64> {
     vec![]
 }
 
-/// Render histogram(s) for selected columns (supports multi-series overlay).
-fn render_histogram(ui: &mut egui::Ui, state: &AppState, plot_height: f32) {
-    // Use Y columns if available, else fall back to X
-    let columns: Vec<String> = if !state.plot_y_columns.is_empty() {
-        state.plot_y_columns.clone()
-    } else if !state.plot_x.is_empty() {
-        vec![state.plot_x.clone()]
+/// Columns shown by a histogram: the Y series if any, else the X column.
+pub fn histogram_columns(x: &str, y_columns: &[String]) -> Vec<String> {
+    if !y_columns.is_empty() {
+        y_columns.to_vec()
+    } else if !x.is_empty() {
+        vec![x.to_string()]
     } else {
+        vec![]
+    }
+}
+
+/// Render histogram(s) for selected columns (supports multi-series overlay).
+pub fn render_histogram(
+    ui: &mut egui::Ui,
+    id: &str,
+    df: &DataFrame,
+    config: &PlotConfig,
+    view: &PlotView,
+) {
+    let columns = histogram_columns(&config.x, &config.y_columns);
+    if columns.is_empty() {
         ui.label("Select columns for the histogram (use Y series or X).");
         return;
-    };
-
-    // Use full dataset for histogram (fall back to preview if unavailable)
-    let df = match state.full_df.as_ref().or(state.preview_df.as_ref()) {
-        Some(df) => df,
-        None => {
-            ui.label("No data.");
-            return;
-        }
-    };
+    }
 
     let mut all_series: Vec<(String, Vec<Bar>)> = Vec::new();
     let mut global_x_min = f64::INFINITY;
@@ -405,7 +936,7 @@ fn render_histogram(ui: &mut egui::Ui, state: &AppState, plot_height: f32) {
         if values.is_empty() {
             continue;
         }
-        let (centers, counts, bin_width) = compute_histogram(&values, state.histogram_bins);
+        let (centers, counts, bin_width) = compute_histogram(&values, config.histogram_bins);
         if let (Some(&first), Some(&last)) = (centers.first(), centers.last()) {
             global_x_min = global_x_min.min(first - bin_width);
             global_x_max = global_x_max.max(last + bin_width);
@@ -417,7 +948,7 @@ fn render_histogram(ui: &mut egui::Ui, state: &AppState, plot_height: f32) {
             .zip(counts.iter())
             .map(|(&c, &count)| Bar::new(c, count).width(bin_width * 0.95))
             .collect();
-        all_series.push((col_name.clone(), bars));
+        all_series.push((with_unit(col_name, view.units), bars));
     }
 
     if all_series.is_empty() {
@@ -425,13 +956,15 @@ fn render_histogram(ui: &mut egui::Ui, state: &AppState, plot_height: f32) {
         return;
     }
 
-    let rc = state.plot_reset_counter;
-    let mut plot = Plot::new(format!("histogram_{rc}"))
-        .height(plot_height)
+    let mut plot = Plot::new(id)
+        .height(view.height)
         .y_axis_label("Count")
         .show_axes([true, true])
         .show_grid([true, true])
         .legend(Legend::default());
+    if let Some(unit) = common_unit(&columns, view.units) {
+        plot = plot.x_axis_label(unit);
+    }
     if global_x_min.is_finite() && global_x_max.is_finite() {
         plot = plot
             .include_x(global_x_min)
@@ -443,9 +976,79 @@ fn render_histogram(ui: &mut egui::Ui, state: &AppState, plot_height: f32) {
         for (name, bars) in all_series {
             plot_ui.bar_chart(BarChart::new(name, bars));
         }
+        draw_annotations(plot_ui, &config.annotations);
     });
 }
 
+// ─── Plot Data Export ─────────────────────────────────────────────────────────
+
+/// The plot's data exactly as displayed: histogram bins for histograms, the
+/// plotted points (lines sorted by X) otherwise. Returns (headers, rows).
+fn plot_data_grid(state: &AppState) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut rows = Vec::new();
+
+    if state.plot_type == PlotType::Histogram {
+        let headers = ["series", "bin_start", "bin_end", "count"].map(String::from);
+        if let Some(df) = state.full_df.as_ref().or(state.preview_df.as_ref()) {
+            for col_name in histogram_columns(&state.plot_x, &state.plot_y_columns) {
+                let values = extract_f64_column(df, &col_name);
+                let (centers, counts, bin_width) = compute_histogram(&values, state.histogram_bins);
+                for (center, count) in centers.iter().zip(counts) {
+                    rows.push(vec![
+                        col_name.clone(),
+                        (center - bin_width / 2.0).to_string(),
+                        (center + bin_width / 2.0).to_string(),
+                        count.to_string(),
+                    ]);
+                }
+            }
+        }
+        return (headers.to_vec(), rows);
+    }
+
+    let x = state.plot_x_label.as_ref().unwrap_or(&state.plot_x);
+    let headers = vec!["series".to_string(), x.clone(), "y".to_string()];
+    for (name, points) in &state.plot_multi_data {
+        let mut points = points.clone();
+        if state.plot_type == PlotType::Line {
+            points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(std::cmp::Ordering::Equal));
+        }
+        for [x, y] in points {
+            let x = if state.plot_x_is_datetime {
+                DateTime::from_timestamp(x as i64, 0)
+                    .map(|dt| dt.naive_utc().format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| x.to_string())
+            } else {
+                x.to_string()
+            };
+            rows.push(vec![name.clone(), x, y.to_string()]);
+        }
+    }
+    (headers, rows)
+}
+
+/// Save the plot's data (see `plot_data_grid`) to a CSV file.
+fn export_plot_data(state: &mut AppState) {
+    let (headers, rows) = plot_data_grid(state);
+    if rows.is_empty() {
+        state
+            .notifications
+            .warn("Nothing to export: the plot is empty");
+        return;
+    }
+    if let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV file", &["csv"])
+        .save_file()
+    {
+        match execution::export_grid_csv(&headers, &rows, &path) {
+            Ok(()) => state
+                .notifications
+                .info(format!("Plot data exported to {}", path.display())),
+            Err(e) => state.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+}
+
 // ─── Histogram Computation ────────────────────────────────────────────────────
 
 /// Compute histogram bins: returns (bin_centers, counts, bin_width).
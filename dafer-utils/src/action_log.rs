@@ -0,0 +1,145 @@
+//! Action log module: append-only record of what the user did.
+//!
+//! One JSON object per line in `actions.jsonl` in the application data directory,
+//! kept across sessions, so the steps behind an exported file can be traced later.
+
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::Operation;
+use crate::persistence::app_data_dir;
+
+// ─── Actions ──────────────────────────────────────────────────────────────────
+
+/// A user action worth recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    FileOpened {
+        path: String,
+    },
+    OperationAdded {
+        operation: Operation,
+    },
+    OperationRemoved {
+        index: usize,
+        operation: Operation,
+    },
+    /// The whole pipeline was replaced (cleared, reverted, loaded, snapshot).
+    PipelineReplaced {
+        reason: String,
+        operations: Vec<Operation>,
+    },
+    Exported {
+        path: String,
+        format: String,
+        source: Option<String>,
+        operations: Vec<Operation>,
+        success: bool,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::FileOpened { path } => write!(f, "Opened {}", path),
+            Action::OperationAdded { operation } => write!(f, "Added {}", operation),
+            Action::OperationRemoved { index, operation } => {
+                write!(f, "Removed step {}: {}", index + 1, operation)
+            }
+            Action::PipelineReplaced { reason, operations } => {
+                write!(f, "Pipeline {} ({} ops)", reason, operations.len())
+            }
+            Action::Exported {
+                path,
+                format,
+                operations,
+                success,
+                ..
+            } => {
+                let outcome = if *success {
+                    "Exported"
+                } else {
+                    "Export failed:"
+                };
+                write!(
+                    f,
+                    "{} {} to {} ({} ops)",
+                    outcome,
+                    format,
+                    path,
+                    operations.len()
+                )
+            }
+        }
+    }
+}
+
+/// One line of the log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Local time, `YYYY-MM-DD HH:MM:SS`.
+    pub time: String,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+// ─── Log File ─────────────────────────────────────────────────────────────────
+
+/// Location of the action log.
+pub fn log_path() -> PathBuf {
+    app_data_dir().join("actions.jsonl")
+}
+
+/// Appends actions to the log file. Write failures are kept in `write_error`
+/// rather than returned, so recording never interrupts the action itself.
+#[derive(Debug, Default)]
+pub struct ActionLog {
+    pub write_error: Option<String>,
+}
+
+impl ActionLog {
+    pub fn record(&mut self, action: Action) {
+        let entry = LogEntry {
+            time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            action,
+        };
+        self.write_error = append(&entry).err().map(|e| e.to_string());
+    }
+}
+
+fn append(entry: &LogEntry) -> Result<()> {
+    let path = log_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// The last `limit` entries of the log, oldest first. Unreadable lines
+/// (e.g. from a newer version) are skipped.
+pub fn read_recent(limit: usize) -> Result<Vec<LogEntry>> {
+    let file = match std::fs::File::open(log_path()) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries: Vec<LogEntry> = BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.drain(..skip);
+    Ok(entries)
+}
@@ -0,0 +1,855 @@
+//! Analysis module: quick summary tables and data-quality checks on a pipeline result.
+//!
+//! These are read-only views and checks; they never change the pipeline themselves.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use anyhow::Result;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::DTypeTag;
+
+// ─── Crosstab ─────────────────────────────────────────────────────────────────
+
+/// Aggregate for the cells of a crosstab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrosstabAgg {
+    /// Number of rows per (row, column) category pair.
+    #[default]
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl CrosstabAgg {
+    pub fn all() -> &'static [CrosstabAgg] {
+        &[
+            CrosstabAgg::Count,
+            CrosstabAgg::Sum,
+            CrosstabAgg::Mean,
+            CrosstabAgg::Min,
+            CrosstabAgg::Max,
+        ]
+    }
+
+    /// Returns true if this aggregate needs a value column.
+    pub fn needs_value(&self) -> bool {
+        !matches!(self, CrosstabAgg::Count)
+    }
+}
+
+impl fmt::Display for CrosstabAgg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrosstabAgg::Count => write!(f, "Count"),
+            CrosstabAgg::Sum => write!(f, "Sum"),
+            CrosstabAgg::Mean => write!(f, "Mean"),
+            CrosstabAgg::Min => write!(f, "Min"),
+            CrosstabAgg::Max => write!(f, "Max"),
+        }
+    }
+}
+
+/// Contingency table: one row per distinct `row_col` value, one column per
+/// distinct `col_col` value, cells holding the count (or `agg` of `value`).
+///
+/// Keys are compared as strings and sorted; nulls become "(null)". Count cells
+/// with no rows are 0, aggregate cells with no rows are null.
+pub fn crosstab(
+    df: &DataFrame,
+    row_col: &str,
+    col_col: &str,
+    value: Option<&str>,
+    agg: CrosstabAgg,
+) -> Result<DataFrame> {
+    let grouped = df
+        .clone()
+        .lazy()
+        .group_by([key(row_col).alias("__row"), key(col_col).alias("__col")])
+        .agg([agg_expr(agg, value)?.alias("__value")])
+        .collect()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let rows = grouped.column("__row")?.str()?;
+    let cols = grouped.column("__col")?.str()?;
+    let values = grouped.column("__value")?.f64()?;
+    let mut cells: BTreeMap<(String, String), Option<f64>> = BTreeMap::new();
+    let mut row_keys = BTreeSet::new();
+    let mut col_keys = BTreeSet::new();
+    for ((r, c), v) in rows.iter().zip(cols.iter()).zip(values.iter()) {
+        let (r, c) = (
+            r.unwrap_or_default().to_string(),
+            c.unwrap_or_default().to_string(),
+        );
+        row_keys.insert(r.clone());
+        col_keys.insert(c.clone());
+        cells.insert((r, c), v);
+    }
+
+    let row_keys: Vec<String> = row_keys.into_iter().collect();
+    let mut out = Column::new(row_col.into(), row_keys.clone()).into_frame();
+    for c in col_keys {
+        let get = |r: &String| cells.get(&(r.clone(), c.clone())).copied().flatten();
+        let column = if agg == CrosstabAgg::Count {
+            let counts: Vec<u64> = row_keys
+                .iter()
+                .map(|r| get(r).unwrap_or(0.0) as u64)
+                .collect();
+            Column::new(c.as_str().into(), counts)
+        } else {
+            let values: Vec<Option<f64>> = row_keys.iter().map(get).collect();
+            Column::new(c.as_str().into(), values)
+        };
+        out.with_column(column)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    Ok(out)
+}
+
+/// Group key: the column as a string, nulls as "(null)".
+fn key(name: &str) -> Expr {
+    col(name).cast(DataType::String).fill_null(lit("(null)"))
+}
+
+/// `agg` of `value` (as Float64); Count ignores `value`.
+fn agg_expr(agg: CrosstabAgg, value: Option<&str>) -> Result<Expr> {
+    let v = || match value {
+        Some(value) => Ok(col(value).cast(DataType::Float64)),
+        None => Err(anyhow::anyhow!("{} needs a value column", agg)),
+    };
+    Ok(match agg {
+        CrosstabAgg::Count => len().cast(DataType::Float64),
+        CrosstabAgg::Sum => v()?.sum(),
+        CrosstabAgg::Mean => v()?.mean(),
+        CrosstabAgg::Min => v()?.min(),
+        CrosstabAgg::Max => v()?.max(),
+    })
+}
+
+// ─── Pivot ────────────────────────────────────────────────────────────────────
+
+/// Layout of a pivot table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PivotSpec {
+    /// One output row per distinct combination of these columns.
+    pub rows: Vec<String>,
+    /// One output column per distinct combination of these columns (and value).
+    pub columns: Vec<String>,
+    /// Columns aggregated into the cells (ignored for Count).
+    pub values: Vec<String>,
+    pub agg: CrosstabAgg,
+}
+
+impl PivotSpec {
+    /// Header of each aggregated measure, e.g. `sum(price)`.
+    fn measures(&self) -> Vec<String> {
+        if !self.agg.needs_value() {
+            return vec!["count".to_string()];
+        }
+        self.values
+            .iter()
+            .map(|v| format!("{}({})", self.agg.to_string().to_lowercase(), v))
+            .collect()
+    }
+}
+
+/// Pivot table of `lf`: a lazy group-by over the row and column keys, then the
+/// column keys spread into one column each (joined with " / "), per measure.
+///
+/// Keys are compared as strings and sorted, like `crosstab`. With no column
+/// keys the result is a plain grouped summary.
+pub fn pivot(lf: LazyFrame, spec: &PivotSpec) -> Result<DataFrame> {
+    if spec.agg.needs_value() && spec.values.is_empty() {
+        anyhow::bail!("{} needs at least one value column", spec.agg);
+    }
+    let measures = spec.measures();
+    let aggs: Vec<Expr> = if spec.agg.needs_value() {
+        spec.values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| Ok(agg_expr(spec.agg, Some(v))?.alias(format!("__m{i}"))))
+            .collect::<Result<_>>()?
+    } else {
+        vec![agg_expr(spec.agg, None)?.alias("__m0")]
+    };
+    let keys: Vec<Expr> = spec
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, r)| key(r).alias(format!("__r{i}")))
+        .chain(
+            spec.columns
+                .iter()
+                .enumerate()
+                .map(|(i, c)| key(c).alias(format!("__c{i}"))),
+        )
+        .collect();
+    let grouped = if keys.is_empty() {
+        lf.select(aggs)
+    } else {
+        lf.group_by(keys).agg(aggs)
+    }
+    .collect()
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let strings = |prefix: &str, n: usize| -> Result<Vec<StringChunked>> {
+        (0..n)
+            .map(|i| Ok(grouped.column(&format!("{prefix}{i}"))?.str()?.clone()))
+            .collect()
+    };
+    let row_cols = strings("__r", spec.rows.len())?;
+    let col_cols = strings("__c", spec.columns.len())?;
+    let measure_cols: Vec<Float64Chunked> = (0..measures.len())
+        .map(|i| Ok(grouped.column(&format!("__m{i}"))?.f64()?.clone()))
+        .collect::<Result<_>>()?;
+
+    let get = |ca: &StringChunked, i: usize| ca.get(i).unwrap_or_default().to_string();
+    let mut cells: BTreeMap<(Vec<String>, String), Vec<Option<f64>>> = BTreeMap::new();
+    let mut row_keys = BTreeSet::new();
+    let mut col_keys = BTreeSet::new();
+    for i in 0..grouped.height() {
+        let r: Vec<String> = row_cols.iter().map(|ca| get(ca, i)).collect();
+        let c: Vec<String> = col_cols.iter().map(|ca| get(ca, i)).collect();
+        let c = c.join(" / ");
+        row_keys.insert(r.clone());
+        col_keys.insert(c.clone());
+        cells.insert((r, c), measure_cols.iter().map(|ca| ca.get(i)).collect());
+    }
+
+    let row_keys: Vec<Vec<String>> = row_keys.into_iter().collect();
+    let mut columns = Vec::new();
+    for (j, name) in spec.rows.iter().enumerate() {
+        let values: Vec<String> = row_keys.iter().map(|r| r[j].clone()).collect();
+        columns.push(Column::new(name.as_str().into(), values));
+    }
+    for c in &col_keys {
+        for (m, measure) in measures.iter().enumerate() {
+            let name = match (spec.columns.is_empty(), measures.len()) {
+                (true, _) => measure.clone(),
+                (false, 1) => c.clone(),
+                (false, _) => format!("{} · {}", c, measure),
+            };
+            let get = |r: &Vec<String>| {
+                cells
+                    .get(&(r.clone(), c.clone()))
+                    .and_then(|values| values[m])
+            };
+            columns.push(if spec.agg == CrosstabAgg::Count {
+                let counts: Vec<u64> = row_keys
+                    .iter()
+                    .map(|r| get(r).unwrap_or(0.0) as u64)
+                    .collect();
+                Column::new(name.as_str().into(), counts)
+            } else {
+                let values: Vec<Option<f64>> = row_keys.iter().map(get).collect();
+                Column::new(name.as_str().into(), values)
+            });
+        }
+    }
+
+    let mut columns = columns.into_iter();
+    let Some(first) = columns.next() else {
+        return Ok(DataFrame::default());
+    };
+    let mut out = first.into_frame();
+    for column in columns {
+        out.with_column(column)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    Ok(out)
+}
+
+// ─── Linear Fit ───────────────────────────────────────────────────────────────
+
+/// Ordinary least squares fit `y = slope·x + intercept`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coefficient of determination (1 = perfect fit).
+    pub r_squared: f64,
+    /// Standard deviation of the residuals (n − 2 degrees of freedom).
+    pub residual_std: f64,
+    /// Number of (x, y) pairs used.
+    pub n: usize,
+}
+
+impl LinearFit {
+    /// Fit `[x, y]` points, skipping non-finite ones. `None` with fewer than
+    /// 3 points or when all X values are equal.
+    pub fn from_points(points: &[[f64; 2]]) -> Option<LinearFit> {
+        let points: Vec<[f64; 2]> = points
+            .iter()
+            .copied()
+            .filter(|[x, y]| x.is_finite() && y.is_finite())
+            .collect();
+        let n = points.len();
+        if n < 3 {
+            return None;
+        }
+        let mean_x = points.iter().map(|p| p[0]).sum::<f64>() / n as f64;
+        let mean_y = points.iter().map(|p| p[1]).sum::<f64>() / n as f64;
+        let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+        for [x, y] in &points {
+            let (dx, dy) = (x - mean_x, y - mean_y);
+            sxx += dx * dx;
+            sxy += dx * dy;
+            syy += dy * dy;
+        }
+        if sxx == 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+        let ss_res: f64 = points
+            .iter()
+            .map(|[x, y]| (y - (slope * x + intercept)).powi(2))
+            .sum();
+        Some(LinearFit {
+            slope,
+            intercept,
+            r_squared: if syy == 0.0 { 1.0 } else { 1.0 - ss_res / syy },
+            residual_std: (ss_res / (n - 2) as f64).sqrt(),
+            n,
+        })
+    }
+
+    pub fn predict(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+}
+
+/// Least squares fit of column `y` on column `x` (both cast to Float64), over
+/// the rows where both are non-null.
+pub fn linear_fit(df: &DataFrame, x: &str, y: &str) -> Result<LinearFit> {
+    let column = |name: &str| -> Result<Float64Chunked> {
+        Ok(df
+            .column(name)?
+            .cast(&DataType::Float64)
+            .map_err(|e| anyhow::anyhow!("{}", e))?
+            .f64()?
+            .clone())
+    };
+    let (xs, ys) = (column(x)?, column(y)?);
+    let points: Vec<[f64; 2]> = xs
+        .iter()
+        .zip(ys.iter())
+        .filter_map(|(x, y)| Some([x?, y?]))
+        .collect();
+    LinearFit::from_points(&points)
+        .ok_or_else(|| anyhow::anyhow!("Need at least 3 rows with distinct {} values", x))
+}
+
+// ─── Binned Statistics ────────────────────────────────────────────────────────
+
+/// Band drawn around the per-bin mean of `binned_stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinSpread {
+    /// Mean ± one standard deviation.
+    #[default]
+    Std,
+    /// 10th to 90th percentile.
+    Percentile,
+}
+
+impl BinSpread {
+    pub fn all() -> &'static [BinSpread] {
+        &[BinSpread::Std, BinSpread::Percentile]
+    }
+}
+
+impl fmt::Display for BinSpread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinSpread::Std => write!(f, "± std"),
+            BinSpread::Percentile => write!(f, "p10–p90"),
+        }
+    }
+}
+
+/// Statistics of Y in one X bin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinStats {
+    /// Bin center.
+    pub x: f64,
+    pub mean: f64,
+    pub low: f64,
+    pub high: f64,
+    pub count: usize,
+}
+
+/// Mean of `y` per equal-width bin of `x`, with a `spread` band. Computed
+/// lazily: only the bounds and one row per bin are collected.
+///
+/// Date/Datetime X is binned in seconds since the epoch (as plotted); other X
+/// columns are cast to Float64. Rows with a null X or Y are skipped and empty
+/// bins omitted. A bin with one row has a zero-width band.
+pub fn binned_stats(
+    mut lf: LazyFrame,
+    x: &str,
+    y: &str,
+    bins: usize,
+    spread: BinSpread,
+) -> Result<Vec<BinStats>> {
+    let schema = lf.collect_schema().map_err(|e| anyhow::anyhow!("{}", e))?;
+    let xe = match schema.get(x) {
+        Some(DataType::Datetime(unit, _)) => {
+            let per_second = match unit {
+                TimeUnit::Nanoseconds => 1e9,
+                TimeUnit::Microseconds => 1e6,
+                TimeUnit::Milliseconds => 1e3,
+            };
+            col(x).cast(DataType::Int64).cast(DataType::Float64) / lit(per_second)
+        }
+        Some(DataType::Date) => {
+            col(x).cast(DataType::Int32).cast(DataType::Float64) * lit(86_400.0)
+        }
+        Some(_) => col(x).cast(DataType::Float64),
+        None => anyhow::bail!("No column {}", x),
+    };
+    let ye = col(y).cast(DataType::Float64);
+    let lf = lf.filter(xe.clone().is_not_null().and(ye.clone().is_not_null()));
+
+    let bounds = lf
+        .clone()
+        .select([xe.clone().min().alias("lo"), xe.clone().max().alias("hi")])
+        .collect()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let bound = |name: &str| -> Result<Option<f64>> { Ok(bounds.column(name)?.f64()?.get(0)) };
+    let (Some(lo), Some(hi)) = (bound("lo")?, bound("hi")?) else {
+        return Ok(Vec::new());
+    };
+    let bins = bins.max(1);
+    let width = if hi > lo {
+        (hi - lo) / bins as f64
+    } else {
+        1.0
+    };
+
+    // The maximum lands on the upper edge of the last bin, not past it
+    let raw = ((xe - lit(lo)) / lit(width)).floor();
+    let last = (bins - 1) as f64;
+    let bin = when(raw.clone().gt(lit(last)))
+        .then(lit(last))
+        .otherwise(raw);
+    let (low, high) = match spread {
+        BinSpread::Std => (
+            ye.clone().mean() - ye.clone().std(1),
+            ye.clone().mean() + ye.clone().std(1),
+        ),
+        BinSpread::Percentile => (
+            ye.clone().quantile(lit(0.1), QuantileMethod::Linear),
+            ye.clone().quantile(lit(0.9), QuantileMethod::Linear),
+        ),
+    };
+    let grouped = lf
+        .group_by([bin.alias("__bin")])
+        .agg([
+            ye.mean().alias("__mean"),
+            low.alias("__low"),
+            high.alias("__high"),
+            len().cast(DataType::Float64).alias("__n"),
+        ])
+        .collect()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let column = |name: &str| -> Result<Float64Chunked> {
+        Ok(grouped
+            .column(name)?
+            .cast(&DataType::Float64)
+            .map_err(|e| anyhow::anyhow!("{}", e))?
+            .f64()?
+            .clone())
+    };
+    let (bin, mean, low, high, n) = (
+        column("__bin")?,
+        column("__mean")?,
+        column("__low")?,
+        column("__high")?,
+        column("__n")?,
+    );
+    let mut out: Vec<BinStats> = (0..grouped.height())
+        .filter_map(|i| {
+            let mean = mean.get(i)?;
+            Some(BinStats {
+                x: lo + (bin.get(i)? + 0.5) * width,
+                mean,
+                low: low.get(i).unwrap_or(mean),
+                high: high.get(i).unwrap_or(mean),
+                count: n.get(i).unwrap_or(0.0) as usize,
+            })
+        })
+        .collect();
+    out.sort_by(|a, b| a.x.total_cmp(&b.x));
+    Ok(out)
+}
+
+// ─── Signal Analysis ──────────────────────────────────────────────────────────
+
+/// Points sorted by X.
+fn sorted_by_x(points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    points
+}
+
+/// Power spectrum of a series sampled at (roughly) even X steps, as
+/// `[frequency, power]` from 0 up to the Nyquist frequency. Frequencies are in
+/// cycles per X unit (Hz for a datetime X in seconds); the step is the median
+/// X spacing. The mean is removed and a Hann window applied before a radix-2
+/// FFT, zero-padding to a power of two. Fewer than 4 points give nothing.
+pub fn power_spectrum(points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let points = sorted_by_x(points);
+    let mut steps: Vec<f64> = points
+        .windows(2)
+        .map(|w| w[1][0] - w[0][0])
+        .filter(|d| *d > 0.0)
+        .collect();
+    if points.len() < 4 || steps.is_empty() {
+        return Vec::new();
+    }
+    steps.sort_by(f64::total_cmp);
+    let step = steps[steps.len() / 2];
+
+    let n = points.len();
+    let mean = points.iter().map(|p| p[1]).sum::<f64>() / n as f64;
+    let size = n.next_power_of_two();
+    let mut re = vec![0.0; size];
+    let mut im = vec![0.0; size];
+    for (i, p) in points.iter().enumerate() {
+        let hann = 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / (n - 1) as f64).cos();
+        re[i] = (p[1] - mean) * hann;
+    }
+    fft(&mut re, &mut im);
+    (0..=size / 2)
+        .map(|k| {
+            let frequency = k as f64 / (size as f64 * step);
+            [frequency, (re[k] * re[k] + im[k] * im[k]) / n as f64]
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; the length must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * std::f64::consts::PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// A series split into trend, seasonal and residual parts (see `decompose`).
+#[derive(Debug, Clone, Default)]
+pub struct Decomposition {
+    pub trend: Vec<[f64; 2]>,
+    pub seasonal: Vec<[f64; 2]>,
+    pub residual: Vec<[f64; 2]>,
+}
+
+/// Classical additive decomposition (a moving-average "STL-lite") of a series
+/// whose season is `period` points long. The trend is a centered moving
+/// average over one period, the seasonal part the mean detrended value at each
+/// position in the period (centered on zero), the residual what is left.
+/// Points are sorted by X; the first and last half period have no trend or
+/// residual. Needs at least two full periods.
+pub fn decompose(points: &[[f64; 2]], period: usize) -> Decomposition {
+    let points = sorted_by_x(points);
+    let n = points.len();
+    if period < 2 || n < 2 * period {
+        return Decomposition::default();
+    }
+    let y: Vec<f64> = points.iter().map(|p| p[1]).collect();
+    let mut prefix = vec![0.0; n + 1];
+    for (i, v) in y.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + v;
+    }
+    let sum = |from: usize, to: usize| prefix[to] - prefix[from];
+
+    // Even periods use a 2×period average so the window stays centered
+    let half = period / 2;
+    let trend: Vec<Option<f64>> = (0..n)
+        .map(|i| {
+            if i < half || i + half >= n {
+                return None;
+            }
+            Some(if period % 2 == 1 {
+                sum(i - half, i + half + 1) / period as f64
+            } else {
+                (sum(i - half + 1, i + half) + 0.5 * (y[i - half] + y[i + half])) / period as f64
+            })
+        })
+        .collect();
+
+    let mut totals = vec![(0.0, 0usize); period];
+    for (i, t) in trend.iter().enumerate() {
+        if let Some(t) = t {
+            totals[i % period].0 += y[i] - t;
+            totals[i % period].1 += 1;
+        }
+    }
+    let mut season: Vec<f64> = totals
+        .iter()
+        .map(|(total, count)| total / (*count).max(1) as f64)
+        .collect();
+    let offset = season.iter().sum::<f64>() / period as f64;
+    for s in &mut season {
+        *s -= offset;
+    }
+
+    let mut out = Decomposition::default();
+    for (i, p) in points.iter().enumerate() {
+        let seasonal = season[i % period];
+        out.seasonal.push([p[0], seasonal]);
+        if let Some(t) = trend[i] {
+            out.trend.push([p[0], t]);
+            out.residual.push([p[0], y[i] - t - seasonal]);
+        }
+    }
+    out
+}
+
+// ─── Mixed-Type Columns ───────────────────────────────────────────────────────
+
+/// What a string value looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Number,
+    /// ISO date (`YYYY-MM-DD`).
+    Date,
+    Text,
+}
+
+impl ValueKind {
+    /// Classify a single (non-null) string value.
+    pub fn of(value: &str) -> ValueKind {
+        let trimmed = value.trim();
+        if trimmed.parse::<f64>().is_ok() {
+            ValueKind::Number
+        } else if chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d").is_ok() {
+            ValueKind::Date
+        } else {
+            ValueKind::Text
+        }
+    }
+
+    /// Target type when casting a column to this kind; `None` for text.
+    pub fn dtype(&self) -> Option<DTypeTag> {
+        match self {
+            ValueKind::Number => Some(DTypeTag::Float64),
+            ValueKind::Date => Some(DTypeTag::Date),
+            ValueKind::Text => None,
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueKind::Number => write!(f, "number"),
+            ValueKind::Date => write!(f, "date"),
+            ValueKind::Text => write!(f, "text"),
+        }
+    }
+}
+
+/// A string column whose values are a mix of numbers, dates, and text.
+#[derive(Debug, Clone)]
+pub struct MixedTypeReport {
+    pub column: String,
+    /// (kind, count) over the sampled non-null, non-empty values, most common first.
+    pub counts: Vec<(ValueKind, usize)>,
+    /// A few example values per kind.
+    pub samples: Vec<(ValueKind, Vec<String>)>,
+}
+
+impl MixedTypeReport {
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, n)| n).sum()
+    }
+
+    /// Most common kind of value.
+    pub fn majority(&self) -> ValueKind {
+        self.counts[0].0
+    }
+
+    /// Share of values of `kind`, in [0, 1].
+    pub fn fraction(&self, kind: ValueKind) -> f64 {
+        let n = self
+            .counts
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map_or(0, |(_, n)| *n);
+        n as f64 / self.total().max(1) as f64
+    }
+}
+
+/// Flag String columns holding more than one kind of value, by sampling up to
+/// `max_samples` values per column. `detect_numeric_string_columns` covers the
+/// clean (≥90% numeric) case; this reports everything in between.
+pub fn detect_mixed_type_columns(df: &DataFrame, max_samples: usize) -> Vec<MixedTypeReport> {
+    const EXAMPLES: usize = 3;
+    let mut reports = Vec::new();
+    for column in df.get_columns() {
+        let Ok(ca) = column.as_materialized_series().str() else {
+            continue;
+        };
+        let mut counts: BTreeMap<u8, (ValueKind, usize)> = BTreeMap::new();
+        let mut samples: BTreeMap<u8, Vec<String>> = BTreeMap::new();
+        for value in ca.iter().take(max_samples).flatten() {
+            if value.trim().is_empty() {
+                continue;
+            }
+            let kind = ValueKind::of(value);
+            let key = kind as u8;
+            counts.entry(key).or_insert((kind, 0)).1 += 1;
+            let examples = samples.entry(key).or_default();
+            if examples.len() < EXAMPLES && !examples.iter().any(|e| e == value) {
+                examples.push(value.to_string());
+            }
+        }
+        if counts.len() < 2 {
+            continue;
+        }
+        let mut counts: Vec<(ValueKind, usize)> = counts.into_values().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1));
+        let samples = counts
+            .iter()
+            .map(|(kind, _)| (*kind, samples.remove(&(*kind as u8)).unwrap_or_default()))
+            .collect();
+        reports.push(MixedTypeReport {
+            column: column.name().to_string(),
+            counts,
+            samples,
+        });
+    }
+    reports
+}
+
+// ─── Column Roles ─────────────────────────────────────────────────────────────
+
+/// What a column means for analysis, beyond its dtype.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnRole {
+    /// Identifier (order number, user id): numeric but not a quantity.
+    Id,
+    Timestamp,
+    /// Numeric quantity to aggregate and plot.
+    Measure,
+    /// Category to group or filter by.
+    Dimension,
+}
+
+impl ColumnRole {
+    pub fn all() -> &'static [ColumnRole] {
+        &[
+            ColumnRole::Id,
+            ColumnRole::Timestamp,
+            ColumnRole::Measure,
+            ColumnRole::Dimension,
+        ]
+    }
+}
+
+impl fmt::Display for ColumnRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnRole::Id => write!(f, "ID"),
+            ColumnRole::Timestamp => write!(f, "Timestamp"),
+            ColumnRole::Measure => write!(f, "Measure"),
+            ColumnRole::Dimension => write!(f, "Dimension"),
+        }
+    }
+}
+
+/// Minimum non-null values before cardinality alone marks a column as an ID.
+const ID_MIN_ROWS: usize = 20;
+
+/// Share of distinct values above which a whole-number column is an ID.
+const ID_DISTINCT_RATIO: f64 = 0.95;
+
+/// Inferred role of every column.
+pub fn infer_roles(df: &DataFrame) -> BTreeMap<String, ColumnRole> {
+    df.get_columns()
+        .iter()
+        .map(|c| (c.name().to_string(), infer_role(c)))
+        .collect()
+}
+
+/// Likely role of a column: temporal → Timestamp; whole numbers that are
+/// (nearly) all distinct or named like an identifier → Id; other numbers →
+/// Measure; everything else → Dimension.
+pub fn infer_role(column: &Column) -> ColumnRole {
+    let dtype = column.dtype();
+    if matches!(dtype, DataType::Date | DataType::Datetime(_, _)) {
+        return ColumnRole::Timestamp;
+    }
+    if !dtype.is_integer() && !dtype.is_float() {
+        return ColumnRole::Dimension;
+    }
+    if dtype.is_float() && !is_whole_numbers(column) {
+        return ColumnRole::Measure;
+    }
+    let non_null = column.len() - column.null_count();
+    let high_cardinality = non_null >= ID_MIN_ROWS
+        && column
+            .n_unique()
+            .is_ok_and(|n| n as f64 >= non_null as f64 * ID_DISTINCT_RATIO);
+    if high_cardinality || has_id_name(column.name()) {
+        ColumnRole::Id
+    } else {
+        ColumnRole::Measure
+    }
+}
+
+/// Every (sampled) value of a float column is a whole number.
+fn is_whole_numbers(column: &Column) -> bool {
+    const SAMPLE: usize = 1000;
+    let Ok(casted) = column.as_materialized_series().cast(&DataType::Float64) else {
+        return false;
+    };
+    let Ok(ca) = casted.f64() else {
+        return false;
+    };
+    ca.iter().take(SAMPLE).flatten().all(|v| v.fract() == 0.0)
+}
+
+/// "id", "user_id", "userId", "order_no", "order_number".
+fn has_id_name(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower == "id"
+        || name.ends_with("Id")
+        || name.ends_with("ID")
+        || ["_id", "_no", "_number"]
+            .iter()
+            .any(|suffix| lower.ends_with(suffix))
+}
@@ -0,0 +1,61 @@
+//! Command-line entry points for running saved pipelines outside the app.
+//!
+//! ```text
+//! dafer bundle <state.dfr> <out.zip> [--sample <rows>]
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use dafer_utils::bundle::{self, BundleOptions};
+use dafer_utils::persistence::PersistentState;
+
+const USAGE: &str = "Usage: dafer bundle <state.dfr> <out.zip> [--sample <rows>]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("bundle") => bundle_command(&args[1..]),
+        Some("-h" | "--help" | "help") => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `dafer bundle`: write the reproducible bundle of a saved state file.
+fn bundle_command(args: &[String]) -> Result<()> {
+    let mut paths = Vec::new();
+    let mut options = BundleOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sample" => {
+                let rows = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--sample needs a row count"))?;
+                options.sample_rows = Some(rows.parse()?);
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let [state, output] = paths.as_slice() else {
+        anyhow::bail!("{}", USAGE);
+    };
+    let persistent = PersistentState::load(state)?;
+    let files = bundle::export_bundle(&persistent, output, &options)?;
+    println!("Wrote {} ({})", output.display(), files.join(", "));
+    Ok(())
+}
@@ -0,0 +1,138 @@
+//! Bundle module: one zip file to reproduce or audit a pipeline run.
+//!
+//! A bundle holds the pipeline (as JSON and as a loadable state file), the
+//! source parameters, the provenance record, an HTML report and optionally a
+//! sample of the input. Entries are streamed into the archive file as they are
+//! produced, so a large sample is never held in memory whole.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use polars::prelude::*;
+use zip::write::{SimpleFileOptions, ZipWriter};
+
+use crate::datasource::DataSource;
+use crate::execution::{self, Provenance, format_any_value, html_escape};
+use crate::operations::Operation;
+use crate::persistence::PersistentState;
+use crate::query_engine;
+
+/// Rows of the pipeline result shown in the report.
+const REPORT_ROWS: u32 = 50;
+
+/// What goes into a bundle besides the pipeline, parameters and provenance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BundleOptions {
+    /// Include a CSV sample of about this many input rows (see
+    /// `query_engine::sample`); `None` = no input data.
+    pub sample_rows: Option<usize>,
+}
+
+/// Write the bundle for `state` (source + pipeline) to `path`, atomically: an
+/// interrupted export leaves no truncated zip behind.
+/// Returns the names of the files in the bundle.
+pub fn export_bundle(
+    state: &PersistentState,
+    path: &Path,
+    options: &BundleOptions,
+) -> Result<Vec<String>> {
+    let Some(source) = &state.source else {
+        anyhow::bail!("The pipeline has no source");
+    };
+    let provenance = Provenance::new(source, &state.operations, None)?;
+    let mut names = Vec::new();
+    execution::write_file(path, true, |file| {
+        let mut zip = ZipWriter::new(file);
+        let mut add = |zip: &mut ZipWriter<File>, name: &str, data: &[u8]| -> Result<()> {
+            zip.start_file(name, entry_options())?;
+            zip.write_all(data)?;
+            names.push(name.to_string());
+            Ok(())
+        };
+        add(&mut zip, "pipeline.json", &serde_json::to_vec_pretty(&state.operations)?)?;
+        add(&mut zip, "pipeline.dfr", &serde_json::to_vec_pretty(state)?)?;
+        add(&mut zip, "parameters.json", &serde_json::to_vec_pretty(source)?)?;
+        add(&mut zip, "provenance.json", &serde_json::to_vec_pretty(&provenance)?)?;
+        let report = report_html(source, &state.operations, &provenance)?;
+        add(&mut zip, "report.html", report.as_bytes())?;
+        if let Some(n) = options.sample_rows {
+            let (df, _) = query_engine::sample(source, &[], n)?;
+            zip.start_file("input_sample.csv", entry_options())?;
+            write_frame_csv(&df, &mut zip)?;
+            names.push("input_sample.csv".to_string());
+        }
+        zip.finish()?;
+        Ok(())
+    })?;
+    Ok(names)
+}
+
+/// Deflated, stamped with the local time; large (zip64) so a big sample fits.
+fn entry_options() -> SimpleFileOptions {
+    use chrono::{Datelike, Timelike};
+    let now = chrono::Local::now();
+    let modified = zip::DateTime::from_date_and_time(
+        now.year().clamp(1980, 2107) as u16,
+        now.month() as u8,
+        now.day() as u8,
+        now.hour() as u8,
+        now.minute() as u8,
+        now.second() as u8,
+    )
+    .unwrap_or_default();
+    SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .last_modified_time(modified)
+        .large_file(true)
+}
+
+/// Self-contained HTML report: source, provenance, steps and the first rows.
+fn report_html(
+    source: &DataSource,
+    operations: &[Operation],
+    provenance: &Provenance,
+) -> Result<String> {
+    let rows = query_engine::count_rows(source, operations, &[])?;
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Pipeline report</title>\n</head>\n<body>\n<h1>Pipeline report</h1>\n",
+    );
+    out.push_str(&format!(
+        "<p>Source: <code>{}</code><br>SHA-256: <code>{}</code><br>\
+         Exported {} with dafer-utils {}<br>Result: {} rows</p>\n",
+        html_escape(&provenance.source),
+        provenance.source_sha256,
+        html_escape(&provenance.exported_at),
+        html_escape(&provenance.app_version),
+        rows
+    ));
+    out.push_str("<h2>Steps</h2>\n<ol>\n");
+    for op in operations {
+        out.push_str(&format!("  <li>{}</li>\n", html_escape(&op.to_string())));
+    }
+    out.push_str("</ol>\n");
+    out.push_str(&format!("<h2>First {} rows</h2>\n", REPORT_ROWS.min(rows as u32)));
+    out.push_str(&execution::html_table(source, operations, REPORT_ROWS)?);
+    out.push_str(
+        "<p>Load <code>pipeline.dfr</code> with File &gt; Load State to re-run the \
+         pipeline on the original source.</p>\n</body>\n</html>\n",
+    );
+    Ok(out)
+}
+
+/// Write a DataFrame as CSV (nulls empty) to `out`, one row at a time.
+fn write_frame_csv(df: &DataFrame, out: impl Write) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(df.get_column_names().iter().map(|s| s.as_str()))?;
+    for i in 0..df.height() {
+        writer.write_record(
+            df.get_columns()
+                .iter()
+                .map(|col| col.get(i).map(|v| format_any_value(&v)).unwrap_or_default()),
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
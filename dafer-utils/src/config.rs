@@ -0,0 +1,109 @@
+//! Config module: per-user application settings, persisted across sessions.
+//!
+//! Stored as JSON in the application data directory. A missing or
+//! unreadable config file silently falls back to defaults.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::display::DockLayout;
+use crate::i18n::Language;
+use crate::persistence::app_data_dir;
+use crate::plot::PlotPreset;
+use crate::query_engine::PerformanceSettings;
+use crate::redaction::RedactionProfile;
+
+/// Per-user application settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    pub dock: DockLayout,
+    /// Shell command run after each export (see `execution::run_hook`); empty = off.
+    #[serde(default)]
+    pub completion_hook: String,
+    #[serde(default)]
+    pub language: Language,
+    /// Saved export redaction profiles.
+    #[serde(default)]
+    pub redaction_profiles: Vec<RedactionProfile>,
+    /// Saved plot setups (see `plot::PlotPreset`).
+    #[serde(default)]
+    pub plot_presets: Vec<PlotPreset>,
+    /// Starred datasets (see `Bookmark`).
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    pub performance: PerformanceSettings,
+}
+
+/// A frequently used dataset, starred with a label and tags for quick opening.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub label: String,
+    pub path: PathBuf,
+    /// Comma-separated tags (kept as typed).
+    pub tags: String,
+}
+
+impl Bookmark {
+    pub fn new(path: PathBuf) -> Self {
+        let label = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            label,
+            path,
+            tags: String::new(),
+        }
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.split(',').map(str::trim).filter(|t| !t.is_empty())
+    }
+
+    /// True if every word of `query` appears (case-insensitively) in the label,
+    /// the path or a tag; `#word` only matches tags.
+    pub fn matches(&self, query: &str) -> bool {
+        let label = self.label.to_lowercase();
+        let path = self.path.to_string_lossy().to_lowercase();
+        let tags: Vec<String> = self.tags().map(str::to_lowercase).collect();
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            match word.strip_prefix('#') {
+                Some(tag) => tags.iter().any(|t| t.contains(tag)),
+                None => {
+                    label.contains(&word)
+                        || path.contains(&word)
+                        || tags.iter().any(|t| t.contains(&word))
+                }
+            }
+        })
+    }
+}
+
+impl AppConfig {
+    /// Load the config file, falling back to defaults.
+    pub fn load() -> Self {
+        std::fs::read(config_path())
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the config file.
+    pub fn save(&self) -> Result<()> {
+        let path = config_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Location of the application config file.
+pub fn config_path() -> PathBuf {
+    app_data_dir().join("config.json")
+}
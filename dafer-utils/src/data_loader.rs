@@ -1,30 +1,116 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 use polars::prelude::*;
 
+use crate::datasource::{CsvOptions, DataSource};
+use crate::operations::{self, Operation};
+use crate::persistence::app_data_dir;
+
 /// Scan a CSV file as a LazyFrame.
-/// Uses a high schema inference length to correctly detect numeric columns
-/// even when values are quoted (e.g. "2.124879").
-pub fn scan_csv(path: &str) -> Result<LazyFrame, PolarsError> {
+/// The default schema inference length is high enough to detect numeric
+/// columns even when values are quoted (e.g. "2.124879"); it and the memory
+/// knobs come from `options`.
+/// `options.delimiter` must be a single byte (see `needs_tokenizer`).
+pub fn scan_csv(path: &str, options: &CsvOptions) -> Result<LazyFrame, PolarsError> {
     LazyCsvReader::new(PlPath::from_str(path))
-        .with_has_header(true)
-        .with_infer_schema_length(Some(10000))
+        .with_separator(options.delimiter.as_bytes()[0])
+        .with_has_header(options.has_header)
+        .with_skip_rows(options.skip_rows)
+        .with_infer_schema_length(options.infer_schema_length)
+        .with_low_memory(options.low_memory)
+        .with_rechunk(options.rechunk)
+        .with_chunk_size(options.chunk_size.max(1))
         .finish()
 }
 
 /// Scan a Parquet file as a LazyFrame.
+#[cfg(feature = "parquet")]
 pub fn scan_parquet(path: &str) -> Result<LazyFrame, PolarsError> {
     LazyFrame::scan_parquet(PlPath::from_str(path), ScanArgsParquet::default())
 }
 
+// ─── Row Group Sampling ──────────────────────────────────────────────────────
+
+/// Number of slices a spread preview is taken from.
+pub const SPREAD_SLICES: usize = 8;
+
+/// Row count of each row group of a Parquet file, read from its footer.
+#[cfg(feature = "parquet")]
+pub fn parquet_row_groups(path: &Path) -> Result<Vec<usize>> {
+    let file = std::fs::File::open(path)?;
+    let metadata = ParquetReader::new(file)
+        .get_metadata()
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .clone();
+    Ok(metadata.row_groups.iter().map(|rg| rg.num_rows()).collect())
+}
+
+/// `(offset, len)` slices of about `n` rows in total, spread evenly over a file
+/// with the given row groups. Each slice starts at the beginning of the row
+/// group containing its evenly spaced offset, so it is read from a single row
+/// group; files with fewer row groups than slices are split by rows instead.
+pub fn spread_slices(row_groups: &[usize], n: usize) -> Vec<(i64, usize)> {
+    let total: usize = row_groups.iter().sum();
+    if total <= n {
+        return vec![(0, total)];
+    }
+    let len = n.div_ceil(SPREAD_SLICES);
+    let starts: Vec<usize> = row_groups
+        .iter()
+        .scan(0, |start, rows| {
+            let group_start = *start;
+            *start += rows;
+            Some(group_start)
+        })
+        .collect();
+    let mut slices: Vec<(i64, usize)> = Vec::new();
+    for i in 0..SPREAD_SLICES {
+        let target = (total - len) * i / (SPREAD_SLICES - 1);
+        let offset = if row_groups.len() >= SPREAD_SLICES {
+            let group = starts.partition_point(|&s| s <= target) - 1;
+            starts[group]
+        } else {
+            target
+        };
+        if slices.last().is_none_or(|&(last, _)| last as usize + len <= offset) {
+            slices.push((offset as i64, len));
+        }
+    }
+    slices
+}
+
+/// Lazily scan about `n` rows taken from across a Parquet file (see
+/// `spread_slices`) instead of only its head. Slices are pushed down to the
+/// reader, so only the row groups holding them are decoded.
+#[cfg(feature = "parquet")]
+pub fn scan_parquet_spread(path: &Path, n: usize) -> Result<LazyFrame> {
+    let path_str = path.to_str().unwrap_or_default();
+    let row_groups = parquet_row_groups(path)?;
+    let frames: Vec<LazyFrame> = spread_slices(&row_groups, n)
+        .into_iter()
+        .map(|(offset, len)| scan_parquet(path_str).map(|lf| lf.slice(offset, len as IdxSize)))
+        .collect::<Result<_, PolarsError>>()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    concat(frames, UnionArgs::default()).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
 /// Collect the first n rows from a LazyFrame into a DataFrame.
 pub fn collect_head(lf: &LazyFrame, n: usize) -> Result<DataFrame, PolarsError> {
     lf.clone().limit(n as u32).collect()
 }
 
-/// Get column statistics: min, max, null count, error count (for numeric columns).
+/// Get column statistics: min, max, null count, error count (for numeric columns),
+/// lexicographic min/max + mode + average length (String), true/false counts (Boolean),
+/// and distinct counts (see [`DISTINCT_IS_APPROX`]).
 pub fn column_stats(df: &DataFrame) -> Vec<ColumnStats> {
+    let distinct = distinct_counts(df);
     df.iter()
-        .map(|series| {
+        .zip(distinct)
+        .map(|(series, distinct)| {
             let dtype = series.dtype();
             let null_count = series.null_count();
             let name = series.name().to_string();
@@ -39,6 +125,16 @@ pub fn column_stats(df: &DataFrame) -> Vec<ColumnStats> {
                     series.max::<i32>().ok().flatten().map(|v| v.to_string()),
                     0,
                 ),
+                DataType::UInt32 => (
+                    series.min::<u32>().ok().flatten().map(|v| v.to_string()),
+                    series.max::<u32>().ok().flatten().map(|v| v.to_string()),
+                    0,
+                ),
+                DataType::UInt64 => (
+                    series.min::<u64>().ok().flatten().map(|v| v.to_string()),
+                    series.max::<u64>().ok().flatten().map(|v| v.to_string()),
+                    0,
+                ),
                 DataType::Float64 => (
                     series.min::<f64>().ok().flatten().map(|v| v.to_string()),
                     series.max::<f64>().ok().flatten().map(|v| v.to_string()),
@@ -49,8 +145,34 @@ pub fn column_stats(df: &DataFrame) -> Vec<ColumnStats> {
                     series.max::<f32>().ok().flatten().map(|v| v.to_string()),
                     0,
                 ),
+                DataType::Date | DataType::Datetime(_, _) => (
+                    temporal_extreme(series.min_reduce()),
+                    temporal_extreme(series.max_reduce()),
+                    0,
+                ),
                 _ => (None, None, 0),
             };
+            let string_summary = match dtype {
+                DataType::String => string_summary(series),
+                _ => None,
+            };
+            let bool_counts = match dtype {
+                DataType::Boolean => bool_counts(series),
+                _ => None,
+            };
+            let (min, max) = match &string_summary {
+                Some(summary) => (Some(summary.min.clone()), Some(summary.max.clone())),
+                None => (min, max),
+            };
+            let mode = match (&string_summary, bool_counts) {
+                (Some(summary), _) => Some(summary.mode.clone()),
+                (None, Some((t, f))) if t + f > 0 => Some((t >= f).to_string()),
+                _ => None,
+            };
+            let timezone = match dtype {
+                DataType::Datetime(_, Some(tz)) => Some(tz.to_string()),
+                _ => None,
+            };
             ColumnStats {
                 name,
                 dtype: format!("{:?}", dtype),
@@ -58,6 +180,13 @@ pub fn column_stats(df: &DataFrame) -> Vec<ColumnStats> {
                 max,
                 null_count,
                 error_count,
+                sparkline: sparkline_bins(series, SPARKLINE_BINS, SPARKLINE_SAMPLE),
+                span: temporal_span_seconds(series).map(format_span),
+                timezone,
+                mode,
+                avg_length: string_summary.map(|summary| summary.avg_length),
+                bool_counts,
+                distinct,
             }
         })
         .collect()
@@ -84,38 +213,603 @@ pub struct ColumnStats {
     pub max: Option<String>,
     pub null_count: usize,
     pub error_count: usize,
+    /// Histogram bin counts of a sample of the column (empty for non-numeric columns).
+    pub sparkline: Vec<u32>,
+    /// Time span covered (max − min) for Date/Datetime columns, human readable.
+    pub span: Option<String>,
+    /// Time zone of a Datetime column, if any.
+    pub timezone: Option<String>,
+    /// Most frequent non-null value (String and Boolean columns).
+    pub mode: Option<String>,
+    /// Average length in characters of non-null values (String columns).
+    pub avg_length: Option<f64>,
+    /// (true, false) counts for Boolean columns.
+    pub bool_counts: Option<(usize, usize)>,
+    /// Number of distinct values (an estimate if [`DISTINCT_IS_APPROX`]).
+    pub distinct: Option<usize>,
+}
+
+// ─── Stats Cache ─────────────────────────────────────────────────────────────
+
+/// Entries kept by `StatsCache` before the oldest are evicted.
+pub const STATS_CACHE_ENTRIES: usize = 2_000;
+
+/// Statistics of earlier pipeline results by (fingerprint, column), so an edit
+/// only recomputes the columns it affects and undoing it recomputes nothing.
+#[derive(Debug, Default)]
+pub struct StatsCache {
+    entries: HashMap<(u64, String), ColumnStats>,
+    /// Insertion order, oldest first.
+    order: VecDeque<(u64, String)>,
+}
+
+impl StatsCache {
+    pub fn get(&self, fingerprint: u64, column: &str) -> Option<&ColumnStats> {
+        self.entries.get(&(fingerprint, column.to_string()))
+    }
+
+    /// Store the statistics of `stats.name` under `fingerprint`.
+    pub fn insert(&mut self, fingerprint: u64, stats: ColumnStats) {
+        let key = (fingerprint, stats.name.clone());
+        if self.entries.insert(key.clone(), stats).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > STATS_CACHE_ENTRIES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Fingerprint of each of `columns` in the result of `operations` on `source`:
+/// the source file (path, size, modification time, parse and sample options)
+/// and the steps in the column's lineage (see `operations::lineage`).
+/// Empty for unseeded samples, which are different rows on every run.
+pub fn stats_fingerprints(
+    source: &DataSource,
+    operations: &[Operation],
+    columns: &[String],
+) -> HashMap<String, u64> {
+    if source.stats_sample.is_some() && source.seed.is_none() {
+        return HashMap::new();
+    }
+    let mut base = DefaultHasher::new();
+    serde_json::to_string(source)
+        .unwrap_or_default()
+        .hash(&mut base);
+    if let Ok(meta) = std::fs::metadata(&source.path) {
+        meta.len().hash(&mut base);
+        meta.modified().ok().hash(&mut base);
+    }
+    let lineage = operations::lineage(operations);
+    columns
+        .iter()
+        .map(|column| {
+            let mut hasher = base.clone();
+            lineage.of(column).hash(&mut hasher);
+            (column.clone(), hasher.finish())
+        })
+        .collect()
+}
+
+/// `column_stats` of the columns missing from `cached` (by name); the cached
+/// ones are returned as given, in column order.
+pub fn column_stats_with(
+    df: &DataFrame,
+    mut cached: HashMap<String, ColumnStats>,
+) -> Vec<ColumnStats> {
+    let missing: Vec<String> = df
+        .get_column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .filter(|name| !cached.contains_key(name))
+        .collect();
+    let computed = match df.select(missing) {
+        Ok(missing) => column_stats(&missing),
+        Err(_) => return column_stats(df),
+    };
+    let mut computed = computed.into_iter();
+    df.get_column_names()
+        .iter()
+        .filter_map(|name| cached.remove(name.as_str()).or_else(|| computed.next()))
+        .collect()
+}
+
+// ─── Distinct Counts ─────────────────────────────────────────────────────────
+
+/// Whether distinct counts are HyperLogLog estimates rather than exact.
+/// Enabled by the `approx-distinct` feature: exact counts hash every value and
+/// get slow and memory-hungry on very large inputs.
+pub const DISTINCT_IS_APPROX: bool = cfg!(feature = "approx-distinct");
+
+/// Approximate distinct count per column, computed in a single query.
+#[cfg(feature = "approx-distinct")]
+fn distinct_counts(df: &DataFrame) -> Vec<Option<usize>> {
+    let exprs: Vec<Expr> = df
+        .get_column_names()
+        .iter()
+        .map(|name| col(name.as_str()).approx_n_unique())
+        .collect();
+    let Ok(out) = df.clone().lazy().select(exprs).collect() else {
+        return vec![None; df.width()];
+    };
+    out.get_columns()
+        .iter()
+        .map(|c| {
+            let c = c.cast(&DataType::UInt64).ok()?;
+            c.u64().ok()?.get(0).map(|v| v as usize)
+        })
+        .collect()
+}
+
+/// Exact distinct count per column.
+#[cfg(not(feature = "approx-distinct"))]
+fn distinct_counts(df: &DataFrame) -> Vec<Option<usize>> {
+    df.get_columns().iter().map(|c| c.n_unique().ok()).collect()
+}
+
+// ─── String / Boolean Statistics ─────────────────────────────────────────────
+
+/// Summary of a String column's non-null values.
+struct StringSummary {
+    min: String,
+    max: String,
+    mode: String,
+    avg_length: f64,
+}
+
+/// Compute lexicographic min/max, most frequent value and average length.
+/// Ties for the most frequent value are broken lexicographically.
+fn string_summary(series: &Series) -> Option<StringSummary> {
+    let ca = series.str().ok()?;
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut total_length = 0usize;
+    let mut min: Option<&str> = None;
+    let mut max: Option<&str> = None;
+    for value in ca.into_iter().flatten() {
+        total_length += value.chars().count();
+        *counts.entry(value).or_insert(0) += 1;
+        min = Some(min.map_or(value, |m| m.min(value)));
+        max = Some(max.map_or(value, |m| m.max(value)));
+    }
+    let non_null: usize = counts.values().sum();
+    let (mode, _) = counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))?;
+    Some(StringSummary {
+        min: min?.to_string(),
+        max: max?.to_string(),
+        mode: mode.to_string(),
+        avg_length: total_length as f64 / non_null as f64,
+    })
+}
+
+/// Count (true, false) values of a Boolean column, ignoring nulls.
+fn bool_counts(series: &Series) -> Option<(usize, usize)> {
+    let ca = series.bool().ok()?;
+    let trues = ca.into_iter().flatten().filter(|b| *b).count();
+    let non_null = ca.len() - ca.null_count();
+    Some((trues, non_null - trues))
+}
+
+// ─── Temporal Statistics ─────────────────────────────────────────────────────
+
+/// Render a min/max reduction of a temporal column (None for all-null columns).
+fn temporal_extreme(scalar: PolarsResult<Scalar>) -> Option<String> {
+    let scalar = scalar.ok()?;
+    let value = scalar.value();
+    if value.is_null() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Time span (max − min) of a Date/Datetime column in seconds.
+fn temporal_span_seconds(series: &Series) -> Option<f64> {
+    let scale = match series.dtype() {
+        DataType::Date => 86_400.0,
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => 1e-9,
+        DataType::Datetime(TimeUnit::Microseconds, _) => 1e-6,
+        DataType::Datetime(TimeUnit::Milliseconds, _) => 1e-3,
+        _ => return None,
+    };
+    let physical = series.cast(&DataType::Float64).ok()?;
+    let min = physical.min::<f64>().ok().flatten()?;
+    let max = physical.max::<f64>().ok().flatten()?;
+    Some((max - min) * scale)
+}
+
+/// Format a duration in seconds as a compact human-readable span (e.g. "12d 3h").
+pub fn format_span(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    let (days, hours) = (secs / 86_400, (secs % 86_400) / 3_600);
+    let (minutes, seconds) = ((secs % 3_600) / 60, secs % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+// ─── Sparklines ──────────────────────────────────────────────────────────────
+
+/// Number of bins in the per-column sparkline histogram.
+const SPARKLINE_BINS: usize = 24;
+
+/// Maximum number of values sampled to build a sparkline.
+const SPARKLINE_SAMPLE: usize = 5000;
+
+/// Compute a small histogram of a numeric column from an evenly strided sample.
+/// Returns an empty vector for non-numeric or all-null columns.
+pub fn sparkline_bins(series: &Series, n_bins: usize, max_samples: usize) -> Vec<u32> {
+    let is_numeric = matches!(
+        series.dtype(),
+        DataType::Int32
+            | DataType::Int64
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+    );
+    if !is_numeric || n_bins == 0 || max_samples == 0 {
+        return Vec::new();
+    }
+    let Ok(casted) = series.cast(&DataType::Float64) else {
+        return Vec::new();
+    };
+    let Ok(ca) = casted.f64() else {
+        return Vec::new();
+    };
+
+    let stride = (ca.len() / max_samples).max(1);
+    let values: Vec<f64> = ca
+        .into_iter()
+        .step_by(stride)
+        .flatten()
+        .filter(|v| v.is_finite())
+        .collect();
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut bins = vec![0u32; n_bins];
+    if (max - min).abs() < f64::EPSILON {
+        bins[n_bins / 2] = values.len() as u32;
+        return bins;
+    }
+    let width = (max - min) / n_bins as f64;
+    for v in values {
+        let bin = (((v - min) / width).floor() as usize).min(n_bins - 1);
+        bins[bin] += 1;
+    }
+    bins
 }
 
 // ─── Auto-detection of numeric String columns ────────────────────────────────
 
-/// Detect String columns that contain primarily numeric values.
-/// Returns column names that should be auto-cast to Float64.
-pub fn detect_numeric_string_columns(df: &DataFrame) -> Vec<String> {
+/// Numeric type an auto-cast column is converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericKind {
+    Integer,
+    Float,
+}
+
+impl std::fmt::Display for NumericKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumericKind::Integer => write!(f, "Int64"),
+            NumericKind::Float => write!(f, "Float64"),
+        }
+    }
+}
+
+/// Detect String columns that contain primarily numeric values, with the
+/// suggested type: Int64 if every numeric value is a whole number, else Float64.
+/// Zero-padded codes ("00123", fixed-width IDs) are skipped: casting would
+/// drop the leading zeros.
+pub fn detect_numeric_string_columns(df: &DataFrame) -> Vec<(String, NumericKind)> {
     df.get_columns()
         .iter()
         .filter(|s| s.dtype() == &DataType::String)
-        .filter(|s| is_numeric_string_column(s))
-        .map(|s| s.name().to_string())
+        .filter_map(|s| numeric_string_kind(s).map(|kind| (s.name().to_string(), kind)))
         .collect()
 }
 
 /// Check if a String column contains primarily numeric values by sampling.
-fn is_numeric_string_column(col: &Column) -> bool {
+fn numeric_string_kind(col: &Column) -> Option<NumericKind> {
     let series = col.as_materialized_series();
-    let Ok(str_ca) = series.str() else {
-        return false;
-    };
+    let str_ca = series.str().ok()?;
     let sample_size = series.len().min(200);
     let mut numeric_count = 0usize;
     let mut non_null_count = 0usize;
+    let mut all_integer = true;
     for i in 0..sample_size {
         if let Some(val) = str_ca.get(i) {
             non_null_count += 1;
             let trimmed = val.trim();
+            if has_leading_zero(trimmed) {
+                return None;
+            }
             if !trimmed.is_empty() && trimmed.parse::<f64>().is_ok() {
                 numeric_count += 1;
+                all_integer &= trimmed.parse::<i64>().is_ok();
             }
         }
     }
-    non_null_count > 0 && numeric_count * 10 >= non_null_count * 9
+    let numeric = non_null_count > 0 && numeric_count * 10 >= non_null_count * 9;
+    numeric.then_some(if all_integer {
+        NumericKind::Integer
+    } else {
+        NumericKind::Float
+    })
+}
+
+/// "007", "-0042": a zero before another digit (but not "0" or "0.5").
+fn has_leading_zero(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let mut chars = digits.chars();
+    chars.next() == Some('0') && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+// ─── Delimiter Detection ─────────────────────────────────────────────────────
+
+/// Delimiters tried by `sniff_delimiter`, in order of preference on a tie
+/// (`||` before `|`, so a doubled pipe is not read as two).
+pub const DELIMITER_CANDIDATES: [&str; 5] = [",", ";", "\t", "||", "|"];
+
+/// Bytes read from the start of the file for sniffing.
+const SNIFF_BYTES: usize = 1024;
+
+/// Display name of a delimiter for the import options.
+pub fn delimiter_name(delimiter: &str) -> String {
+    match delimiter {
+        "," => "Comma (,)".to_string(),
+        ";" => "Semicolon (;)".to_string(),
+        "\t" => "Tab".to_string(),
+        "|" => "Pipe (|)".to_string(),
+        "||" => "Double pipe (||)".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Guess the delimiter of a text file from its first KB: the candidate that
+/// appears the same (non-zero) number of times, outside quotes, on the most
+/// lines wins, weighted by that count and its length. `.tsv` files are always
+/// tab-separated.
+pub fn sniff_delimiter(path: &Path) -> Result<String> {
+    let is_tsv = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("tsv"));
+    if is_tsv {
+        return Ok("\t".to_string());
+    }
+    let mut sample = Vec::with_capacity(SNIFF_BYTES);
+    std::fs::File::open(path)?
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)?;
+    let text = String::from_utf8_lossy(&sample);
+    let mut lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    // The last line is probably cut off by the sample limit
+    if sample.len() == SNIFF_BYTES && lines.len() > 1 {
+        lines.pop();
+    }
+    let best = DELIMITER_CANDIDATES
+        .iter()
+        .map(|&d| (d, delimiter_score(&lines, d)))
+        .fold(
+            (",", 0),
+            |best, (d, score)| {
+                if score > best.1 { (d, score) } else { best }
+            },
+        );
+    Ok(best.0.to_string())
+}
+
+/// Lines sharing the most common per-line count × that count × the delimiter
+/// length (0 if absent).
+fn delimiter_score(lines: &[&str], delimiter: &str) -> usize {
+    let mut frequency: HashMap<usize, usize> = HashMap::new();
+    for line in lines {
+        let count = split_fields(line, delimiter).len() - 1;
+        *frequency.entry(count).or_default() += 1;
+    }
+    frequency
+        .into_iter()
+        .filter(|&(count, _)| count > 0)
+        .map(|(count, n_lines)| n_lines * count * delimiter.len())
+        .max()
+        .unwrap_or(0)
+}
+
+// ─── Multi-Character Delimiters ──────────────────────────────────────────────
+//
+// Polars' CSV reader only takes a single-byte separator. Other delimiters
+// (e.g. `||` in log exports) go through this simpler reader: split each line,
+// build String columns, and let the numeric auto-detection offer casts. The
+// whole file is parsed into memory, so the last result is cached until the
+// file or the options change (a preview refresh scans the source several times).
+
+/// Whether `delimiter` needs `read_delimited` instead of the Polars scan.
+pub fn needs_tokenizer(delimiter: &str) -> bool {
+    delimiter.len() != 1
+}
+
+/// Split one line on `delimiter`, ignoring delimiters inside double quotes and
+/// removing the quotes around quoted fields. An empty delimiter never splits.
+pub fn split_fields<'a>(line: &'a str, delimiter: &str) -> Vec<&'a str> {
+    let mut fields = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    let mut i = 0;
+    while i < line.len() {
+        if line.as_bytes()[i] == b'"' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes
+            && !delimiter.is_empty()
+            && line.as_bytes()[i..].starts_with(delimiter.as_bytes())
+        {
+            // Byte comparison: `i` may be inside a multi-byte character, but a
+            // match always starts on a character boundary.
+            fields.push(unquote(&line[start..i]));
+            i += delimiter.len();
+            start = i;
+            continue;
+        }
+        i += 1;
+    }
+    fields.push(unquote(&line[start..]));
+    fields
+}
+
+fn unquote(field: &str) -> &str {
+    field
+        .strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+/// What a cached `read_delimited` result was parsed from.
+#[derive(PartialEq)]
+struct DelimitedKey {
+    path: PathBuf,
+    modified: Option<std::time::SystemTime>,
+    len: u64,
+    options: CsvOptions,
+}
+
+/// Last file parsed by `read_delimited`.
+static DELIMITED_CACHE: std::sync::Mutex<Option<(DelimitedKey, DataFrame)>> =
+    std::sync::Mutex::new(None);
+
+/// Read a delimited text file into String columns. Without a header row the
+/// columns are named `column_1`, `column_2`, ... like the Polars reader.
+/// Short lines are padded with nulls, empty fields are null, and a line with
+/// more fields than the first is an error (as with the Polars reader).
+/// The result is reused while the file's size, modification time and
+/// `options` stay the same.
+pub fn read_delimited(path: &Path, options: &CsvOptions) -> Result<DataFrame, PolarsError> {
+    let metadata = std::fs::metadata(path)?;
+    let key = DelimitedKey {
+        path: path.to_path_buf(),
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+        options: options.clone(),
+    };
+    // A poisoned lock only means another thread panicked mid-parse: start over
+    let mut cache = DELIMITED_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, df)) = cache.as_ref().filter(|(cached, _)| *cached == key) {
+        return Ok(df.clone());
+    }
+    let df = parse_delimited(path, options)?;
+    *cache = Some((key, df.clone()));
+    Ok(df)
+}
+
+fn parse_delimited(path: &Path, options: &CsvOptions) -> Result<DataFrame, PolarsError> {
+    let delimiter = options.delimiter.as_str();
+    let text = std::fs::read_to_string(path)?;
+    let mut lines = text
+        .lines()
+        .skip(options.skip_rows)
+        .filter(|l| !l.is_empty())
+        .peekable();
+    let Some(&first) = lines.peek() else {
+        return Err(PolarsError::NoData("empty file".into()));
+    };
+    let header = split_fields(first, delimiter);
+    let header = if options.has_header {
+        lines.next();
+        header
+    } else {
+        vec![""; header.len()]
+    };
+    let first_line = options.skip_rows + usize::from(options.has_header) + 1;
+    let mut names: Vec<String> = Vec::new();
+    for (i, name) in header.into_iter().enumerate() {
+        let name = if name.is_empty() {
+            format!("column_{}", i + 1)
+        } else {
+            name.to_string()
+        };
+        // Same suffix as Polars for repeated header names
+        if names.contains(&name) {
+            names.push(format!("{}_duplicated_{}", name, i));
+        } else {
+            names.push(name);
+        }
+    }
+
+    let mut values: Vec<Vec<Option<String>>> = vec![Vec::new(); names.len()];
+    for (n, line) in lines.enumerate() {
+        let fields = split_fields(line, delimiter);
+        if fields.len() > names.len() {
+            return Err(PolarsError::ComputeError(
+                format!(
+                    "line {}: expected {} fields, found {}",
+                    n + first_line,
+                    names.len(),
+                    fields.len()
+                )
+                .into(),
+            ));
+        }
+        for (j, column) in values.iter_mut().enumerate() {
+            let field = fields.get(j).copied().unwrap_or_default();
+            column.push((!field.is_empty()).then(|| field.to_string()));
+        }
+    }
+
+    let mut columns = names
+        .iter()
+        .zip(values)
+        .map(|(name, column)| Column::new(name.as_str().into(), column));
+    let Some(first) = columns.next() else {
+        return Err(PolarsError::NoData("no columns".into()));
+    };
+    let mut df = first.into_frame();
+    for column in columns {
+        df.with_column(column)?;
+    }
+    Ok(df)
+}
+
+// ─── Raw Preview ─────────────────────────────────────────────────────────────
+
+/// The first `n` lines of a text file as-is (invalid UTF-8 replaced), for
+/// choosing import options before parsing.
+pub fn read_raw_lines(path: &Path, n: usize) -> Result<Vec<String>> {
+    BufReader::new(std::fs::File::open(path)?)
+        .split(b'\n')
+        .take(n)
+        .map(|line| {
+            let line = line?;
+            Ok(String::from_utf8_lossy(&line)
+                .trim_end_matches('\r')
+                .to_string())
+        })
+        .collect()
+}
+
+// ─── Sample Dataset ──────────────────────────────────────────────────────────
+
+/// Iris measurements bundled with the app, for trying it without own data.
+const SAMPLE_CSV: &str = include_str!("../../data/Iris.csv");
+
+/// Write the bundled sample dataset to the application data directory and
+/// return its path (rewritten each time, so edits never stick).
+pub fn write_sample_dataset() -> Result<PathBuf> {
+    let dir = app_data_dir().join("samples");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("Iris.csv");
+    std::fs::write(&path, SAMPLE_CSV)?;
+    Ok(path)
 }
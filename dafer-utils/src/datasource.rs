@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use polars::prelude::*;
@@ -21,6 +22,13 @@ impl std::fmt::Display for DataSourceType {
     }
 }
 
+/// Sources larger than this open in "big file" mode: statistics and plots
+/// start on a sample (`stats_sample`) instead of collecting every row.
+pub const BIG_FILE_BYTES: u64 = 1 << 30;
+
+/// Sample size used by big file mode.
+pub const BIG_FILE_SAMPLE_ROWS: usize = 100_000;
+
 /// Represents a data source file with its type.
 /// Immutable reference to the source — all transformations build on top of this.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,10 +39,83 @@ pub struct DataSource {
     /// Set after the first preview collection; applied during scan.
     #[serde(default)]
     pub auto_numeric_cols: Vec<String>,
+    /// Detected numeric String columns cast to Int64 instead of Float64
+    /// (integer-preserving mode, e.g. order numbers).
+    #[serde(default)]
+    pub auto_integer_cols: Vec<String>,
+    /// Numeric-looking columns the user chose to keep as String; not offered again.
+    #[serde(default)]
+    pub auto_cast_declined: Vec<String>,
+    /// "Fast stats" mode: compute statistics (and plots) on a sample of about
+    /// this many rows instead of the full pipeline result. `None` = full data.
+    #[serde(default)]
+    pub stats_sample: Option<usize>,
+    /// Seed for sampling, so samples are the same rows on every run and
+    /// machine. `None` = a new random sample each time.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Physical units by source column name (e.g. "°C", "kW"). Carried through
+    /// the pipeline by `operations::propagate_units`.
+    #[serde(default)]
+    pub units: BTreeMap<String, String>,
+    /// Parsing options for CSV sources (ignored for Parquet).
+    #[serde(default)]
+    pub csv: CsvOptions,
+    /// File was larger than `BIG_FILE_BYTES` when opened.
+    #[serde(default)]
+    pub big_file: bool,
+    /// Preview rows taken from row groups across the file instead of its head
+    /// (Parquet only, see `data_loader::scan_parquet_spread`).
+    #[serde(default)]
+    pub spread_preview: bool,
+}
+
+/// How a CSV/TSV file is parsed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CsvOptions {
+    /// One or more characters; anything other than a single byte is read
+    /// with `data_loader::read_delimited`.
+    pub delimiter: String,
+    /// First (non-skipped) line holds the column names.
+    pub has_header: bool,
+    /// Lines skipped before the header (titles, export banners).
+    pub skip_rows: usize,
+    /// Rows read to infer column types; `None` reads the whole file (exact,
+    /// but a second full pass on large files).
+    pub infer_schema_length: Option<usize>,
+    /// Slower parsing with a smaller peak memory footprint.
+    pub low_memory: bool,
+    /// Merge parsed chunks into contiguous memory after reading.
+    pub rechunk: bool,
+    /// Rows parsed per batch.
+    pub chunk_size: usize,
+}
+
+/// Rows read for type inference unless configured otherwise.
+pub const DEFAULT_INFER_SCHEMA_LENGTH: usize = 10_000;
+
+/// Polars' default CSV batch size.
+pub const DEFAULT_CHUNK_SIZE: usize = 1 << 18;
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ",".to_string(),
+            has_header: true,
+            skip_rows: 0,
+            infer_schema_length: Some(DEFAULT_INFER_SCHEMA_LENGTH),
+            low_memory: false,
+            rechunk: false,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
 }
 
 impl DataSource {
-    /// Create a DataSource from a file path, inferring the type from the extension.
+    /// Create a DataSource from a file path, inferring the type from the extension
+    /// and, for CSV, the delimiter from the first KB of the file. Big files
+    /// start with fast stats enabled.
     /// Returns `None` if the extension is not recognized.
     pub fn from_path(path: PathBuf) -> Option<Self> {
         let ext = path.extension()?.to_str()?.to_ascii_lowercase();
@@ -43,10 +124,26 @@ impl DataSource {
             "parquet" | "pq" => DataSourceType::Parquet,
             _ => return None,
         };
+        let csv = match source_type {
+            DataSourceType::Csv => CsvOptions {
+                delimiter: data_loader::sniff_delimiter(&path).unwrap_or_else(|_| ",".to_string()),
+                ..CsvOptions::default()
+            },
+            DataSourceType::Parquet => CsvOptions::default(),
+        };
+        let big_file = std::fs::metadata(&path).is_ok_and(|m| m.len() > BIG_FILE_BYTES);
         Some(Self {
             path,
             source_type,
             auto_numeric_cols: Vec::new(),
+            auto_integer_cols: Vec::new(),
+            auto_cast_declined: Vec::new(),
+            stats_sample: big_file.then_some(BIG_FILE_SAMPLE_ROWS),
+            seed: None,
+            units: BTreeMap::new(),
+            csv,
+            big_file,
+            spread_preview: false,
         })
     }
 
@@ -54,19 +151,51 @@ impl DataSource {
     /// Automatically applies numeric casts for detected numeric String columns.
     pub fn scan(&self) -> Result<LazyFrame, PolarsError> {
         let path_str = self.path.to_str().unwrap_or_default();
-        let mut lf = match self.source_type {
-            DataSourceType::Csv => data_loader::scan_csv(path_str)?,
+        let lf = match self.source_type {
+            DataSourceType::Csv if data_loader::needs_tokenizer(&self.csv.delimiter) => {
+                data_loader::read_delimited(&self.path, &self.csv)?.lazy()
+            }
+            DataSourceType::Csv => data_loader::scan_csv(path_str, &self.csv)?,
+            #[cfg(feature = "parquet")]
             DataSourceType::Parquet => data_loader::scan_parquet(path_str)?,
+            #[cfg(not(feature = "parquet"))]
+            DataSourceType::Parquet => {
+                return Err(PolarsError::ComputeError(
+                    "Parquet support is not compiled in (`parquet` feature)".into(),
+                ));
+            }
         };
-        // Auto-cast detected numeric String columns to Float64
-        if !self.auto_numeric_cols.is_empty() {
-            let exprs: Vec<Expr> = self
-                .auto_numeric_cols
-                .iter()
-                .map(|name| col(name.as_str()).cast(DataType::Float64))
-                .collect();
+        Ok(self.with_auto_casts(lf))
+    }
+
+    /// True if the preview is taken from across the file (see `spread_preview`).
+    pub fn previews_spread(&self) -> bool {
+        self.spread_preview && self.source_type == DataSourceType::Parquet
+    }
+
+    /// Scan about `n` rows from row groups across a Parquet source, with the
+    /// same automatic casts as `scan`.
+    #[cfg(feature = "parquet")]
+    pub fn scan_spread(&self, n: usize) -> anyhow::Result<LazyFrame> {
+        let lf = data_loader::scan_parquet_spread(&self.path, n)?;
+        Ok(self.with_auto_casts(lf))
+    }
+
+    /// Auto-cast confirmed numeric String columns to Float64 / Int64.
+    fn with_auto_casts(&self, mut lf: LazyFrame) -> LazyFrame {
+        let exprs: Vec<Expr> = self
+            .auto_numeric_cols
+            .iter()
+            .map(|name| col(name.as_str()).cast(DataType::Float64))
+            .chain(
+                self.auto_integer_cols
+                    .iter()
+                    .map(|name| col(name.as_str()).cast(DataType::Int64)),
+            )
+            .collect();
+        if !exprs.is_empty() {
             lf = lf.with_columns(exprs);
         }
-        Ok(lf)
+        lf
     }
 }
@@ -0,0 +1,268 @@
+//! Display module: presentation-only settings for the preview table.
+//!
+//! Nothing here affects the pipeline or exports — these settings only change
+//! how cached cell strings are rendered.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// ─── Binary Values ────────────────────────────────────────────────────────────
+
+/// Lowercase hex of a binary value, `0x`-prefixed (used by the table and by
+/// CSV export so both show the same text).
+pub fn hex_bytes(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for b in bytes {
+        hex.push_str(&format!("{b:02x}"));
+    }
+    hex
+}
+
+/// Short hex preview of a binary value: the first `max_bytes` bytes plus the
+/// total size when it is cut.
+pub fn hex_preview(bytes: &[u8], max_bytes: usize) -> String {
+    if bytes.len() <= max_bytes {
+        hex_bytes(bytes)
+    } else {
+        format!(
+            "{}… ({} bytes)",
+            hex_bytes(&bytes[..max_bytes]),
+            bytes.len()
+        )
+    }
+}
+
+// ─── Sizes ────────────────────────────────────────────────────────────────────
+
+/// Byte count in decimal units: `512 bytes`, `1.5 KB`, `12.34 MB`, `1.20 GB`.
+pub fn format_bytes(size: u64) -> String {
+    if size > 1_000_000_000 {
+        format!("{:.2} GB", size as f64 / 1_000_000_000.0)
+    } else if size > 1_000_000 {
+        format!("{:.2} MB", size as f64 / 1_000_000.0)
+    } else if size > 1_000 {
+        format!("{:.1} KB", size as f64 / 1_000.0)
+    } else {
+        format!("{} bytes", size)
+    }
+}
+
+// ─── Conditional Formatting ───────────────────────────────────────────────────
+
+/// A per-column conditional formatting rule, evaluated during cell rendering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FormatRule {
+    /// Shade numeric cells on a low → high color scale.
+    ColorScale,
+    /// Highlight empty (null) cells.
+    HighlightNulls,
+    /// Highlight numeric values below `min` or above `max`.
+    OutsideRange { min: f64, max: f64 },
+}
+
+impl FormatRule {
+    /// Returns true if both rules are of the same kind (ignoring parameters).
+    pub fn same_kind(&self, other: &FormatRule) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl fmt::Display for FormatRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatRule::ColorScale => write!(f, "Color scale"),
+            FormatRule::HighlightNulls => write!(f, "Highlight nulls"),
+            FormatRule::OutsideRange { min, max } => write!(f, "Outside [{}, {}]", min, max),
+        }
+    }
+}
+
+// ─── Display Config ───────────────────────────────────────────────────────────
+
+/// Serializable table display settings, keyed by column name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    pub column_rules: BTreeMap<String, Vec<FormatRule>>,
+    /// Columns left out of the table view (still in the pipeline and exports).
+    #[serde(default)]
+    pub hidden_columns: BTreeSet<String>,
+}
+
+impl DisplayConfig {
+    /// Formatting rules for a column (empty if none are configured).
+    pub fn rules(&self, column: &str) -> &[FormatRule] {
+        self.column_rules
+            .get(column)
+            .map(|r| r.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns true if the column has a rule of the same kind as `rule`.
+    pub fn has_rule(&self, column: &str, rule: &FormatRule) -> bool {
+        self.rules(column).iter().any(|r| r.same_kind(rule))
+    }
+
+    /// Add a rule to a column, replacing any existing rule of the same kind.
+    pub fn set_rule(&mut self, column: &str, rule: FormatRule) {
+        let rules = self.column_rules.entry(column.to_string()).or_default();
+        rules.retain(|r| !r.same_kind(&rule));
+        rules.push(rule);
+    }
+
+    /// Remove the rule of the same kind as `rule` from a column.
+    pub fn remove_rule(&mut self, column: &str, rule: &FormatRule) {
+        if let Some(rules) = self.column_rules.get_mut(column) {
+            rules.retain(|r| !r.same_kind(rule));
+            if rules.is_empty() {
+                self.column_rules.remove(column);
+            }
+        }
+    }
+
+    /// Remove all formatting rules from a column.
+    pub fn clear_column(&mut self, column: &str) {
+        self.column_rules.remove(column);
+    }
+
+    /// Returns true if the column is hidden from the table view.
+    pub fn is_hidden(&self, column: &str) -> bool {
+        self.hidden_columns.contains(column)
+    }
+
+    /// Hide or show a column in the table view.
+    pub fn set_hidden(&mut self, column: &str, hidden: bool) {
+        if hidden {
+            self.hidden_columns.insert(column.to_string());
+        } else {
+            self.hidden_columns.remove(column);
+        }
+    }
+}
+
+// ─── Dock Layout ──────────────────────────────────────────────────────────────
+
+/// A panel that can be placed in the dock layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DockPanel {
+    Pipeline,
+    Builder,
+    Export,
+    Table,
+    Stats,
+    Plot,
+}
+
+impl DockPanel {
+    pub fn all() -> &'static [DockPanel] {
+        &[
+            DockPanel::Pipeline,
+            DockPanel::Builder,
+            DockPanel::Export,
+            DockPanel::Table,
+            DockPanel::Stats,
+            DockPanel::Plot,
+        ]
+    }
+}
+
+impl fmt::Display for DockPanel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DockPanel::Pipeline => write!(f, "Pipeline"),
+            DockPanel::Builder => write!(f, "Operation Builder"),
+            DockPanel::Export => write!(f, "Export"),
+            DockPanel::Table => write!(f, "Table"),
+            DockPanel::Stats => write!(f, "Statistics"),
+            DockPanel::Plot => write!(f, "Plot"),
+        }
+    }
+}
+
+/// Where a dock panel is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockRegion {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+    Hidden,
+}
+
+impl DockRegion {
+    pub fn all() -> &'static [DockRegion] {
+        &[
+            DockRegion::Left,
+            DockRegion::Right,
+            DockRegion::Top,
+            DockRegion::Bottom,
+            DockRegion::Center,
+            DockRegion::Hidden,
+        ]
+    }
+}
+
+impl fmt::Display for DockRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DockRegion::Left => write!(f, "Left"),
+            DockRegion::Right => write!(f, "Right"),
+            DockRegion::Top => write!(f, "Top"),
+            DockRegion::Bottom => write!(f, "Bottom"),
+            DockRegion::Center => write!(f, "Center"),
+            DockRegion::Hidden => write!(f, "Hidden"),
+        }
+    }
+}
+
+/// Assignment of dock panels to regions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DockLayout {
+    pub regions: Vec<(DockPanel, DockRegion)>,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self {
+            regions: vec![
+                (DockPanel::Pipeline, DockRegion::Left),
+                (DockPanel::Builder, DockRegion::Left),
+                (DockPanel::Export, DockRegion::Left),
+                (DockPanel::Table, DockRegion::Center),
+                (DockPanel::Stats, DockRegion::Bottom),
+                (DockPanel::Plot, DockRegion::Right),
+            ],
+        }
+    }
+}
+
+impl DockLayout {
+    /// Region of a panel (panels missing from the layout are hidden).
+    pub fn region(&self, panel: DockPanel) -> DockRegion {
+        self.regions
+            .iter()
+            .find(|(p, _)| *p == panel)
+            .map(|(_, r)| *r)
+            .unwrap_or(DockRegion::Hidden)
+    }
+
+    /// Move a panel to a region.
+    pub fn set_region(&mut self, panel: DockPanel, region: DockRegion) {
+        match self.regions.iter_mut().find(|(p, _)| *p == panel) {
+            Some(entry) => entry.1 = region,
+            None => self.regions.push((panel, region)),
+        }
+    }
+
+    /// Panels placed in a region, in `DockPanel::all()` order.
+    pub fn panels_in(&self, region: DockRegion) -> Vec<DockPanel> {
+        DockPanel::all()
+            .iter()
+            .copied()
+            .filter(|p| self.region(*p) == region)
+            .collect()
+    }
+}
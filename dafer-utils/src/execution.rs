@@ -4,60 +4,887 @@
 //! For very large datasets, consider streaming exports (sink_parquet/sink_csv)
 //! which can be added as a future optimization.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use crate::data_loader;
 use crate::datasource::DataSource;
+use crate::display;
 use crate::operations::Operation;
 use crate::query_engine;
+use crate::redaction::{RedactionAudit, RedactionProfile};
+
+/// Name of the per-row hash column appended by `ExportOptions::row_hash`.
+pub const ROW_HASH_COLUMN: &str = "_row_sha256";
+
+/// Integrity extras for full pipeline exports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Append `ROW_HASH_COLUMN`: SHA-256 (hex) of each row's cells as written
+    /// to CSV, joined by the unit separator (`\x1f`).
+    pub row_hash: bool,
+    /// Write a `<file>.manifest.json` sidecar with row count, schema and the
+    /// SHA-256 of the exported file.
+    pub manifest: bool,
+    /// Write a `<file>.provenance.json` sidecar next to CSV exports. Parquet
+    /// exports always carry provenance in their key-value metadata.
+    pub provenance: bool,
+    /// Redaction profile applied to the result before writing. Runs before the
+    /// row hash, so hashes match the cells as written.
+    pub redaction: Option<RedactionProfile>,
+    /// Write to `<file>.partial` and rename it over the target once complete,
+    /// so an interrupted export never leaves a truncated file.
+    pub atomic: bool,
+    /// Split the result into numbered files (`out_0001.csv`, `out_0002.csv`,
+    /// ...) of at most this size, each with its own sidecars.
+    pub split: Option<SplitLimit>,
+    /// Write a `<stem>_summary.csv` with the column statistics of the exported
+    /// result (one file for the whole export, split or not).
+    pub summary: bool,
+    /// Round floats in CSV files to this many decimals. `None` writes the
+    /// shortest text that reads back as the same value.
+    pub float_precision: Option<usize>,
+}
+
+/// Maximum size of each file of a split export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitLimit {
+    Rows(usize),
+    /// Approximate. CSV parts are cut once the written cells reach the size;
+    /// Parquet parts are sized from the in-memory result, so compression
+    /// makes them smaller.
+    Megabytes(usize),
+}
+
+impl SplitLimit {
+    fn bytes(mb: usize) -> usize {
+        mb.saturating_mul(1_000_000)
+    }
+}
+
+/// Parquet key-value metadata key holding the provenance JSON.
+pub const PROVENANCE_KEY: &str = "dafer_utils.provenance";
+
+/// Where an exported file came from: enough to re-run the pipeline and check
+/// that the input has not changed since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    pub source: String,
+    /// SHA-256 (hex) of the source file at export time.
+    pub source_sha256: String,
+    pub operations: Vec<Operation>,
+    pub app_version: String,
+    /// Local time with offset (RFC 3339).
+    pub exported_at: String,
+    /// Redaction profile applied at export, if any.
+    #[serde(default)]
+    pub redaction: Option<RedactionAudit>,
+    /// Sampling seed of the source (see `DataSource::seed`).
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl Provenance {
+    pub fn new(
+        source: &DataSource,
+        operations: &[Operation],
+        redaction: Option<RedactionAudit>,
+    ) -> Result<Self> {
+        Ok(Self {
+            source: source.path.display().to_string(),
+            source_sha256: file_sha256(&source.path)?,
+            operations: operations.to_vec(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: chrono::Local::now().to_rfc3339(),
+            redaction,
+            seed: source.seed,
+        })
+    }
+
+    /// Compare the recorded fingerprint with the source file as it is now.
+    pub fn source_status(&self) -> SourceStatus {
+        match file_sha256(Path::new(&self.source)) {
+            Ok(sha) if sha == self.source_sha256 => SourceStatus::Unchanged,
+            Ok(_) => SourceStatus::Changed,
+            Err(_) => SourceStatus::Missing,
+        }
+    }
+}
+
+/// State of a provenance record's source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceStatus {
+    Unchanged,
+    /// Exists but its SHA-256 differs from the one recorded at export.
+    Changed,
+    /// Missing or unreadable.
+    Missing,
+}
+
+/// Provenance of a previously exported file: the Parquet key-value metadata,
+/// or the `.provenance.json` sidecar for other files. `None` if there is none.
+pub fn read_provenance(path: &Path) -> Result<Option<Provenance>> {
+    #[cfg(feature = "parquet")]
+    let is_parquet = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "parquet" | "pq"));
+    #[cfg(feature = "parquet")]
+    if is_parquet {
+        let mut reader = ParquetReader::new(std::fs::File::open(path)?);
+        let metadata = reader
+            .get_metadata()
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let value = metadata
+            .key_value_metadata
+            .iter()
+            .flatten()
+            .find(|kv| kv.key == PROVENANCE_KEY)
+            .and_then(|kv| kv.value.as_deref());
+        return match value {
+            Some(json) => Ok(Some(serde_json::from_str(json)?)),
+            None => Ok(None),
+        };
+    }
+    match std::fs::read_to_string(provenance_path(path)) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sidecar manifest describing an exported file.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    file: String,
+    sha256: String,
+    row_count: usize,
+    columns: Vec<ManifestColumn>,
+    row_hash_column: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestColumn {
+    name: String,
+    dtype: String,
+}
+
+/// Columns of the pipeline result that a CSV file cannot represent exactly,
+/// one message each. Empty if a CSV round-trip keeps everything.
+pub fn csv_export_warnings(
+    source: &DataSource,
+    operations: &[Operation],
+    options: &ExportOptions,
+) -> Result<Vec<String>> {
+    let schema = query_engine::build_lazy(source, operations)?
+        .collect_schema()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(schema
+        .iter()
+        .filter_map(|(name, dtype)| {
+            csv_loss(dtype, options.float_precision).map(|loss| format!("{}: {}", name, loss))
+        })
+        .collect())
+}
+
+/// What a column of this type loses when written as CSV text. Floats are
+/// written exactly unless a precision is set.
+fn csv_loss(dtype: &DataType, float_precision: Option<usize>) -> Option<String> {
+    match dtype {
+        DataType::Float32 | DataType::Float64 => {
+            float_precision.map(|p| format!("floats are rounded to {} decimals", p))
+        }
+        DataType::Datetime(_, Some(tz)) => Some(format!(
+            "time zone {} is only kept as text; readers load plain strings",
+            tz
+        )),
+        DataType::List(_) => Some("list values are written as strings".to_string()),
+        DataType::Struct(_) => Some("struct values are written as strings".to_string()),
+        DataType::Binary => Some("bytes are written as hex text".to_string()),
+        _ => None,
+    }
+}
 
 /// Export the full pipeline result as a CSV file.
 /// Uses the `csv` crate for writing to avoid requiring extra Polars feature flags.
-pub fn export_csv(source: &DataSource, operations: &[Operation], path: &Path) -> Result<()> {
-    let df = query_engine::execute(source, operations)?;
+/// Returns the files written (more than one when `options.split` is set).
+pub fn export_csv(
+    source: &DataSource,
+    operations: &[Operation],
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<Vec<PathBuf>> {
+    let frame = ExportFrame::collect(source, operations, options)?;
+    let files = frame.write_csv(path)?;
+    frame.write_summary(path)?;
+    Ok(files)
+}
+
+/// Export the full pipeline result as a Parquet file.
+/// Uses Polars' built-in ParquetWriter (columnar, compressed, schema-preserving).
+/// Provenance is stored under `PROVENANCE_KEY` in the file's key-value metadata.
+#[cfg(feature = "parquet")]
+pub fn export_parquet(
+    source: &DataSource,
+    operations: &[Operation],
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<Vec<PathBuf>> {
+    let mut frame = ExportFrame::collect(source, operations, options)?;
+    let files = frame.write_parquet(path)?;
+    frame.write_summary(path)?;
+    Ok(files)
+}
+
+/// Export the full pipeline result as both `<path>.csv` and `<path>.parquet`
+/// (replacing any extension of `path`), running the pipeline once. Returns
+/// the CSV files written, then the Parquet files.
+#[cfg(feature = "parquet")]
+pub fn export_csv_and_parquet(
+    source: &DataSource,
+    operations: &[Operation],
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<Vec<PathBuf>> {
+    let mut frame = ExportFrame::collect(source, operations, options)?;
+    let mut files = frame.write_csv(&path.with_extension("csv"))?;
+    files.extend(frame.write_parquet(&path.with_extension("parquet"))?);
+    frame.write_summary(path)?;
+    Ok(files)
+}
+
+/// A collected pipeline result with the export options applied, ready to be
+/// written in any number of formats without running the pipeline again.
+pub(crate) struct ExportFrame<'a> {
+    source: &'a DataSource,
+    operations: &'a [Operation],
+    options: &'a ExportOptions,
+    df: DataFrame,
+    redaction: Option<RedactionAudit>,
+}
+
+impl<'a> ExportFrame<'a> {
+    /// Collect the full pipeline, then apply the redaction profile and append
+    /// the row hash column if requested.
+    pub fn collect(
+        source: &'a DataSource,
+        operations: &'a [Operation],
+        options: &'a ExportOptions,
+    ) -> Result<Self> {
+        let mut df = query_engine::execute(source, operations)?;
+        let mut redaction = None;
+        if let Some(profile) = &options.redaction {
+            let (redacted, applied) = profile.apply(df)?;
+            df = redacted;
+            redaction = Some(applied);
+        }
+        if options.row_hash {
+            let hashes: Vec<String> = (0..df.height())
+                .map(|i| {
+                    let mut hasher = Sha256::new();
+                    for (j, col) in df.get_columns().iter().enumerate() {
+                        if j > 0 {
+                            hasher.update([0x1f]);
+                        }
+                        let cell = col
+                            .get(i)
+                            .map(|v| format_csv_value(&v, options.float_precision))
+                            .unwrap_or_default();
+                        hasher.update(cell.as_bytes());
+                    }
+                    format!("{:x}", hasher.finalize())
+                })
+                .collect();
+            df.with_column(Column::new(ROW_HASH_COLUMN.into(), hashes))
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+        }
+        Ok(Self {
+            source,
+            operations,
+            options,
+            df,
+            redaction,
+        })
+    }
+
+    fn provenance(&self) -> Result<Provenance> {
+        Provenance::new(self.source, self.operations, self.redaction.clone())
+    }
+
+    /// Write the column statistics of the result to `summary_path(path)` if
+    /// `options.summary` is set.
+    pub fn write_summary(&self, path: &Path) -> Result<()> {
+        if !self.options.summary {
+            return Ok(());
+        }
+        let rows = self.df.height();
+        let headers: Vec<String> = [
+            "column",
+            "dtype",
+            "rows",
+            "null_count",
+            "null_pct",
+            "distinct",
+            "min",
+            "max",
+            "mode",
+            "avg_length",
+            "true_count",
+            "false_count",
+            "span",
+        ]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let grid: Vec<Vec<String>> = data_loader::column_stats(&self.df)
+            .iter()
+            .map(|stats| {
+                let null_pct = if rows == 0 {
+                    0.0
+                } else {
+                    stats.null_count as f64 / rows as f64 * 100.0
+                };
+                vec![
+                    stats.name.clone(),
+                    stats.dtype.clone(),
+                    rows.to_string(),
+                    stats.null_count.to_string(),
+                    format!("{:.2}", null_pct),
+                    optional(&stats.distinct.map(|d| d.to_string())),
+                    optional(&stats.min),
+                    optional(&stats.max),
+                    optional(&stats.mode),
+                    optional(&stats.avg_length.map(|l| format!("{:.2}", l))),
+                    optional(&stats.bool_counts.map(|(t, _)| t.to_string())),
+                    optional(&stats.bool_counts.map(|(_, f)| f.to_string())),
+                    optional(&stats.span),
+                ]
+            })
+            .collect();
+        write_file(&summary_path(path), self.options.atomic, |file| {
+            let mut writer = csv::Writer::from_writer(file);
+            write_grid(&mut writer, &headers, &grid)?;
+            writer.flush()?;
+            Ok(())
+        })
+    }
+
+    /// Write as CSV (plus the provenance sidecar and manifest if requested),
+    /// split into numbered parts if `options.split` is set.
+    pub fn write_csv(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        // Hashes the whole source, so done once for all parts
+        let provenance = if self.options.provenance {
+            Some(serde_json::to_string_pretty(&self.provenance()?)?)
+        } else {
+            None
+        };
+        if self.options.split.is_none() {
+            self.write_csv_part(path, 0, provenance.as_deref())?;
+            return Ok(vec![path.to_path_buf()]);
+        }
+        let mut files = Vec::new();
+        let mut start = 0;
+        // An empty result still gets one (header-only) part
+        while start < self.df.height() || files.is_empty() {
+            let part = part_path(path, files.len() + 1);
+            start = self.write_csv_part(&part, start, provenance.as_deref())?;
+            files.push(part);
+        }
+        Ok(files)
+    }
+
+    /// Write rows from `start` until the split limit (if any) is reached and
+    /// return the index of the first row not written. Rows are formatted one
+    /// at a time, so a part is cut without buffering it. `provenance` is the
+    /// sidecar JSON, if requested.
+    fn write_csv_part(&self, path: &Path, start: usize, provenance: Option<&str>) -> Result<usize> {
+        let df = &self.df;
+        let mut end = start;
+        write_file(path, self.options.atomic, |file| {
+            let mut writer = csv::Writer::from_writer(file);
+
+            // Write header
+            let headers: Vec<String> = df
+                .get_column_names()
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            writer.write_record(&headers)?;
+
+            // Write rows; every part gets at least one so it always advances
+            let mut bytes = 0;
+            for i in start..df.height() {
+                let full = match self.options.split {
+                    Some(SplitLimit::Rows(n)) => i - start >= n,
+                    Some(SplitLimit::Megabytes(mb)) => bytes >= SplitLimit::bytes(mb),
+                    None => false,
+                };
+                if full && i > start {
+                    break;
+                }
+                let precision = self.options.float_precision;
+                let row: Vec<String> = df
+                    .get_columns()
+                    .iter()
+                    .map(|col| {
+                        col.get(i)
+                            .map(|v| format_csv_value(&v, precision))
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                // Cells plus separators and newline; quoting is not counted
+                bytes += row.iter().map(|c| c.len() + 1).sum::<usize>();
+                writer.write_record(&row)?;
+                end = i + 1;
+            }
+
+            writer.flush()?;
+            Ok(())
+        })?;
+        if let Some(provenance) = provenance {
+            std::fs::write(provenance_path(path), provenance)?;
+        }
+        finish_export(&df.slice(start as i64, end - start), path, self.options)?;
+        Ok(end)
+    }
+
+    /// Write as Parquet with the provenance in its key-value metadata (plus the
+    /// manifest if requested), split into numbered parts if `options.split` is set.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet(&mut self, path: &Path) -> Result<Vec<PathBuf>> {
+        let provenance = serde_json::to_string(&self.provenance()?)?;
+        let height = self.df.height();
+        let rows = match self.options.split {
+            None => {
+                write_parquet_file(&mut self.df, path, &provenance, self.options)?;
+                return Ok(vec![path.to_path_buf()]);
+            }
+            Some(SplitLimit::Rows(n)) => n.max(1),
+            Some(SplitLimit::Megabytes(mb)) => {
+                let row_bytes = (self.df.estimated_size() / height.max(1)).max(1);
+                (SplitLimit::bytes(mb) / row_bytes).max(1)
+            }
+        };
+        let mut files = Vec::new();
+        let mut start = 0;
+        while start < height || files.is_empty() {
+            let part = part_path(path, files.len() + 1);
+            let mut df = self.df.slice(start as i64, rows);
+            write_parquet_file(&mut df, &part, &provenance, self.options)?;
+            files.push(part);
+            start += rows;
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet_file(
+    df: &mut DataFrame,
+    path: &Path,
+    provenance: &str,
+    options: &ExportOptions,
+) -> Result<()> {
+    write_file(path, options.atomic, |file| {
+        ParquetWriter::new(file)
+            .with_key_value_metadata(Some(KeyValueMetadata::from_static(vec![(
+                PROVENANCE_KEY.to_string(),
+                provenance.to_string(),
+            )])))
+            .finish(df)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(())
+    })?;
+    finish_export(df, path, options)
+}
+
+/// Create or replace `table` in the DuckDB database at `db_path` (created if
+/// missing) with the pipeline result. The result is staged as a temporary
+/// Parquet file and loaded with `read_parquet`, so column types carry over;
+/// the provenance JSON is stored as the table comment.
+#[cfg(feature = "duckdb")]
+pub fn export_duckdb(
+    source: &DataSource,
+    operations: &[Operation],
+    db_path: &Path,
+    table: &str,
+    options: &ExportOptions,
+) -> Result<()> {
+    use crate::sql::{ident, literal};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let mut frame = ExportFrame::collect(source, operations, options)?;
+    let provenance = serde_json::to_string(&frame.provenance()?)?;
+    // Unique per export, so concurrent exports of one process never share it
+    static STAGED: AtomicUsize = AtomicUsize::new(0);
+    let staged = std::env::temp_dir().join(format!(
+        "dafer-utils-{}-{}.parquet",
+        std::process::id(),
+        STAGED.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = (|| -> Result<()> {
+        ParquetWriter::new(std::fs::File::create(&staged)?)
+            .finish(&mut frame.df)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let conn =
+            duckdb::Connection::open(db_path).map_err(|e| anyhow::anyhow!("DuckDB: {}", e))?;
+        conn.execute_batch(&format!(
+            "CREATE OR REPLACE TABLE {table} AS SELECT * FROM read_parquet({file});\n\
+             COMMENT ON TABLE {table} IS {comment};",
+            table = ident(table),
+            file = literal(&staged.display().to_string()),
+            comment = literal(&provenance),
+        ))
+        .map_err(|e| anyhow::anyhow!("DuckDB: {}", e))
+    })();
+    let _ = std::fs::remove_file(&staged);
+    result
+}
+
+/// `path` with `.ext` appended unless it already ends in it (any case), so
+/// `report` and `report.v2` become `report.csv` and `report.v2.csv`.
+pub fn with_extension(path: &Path, ext: &str) -> PathBuf {
+    let has_ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext));
+    if has_ext {
+        return path.to_path_buf();
+    }
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
+
+/// Path of part `index` (from 1) of a split export: `out.csv` → `out_0001.csv`.
+pub fn part_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    path.with_file_name(format!("{}_{:04}{}", stem, index, ext))
+}
+
+/// First of `name (1).ext`, `name (2).ext`, ... that does not exist yet.
+pub fn next_free_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{} ({}){}", stem, n, ext)))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Create `path` and fill it with `write`. Atomic writes go to a `.partial`
+/// file next to it that is renamed over `path` only once `write` succeeded,
+/// and removed if it failed.
+pub(crate) fn write_file(
+    path: &Path,
+    atomic: bool,
+    write: impl FnOnce(std::fs::File) -> Result<()>,
+) -> Result<()> {
+    if !atomic {
+        return write(std::fs::File::create(path)?);
+    }
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let result = std::fs::File::create(&partial)
+        .map_err(anyhow::Error::from)
+        .and_then(write)
+        .and_then(|()| std::fs::rename(&partial, path).map_err(anyhow::Error::from));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+/// Path of the manifest written next to an export (`data.csv` → `data.csv.manifest.json`).
+pub fn manifest_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".manifest.json");
+    PathBuf::from(name)
+}
+
+/// Path of the column summary written next to an export
+/// (`data.csv` → `data_summary.csv`).
+pub fn summary_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}_summary.csv", stem))
+}
+
+/// Path of the provenance sidecar (`data.csv` → `data.csv.provenance.json`).
+pub fn provenance_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".provenance.json");
+    PathBuf::from(name)
+}
+
+/// Write the sidecar manifest once the export file is complete.
+fn finish_export(df: &DataFrame, path: &Path, options: &ExportOptions) -> Result<()> {
+    if !options.manifest {
+        return Ok(());
+    }
+    let manifest = Manifest {
+        file: path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        sha256: file_sha256(path)?,
+        row_count: df.height(),
+        columns: df
+            .schema()
+            .iter()
+            .map(|(name, dtype)| ManifestColumn {
+                name: name.to_string(),
+                dtype: dtype.to_string(),
+            })
+            .collect(),
+        row_hash_column: options.row_hash.then(|| ROW_HASH_COLUMN.to_string()),
+    };
+    let json = serde_json::to_string_pretty(&manifest)?;
+    std::fs::write(manifest_path(path), json)?;
+    Ok(())
+}
+
+/// SHA-256 (hex) of a file's contents.
+fn file_sha256(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Write a pre-formatted string grid (e.g. the preview table exactly as displayed) as CSV.
+/// Unlike `export_csv`, this does not run the pipeline — it writes the given cells verbatim.
+pub fn export_grid_csv(headers: &[String], rows: &[Vec<String>], path: &Path) -> Result<()> {
     let file = std::fs::File::create(path)?;
     let mut writer = csv::Writer::from_writer(file);
+    write_grid(&mut writer, headers, rows)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Render a pre-formatted string grid as CSV text (e.g. for the clipboard).
+pub fn grid_to_csv_string(headers: &[String], rows: &[Vec<String>]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    write_grid(&mut writer, headers, rows)?;
+    let bytes = writer.into_inner().map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+// ─── Markdown / HTML Tables ───────────────────────────────────────────────────
 
-    // Write header
-    let headers: Vec<String> = df
+/// First `n` rows of the pipeline result as a GitHub-flavored Markdown table.
+pub fn markdown_table(source: &DataSource, operations: &[Operation], n: u32) -> Result<String> {
+    let (headers, rows) = result_grid(source, operations, n)?;
+    let escape = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+    let mut out = format!(
+        "| {} |\n|{}\n",
+        headers
+            .iter()
+            .map(|h| escape(h))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        " --- |".repeat(headers.len())
+    );
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| escape(c)).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    Ok(out)
+}
+
+/// First `n` rows of the pipeline result as an HTML `<table>`.
+pub fn html_table(source: &DataSource, operations: &[Operation], n: u32) -> Result<String> {
+    let (headers, rows) = result_grid(source, operations, n)?;
+    let mut out = String::from("<table>\n  <thead>\n    <tr>");
+    for h in &headers {
+        out.push_str(&format!("<th>{}</th>", html_escape(h)));
+    }
+    out.push_str("</tr>\n  </thead>\n  <tbody>\n");
+    for row in rows {
+        out.push_str("    <tr>");
+        for cell in row {
+            out.push_str(&format!("<td>{}</td>", html_escape(&cell)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("  </tbody>\n</table>\n");
+    Ok(out)
+}
+
+/// Escape text for HTML element content and quoted attribute values.
+pub(crate) fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// First `n` rows of the pipeline result as strings (nulls empty).
+fn result_grid(
+    source: &DataSource,
+    operations: &[Operation],
+    n: u32,
+) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+    let df = query_engine::preview(source, operations, n)?;
+    let headers = df
         .get_column_names()
         .iter()
         .map(|s| s.to_string())
         .collect();
-    writer.write_record(&headers)?;
+    let rows = (0..df.height())
+        .map(|i| {
+            df.get_columns()
+                .iter()
+                .map(|col| col.get(i).map(|v| format_any_value(&v)).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    Ok((headers, rows))
+}
 
-    // Write rows
-    for i in 0..df.height() {
-        let row: Vec<String> = df
-            .get_columns()
-            .iter()
-            .map(|col| col.get(i).map(|v| format_any_value(&v)).unwrap_or_default())
-            .collect();
-        writer.write_record(&row)?;
-    }
+// ─── Open Externally ──────────────────────────────────────────────────────────
 
-    writer.flush()?;
+/// Export the full result to a timestamped CSV in the temp directory and open
+/// it with the OS default application (usually a spreadsheet). Returns the path.
+pub fn open_externally(source: &DataSource, operations: &[Operation]) -> Result<PathBuf> {
+    let stem = source
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "result".to_string());
+    let path = std::env::temp_dir().join(format!(
+        "{}-{}.csv",
+        stem,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    export_csv(source, operations, &path, &ExportOptions::default())?;
+    open_path(&path)?;
+    Ok(path)
+}
+
+/// Open a file with the OS default application.
+pub(crate) fn open_path(path: &Path) -> Result<()> {
+    use std::process::Command;
+
+    let mut command = if cfg!(windows) {
+        // The empty argument is `start`'s window title
+        let mut c = Command::new("cmd");
+        c.args(["/C", "start", ""]);
+        c
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    let mut child = command.arg(path).spawn()?;
+    std::thread::spawn(move || child.wait());
     Ok(())
 }
 
-/// Export the full pipeline result as a Parquet file.
-/// Uses Polars' built-in ParquetWriter (columnar, compressed, schema-preserving).
-pub fn export_parquet(source: &DataSource, operations: &[Operation], path: &Path) -> Result<()> {
-    let mut df = query_engine::execute(source, operations)?;
-    let file = std::fs::File::create(path)?;
-    ParquetWriter::new(file)
-        .finish(&mut df)
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+// ─── Completion Hook ──────────────────────────────────────────────────────────
+//
+// An optional user command run after each full export (success or failure), so
+// exports can feed existing alerting. A webhook is a command too, e.g.
+// `curl -s -X POST -H 'Content-Type: application/json' -d @- https://...`.
+
+/// Outcome of an export, passed to the completion hook as JSON on stdin.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    pub success: bool,
+    pub output: String,
+    pub source: Option<String>,
+    pub operations: usize,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Start `command` through the system shell with the summary JSON on stdin and
+/// `DAFER_RUN_STATUS` set to `success`/`failure`. Does not wait for it to finish.
+pub fn run_hook(command: &str, summary: &RunSummary) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let json = serde_json::to_vec_pretty(summary)?;
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .args([flag, command])
+        .env(
+            "DAFER_RUN_STATUS",
+            if summary.success {
+                "success"
+            } else {
+                "failure"
+            },
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin may exit before reading it
+        let _ = stdin.write_all(&json);
+    }
+    // Reap the process in the background
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn write_grid<W: std::io::Write>(
+    writer: &mut csv::Writer<W>,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> Result<()> {
+    writer.write_record(headers)?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
     Ok(())
 }
 
 /// Format an AnyValue for CSV output.
-/// Null values become empty strings (standard CSV convention).
-fn format_any_value(v: &AnyValue) -> String {
+/// Null values become empty strings (standard CSV convention); binary values are hex.
+/// Floats are written in full: Polars' display rounds them to six decimals.
+pub(crate) fn format_any_value(v: &AnyValue) -> String {
     match v {
         AnyValue::Null => String::new(),
+        AnyValue::Float32(f) => f.to_string(),
+        AnyValue::Float64(f) => f.to_string(),
+        AnyValue::Binary(b) => display::hex_bytes(b),
+        AnyValue::BinaryOwned(b) => display::hex_bytes(b),
         other => other.to_string(),
     }
 }
+
+/// `format_any_value` with floats rounded to `float_precision` decimals, if set.
+fn format_csv_value(v: &AnyValue, float_precision: Option<usize>) -> String {
+    match (v, float_precision) {
+        (AnyValue::Float32(f), Some(p)) => format!("{:.*}", p, f),
+        (AnyValue::Float64(f), Some(p)) => format!("{:.*}", p, f),
+        _ => format_any_value(v),
+    }
+}
@@ -0,0 +1,177 @@
+//! Fixture module: expected output pinned to a pipeline, for regression checks.
+//!
+//! A fixture records a SHA-256 over the pipeline result (its first rows or all
+//! of it) and keeps the first rows as text. It is saved with the state file;
+//! verifying re-runs the pipeline and lists what differs.
+
+use anyhow::Result;
+use chrono::Local;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::datasource::DataSource;
+use crate::execution::format_any_value;
+use crate::operations::Operation;
+use crate::query_engine;
+
+/// Rows kept as text for reporting cell differences.
+pub const GOLDEN_ROWS: usize = 200;
+
+/// Differences listed in a report before the rest are summarized.
+const MAX_DIFFERENCES: usize = 20;
+
+/// Expected output of a pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    /// Rows covered: the first `n`, or the whole result (`None`).
+    pub rows: Option<usize>,
+    pub columns: Vec<String>,
+    pub row_count: usize,
+    /// SHA-256 (hex) of the covered rows, cells as written to CSV.
+    pub sha256: String,
+    /// First `GOLDEN_ROWS` covered rows as text.
+    pub golden: Vec<Vec<String>>,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`.
+    pub pinned_at: String,
+}
+
+/// Outcome of verifying a pipeline against its fixture.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureReport {
+    pub differences: Vec<String>,
+}
+
+impl FixtureReport {
+    pub fn passed(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl Fixture {
+    /// Pin the current output of the pipeline.
+    pub fn pin(source: &DataSource, operations: &[Operation], rows: Option<usize>) -> Result<Self> {
+        let df = covered_rows(source, operations, rows)?;
+        let grid = text_rows(&df);
+        Ok(Self {
+            rows,
+            columns: column_names(&df),
+            row_count: df.height(),
+            sha256: hash_rows(&grid),
+            golden: grid.into_iter().take(GOLDEN_ROWS).collect(),
+            pinned_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        })
+    }
+
+    /// Re-run the pipeline and compare with the pinned output.
+    pub fn verify(&self, source: &DataSource, operations: &[Operation]) -> Result<FixtureReport> {
+        let df = covered_rows(source, operations, self.rows)?;
+        let grid = text_rows(&df);
+        let mut report = FixtureReport::default();
+        if hash_rows(&grid) == self.sha256 {
+            return Ok(report);
+        }
+        let diffs = &mut report.differences;
+
+        let columns = column_names(&df);
+        if columns != self.columns {
+            for name in self.columns.iter().filter(|c| !columns.contains(c)) {
+                diffs.push(format!("Column missing: {}", name));
+            }
+            for name in columns.iter().filter(|c| !self.columns.contains(c)) {
+                diffs.push(format!("Unexpected column: {}", name));
+            }
+            if diffs.is_empty() {
+                diffs.push("Columns are in a different order".to_string());
+            }
+        }
+        if df.height() != self.row_count {
+            diffs.push(format!(
+                "Row count: expected {}, got {}",
+                self.row_count,
+                df.height()
+            ));
+        }
+
+        // Cell differences in the kept rows, matched by column name
+        let mut cell_diffs = 0;
+        for (i, (expected, actual)) in self.golden.iter().zip(&grid).enumerate() {
+            for (j, name) in self.columns.iter().enumerate() {
+                let Some(k) = columns.iter().position(|c| c == name) else {
+                    continue;
+                };
+                let (want, got) = (&expected[j], &actual[k]);
+                if want != got {
+                    cell_diffs += 1;
+                    if cell_diffs <= MAX_DIFFERENCES {
+                        diffs.push(format!(
+                            "Row {}, {}: expected {:?}, got {:?}",
+                            i + 1,
+                            name,
+                            want,
+                            got
+                        ));
+                    }
+                }
+            }
+        }
+        if cell_diffs > MAX_DIFFERENCES {
+            diffs.push(format!(
+                "... and {} more cell differences",
+                cell_diffs - MAX_DIFFERENCES
+            ));
+        }
+        if diffs.is_empty() {
+            diffs.push(format!(
+                "Output differs after row {} (only the first {} rows are kept for comparison)",
+                self.golden.len(),
+                GOLDEN_ROWS
+            ));
+        }
+        Ok(report)
+    }
+}
+
+fn covered_rows(
+    source: &DataSource,
+    operations: &[Operation],
+    rows: Option<usize>,
+) -> Result<DataFrame> {
+    match rows {
+        Some(n) => query_engine::preview(source, operations, n as u32),
+        None => query_engine::execute(source, operations),
+    }
+}
+
+fn column_names(df: &DataFrame) -> Vec<String> {
+    df.get_column_names()
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn text_rows(df: &DataFrame) -> Vec<Vec<String>> {
+    (0..df.height())
+        .map(|i| {
+            df.get_columns()
+                .iter()
+                .map(|col| col.get(i).map(|v| format_any_value(&v)).unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
+
+/// Cells joined by the unit separator, rows by the record separator.
+fn hash_rows(rows: &[Vec<String>]) -> String {
+    let mut hasher = Sha256::new();
+    for row in rows {
+        for (j, cell) in row.iter().enumerate() {
+            if j > 0 {
+                hasher.update([0x1f]);
+            }
+            hasher.update(cell.as_bytes());
+        }
+        hasher.update([0x1e]);
+    }
+    format!("{:x}", hasher.finalize())
+}
@@ -0,0 +1,284 @@
+//! Help module: parameter documentation for each operation.
+//!
+//! Plain static text so every frontend shows the same explanations. Longer
+//! reference material (format codes, regex syntax) lives in `Topic`s that
+//! operations link to.
+
+use crate::operations::OperationType;
+
+// ─── Operation Help ───────────────────────────────────────────────────────────
+
+/// Explanation of one operation builder.
+#[derive(Debug, Clone, Copy)]
+pub struct OperationHelp {
+    pub summary: &'static str,
+    /// (parameter, explanation)
+    pub params: &'static [(&'static str, &'static str)],
+    pub examples: &'static [&'static str],
+    /// Reference tables relevant to this operation.
+    pub topics: &'static [Topic],
+}
+
+/// Help for an operation type.
+pub fn operation_help(op: OperationType) -> OperationHelp {
+    match op {
+        OperationType::Filter => OperationHelp {
+            summary: "Keep only the rows matching a condition.",
+            params: &[
+                ("Column", "Column the condition is checked on."),
+                (
+                    "Operator",
+                    "= ≠ > ≥ < ≤ compare values; contains matches a regex; is null / is not null need no value; between takes two bounds.",
+                ),
+                (
+                    "Value",
+                    "Literal to compare with. Dates are typed as YYYY-MM-DD. With \"value is a column\" it names another column.",
+                ),
+                (
+                    "Options",
+                    "Case-insensitive compares strings ignoring case; null-safe makes null = null match.",
+                ),
+            ],
+            examples: &[
+                "price > 100",
+                "city contains ^San (starts with \"San\")",
+                "shipped ≥ 2024-01-01",
+            ],
+            topics: &[Topic::Regex],
+        },
+        OperationType::Sort => OperationHelp {
+            summary: "Order the rows by one column.",
+            params: &[
+                ("Column", "Column to sort by."),
+                ("Descending", "Largest values first. Nulls always go last."),
+            ],
+            examples: &["revenue, descending"],
+            topics: &[],
+        },
+        OperationType::DropColumn => OperationHelp {
+            summary: "Remove a column from the result.",
+            params: &[("Column", "Column to remove.")],
+            examples: &[],
+            topics: &[],
+        },
+        OperationType::RenameColumn => OperationHelp {
+            summary: "Give a column a new name. Later steps use the new name.",
+            params: &[
+                ("Column", "Column to rename."),
+                ("New name", "Must not clash with another column."),
+            ],
+            examples: &["cust_id → customer_id"],
+            topics: &[],
+        },
+        OperationType::SelectColumns => OperationHelp {
+            summary: "Keep only the chosen columns, in the chosen order.",
+            params: &[("Columns", "Columns to keep; all others are dropped.")],
+            examples: &[],
+            topics: &[],
+        },
+        OperationType::Limit => OperationHelp {
+            summary: "Keep only the first N rows.",
+            params: &[("Rows", "Number of rows to keep.")],
+            examples: &["Sort by revenue descending, then limit 10 = top 10"],
+            topics: &[],
+        },
+        OperationType::Skip => OperationHelp {
+            summary: "Drop the first N rows and keep the rest.",
+            params: &[("Rows", "Number of rows to drop from the top.")],
+            examples: &["Skip 3 = drop the notes above the real data"],
+            topics: &[],
+        },
+        OperationType::Tail => OperationHelp {
+            summary: "Keep only the last N rows.",
+            params: &[("Rows", "Number of rows to keep from the end.")],
+            examples: &["Tail 100 = the end of a log file, without sorting"],
+            topics: &[],
+        },
+        OperationType::FillNull => OperationHelp {
+            summary: "Replace missing values.",
+            params: &[
+                ("Column", "Column whose nulls are filled."),
+                (
+                    "Strategy",
+                    "How the replacement is chosen, see Fill strategies.",
+                ),
+                ("Value", "Replacement for the \"with value\" strategy."),
+                (
+                    "Over",
+                    "Group columns: fill within each group (e.g. forward-fill per sensor).",
+                ),
+            ],
+            examples: &["temperature, forward, over sensor_id"],
+            topics: &[Topic::FillStrategies],
+        },
+        OperationType::CastColumn => OperationHelp {
+            summary: "Convert a column to another type.",
+            params: &[
+                ("Column", "Column to convert."),
+                (
+                    "Target type",
+                    "Values that cannot be converted become null.",
+                ),
+                (
+                    "Quarantine",
+                    "Optional column that keeps the original text of the values that failed.",
+                ),
+            ],
+            examples: &["\"12.5\" → Float64", "\"abc\" → Float64 gives null"],
+            topics: &[],
+        },
+        OperationType::ParseDatetime => OperationHelp {
+            summary: "Turn a text column into a datetime using a format string.",
+            params: &[
+                ("Column", "Text column to parse."),
+                ("Format", "strftime-style pattern, see Datetime formats."),
+            ],
+            examples: &[
+                "2024-03-01 14:30:00 → %Y-%m-%d %H:%M:%S",
+                "01/03/2024 → %d/%m/%Y",
+            ],
+            topics: &[Topic::Strptime],
+        },
+        OperationType::JsonExtract => OperationHelp {
+            summary: "Pull one field out of a JSON text column into a new column.",
+            params: &[
+                ("Column", "Column holding JSON text."),
+                ("Path", "JSONPath to the field, starting at $."),
+                ("Output", "Name of the new column."),
+                ("Output type", "Type of the extracted values."),
+            ],
+            examples: &["$.user.id", "$.items[0].price"],
+            topics: &[Topic::JsonPath],
+        },
+        OperationType::Round => OperationHelp {
+            summary: "Round a numeric column in the data itself, not only for display.",
+            params: &[
+                ("Column", "Numeric column to round."),
+                ("Decimals", "Digits kept after the decimal point."),
+                (
+                    "Mode",
+                    "Half to even: 2.5 → 2 (unbiased). Half away from zero: 2.5 → 3. Floor / ceil always round down / up.",
+                ),
+            ],
+            examples: &["3.14159, 2 decimals → 3.14"],
+            topics: &[],
+        },
+        OperationType::Normalize => OperationHelp {
+            summary: "Rescale a numeric column.",
+            params: &[
+                ("Column", "Numeric column to rescale."),
+                (
+                    "Method",
+                    "Z-score: (x − mean) / std. Min-max: into [0, 1]. Robust: (x − median) / IQR, for data with outliers.",
+                ),
+            ],
+            examples: &[],
+            topics: &[],
+        },
+        OperationType::Window => OperationHelp {
+            summary: "Compute a running or ranking value for every row into a new column.",
+            params: &[
+                ("Column", "Column the function runs over, in row order."),
+                (
+                    "Function",
+                    "Cumulative sum / min / max, difference to the previous row, or rank (1 = smallest, or largest when descending).",
+                ),
+                (
+                    "Over",
+                    "Group columns: restart the computation for each group.",
+                ),
+                ("Output", "Name of the new column."),
+            ],
+            examples: &["amount, cumulative sum, over customer → running total per customer"],
+            topics: &[],
+        },
+        OperationType::Coalesce => OperationHelp {
+            summary: "Take the first non-null value across several columns.",
+            params: &[
+                ("Columns", "Checked in the given order."),
+                ("Output", "Name of the new column."),
+            ],
+            examples: &["mobile, home, work → phone"],
+            topics: &[],
+        },
+        OperationType::DateRange => OperationHelp {
+            summary: "Keep the rows whose date lies within a range.",
+            params: &[
+                ("Column", "Date or datetime column."),
+                (
+                    "From / To",
+                    "Bounds with an optional time; untick one for an open-ended range.",
+                ),
+            ],
+            examples: &[],
+            topics: &[],
+        },
+    }
+}
+
+// ─── Reference Topics ─────────────────────────────────────────────────────────
+
+/// Reference table shared by several operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Strptime,
+    Regex,
+    FillStrategies,
+    JsonPath,
+}
+
+impl Topic {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Topic::Strptime => "Datetime formats",
+            Topic::Regex => "Regex syntax",
+            Topic::FillStrategies => "Fill strategies",
+            Topic::JsonPath => "JSONPath",
+        }
+    }
+
+    /// (syntax, meaning) rows.
+    pub fn entries(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Topic::Strptime => &[
+                ("%Y", "4-digit year (2024)"),
+                ("%y", "2-digit year (24)"),
+                ("%m", "month 01-12"),
+                ("%b", "abbreviated month (Mar)"),
+                ("%d", "day 01-31"),
+                ("%H", "hour 00-23"),
+                ("%I / %p", "hour 01-12 / AM-PM"),
+                ("%M", "minute 00-59"),
+                ("%S", "second 00-60"),
+                ("%.f", "fractional seconds (.123)"),
+                ("%z", "UTC offset (+0100)"),
+                ("%j", "day of year 001-366"),
+                ("%%", "a literal %"),
+            ],
+            Topic::Regex => &[
+                (".", "any character"),
+                ("^ / $", "start / end of the text"),
+                ("\\d \\w \\s", "digit, word character, whitespace"),
+                ("[abc] [^abc]", "one of / none of the characters"),
+                ("* + ?", "0 or more, 1 or more, optional"),
+                ("{2,4}", "between 2 and 4 repetitions"),
+                ("a|b", "a or b"),
+                ("(?i)", "ignore case from here on"),
+                ("\\.", "a literal dot (escape special characters)"),
+            ],
+            Topic::FillStrategies => &[
+                ("forward", "last non-null value above"),
+                ("backward", "next non-null value below"),
+                ("with value", "a fixed value you type"),
+                ("mean", "average of the column (numbers only)"),
+                ("min / max", "smallest / largest value of the column"),
+            ],
+            Topic::JsonPath => &[
+                ("$", "the whole document"),
+                ("$.a.b", "field b inside field a"),
+                ("$.items[0]", "first element of an array"),
+                ("$['odd key']", "field whose name has spaces or dots"),
+            ],
+        }
+    }
+}
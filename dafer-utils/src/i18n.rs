@@ -0,0 +1,119 @@
+//! I18n module: translations of UI strings.
+//!
+//! The English text is the key: `tr(lang, "Open...")` returns the translation
+//! for `lang`, or the English text itself when there is none. Frontends wrap
+//! their labels with `tr`, so a missing entry shows English instead of a blank.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// ─── Language ─────────────────────────────────────────────────────────────────
+
+/// UI language, chosen in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub fn all() -> &'static [Language] {
+        &[Language::English, Language::Spanish]
+    }
+}
+
+/// Shown in the language's own name, so it can be found when the UI is unreadable.
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Spanish => write!(f, "Español"),
+        }
+    }
+}
+
+// ─── Translation ──────────────────────────────────────────────────────────────
+
+/// Translate an English UI string.
+pub fn tr(lang: Language, text: &str) -> &str {
+    let translated = match lang {
+        Language::English => None,
+        Language::Spanish => spanish(text),
+    };
+    translated.unwrap_or(text)
+}
+
+fn spanish(text: &str) -> Option<&'static str> {
+    let translated = match text {
+        // Menus
+        "File" => "Archivo",
+        "Open..." => "Abrir...",
+        "Save State..." => "Guardar estado...",
+        "Load State..." => "Cargar estado...",
+        "Apply Pipeline from State..." => "Aplicar pipeline desde estado...",
+        "Copy Pipeline" => "Copiar pipeline",
+        "Copy Pipeline as Base64" => "Copiar pipeline en base64",
+        "Paste Pipeline..." => "Pegar pipeline...",
+        "Export Bundle..." => "Exportar paquete...",
+        "Bookmarks..." => "Marcadores...",
+        "Quick Open..." => "Apertura rápida...",
+        "Save Workspace..." => "Guardar espacio de trabajo...",
+        "Open Workspace..." => "Abrir espacio de trabajo...",
+        "Action History..." => "Historial de acciones...",
+        "Notes..." => "Notas...",
+        "Exit" => "Salir",
+        "Edit" => "Editar",
+        "Undo" => "Deshacer",
+        "Redo" => "Rehacer",
+        "Undo View Change" => "Deshacer cambio de vista",
+        "Redo View Change" => "Rehacer cambio de vista",
+        "Clear Pipeline" => "Vaciar pipeline",
+        "Revert to Last Saved" => "Volver a lo último guardado",
+        "Snapshots" => "Instantáneas",
+        "Result Snapshots" => "Instantáneas de resultados",
+        "Expected Output" => "Salida esperada",
+        "Performance..." => "Rendimiento...",
+        "Lock Pipeline" => "Bloquear pipeline",
+        "Reset Auto-Cast Choices" => "Restablecer conversiones automáticas",
+        "Language" => "Idioma",
+        "Analysis" => "Análisis",
+        "Crosstab..." => "Tabla cruzada...",
+        "Mixed-Type Columns..." => "Columnas de tipo mixto...",
+        "About" => "Acerca de",
+
+        // Operation builder
+        "Add Operation" => "Añadir operación",
+        "Operation" => "Operación",
+        "Column" => "Columna",
+        "Operator" => "Operador",
+        "Strategy" => "Estrategia",
+        "Target type" => "Tipo destino",
+        "Output type" => "Tipo de salida",
+        "Mode" => "Modo",
+        "Method" => "Método",
+        "Function" => "Función",
+
+        // Operation types
+        "Filter" => "Filtrar",
+        "Sort" => "Ordenar",
+        "Drop Column" => "Eliminar columna",
+        "Rename Column" => "Renombrar columna",
+        "Select Columns" => "Seleccionar columnas",
+        "Limit Rows" => "Limitar filas",
+        "Skip Rows" => "Omitir filas",
+        "Last Rows" => "Últimas filas",
+        "Fill Null" => "Rellenar nulos",
+        "Cast Column Type" => "Cambiar tipo de columna",
+        "Parse Datetime" => "Interpretar fecha/hora",
+        "Extract JSON Field" => "Extraer campo JSON",
+        "Round Numbers" => "Redondear números",
+        "Normalize / Scale" => "Normalizar / escalar",
+        "Window (cumulative / rank)" => "Ventana (acumulado / rango)",
+        "Coalesce Columns" => "Combinar columnas (primer no nulo)",
+        "Date Range Filter" => "Filtro por rango de fechas",
+        _ => return None,
+    };
+    Some(translated)
+}
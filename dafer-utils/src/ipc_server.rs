@@ -0,0 +1,106 @@
+//! IPC server module: serves the pipeline result as an Arrow IPC stream on localhost.
+//!
+//! Every connection runs the currently published pipeline in full and writes
+//! the result as one Arrow IPC stream, then closes. From Python:
+//!
+//! ```text
+//! import socket, pyarrow as pa
+//! with socket.create_connection(("127.0.0.1", 8815)) as s:
+//!     table = pa.ipc.open_stream(s.makefile("rb")).read_all()
+//! ```
+//!
+//! Only the loopback interface is bound; there is no authentication.
+
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use polars::prelude::*;
+
+use crate::datasource::DataSource;
+use crate::operations::Operation;
+use crate::query_engine;
+
+/// Port used when none is configured.
+pub const DEFAULT_PORT: u16 = 8815;
+
+type Published = Arc<Mutex<Option<(DataSource, Vec<Operation>)>>>;
+
+/// Running server; stops when dropped.
+pub struct IpcServer {
+    addr: SocketAddr,
+    published: Published,
+    stop: Arc<AtomicBool>,
+}
+
+impl IpcServer {
+    /// Bind `127.0.0.1:port` (0 picks a free port) and start accepting.
+    pub fn start(port: u16) -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let addr = listener.local_addr()?;
+        let published: Published = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let (shared, stopped) = (published.clone(), stop.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let pipeline = shared.lock().ok().and_then(|p| p.clone());
+                // One thread per client so a slow reader does not block others
+                std::thread::spawn(move || {
+                    let _ = serve(stream, pipeline);
+                });
+            }
+        });
+        Ok(Self {
+            addr,
+            published,
+            stop,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Replace the pipeline served to the next connections.
+    pub fn publish(&self, source: &DataSource, operations: &[Operation]) {
+        if let Ok(mut published) = self.published.lock() {
+            *published = Some((source.clone(), operations.to_vec()));
+        }
+    }
+
+    /// Serve nothing (connections are closed immediately).
+    pub fn clear(&self) {
+        if let Ok(mut published) = self.published.lock() {
+            *published = None;
+        }
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn serve(mut stream: TcpStream, pipeline: Option<(DataSource, Vec<Operation>)>) -> Result<()> {
+    let Some((source, operations)) = pipeline else {
+        return Ok(());
+    };
+    let mut df = query_engine::execute(&source, &operations)?;
+    IpcStreamWriter::new(&mut stream)
+        .finish(&mut df)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    stream.flush()?;
+    Ok(())
+}
@@ -1,6 +1,82 @@
-pub mod data_loader;
+//! Data pipeline engine behind the dafer-utils frontends.
+//!
+//! The crate has no UI dependencies: a pipeline is a `DataSource` plus a list
+//! of `Operation`s, run lazily with Polars. External tools can embed it:
+//!
+//! ```no_run
+//! use dafer_utils::prelude::*;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let state = PersistentState::load("pipeline.dfr".as_ref())?;
+//! let source = state.source.expect("saved pipeline has a source");
+//! let head = preview(&source, &state.operations, 10)?;
+//! println!("{}", head);
+//! export_csv(&source, &state.operations, "out.csv".as_ref(), &ExportOptions::default())?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `prelude` is the stable surface: the source (`datasource`), the
+//! operations (`operations`), running them (`query_engine`), writing results
+//! (`execution`) and saved pipelines (`persistence`). Breaking changes to
+//! those items bump the minor version while the crate is 0.x. That includes
+//! the types their fields use from other modules (redaction profiles,
+//! fixtures, plot and display settings of workspaces), which the prelude
+//! re-exports. The rest of those modules (settings, help text, translations,
+//! plot descriptions) serves the frontends, is hidden from the docs and may
+//! change with them.
+//!
+//! Cargo features: `parquet` (default) reads and writes Parquet; without it
+//! the engine is CSV-only and Parquet sources fail to scan. `duckdb`,
+//! `ipc-server`, `approx-distinct` and `streaming` are opt-in extras.
+
+// ─── Stable Surface ──────────────────────────────────────────────────────────
+
 pub mod datasource;
 pub mod execution;
 pub mod operations;
 pub mod persistence;
+pub mod prelude;
 pub mod query_engine;
+
+// ─── Frontend Support ────────────────────────────────────────────────────────
+//
+// Public only so the app (and the fuzz targets) can reach them; hidden from
+// the docs and not covered by the stability promise above, except for the
+// types the prelude re-exports.
+
+#[doc(hidden)]
+pub mod action_log;
+#[doc(hidden)]
+pub mod analysis;
+#[doc(hidden)]
+pub mod bundle;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod data_loader;
+#[doc(hidden)]
+pub mod display;
+#[doc(hidden)]
+pub mod fixture;
+#[doc(hidden)]
+pub mod help;
+#[doc(hidden)]
+pub mod i18n;
+#[cfg(feature = "ipc-server")]
+#[doc(hidden)]
+pub mod ipc_server;
+#[doc(hidden)]
+pub mod lint;
+#[doc(hidden)]
+pub mod mapping;
+#[doc(hidden)]
+pub mod memory;
+#[doc(hidden)]
+pub mod plot;
+#[doc(hidden)]
+pub mod redaction;
+#[doc(hidden)]
+pub mod snapshot;
+#[doc(hidden)]
+pub mod sql;
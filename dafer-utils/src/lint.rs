@@ -0,0 +1,199 @@
+//! Pipeline linter: spots step orders that waste work or do nothing, and
+//! proposes a fix (a move or a removal) that the UI can apply in one click.
+//!
+//! Suggestions are advisory. A reordering can change results (e.g. filtering
+//! before a limit keeps different rows), so each message says what it affects.
+
+use std::collections::HashSet;
+
+use crate::operations::{FillNullStrategy, Operation};
+
+/// One-click fix for a suggestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fix {
+    /// Move the step at `from` so it runs at position `to` (`to < from`).
+    Move { from: usize, to: usize },
+    /// Remove the step at this index.
+    Remove(usize),
+}
+
+impl Fix {
+    /// Apply the fix to the pipeline it was computed for.
+    pub fn apply(&self, operations: &mut Vec<Operation>) {
+        match *self {
+            Fix::Move { from, to } => {
+                if from < operations.len() && to <= from {
+                    let op = operations.remove(from);
+                    operations.insert(to, op);
+                }
+            }
+            Fix::Remove(idx) => {
+                if idx < operations.len() {
+                    operations.remove(idx);
+                }
+            }
+        }
+    }
+}
+
+/// A lint finding about the step at `step` (0-based).
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub step: usize,
+    pub message: String,
+    pub fix: Fix,
+}
+
+/// Check the top-level steps of a pipeline (macros count as one step).
+pub fn lint(operations: &[Operation]) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    for (i, op) in operations.iter().enumerate() {
+        match op {
+            Operation::RenameColumn { from, to } if from == to => suggestions.push(Suggestion {
+                step: i,
+                message: format!("Step {}: renames {} to itself", i + 1, from),
+                fix: Fix::Remove(i),
+            }),
+            Operation::Filter { .. } => {
+                if let Some(limit) = operations[..i]
+                    .iter()
+                    .position(|op| matches!(op, Operation::Limit(_)))
+                {
+                    suggestions.push(Suggestion {
+                        step: i,
+                        message: format!(
+                            "Step {}: filter runs after the limit in step {}, so it only sees \
+                             the first rows and cannot be pushed into the scan",
+                            i + 1,
+                            limit + 1
+                        ),
+                        fix: Fix::Move { from: i, to: limit },
+                    });
+                }
+            }
+            Operation::CastColumn { column, .. } => {
+                if let Some(filter) = first_filter_on(operations, i, column) {
+                    suggestions.push(Suggestion {
+                        step: i,
+                        message: format!(
+                            "Step {}: {} is cast after it is filtered in step {}; casting \
+                             first lets the filter compare typed values",
+                            i + 1,
+                            column,
+                            filter + 1
+                        ),
+                        fix: Fix::Move {
+                            from: i,
+                            to: filter,
+                        },
+                    });
+                }
+            }
+            Operation::SelectColumns(columns) => {
+                let earliest = earliest_select_position(operations, i, columns);
+                if earliest < i {
+                    suggestions.push(Suggestion {
+                        step: i,
+                        message: format!(
+                            "Step {}: selecting columns earlier (before step {}) reads less data",
+                            i + 1,
+                            earliest + 1
+                        ),
+                        fix: Fix::Move {
+                            from: i,
+                            to: earliest,
+                        },
+                    });
+                }
+            }
+            Operation::Sort { .. } => {
+                if let Some(next) = overriding_sort(operations, i) {
+                    suggestions.push(Suggestion {
+                        step: i,
+                        message: format!(
+                            "Step {}: sort is overridden by the sort in step {}",
+                            i + 1,
+                            next + 1
+                        ),
+                        fix: Fix::Remove(i),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    suggestions
+}
+
+/// Columns a step reads.
+fn reads(op: &Operation) -> Vec<String> {
+    let mut op = op.clone();
+    let refs = op.column_refs_mut();
+    refs.iter().map(|c| c.to_string()).collect()
+}
+
+/// Does the step rename, drop or (re)create `column`?
+fn touches(op: &Operation, column: &str) -> bool {
+    match op {
+        Operation::RenameColumn { from, to } => from == column || to == column,
+        Operation::DropColumn(name) => name == column,
+        Operation::SelectColumns(columns) => !columns.iter().any(|c| c == column),
+        Operation::Macro { .. } => reads(op).iter().any(|c| c == column),
+        op => op.created_column() == Some(column),
+    }
+}
+
+/// Earliest filter on `column` before step `cast`, if nothing in between
+/// renames, drops or recreates the column.
+fn first_filter_on(operations: &[Operation], cast: usize, column: &str) -> Option<usize> {
+    let mut found = None;
+    for (j, op) in operations[..cast].iter().enumerate().rev() {
+        if touches(op, column) {
+            break;
+        }
+        if matches!(op, Operation::Filter { column: c, .. } if c == column) {
+            found = Some(j);
+        }
+    }
+    found
+}
+
+/// How far back a SelectColumns can move: past steps that only read selected
+/// columns and create none.
+fn earliest_select_position(operations: &[Operation], select: usize, columns: &[String]) -> usize {
+    let kept: HashSet<&str> = columns.iter().map(|c| c.as_str()).collect();
+    let mut position = select;
+    for (j, op) in operations[..select].iter().enumerate().rev() {
+        let movable = op.created_column().is_none()
+            && !matches!(op, Operation::Macro { .. } | Operation::SelectColumns(_))
+            && reads(op).iter().all(|c| kept.contains(c.as_str()));
+        if !movable {
+            break;
+        }
+        position = j;
+    }
+    position
+}
+
+/// A later sort that replaces the order of the sort at `sort`, with no
+/// order-dependent step in between.
+fn overriding_sort(operations: &[Operation], sort: usize) -> Option<usize> {
+    for (j, op) in operations.iter().enumerate().skip(sort + 1) {
+        match op {
+            Operation::Sort { .. } => return Some(j),
+            Operation::Limit(_)
+            | Operation::Skip(_)
+            | Operation::Tail(_)
+            | Operation::Window { .. }
+            | Operation::Macro { .. } => {
+                return None;
+            }
+            Operation::FillNull {
+                strategy: FillNullStrategy::Forward | FillNullStrategy::Backward,
+                ..
+            } => return None,
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,84 @@
+//! Column mapping: re-targets a saved pipeline at a source whose column names differ.
+//!
+//! Finds the columns a pipeline reads that the new source lacks, suggests the
+//! closest available names, and rewrites the operations with the chosen mapping.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::operations::Operation;
+
+/// Columns read by `operations` that are neither in `available` nor created by
+/// an earlier step, in order of first use.
+pub fn missing_columns(operations: &[Operation], available: &[String]) -> Vec<String> {
+    let mut present: HashSet<String> = available.iter().cloned().collect();
+    let mut missing = Vec::new();
+    for op in flatten(operations) {
+        let mut op = op.clone();
+        for name in op.column_refs_mut() {
+            if !present.contains(name.as_str()) && !missing.contains(&*name) {
+                missing.push(name.clone());
+            }
+        }
+        if let Some(created) = op.created_column() {
+            present.insert(created.to_string());
+        }
+    }
+    missing
+}
+
+/// Rewrite column references using `mapping` (pipeline column → new column).
+pub fn remap_columns(operations: &mut [Operation], mapping: &BTreeMap<String, String>) {
+    for op in operations {
+        for name in op.column_refs_mut() {
+            if let Some(new_name) = mapping.get(name.as_str()) {
+                *name = new_name.clone();
+            }
+        }
+    }
+}
+
+/// Closest candidate to `name`: equal after normalizing case and punctuation,
+/// or within a small edit distance. `None` if nothing is reasonably close.
+pub fn suggest_column(name: &str, candidates: &[String]) -> Option<String> {
+    let target = normalize(name);
+    let max_distance = (target.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|c| (levenshtein(&target, &normalize(c)), c))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c.clone())
+}
+
+/// Steps in execution order, with macros expanded.
+fn flatten(operations: &[Operation]) -> Vec<&Operation> {
+    operations
+        .iter()
+        .flat_map(|op| match op {
+            Operation::Macro { operations, .. } => flatten(operations),
+            op => vec![op],
+        })
+        .collect()
+}
+
+/// Lowercase alphanumerics only ("Sensor_Temp" and "sensor temp" compare equal).
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
@@ -0,0 +1,39 @@
+//! Memory module: process and DataFrame memory usage for the status bar.
+//!
+//! Both numbers are cheap to take: the resident set size is read from the OS
+//! and DataFrame sizes are Polars' estimate of their buffers, not a heap walk.
+
+use polars::prelude::DataFrame;
+
+/// Memory usage taken after a collect.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryUsage {
+    /// Resident set size of this process, in bytes (`None` where unsupported).
+    pub rss: Option<u64>,
+    /// Estimated size of the DataFrames held in memory, in bytes.
+    pub frames: usize,
+}
+
+impl MemoryUsage {
+    pub fn measure<'a>(frames: impl IntoIterator<Item = &'a DataFrame>) -> Self {
+        Self {
+            rss: process_rss(),
+            frames: frames.into_iter().map(|df| df.estimated_size()).sum(),
+        }
+    }
+}
+
+/// Resident set size of this process in bytes, from `/proc/self/status`.
+#[cfg(target_os = "linux")]
+pub fn process_rss() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Resident set size of this process (only available on Linux).
+#[cfg(not(target_os = "linux"))]
+pub fn process_rss() -> Option<u64> {
+    None
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 use polars::prelude::DataType;
@@ -16,11 +17,19 @@ pub enum FilterOp {
     Contains,
     IsNull,
     IsNotNull,
+    /// Range check in a single step. Bounds are carried by the operator,
+    /// so the filter's own `value` is unused.
+    Between {
+        low: String,
+        high: String,
+        inclusive: bool,
+    },
 }
 
 impl FilterOp {
-    pub fn all() -> &'static [FilterOp] {
-        &[
+    /// All operators, with empty inclusive bounds for `Between`.
+    pub fn all() -> Vec<FilterOp> {
+        vec![
             FilterOp::Eq,
             FilterOp::Neq,
             FilterOp::Gt,
@@ -30,12 +39,25 @@ impl FilterOp {
             FilterOp::Contains,
             FilterOp::IsNull,
             FilterOp::IsNotNull,
+            FilterOp::Between {
+                low: String::new(),
+                high: String::new(),
+                inclusive: true,
+            },
         ]
     }
 
     /// Returns true if this operator requires a value input.
     pub fn needs_value(&self) -> bool {
-        !matches!(self, FilterOp::IsNull | FilterOp::IsNotNull)
+        !matches!(
+            self,
+            FilterOp::IsNull | FilterOp::IsNotNull | FilterOp::Between { .. }
+        )
+    }
+
+    /// True if both are the same operator, ignoring `Between` bounds.
+    pub fn same_kind(&self, other: &FilterOp) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
     }
 }
 
@@ -51,6 +73,7 @@ impl fmt::Display for FilterOp {
             FilterOp::Contains => write!(f, "contains"),
             FilterOp::IsNull => write!(f, "is null"),
             FilterOp::IsNotNull => write!(f, "is not null"),
+            FilterOp::Between { .. } => write!(f, "between"),
         }
     }
 }
@@ -61,6 +84,41 @@ impl Default for FilterOp {
     }
 }
 
+/// Modifiers for a filter's comparison.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilterOptions {
+    /// Compare strings ignoring case (`=`, `≠`, `contains`).
+    pub case_insensitive: bool,
+    /// Null-safe `=` / `≠`: nulls compare as values (null = null, null ≠ x)
+    /// instead of never matching.
+    pub null_safe: bool,
+    /// The value (or `Between` bounds) names another column to compare against.
+    #[serde(default)]
+    pub value_is_column: bool,
+}
+
+impl FilterOptions {
+    /// Right-hand side for display: column references are shown in backticks.
+    pub fn rhs(&self, value: &str) -> String {
+        if self.value_is_column {
+            format!("`{}`", value)
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+impl fmt::Display for FilterOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.case_insensitive, self.null_safe) {
+            (false, false) => Ok(()),
+            (true, false) => write!(f, " [ignore case]"),
+            (false, true) => write!(f, " [null-safe]"),
+            (true, true) => write!(f, " [ignore case, null-safe]"),
+        }
+    }
+}
+
 // ─── Fill Null Strategy ───────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -109,6 +167,123 @@ impl Default for FillNullStrategy {
     }
 }
 
+// ─── Round Mode ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum RoundMode {
+    /// Banker's rounding: ties go to the even digit (2.5 → 2).
+    #[default]
+    HalfToEven,
+    /// Ties go away from zero (2.5 → 3).
+    HalfAwayFromZero,
+    /// Always down (toward −∞).
+    Floor,
+    /// Always up (toward +∞).
+    Ceil,
+}
+
+impl RoundMode {
+    pub fn all() -> &'static [RoundMode] {
+        &[
+            RoundMode::HalfToEven,
+            RoundMode::HalfAwayFromZero,
+            RoundMode::Floor,
+            RoundMode::Ceil,
+        ]
+    }
+}
+
+impl fmt::Display for RoundMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundMode::HalfToEven => write!(f, "Half to even"),
+            RoundMode::HalfAwayFromZero => write!(f, "Half away from zero"),
+            RoundMode::Floor => write!(f, "Floor"),
+            RoundMode::Ceil => write!(f, "Ceil"),
+        }
+    }
+}
+
+// ─── Normalize Method ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum NormalizeMethod {
+    /// (x − mean) / std
+    #[default]
+    ZScore,
+    /// (x − min) / (max − min), into [0, 1]
+    MinMax,
+    /// (x − median) / IQR, less sensitive to outliers
+    Robust,
+}
+
+impl NormalizeMethod {
+    pub fn all() -> &'static [NormalizeMethod] {
+        &[
+            NormalizeMethod::ZScore,
+            NormalizeMethod::MinMax,
+            NormalizeMethod::Robust,
+        ]
+    }
+}
+
+impl fmt::Display for NormalizeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NormalizeMethod::ZScore => write!(f, "Z-score"),
+            NormalizeMethod::MinMax => write!(f, "Min-max"),
+            NormalizeMethod::Robust => write!(f, "Robust (median/IQR)"),
+        }
+    }
+}
+
+// ─── Window Function ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum WindowFunction {
+    #[default]
+    CumSum,
+    CumMin,
+    CumMax,
+    /// Difference to the previous row (null on the first row).
+    Diff,
+    /// 1 for the smallest value; ties share the lowest rank.
+    Rank,
+    /// 1 for the largest value; ties share the lowest rank.
+    RankDescending,
+}
+
+impl WindowFunction {
+    pub fn all() -> &'static [WindowFunction] {
+        &[
+            WindowFunction::CumSum,
+            WindowFunction::CumMin,
+            WindowFunction::CumMax,
+            WindowFunction::Diff,
+            WindowFunction::Rank,
+            WindowFunction::RankDescending,
+        ]
+    }
+
+    /// Returns true if the result is in the same unit as the input column.
+    pub fn keeps_unit(&self) -> bool {
+        !matches!(self, WindowFunction::Rank | WindowFunction::RankDescending)
+    }
+}
+
+impl fmt::Display for WindowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunction::CumSum => write!(f, "Cumulative Sum"),
+            WindowFunction::CumMin => write!(f, "Cumulative Min"),
+            WindowFunction::CumMax => write!(f, "Cumulative Max"),
+            WindowFunction::Diff => write!(f, "Diff"),
+            WindowFunction::Rank => write!(f, "Rank"),
+            WindowFunction::RankDescending => write!(f, "Rank (descending)"),
+        }
+    }
+}
+
 // ─── Data Type Tag (serializable representation of Polars DataType) ───────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -120,6 +295,9 @@ pub enum DTypeTag {
     Utf8String,
     Boolean,
     Date,
+    UInt32,
+    UInt64,
+    Binary,
 }
 
 impl DTypeTag {
@@ -132,6 +310,9 @@ impl DTypeTag {
             DTypeTag::Utf8String => DataType::String,
             DTypeTag::Boolean => DataType::Boolean,
             DTypeTag::Date => DataType::Date,
+            DTypeTag::UInt32 => DataType::UInt32,
+            DTypeTag::UInt64 => DataType::UInt64,
+            DTypeTag::Binary => DataType::Binary,
         }
     }
 
@@ -144,6 +325,9 @@ impl DTypeTag {
             DTypeTag::Utf8String,
             DTypeTag::Boolean,
             DTypeTag::Date,
+            DTypeTag::UInt32,
+            DTypeTag::UInt64,
+            DTypeTag::Binary,
         ]
     }
 }
@@ -158,6 +342,9 @@ impl fmt::Display for DTypeTag {
             DTypeTag::Utf8String => write!(f, "String"),
             DTypeTag::Boolean => write!(f, "Boolean"),
             DTypeTag::Date => write!(f, "Date"),
+            DTypeTag::UInt32 => write!(f, "UInt32"),
+            DTypeTag::UInt64 => write!(f, "UInt64"),
+            DTypeTag::Binary => write!(f, "Binary"),
         }
     }
 }
@@ -168,6 +355,79 @@ impl Default for DTypeTag {
     }
 }
 
+// ─── Literal Kind ─────────────────────────────────────────────────────────────
+
+/// How a value typed for a column (filter value, fill value) is entered and
+/// checked, from the column's dtype as Polars displays it (`i64`, `f64`,
+/// `bool`, `date`, `datetime[μs]`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LiteralKind {
+    /// Whole number in the range of the column's integer type.
+    Integer {
+        min: i128,
+        max: i128,
+    },
+    Float,
+    Boolean,
+    Date,
+    Datetime,
+    #[default]
+    Text,
+}
+
+impl LiteralKind {
+    pub fn of_dtype(dtype: &str) -> Self {
+        let integer = |min: i128, max: i128| LiteralKind::Integer { min, max };
+        match dtype {
+            "i8" => integer(i8::MIN.into(), i8::MAX.into()),
+            "i16" => integer(i16::MIN.into(), i16::MAX.into()),
+            "i32" => integer(i32::MIN.into(), i32::MAX.into()),
+            "i64" => integer(i64::MIN.into(), i64::MAX.into()),
+            "u8" => integer(0, u8::MAX.into()),
+            "u16" => integer(0, u16::MAX.into()),
+            "u32" => integer(0, u32::MAX.into()),
+            "u64" => integer(0, u64::MAX.into()),
+            "f32" | "f64" => LiteralKind::Float,
+            "bool" => LiteralKind::Boolean,
+            "date" => LiteralKind::Date,
+            d if d.starts_with("datetime") => LiteralKind::Datetime,
+            _ => LiteralKind::Text,
+        }
+    }
+
+    /// Why `value` is not a valid literal of this kind, if it is not.
+    pub fn check(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        let valid = match self {
+            LiteralKind::Integer { min, max } => value
+                .parse::<i128>()
+                .is_ok_and(|n| (*min..=*max).contains(&n)),
+            LiteralKind::Float => value.parse::<f64>().is_ok(),
+            LiteralKind::Boolean => value.parse::<bool>().is_ok(),
+            LiteralKind::Date => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            LiteralKind::Datetime => {
+                chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+                    || ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+                        .iter()
+                        .any(|f| chrono::NaiveDateTime::parse_from_str(value, f).is_ok())
+            }
+            LiteralKind::Text => true,
+        };
+        (!valid).then(|| match self {
+            LiteralKind::Integer { min, max } => {
+                format!("'{}' is not a whole number from {} to {}", value, min, max)
+            }
+            LiteralKind::Float => format!("'{}' is not a number", value),
+            LiteralKind::Boolean => format!("'{}' is not true or false", value),
+            LiteralKind::Date => format!("'{}' is not a date (YYYY-MM-DD)", value),
+            LiteralKind::Datetime => {
+                format!("'{}' is not a date/time (YYYY-MM-DD HH:MM:SS)", value)
+            }
+            LiteralKind::Text => unreachable!(),
+        })
+    }
+}
+
 // ─── Operation ────────────────────────────────────────────────────────────────
 //
 // Operations are serializable descriptions of transformations.
@@ -180,10 +440,15 @@ pub enum Operation {
         column: String,
         op: FilterOp,
         value: String,
+        #[serde(default)]
+        options: FilterOptions,
     },
     Sort {
         column: String,
         descending: bool,
+        /// Rows with equal values keep their current order.
+        #[serde(default)]
+        stable: bool,
     },
     DropColumn(String),
     RenameColumn {
@@ -196,52 +461,459 @@ pub enum Operation {
         column: String,
         strategy: FillNullStrategy,
         value: Option<String>,
+        /// Group columns: fill within each group instead of across the whole column.
+        #[serde(default)]
+        over: Vec<String>,
     },
     CastColumn {
         column: String,
         dtype: DTypeTag,
+        /// Column that receives the original values that failed the cast
+        /// (they become null in `column`).
+        #[serde(default)]
+        quarantine: Option<String>,
     },
     ParseDatetime {
         column: String,
         format: String,
     },
+    /// Extract a field from a JSON string column into a new column.
+    JsonExtract {
+        column: String,
+        /// JSONPath expression, e.g. `$.user.id`.
+        json_path: String,
+        output: String,
+        dtype: DTypeTag,
+    },
+    /// Round a numeric column in the data (not just for display).
+    Round {
+        column: String,
+        decimals: u32,
+        mode: RoundMode,
+    },
+    /// Scale a numeric column into `output` (statistics over the whole column).
+    Normalize {
+        column: String,
+        method: NormalizeMethod,
+        output: String,
+    },
+    /// Cumulative/rank/diff of a column into `output`, optionally per group
+    /// of the `over` columns (in current row order).
+    Window {
+        column: String,
+        function: WindowFunction,
+        #[serde(default)]
+        over: Vec<String>,
+        output: String,
+    },
+    /// First non-null value across `columns` (in order) into `output`.
+    Coalesce {
+        columns: Vec<String>,
+        output: String,
+    },
+    /// Named group of consecutive steps, shown as one pipeline entry.
+    Macro {
+        name: String,
+        operations: Vec<Operation>,
+    },
+    /// Drop the first N rows (e.g. junk lines above the real data).
+    Skip(u32),
+    /// Keep only the last N rows.
+    Tail(u32),
 }
 
 impl fmt::Display for Operation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Operation::Filter { column, op, value } => {
-                if op.needs_value() {
-                    write!(f, "Filter: {} {} {}", column, op, value)
+            Operation::Filter {
+                column,
+                op,
+                value,
+                options,
+            } => {
+                if let FilterOp::Between {
+                    low,
+                    high,
+                    inclusive,
+                } = op
+                {
+                    let (open, close) = if *inclusive { ('[', ']') } else { ('(', ')') };
+                    write!(
+                        f,
+                        "Filter: {} in {}{}, {}{}",
+                        column,
+                        open,
+                        options.rhs(low),
+                        options.rhs(high),
+                        close
+                    )
+                } else if op.needs_value() {
+                    write!(
+                        f,
+                        "Filter: {} {} {}{}",
+                        column,
+                        op,
+                        options.rhs(value),
+                        options
+                    )
                 } else {
-                    write!(f, "Filter: {} {}", column, op)
+                    write!(f, "Filter: {} {}{}", column, op, options)
                 }
             }
-            Operation::Sort { column, descending } => {
+            Operation::Sort {
+                column,
+                descending,
+                stable,
+            } => {
                 write!(
                     f,
-                    "Sort: {} {}",
+                    "Sort: {} {}{}",
                     column,
-                    if *descending { "DESC" } else { "ASC" }
+                    if *descending { "DESC" } else { "ASC" },
+                    if *stable { " (stable)" } else { "" }
                 )
             }
             Operation::DropColumn(col) => write!(f, "Drop: {}", col),
             Operation::RenameColumn { from, to } => write!(f, "Rename: {} → {}", from, to),
             Operation::SelectColumns(cols) => write!(f, "Select: {}", cols.join(", ")),
             Operation::Limit(n) => write!(f, "Limit: {}", n),
+            Operation::Skip(n) => write!(f, "Skip: {}", n),
+            Operation::Tail(n) => write!(f, "Tail: {}", n),
             Operation::FillNull {
-                column, strategy, ..
+                column,
+                strategy,
+                over,
+                ..
             } => {
-                write!(f, "FillNull: {} ({})", column, strategy)
+                write!(
+                    f,
+                    "FillNull: {} ({}){}",
+                    column,
+                    strategy,
+                    over_suffix(over)
+                )
             }
-            Operation::CastColumn { column, dtype } => {
-                write!(f, "Cast: {} → {}", column, dtype)
+            Operation::CastColumn {
+                column,
+                dtype,
+                quarantine,
+            } => {
+                write!(f, "Cast: {} → {}", column, dtype)?;
+                if let Some(quarantine) = quarantine {
+                    write!(f, " (failures → {})", quarantine)?;
+                }
+                Ok(())
             }
             Operation::ParseDatetime { column, format } => {
                 write!(f, "ParseDatetime: {} ({})", column, format)
             }
+            Operation::JsonExtract {
+                column,
+                json_path,
+                output,
+                dtype,
+            } => {
+                write!(
+                    f,
+                    "JsonExtract: {}{} → {} ({})",
+                    column, json_path, output, dtype
+                )
+            }
+            Operation::Round {
+                column,
+                decimals,
+                mode,
+            } => {
+                write!(f, "Round: {} to {} dp ({})", column, decimals, mode)
+            }
+            Operation::Normalize {
+                column,
+                method,
+                output,
+            } => {
+                write!(f, "Normalize: {} → {} ({})", column, output, method)
+            }
+            Operation::Window {
+                column,
+                function,
+                over,
+                output,
+            } => {
+                write!(
+                    f,
+                    "Window: {} of {} → {}{}",
+                    function,
+                    column,
+                    output,
+                    over_suffix(over)
+                )
+            }
+            Operation::Coalesce { columns, output } => {
+                write!(f, "Coalesce: {} → {}", columns.join(", "), output)
+            }
+            Operation::Macro { name, operations } => {
+                write!(f, "Macro: {} ({} steps)", name, operations.len())
+            }
+        }
+    }
+}
+
+/// " over a, b" for grouped (window) operations, empty when ungrouped.
+fn over_suffix(over: &[String]) -> String {
+    if over.is_empty() {
+        String::new()
+    } else {
+        format!(" over {}", over.join(", "))
+    }
+}
+
+impl Operation {
+    /// Mutable references to every input column name the operation reads
+    /// (including column-to-column filter values and steps inside macros).
+    /// Output names (rename targets, extracted columns) are not included.
+    pub fn column_refs_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            Operation::Filter {
+                column,
+                op,
+                value,
+                options,
+            } => {
+                let mut refs = vec![column];
+                if options.value_is_column {
+                    match op {
+                        FilterOp::Between { low, high, .. } => refs.extend([low, high]),
+                        op if op.needs_value() => refs.push(value),
+                        _ => {}
+                    }
+                }
+                refs
+            }
+            Operation::FillNull { column, over, .. } | Operation::Window { column, over, .. } => {
+                std::iter::once(column).chain(over.iter_mut()).collect()
+            }
+            Operation::Sort { column, .. }
+            | Operation::CastColumn { column, .. }
+            | Operation::ParseDatetime { column, .. }
+            | Operation::JsonExtract { column, .. }
+            | Operation::Round { column, .. }
+            | Operation::Normalize { column, .. } => vec![column],
+            Operation::DropColumn(column) => vec![column],
+            Operation::RenameColumn { from, .. } => vec![from],
+            Operation::SelectColumns(columns) | Operation::Coalesce { columns, .. } => {
+                columns.iter_mut().collect()
+            }
+            Operation::Limit(_) | Operation::Skip(_) | Operation::Tail(_) => Vec::new(),
+            Operation::Macro { operations, .. } => operations
+                .iter_mut()
+                .flat_map(|op| op.column_refs_mut())
+                .collect(),
+        }
+    }
+
+    /// Column created by this operation, if any (rename target, extracted field).
+    pub fn created_column(&self) -> Option<&str> {
+        match self {
+            Operation::RenameColumn { to, .. } => Some(to),
+            Operation::JsonExtract { output, .. }
+            | Operation::Normalize { output, .. }
+            | Operation::Window { output, .. }
+            | Operation::Coalesce { output, .. } => Some(output),
+            Operation::CastColumn { quarantine, .. } => quarantine.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+// ─── Units ────────────────────────────────────────────────────────────────────
+
+/// Carry source column units through the pipeline: renames move the unit,
+/// dropped/unselected columns lose it, and columns created by an operation
+/// start without one. Other operations keep column names and units.
+pub fn propagate_units(
+    units: &BTreeMap<String, String>,
+    operations: &[Operation],
+) -> BTreeMap<String, String> {
+    let mut units = units.clone();
+    for op in operations {
+        match op {
+            Operation::RenameColumn { from, to } => {
+                units.remove(to);
+                if let Some(unit) = units.remove(from) {
+                    units.insert(to.clone(), unit);
+                }
+            }
+            Operation::DropColumn(column) => {
+                units.remove(column);
+            }
+            Operation::SelectColumns(columns) => units.retain(|name, _| columns.contains(name)),
+            // Extracted and normalized (dimensionless) columns have no unit
+            Operation::JsonExtract { output, .. } | Operation::Normalize { output, .. } => {
+                units.remove(output);
+            }
+            Operation::CastColumn {
+                quarantine: Some(quarantine),
+                ..
+            } => {
+                units.remove(quarantine);
+            }
+            // Cumulative sums and diffs keep the unit, ranks are counts
+            Operation::Window {
+                column,
+                function,
+                output,
+                ..
+            } => match units.get(column).cloned() {
+                Some(unit) if function.keeps_unit() => {
+                    units.insert(output.clone(), unit);
+                }
+                _ => {
+                    units.remove(output);
+                }
+            },
+            // Merged columns keep a unit only if all inputs agree on it
+            Operation::Coalesce { columns, output } => {
+                let mut inputs = columns.iter().map(|c| units.get(c));
+                match inputs.next().flatten().cloned() {
+                    Some(unit) if inputs.all(|u| u == Some(&unit)) => {
+                        units.insert(output.clone(), unit);
+                    }
+                    _ => {
+                        units.remove(output);
+                    }
+                }
+            }
+            Operation::Macro { operations, .. } => units = propagate_units(&units, operations),
+            _ => {}
+        }
+    }
+    units
+}
+
+/// Source column behind a pipeline output column (following renames back).
+/// `None` if the column is created by an operation rather than read from the source.
+pub fn source_column(name: &str, operations: &[Operation]) -> Option<String> {
+    let mut name = name.to_string();
+    for op in operations.iter().rev() {
+        match op {
+            Operation::RenameColumn { from, to } if *to == name => name = from.clone(),
+            Operation::JsonExtract { output, .. }
+            | Operation::Normalize { output, .. }
+            | Operation::Window { output, .. }
+            | Operation::Coalesce { output, .. }
+            | Operation::CastColumn {
+                quarantine: Some(output),
+                ..
+            } if *output == name => {
+                return None;
+            }
+            Operation::Macro { operations, .. } => name = source_column(&name, operations)?,
+            _ => {}
+        }
+    }
+    Some(name)
+}
+
+// ─── Lineage ──────────────────────────────────────────────────────────────────
+
+/// Pipeline steps behind each output column, as serialized operations.
+/// Column steps are prefixed with the number of row steps before them, since
+/// e.g. a window over filtered rows differs from a filter after the window.
+#[derive(Debug, Clone, Default)]
+pub struct Lineage {
+    /// Steps that change the rows or their order, and so every column.
+    pub rows: Vec<String>,
+    /// Steps that wrote each column or a column it was derived from.
+    /// Source columns no step touched are absent.
+    pub columns: BTreeMap<String, Vec<String>>,
+}
+
+impl Lineage {
+    /// Steps behind `column`: all row steps, then the column's own.
+    pub fn of(&self, column: &str) -> Vec<&str> {
+        let own = self.columns.get(column).into_iter().flatten();
+        self.rows.iter().chain(own).map(String::as_str).collect()
+    }
+
+    fn extend(&mut self, operations: &[Operation]) {
+        for op in operations {
+            let step = format!(
+                "{}:{}",
+                self.rows.len(),
+                serde_json::to_string(op).unwrap_or_default()
+            );
+            match op {
+                Operation::Filter { .. }
+                | Operation::Sort { .. }
+                | Operation::Limit(_)
+                | Operation::Skip(_)
+                | Operation::Tail(_) => {
+                    self.rows.push(step);
+                }
+                Operation::DropColumn(column) => {
+                    self.columns.remove(column);
+                }
+                Operation::SelectColumns(columns) => {
+                    self.columns.retain(|name, _| columns.contains(name));
+                }
+                Operation::RenameColumn { from, to } => {
+                    self.derive(to, [from], step);
+                    self.columns.remove(from);
+                }
+                Operation::FillNull { column, over, .. } => {
+                    self.derive(column, std::iter::once(column).chain(over), step);
+                }
+                Operation::CastColumn {
+                    column, quarantine, ..
+                } => {
+                    self.derive(column, [column], step);
+                    if let Some(quarantine) = quarantine {
+                        let steps = self.columns.get(column).cloned().unwrap_or_default();
+                        self.columns.insert(quarantine.clone(), steps);
+                    }
+                }
+                Operation::ParseDatetime { column, .. } | Operation::Round { column, .. } => {
+                    self.derive(column, [column], step);
+                }
+                Operation::JsonExtract { column, output, .. }
+                | Operation::Normalize { column, output, .. } => {
+                    self.derive(output, [column], step);
+                }
+                Operation::Window {
+                    column,
+                    over,
+                    output,
+                    ..
+                } => {
+                    self.derive(output, std::iter::once(column).chain(over), step);
+                }
+                Operation::Coalesce { columns, output } => self.derive(output, columns, step),
+                Operation::Macro { operations, .. } => self.extend(operations),
+            }
         }
     }
+
+    /// `target` now depends on the steps of `inputs` plus `step`.
+    fn derive<'a>(
+        &mut self,
+        target: &str,
+        inputs: impl IntoIterator<Item = &'a String>,
+        step: String,
+    ) {
+        let mut steps: Vec<String> = inputs
+            .into_iter()
+            .flat_map(|input| self.columns.get(input).cloned().unwrap_or_default())
+            .collect();
+        steps.push(step);
+        self.columns.insert(target.to_string(), steps);
+    }
+}
+
+/// Lineage of every output column of `operations`.
+pub fn lineage(operations: &[Operation]) -> Lineage {
+    let mut lineage = Lineage::default();
+    lineage.extend(operations);
+    lineage
 }
 
 // ─── Operation Type (for UI selection) ────────────────────────────────────────
@@ -255,9 +927,18 @@ pub enum OperationType {
     RenameColumn,
     SelectColumns,
     Limit,
+    Skip,
+    Tail,
     FillNull,
     CastColumn,
     ParseDatetime,
+    JsonExtract,
+    Round,
+    Normalize,
+    Window,
+    Coalesce,
+    /// UI-only: builds `Gte`/`Lte` filters on a Date/Datetime column.
+    DateRange,
 }
 
 impl OperationType {
@@ -269,9 +950,17 @@ impl OperationType {
             OperationType::RenameColumn,
             OperationType::SelectColumns,
             OperationType::Limit,
+            OperationType::Skip,
+            OperationType::Tail,
             OperationType::FillNull,
             OperationType::CastColumn,
             OperationType::ParseDatetime,
+            OperationType::JsonExtract,
+            OperationType::Round,
+            OperationType::Normalize,
+            OperationType::Window,
+            OperationType::Coalesce,
+            OperationType::DateRange,
         ]
     }
 }
@@ -285,9 +974,17 @@ impl fmt::Display for OperationType {
             OperationType::RenameColumn => write!(f, "Rename Column"),
             OperationType::SelectColumns => write!(f, "Select Columns"),
             OperationType::Limit => write!(f, "Limit Rows"),
+            OperationType::Skip => write!(f, "Skip Rows"),
+            OperationType::Tail => write!(f, "Last Rows"),
             OperationType::FillNull => write!(f, "Fill Null"),
             OperationType::CastColumn => write!(f, "Cast Column Type"),
             OperationType::ParseDatetime => write!(f, "Parse Datetime"),
+            OperationType::JsonExtract => write!(f, "Extract JSON Field"),
+            OperationType::Round => write!(f, "Round Numbers"),
+            OperationType::Normalize => write!(f, "Normalize / Scale"),
+            OperationType::Window => write!(f, "Window (cumulative / rank)"),
+            OperationType::Coalesce => write!(f, "Coalesce Columns"),
+            OperationType::DateRange => write!(f, "Date Range Filter"),
         }
     }
 }
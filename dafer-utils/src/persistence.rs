@@ -1,37 +1,107 @@
-//! Persistence module: save/load application state using bincode.
+//! Persistence module: save/load application state as JSON.
 //!
-//! Only serializable metadata is persisted (data source config + operations).
+//! Only serializable metadata is persisted (data source config + operations,
+//! and for workspaces the layout, plot setup, dashboard and notes).
 //! DataFrames are NEVER serialized — they are rebuilt from the lazy pipeline.
+//!
+//! JSON is self-describing, so fields added later with `#[serde(default)]`
+//! load from older files. State files written before the switch are bincode
+//! in the original layout and are migrated on load (see "Legacy State Files").
 
 use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
 
-use crate::datasource::DataSource;
-use crate::operations::Operation;
+use crate::analysis::ColumnRole;
+use crate::datasource::{CsvOptions, DataSource, DataSourceType};
+use crate::display::DisplayConfig;
+use crate::fixture::Fixture;
+use crate::operations::{DTypeTag, FillNullStrategy, FilterOp, FilterOptions, Operation};
+use crate::plot::{DashboardPlot, PlotConfig};
+use crate::query_engine::CollectEngine;
 
 /// Serializable application state for persistence.
 /// Contains everything needed to reconstruct the full pipeline.
+///
+/// The source path is stored both as-is (absolute) and relative to the state file,
+/// so state files keep working when the project folder is moved or shared.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersistentState {
     pub source: Option<DataSource>,
     pub operations: Vec<Operation>,
+    /// Source path relative to the state file's directory (set on save).
+    #[serde(default)]
+    pub source_relative: Option<PathBuf>,
+    /// Source columns (name, dtype) when the pipeline was saved, used to warn
+    /// about schema drift on load.
+    #[serde(default)]
+    pub expected_schema: Vec<(String, String)>,
+    /// Expected output pinned to the pipeline (see `fixture`).
+    #[serde(default)]
+    pub fixture: Option<Fixture>,
+    /// Collect engine for this pipeline, overriding the app setting.
+    #[serde(default)]
+    pub engine: Option<CollectEngine>,
+    /// Source row count when the pipeline was saved (Parquet sources only).
+    #[serde(default)]
+    pub source_rows: Option<usize>,
 }
 
 impl PersistentState {
-    /// Save state to a binary file using bincode.
+    /// Save state to a JSON file.
     pub fn save(&self, path: &Path) -> Result<()> {
-        let encoded = bincode::serialize(self)?;
+        let encoded = serde_json::to_vec_pretty(&self.with_relative_source(path))?;
         std::fs::write(path, encoded)?;
         Ok(())
     }
 
-    /// Load state from a binary file.
+    /// Load state from a file (JSON, or a legacy bincode state file).
     pub fn load(path: &Path) -> Result<Self> {
         let data = std::fs::read(path)?;
-        let state: Self = bincode::deserialize(&data)?;
+        let mut state = Self::decode(&data)?;
+        state.resolve_relative_source(path);
         Ok(state)
     }
+
+    /// Decode the contents of a state file.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if is_json(data) {
+            serde_json::from_slice(data).map_err(|e| anyhow::anyhow!("Invalid state file: {}", e))
+        } else {
+            Ok(LegacyState::decode(data)?.into())
+        }
+    }
+
+    /// Copy of the state with the source path also recorded relative to `state_file`.
+    fn with_relative_source(&self, state_file: &Path) -> Self {
+        let base_dir = state_file
+            .parent()
+            .and_then(|dir| std::path::absolute(dir).ok());
+        let mut state = self.clone();
+        state.source_relative = match (&self.source, base_dir) {
+            (Some(source), Some(dir)) => relative_path(&dir, &source.path),
+            _ => None,
+        };
+        state
+    }
+
+    /// Resolve the relative source path against the state file's directory.
+    /// The relative path wins when the file exists there; otherwise the absolute
+    /// path is kept as a fallback.
+    fn resolve_relative_source(&mut self, state_file: &Path) {
+        let (Some(relative), Some(source), Some(dir)) =
+            (&self.source_relative, &mut self.source, state_file.parent())
+        else {
+            return;
+        };
+        let candidate = dir.join(relative);
+        if candidate.exists() {
+            source.path = candidate;
+        }
+    }
 }
 
 impl Default for PersistentState {
@@ -39,6 +109,462 @@ impl Default for PersistentState {
         Self {
             source: None,
             operations: Vec::new(),
+            source_relative: None,
+            expected_schema: Vec::new(),
+            fixture: None,
+            engine: None,
+            source_rows: None,
+        }
+    }
+}
+
+// ─── Schema Drift ─────────────────────────────────────────────────────────────
+
+/// Differences between the recorded source schema and the source as it is now.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDrift {
+    pub removed: Vec<String>,
+    pub added: Vec<String>,
+    /// (column, expected dtype, actual dtype)
+    pub retyped: Vec<(String, String, String)>,
+    /// Indices of operations that read a removed or retyped column.
+    pub affected: Vec<usize>,
+    /// (rows when saved, rows now), if the row count changed.
+    pub rows: Option<(usize, usize)>,
+}
+
+impl SchemaDrift {
+    /// Number of removed, added or retyped columns.
+    pub fn columns_changed(&self) -> usize {
+        self.removed.len() + self.added.len() + self.retyped.len()
+    }
+}
+
+impl PersistentState {
+    /// Compare the recorded source schema and row count with `actual` and
+    /// `actual_rows`. `None` if nothing was recorded or nothing changed.
+    pub fn schema_drift(
+        &self,
+        actual: &[(String, String)],
+        actual_rows: Option<usize>,
+    ) -> Option<SchemaDrift> {
+        let mut drift = SchemaDrift {
+            rows: self
+                .source_rows
+                .zip(actual_rows)
+                .filter(|(saved, now)| saved != now),
+            ..SchemaDrift::default()
+        };
+        if self.expected_schema.is_empty() {
+            return drift.rows.is_some().then_some(drift);
+        }
+        for (name, expected) in &self.expected_schema {
+            match actual.iter().find(|(n, _)| n == name) {
+                None => drift.removed.push(name.clone()),
+                Some((_, dtype)) if dtype != expected => {
+                    drift
+                        .retyped
+                        .push((name.clone(), expected.clone(), dtype.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        drift.added = actual
+            .iter()
+            .filter(|(name, _)| !self.expected_schema.iter().any(|(n, _)| n == name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if drift.columns_changed() == 0 {
+            return drift.rows.is_some().then_some(drift);
+        }
+        drift.affected = self
+            .operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| {
+                let mut op = (*op).clone();
+                op.column_refs_mut().iter().any(|c| {
+                    drift.removed.contains(&**c) || drift.retyped.iter().any(|(n, _, _)| *n == **c)
+                })
+            })
+            .map(|(i, _)| i)
+            .collect();
+        Some(drift)
+    }
+}
+
+/// Express `target` relative to `base_dir` (e.g. `../data/file.csv`).
+/// Returns `None` if either path is relative or they don't share a root
+/// (e.g. different drives on Windows).
+pub(crate) fn relative_path(base_dir: &Path, target: &Path) -> Option<PathBuf> {
+    if !base_dir.is_absolute() || !target.is_absolute() {
+        return None;
+    }
+    let base: Vec<Component> = base_dir.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    if base.first() != target.first() {
+        return None;
+    }
+    let common = base.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &target[common..] {
+        relative.push(component.as_os_str());
+    }
+    Some(relative)
+}
+
+// ─── Workspace ────────────────────────────────────────────────────────────────
+
+/// UI layout saved alongside the pipeline in workspace files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiLayout {
+    /// Index of the active main tab.
+    pub tab_index: usize,
+    pub display: DisplayConfig,
+}
+
+/// Workspace file (`.dafer`): the pipeline plus everything needed to reproduce
+/// the analysis view — layout, display config, plot setup, dashboard and notes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    pub state: PersistentState,
+    pub layout: UiLayout,
+    pub plot: PlotConfig,
+    pub notes: String,
+    #[serde(default)]
+    pub dashboard: Vec<DashboardPlot>,
+    #[serde(default)]
+    pub snapshots: Vec<PipelineSnapshot>,
+    /// Column roles set by the user, overriding the inferred ones.
+    #[serde(default)]
+    pub column_roles: BTreeMap<String, ColumnRole>,
+    #[serde(default)]
+    pub last_export: Option<LastExport>,
+}
+
+/// The last export of the pipeline: enough to tell whether the output is
+/// stale (the source was modified, or the pipeline edited, since).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastExport {
+    /// Output file(s) as shown to the user.
+    pub output: String,
+    pub format: String,
+    /// Rows written (before splitting into parts).
+    pub rows: Option<usize>,
+    pub operations: Vec<Operation>,
+    /// Seconds since the Unix epoch.
+    pub exported_at: i64,
+}
+
+impl LastExport {
+    pub fn new(output: &str, format: &str, rows: Option<usize>, operations: &[Operation]) -> Self {
+        Self {
+            output: output.to_string(),
+            format: format.to_string(),
+            rows,
+            operations: operations.to_vec(),
+            exported_at: chrono::Local::now().timestamp(),
+        }
+    }
+
+    /// Local time of the export, `YYYY-MM-DD HH:MM:SS`.
+    pub fn time(&self) -> String {
+        chrono::DateTime::from_timestamp(self.exported_at, 0)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+
+    /// True if the file at `source` was modified after the export.
+    pub fn source_is_newer(&self, source: &Path) -> bool {
+        std::fs::metadata(source)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .is_some_and(|t| t.as_secs() as i64 > self.exported_at)
+    }
+}
+
+/// Named copy of the operation list ("before outlier removal") to jump back to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineSnapshot {
+    pub name: String,
+    pub operations: Vec<Operation>,
+}
+
+/// Which parts of a workspace to apply when loading it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkspaceSelection {
+    pub source: bool,
+    pub operations: bool,
+    pub layout: bool,
+    /// Plot setup of the Visualize tab and the dashboard plots.
+    pub plot: bool,
+    pub notes: bool,
+}
+
+impl Default for WorkspaceSelection {
+    fn default() -> Self {
+        Self {
+            source: true,
+            operations: true,
+            layout: true,
+            plot: true,
+            notes: true,
         }
     }
 }
+
+impl WorkspaceSelection {
+    /// Apply only the pipeline operations (e.g. onto the currently open file).
+    pub fn operations_only() -> Self {
+        Self {
+            source: false,
+            operations: true,
+            layout: false,
+            plot: false,
+            notes: false,
+        }
+    }
+}
+
+impl Workspace {
+    /// Save the workspace to a JSON file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let workspace = Workspace {
+            state: self.state.with_relative_source(path),
+            ..self.clone()
+        };
+        std::fs::write(path, workspace.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Load a workspace from a JSON file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        let mut workspace: Self = serde_json::from_slice(&data)
+            .map_err(|e| anyhow::anyhow!("Invalid workspace file: {}", e))?;
+        workspace.state.resolve_relative_source(path);
+        Ok(workspace)
+    }
+
+    /// Encode the workspace (also used to skip autosaves when nothing changed).
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+}
+
+// ─── Legacy State Files ───────────────────────────────────────────────────────
+//
+// Before the switch to JSON, state files were bincode, which is positional:
+// it cannot skip or default fields, so the original layout is mirrored here
+// and converted. The filter, fill and dtype enums only gained variants at the
+// end, so their bincode encoding is unchanged and they are reused as-is.
+
+/// True if `data` is a JSON object (a legacy bincode state starts with the
+/// `Option` tag of its source, 0 or 1).
+fn is_json(data: &[u8]) -> bool {
+    data.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+}
+
+#[derive(Deserialize)]
+struct LegacyState {
+    source: Option<LegacySource>,
+    operations: Vec<LegacyOperation>,
+}
+
+#[derive(Deserialize)]
+struct LegacySource {
+    path: PathBuf,
+    source_type: DataSourceType,
+    auto_numeric_cols: Vec<String>,
+}
+
+#[derive(Deserialize)]
+enum LegacyOperation {
+    Filter {
+        column: String,
+        op: FilterOp,
+        value: String,
+    },
+    Sort {
+        column: String,
+        descending: bool,
+    },
+    DropColumn(String),
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+    SelectColumns(Vec<String>),
+    Limit(u32),
+    FillNull {
+        column: String,
+        strategy: FillNullStrategy,
+        value: Option<String>,
+    },
+    CastColumn {
+        column: String,
+        dtype: DTypeTag,
+    },
+    ParseDatetime {
+        column: String,
+        format: String,
+    },
+}
+
+impl LegacyState {
+    fn decode(data: &[u8]) -> Result<Self> {
+        bincode::deserialize(data).map_err(|e| anyhow::anyhow!("Invalid state file: {}", e))
+    }
+}
+
+impl From<LegacyState> for PersistentState {
+    fn from(legacy: LegacyState) -> Self {
+        Self {
+            source: legacy.source.map(|source| DataSource {
+                path: source.path,
+                source_type: source.source_type,
+                auto_numeric_cols: source.auto_numeric_cols,
+                auto_integer_cols: Vec::new(),
+                auto_cast_declined: Vec::new(),
+                stats_sample: None,
+                seed: None,
+                units: BTreeMap::new(),
+                csv: CsvOptions::default(),
+                big_file: false,
+                spread_preview: false,
+            }),
+            operations: legacy.operations.into_iter().map(Operation::from).collect(),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<LegacyOperation> for Operation {
+    fn from(legacy: LegacyOperation) -> Self {
+        match legacy {
+            LegacyOperation::Filter { column, op, value } => Operation::Filter {
+                column,
+                op,
+                value,
+                options: FilterOptions::default(),
+            },
+            LegacyOperation::Sort { column, descending } => Operation::Sort {
+                column,
+                descending,
+                stable: false,
+            },
+            LegacyOperation::DropColumn(column) => Operation::DropColumn(column),
+            LegacyOperation::RenameColumn { from, to } => Operation::RenameColumn { from, to },
+            LegacyOperation::SelectColumns(columns) => Operation::SelectColumns(columns),
+            LegacyOperation::Limit(n) => Operation::Limit(n),
+            LegacyOperation::FillNull {
+                column,
+                strategy,
+                value,
+            } => Operation::FillNull {
+                column,
+                strategy,
+                value,
+                over: Vec::new(),
+            },
+            LegacyOperation::CastColumn { column, dtype } => Operation::CastColumn {
+                column,
+                dtype,
+                quarantine: None,
+            },
+            LegacyOperation::ParseDatetime { column, format } => {
+                Operation::ParseDatetime { column, format }
+            }
+        }
+    }
+}
+
+// ─── Pipeline Snippets ────────────────────────────────────────────────────────
+//
+// A pipeline as text to share over chat: the operations as compact JSON, or
+// that JSON base64-encoded behind a prefix so chat clients do not reformat it.
+
+/// Prefix of a base64-encoded pipeline snippet.
+pub const SNIPPET_PREFIX: &str = "dafer-pipeline:";
+
+/// Encode `operations` as a snippet (compact JSON, or base64 with `SNIPPET_PREFIX`).
+pub fn pipeline_to_snippet(operations: &[Operation], base64: bool) -> Result<String> {
+    let json = serde_json::to_string(operations)?;
+    Ok(if base64 {
+        format!("{}{}", SNIPPET_PREFIX, BASE64.encode(json))
+    } else {
+        json
+    })
+}
+
+/// Decode a snippet written by `pipeline_to_snippet` (surrounding whitespace,
+/// and whitespace inside base64 from wrapped lines, is ignored).
+pub fn pipeline_from_snippet(text: &str) -> Result<Vec<Operation>> {
+    let text = text.trim();
+    let json = match text.strip_prefix(SNIPPET_PREFIX) {
+        Some(encoded) => {
+            let encoded: String = encoded.split_ascii_whitespace().collect();
+            let bytes = BASE64
+                .decode(encoded)
+                .map_err(|e| anyhow::anyhow!("Invalid base64: {}", e))?;
+            String::from_utf8(bytes)?
+        }
+        None => text.to_string(),
+    };
+    serde_json::from_str(&json).map_err(|e| anyhow::anyhow!("Not a pipeline snippet: {}", e))
+}
+
+// ─── Session Recovery ─────────────────────────────────────────────────────────
+//
+// The session is autosaved periodically as a workspace so work survives a crash
+// or forced quit. The recovery file is removed on a clean exit, so finding one at
+// startup means the previous session did not shut down normally.
+
+/// Write an encoded workspace to the recovery file.
+/// Writes to a temp file first and renames it, so a crash mid-write never
+/// leaves a truncated recovery file behind.
+pub fn write_recovery(bytes: &[u8]) -> Result<()> {
+    let path = recovery_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("dafer.tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Load the recovery workspace left by a previous session, if any.
+pub fn load_recovery() -> Option<Workspace> {
+    Workspace::load(&recovery_path()).ok()
+}
+
+/// Remove the recovery file (on clean exit or when the user discards it).
+pub fn clear_recovery() {
+    let _ = std::fs::remove_file(recovery_path());
+}
+
+/// Per-user application data directory (`%APPDATA%/dafer-utils` or `~/.dafer-utils`),
+/// falling back to the system temp directory.
+pub(crate) fn app_data_dir() -> PathBuf {
+    if let Some(appdata) = std::env::var_os("APPDATA") {
+        return PathBuf::from(appdata).join("dafer-utils");
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        return PathBuf::from(home).join(".dafer-utils");
+    }
+    std::env::temp_dir().join("dafer-utils")
+}
+
+/// Location of the session recovery file.
+pub(crate) fn recovery_path() -> PathBuf {
+    app_data_dir().join("recovery.dafer")
+}
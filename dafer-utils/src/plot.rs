@@ -0,0 +1,198 @@
+//! Plot module: serializable plot configuration shared by the frontends.
+//!
+//! Only the *description* of a plot lives here (type, axes, series); rendering
+//! is left to the UI layer.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::operations::Operation;
+
+// ─── Plot Type ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PlotType {
+    #[default]
+    Scatter,
+    Line,
+    Bar,
+    Histogram,
+}
+
+impl PlotType {
+    pub fn all() -> &'static [PlotType] {
+        &[
+            PlotType::Scatter,
+            PlotType::Line,
+            PlotType::Bar,
+            PlotType::Histogram,
+        ]
+    }
+
+    /// Returns true if this plot type needs a Y column.
+    pub fn needs_y(&self) -> bool {
+        !matches!(self, PlotType::Histogram)
+    }
+}
+
+impl fmt::Display for PlotType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotType::Scatter => write!(f, "Scatter"),
+            PlotType::Line => write!(f, "Line"),
+            PlotType::Bar => write!(f, "Bar"),
+            PlotType::Histogram => write!(f, "Histogram"),
+        }
+    }
+}
+
+// ─── Plot Config ──────────────────────────────────────────────────────────────
+
+/// A plot setup: type, X column, Y series, histogram bin count and annotations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlotConfig {
+    pub plot_type: PlotType,
+    pub x: String,
+    pub y_columns: Vec<String>,
+    pub histogram_bins: usize,
+    #[serde(default)]
+    pub annotations: Vec<PlotAnnotation>,
+}
+
+impl Default for PlotConfig {
+    fn default() -> Self {
+        Self {
+            plot_type: PlotType::default(),
+            x: String::new(),
+            y_columns: Vec::new(),
+            histogram_bins: 30,
+            annotations: Vec::new(),
+        }
+    }
+}
+
+impl PlotConfig {
+    /// Is `column` the X axis or one of the Y series?
+    pub fn reads(&self, column: &str) -> bool {
+        self.x == column || self.y_columns.iter().any(|c| c == column)
+    }
+
+    /// Point references to `from` at `to` (a rename), or remove them (`None`,
+    /// a drop; the X axis is cleared).
+    pub fn retarget(&mut self, from: &str, to: Option<&str>) {
+        if self.x == from {
+            self.x = to.unwrap_or_default().to_string();
+        }
+        match to {
+            Some(to) => {
+                for c in self.y_columns.iter_mut().filter(|c| c.as_str() == from) {
+                    *c = to.to_string();
+                }
+            }
+            None => self.y_columns.retain(|c| c != from),
+        }
+    }
+}
+
+// ─── Annotations ──────────────────────────────────────────────────────────────
+
+/// Reference line or text marker drawn on top of a plot.
+/// Coordinates are in plot units (seconds since the epoch for datetime axes).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PlotAnnotation {
+    /// Horizontal line, e.g. an alarm threshold.
+    HLine { y: f64, label: String },
+    /// Vertical line, e.g. an event time.
+    VLine { x: f64, label: String },
+    /// Free text anchored at a point.
+    Text { x: f64, y: f64, text: String },
+}
+
+impl fmt::Display for PlotAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotAnnotation::HLine { y, label } if label.is_empty() => write!(f, "y = {}", y),
+            PlotAnnotation::HLine { y, label } => write!(f, "y = {} ({})", y, label),
+            PlotAnnotation::VLine { x, label } if label.is_empty() => write!(f, "x = {}", x),
+            PlotAnnotation::VLine { x, label } => write!(f, "x = {} ({})", x, label),
+            PlotAnnotation::Text { x, y, text } => write!(f, "\"{}\" @ ({}, {})", text, x, y),
+        }
+    }
+}
+
+// ─── Presets ──────────────────────────────────────────────────────────────────
+
+/// A named plot setup kept in the app config, independent of any dataset and
+/// reapplied to another one by column name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlotPreset {
+    pub name: String,
+    pub config: PlotConfig,
+}
+
+impl PlotPreset {
+    /// The preset's setup for a dataset with `columns`, plus the columns it
+    /// names that the dataset lacks. Those are removed from the setup (a
+    /// missing X axis is cleared).
+    pub fn config_for(&self, columns: &[String]) -> (PlotConfig, Vec<String>) {
+        let mut missing: Vec<String> = Vec::new();
+        for column in std::iter::once(&self.config.x).chain(&self.config.y_columns) {
+            if !column.is_empty() && !columns.contains(column) && !missing.contains(column) {
+                missing.push(column.clone());
+            }
+        }
+        let mut config = self.config.clone();
+        for column in &missing {
+            config.retarget(column, None);
+        }
+        (config, missing)
+    }
+}
+
+// ─── Dashboard ────────────────────────────────────────────────────────────────
+
+/// One plot of the dashboard: its own plot setup plus filters that are applied
+/// on top of the pipeline result for this plot only.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DashboardPlot {
+    pub title: String,
+    pub config: PlotConfig,
+    /// Extra `Operation::Filter`s, applied after the pipeline.
+    pub filters: Vec<Operation>,
+}
+
+impl DashboardPlot {
+    /// Do the axes or the plot's filters read `column`?
+    pub fn reads(&self, column: &str) -> bool {
+        self.config.reads(column)
+            || self.filters.iter().any(|f| {
+                f.clone()
+                    .column_refs_mut()
+                    .iter()
+                    .any(|c| c.as_str() == column)
+            })
+    }
+
+    /// `PlotConfig::retarget`, plus renaming (or removing) filters on the column.
+    pub fn retarget(&mut self, from: &str, to: Option<&str>) {
+        self.config.retarget(from, to);
+        match to {
+            Some(to) => {
+                for filter in &mut self.filters {
+                    for c in filter.column_refs_mut() {
+                        if c.as_str() == from {
+                            *c = to.to_string();
+                        }
+                    }
+                }
+            }
+            None => self.filters.retain(|f| {
+                !f.clone()
+                    .column_refs_mut()
+                    .iter()
+                    .any(|c| c.as_str() == from)
+            }),
+        }
+    }
+}
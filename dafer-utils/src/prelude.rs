@@ -0,0 +1,24 @@
+//! Prelude: the types and functions needed to run a pipeline outside the app.
+//!
+//! `use dafer_utils::prelude::*;` brings in the data source, the operations,
+//! the query functions and the exporters. Everything else stays reachable
+//! through its module.
+
+pub use crate::datasource::{CsvOptions, DataSource, DataSourceType};
+pub use crate::execution::{ExportOptions, SplitLimit, export_csv};
+#[cfg(feature = "parquet")]
+pub use crate::execution::{export_csv_and_parquet, export_parquet};
+pub use crate::operations::{FilterOp, Operation};
+pub use crate::persistence::PersistentState;
+pub use crate::query_engine::{build_lazy, execute, preview, schema_info};
+
+// Types reached through the fields of the items above (export redaction,
+// pinned fixtures, workspace files). They are defined in frontend modules but
+// covered by the same stability promise.
+pub use crate::analysis::ColumnRole;
+pub use crate::display::{DisplayConfig, FormatRule};
+pub use crate::fixture::Fixture;
+pub use crate::persistence::{LastExport, PipelineSnapshot, UiLayout, Workspace};
+pub use crate::plot::{DashboardPlot, PlotAnnotation, PlotConfig, PlotType};
+pub use crate::query_engine::CollectEngine;
+pub use crate::redaction::{RedactAction, RedactionAudit, RedactionProfile, RedactionRule};
@@ -2,45 +2,988 @@
 //!
 //! The engine never materializes data unless explicitly asked (preview/execute).
 //! All transformations are applied lazily via Polars logical plan.
+//!
+//! Row order: the result keeps the input order except where a Sort reorders
+//! it. Filters, casts and new columns never reorder; grouped windows and fills
+//! (`over`) return rows in their original positions. A stable Sort keeps tied
+//! rows in their current order, an unstable one may not.
 
 use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
 use crate::datasource::DataSource;
-use crate::operations::{FillNullStrategy, FilterOp, Operation};
+use crate::operations::{
+    DTypeTag, FillNullStrategy, FilterOp, FilterOptions, NormalizeMethod, Operation, RoundMode,
+    WindowFunction,
+};
+
+// ─── Panic Guard ──────────────────────────────────────────────────────────────
+//
+// Polars (and the readers) can panic on inputs they don't expect. The public
+// entry points below run inside `guarded`, so a panic comes back as an error
+// and the frontend stays up.
+
+/// Run `f`, turning a panic into an error carrying the panic message.
+pub fn guarded<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        Err(anyhow::anyhow!(
+            "internal error: {}",
+            panic_message(&*payload)
+        ))
+    })
+}
+
+/// The message of a panic payload (`panic!` with a literal or a format string).
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+// ─── Pipeline ─────────────────────────────────────────────────────────────────
 
 /// Build a LazyFrame by scanning the source and applying all operations in order.
 pub fn build_lazy(source: &DataSource, operations: &[Operation]) -> Result<LazyFrame> {
-    let mut lf = source.scan().map_err(|e| anyhow::anyhow!("{}", e))?;
+    guarded(|| {
+        let mut lf = source.scan().map_err(|e| anyhow::anyhow!("{}", e))?;
+        for op in operations {
+            lf = apply_operation(lf, op)?;
+        }
+        Ok(lf)
+    })
+}
+
+/// Collect a limited preview (N rows) from the full pipeline.
+/// This is fast because .limit(N) is pushed down into the logical plan.
+/// Sources with `spread_preview` run the pipeline on rows sampled from across
+/// the file instead (see `DataSource::scan_spread`).
+pub fn preview(source: &DataSource, operations: &[Operation], n: u32) -> Result<DataFrame> {
+    guarded(|| {
+        let lf = if source.previews_spread() {
+            spread_lazy(source, operations, n as usize)?
+        } else {
+            build_lazy(source, operations)?
+        };
+        lf.limit(n)
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    })
+}
+
+#[cfg(feature = "parquet")]
+fn spread_lazy(source: &DataSource, operations: &[Operation], n: usize) -> Result<LazyFrame> {
+    let mut lf = source.scan_spread(n)?;
     for op in operations {
         lf = apply_operation(lf, op)?;
     }
     Ok(lf)
 }
 
-/// Collect a limited preview (N rows) from the full pipeline.
-/// This is fast because .limit(N) is pushed down into the logical plan.
-pub fn preview(source: &DataSource, operations: &[Operation], n: u32) -> Result<DataFrame> {
-    let lf = build_lazy(source, operations)?;
-    lf.limit(n).collect().map_err(|e| anyhow::anyhow!("{}", e))
+#[cfg(not(feature = "parquet"))]
+fn spread_lazy(source: &DataSource, operations: &[Operation], _n: usize) -> Result<LazyFrame> {
+    build_lazy(source, operations)
 }
 
 /// Get schema information (column names + data types) from the pipeline
 /// without collecting any data.
 pub fn schema_info(source: &DataSource, operations: &[Operation]) -> Result<Vec<(String, String)>> {
-    let mut lf = build_lazy(source, operations)?;
-    let schema = lf.collect_schema().map_err(|e| anyhow::anyhow!("{}", e))?;
-    Ok(schema
-        .iter()
-        .map(|(name, dtype)| (name.to_string(), format!("{}", dtype)))
-        .collect())
+    guarded(|| {
+        let mut lf = build_lazy(source, operations)?;
+        let schema = lf.collect_schema().map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(schema
+            .iter()
+            .map(|(name, dtype)| (name.to_string(), format!("{}", dtype)))
+            .collect())
+    })
 }
 
 /// Execute the full pipeline and collect all results into a DataFrame.
 /// Use with caution for large datasets — prefer streaming export instead.
 pub fn execute(source: &DataSource, operations: &[Operation]) -> Result<DataFrame> {
-    let lf = build_lazy(source, operations)?;
-    lf.collect().map_err(|e| anyhow::anyhow!("{}", e))
+    guarded(|| {
+        let lf = build_lazy(source, operations)?;
+        lf.collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    })
+}
+
+/// Apply operations on top of an already collected DataFrame
+/// (e.g. per-plot filters on the pipeline result).
+pub fn apply_to_frame(df: &DataFrame, operations: &[Operation]) -> Result<DataFrame> {
+    guarded(|| {
+        let mut lf = df.clone().lazy();
+        for op in operations {
+            lf = apply_operation(lf, op)?;
+        }
+        lf.collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    })
+}
+
+// ─── Performance ─────────────────────────────────────────────────────────────
+//
+// The engine used by the collects below is process-wide: the frontend sets it
+// from the app settings (or the pipeline's override) before running queries.
+// Thread count and morsel size are read by Polars from the environment when
+// its thread pool starts, so `PerformanceSettings::apply_env` must run at
+// startup, before any query.
+
+/// Polars engine used to collect query results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectEngine {
+    /// Let Polars pick (in-memory for most queries).
+    #[default]
+    Auto,
+    InMemory,
+    /// Process the data in batches, for results larger than memory. Needs the
+    /// `streaming` feature; falls back to `Auto` without it.
+    Streaming,
+}
+
+impl CollectEngine {
+    pub fn all() -> &'static [CollectEngine] {
+        &[
+            CollectEngine::Auto,
+            CollectEngine::InMemory,
+            CollectEngine::Streaming,
+        ]
+    }
+
+    /// True if this build can use the engine.
+    pub fn available(&self) -> bool {
+        *self != CollectEngine::Streaming || cfg!(feature = "streaming")
+    }
+
+    fn to_polars(self) -> Engine {
+        match self {
+            CollectEngine::Auto => Engine::Auto,
+            CollectEngine::InMemory => Engine::InMemory,
+            #[cfg(feature = "streaming")]
+            CollectEngine::Streaming => Engine::Streaming,
+            #[cfg(not(feature = "streaming"))]
+            CollectEngine::Streaming => Engine::Auto,
+        }
+    }
+}
+
+impl std::fmt::Display for CollectEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollectEngine::Auto => write!(f, "Auto"),
+            CollectEngine::InMemory => write!(f, "In-memory"),
+            CollectEngine::Streaming => write!(f, "Streaming"),
+        }
+    }
+}
+
+/// Advanced Polars settings, saved in the app config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerformanceSettings {
+    pub engine: CollectEngine,
+    /// Size of the Polars thread pool (`POLARS_MAX_THREADS`); `None` = one per core.
+    pub threads: Option<usize>,
+    /// Rows per batch of the streaming engine (`POLARS_IDEAL_MORSEL_SIZE`).
+    pub morsel_size: Option<usize>,
+}
+
+impl PerformanceSettings {
+    /// Export the thread count and morsel size to the environment for Polars.
+    ///
+    /// # Safety
+    /// Must be called before any other thread is started (e.g. first thing in
+    /// `main`), as it modifies the process environment.
+    pub unsafe fn apply_env(&self) {
+        // SAFETY: the caller guarantees no other thread reads the environment
+        unsafe {
+            if let Some(threads) = self.threads {
+                std::env::set_var("POLARS_MAX_THREADS", threads.max(1).to_string());
+            }
+            if let Some(size) = self.morsel_size {
+                std::env::set_var("POLARS_IDEAL_MORSEL_SIZE", size.max(1).to_string());
+            }
+        }
+    }
+}
+
+static ENGINE: std::sync::atomic::AtomicU8 = std::sync::atomic::AtomicU8::new(0);
+
+/// Set the engine used by every collect in this module.
+pub fn set_engine(engine: CollectEngine) {
+    ENGINE.store(engine as u8, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// The engine set with `set_engine`, as a Polars engine.
+fn engine() -> Engine {
+    let current = CollectEngine::all()
+        .get(ENGINE.load(std::sync::atomic::Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default();
+    current.to_polars()
+}
+
+// ─── Sampling ────────────────────────────────────────────────────────────────
+
+/// Size of a sample of the pipeline result used for "fast stats".
+#[derive(Debug, Clone, Copy)]
+pub struct SampleInfo {
+    pub sample_rows: usize,
+    /// Row count of the full pipeline result.
+    pub total_rows: usize,
+}
+
+impl SampleInfo {
+    /// Worst-case 95% margin of error (as a fraction) for proportions estimated
+    /// from the sample, e.g. the share of nulls in a column. Includes the finite
+    /// population correction, so it is 0 when the sample is the whole result.
+    pub fn margin_of_error(&self) -> f64 {
+        let n = self.sample_rows as f64;
+        let total = self.total_rows as f64;
+        if n == 0.0 || total <= 1.0 {
+            return 0.0;
+        }
+        let fpc = ((total - n) / (total - 1.0)).max(0.0).sqrt();
+        1.96 * (0.25 / n).sqrt() * fpc
+    }
+}
+
+/// Collect about `n` rows of the pipeline result by systematic sampling with a
+/// random start (every k-th row), or a start derived from `source.seed`. The
+/// full result is streamed but never materialized, so this stays cheap on very
+/// large sources.
+pub fn sample(
+    source: &DataSource,
+    operations: &[Operation],
+    n: usize,
+) -> Result<(DataFrame, SampleInfo)> {
+    guarded(|| {
+        let lf = build_lazy(source, operations)?;
+        let counted = lf
+            .clone()
+            .select([len()])
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let total_rows = counted
+            .get_columns()
+            .first()
+            .and_then(|c| c.get(0).ok())
+            .and_then(|v| v.extract::<u64>())
+            .unwrap_or(0) as usize;
+
+        let step = (total_rows / n.max(1)).max(1);
+        let df = if step == 1 {
+            lf.collect_with_engine(engine())
+        } else {
+            let offset = match source.seed {
+                Some(seed) => seeded_offset(seed, step),
+                None => random_offset(step),
+            };
+            lf.select([col("*").gather_every(step, offset)])
+                .collect_with_engine(engine())
+        }
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let info = SampleInfo {
+            sample_rows: df.height(),
+            total_rows,
+        };
+        Ok((df, info))
+    })
+}
+
+/// Pseudo-random start offset in `0..step` (clock-based; no RNG dependency).
+fn random_offset(step: usize) -> usize {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as usize)
+        .unwrap_or(0);
+    nanos % step
+}
+
+/// Start offset in `0..step` derived from a seed (SplitMix64 finalizer), the
+/// same on every platform.
+fn seeded_offset(seed: u64, step: usize) -> usize {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z % step as u64) as usize
+}
+
+// ─── Step Timing ─────────────────────────────────────────────────────────────
+
+/// Wall-clock time of each pipeline stage.
+#[derive(Debug, Clone, Default)]
+pub struct StepTimings {
+    /// Time to scan the source with no operations applied.
+    pub scan: Duration,
+    /// Extra time added by each operation, in pipeline order.
+    pub steps: Vec<Duration>,
+}
+
+/// Time each step by collecting cumulative prefixes of the pipeline
+/// (`scan`, `scan + op1`, `scan + op1 + op2`, ...).
+///
+/// A step's time is its prefix time minus the previous prefix time (clamped at
+/// zero). This is an estimate: the optimizer may fuse or reorder work across steps.
+pub fn time_steps(source: &DataSource, operations: &[Operation]) -> Result<StepTimings> {
+    let mut prefix_times = Vec::with_capacity(operations.len() + 1);
+    for n in 0..=operations.len() {
+        let start = Instant::now();
+        execute(source, &operations[..n])?;
+        prefix_times.push(start.elapsed());
+    }
+    Ok(StepTimings {
+        scan: prefix_times[0],
+        steps: prefix_times
+            .windows(2)
+            .map(|w| w[1].saturating_sub(w[0]))
+            .collect(),
+    })
+}
+
+// ─── Scan Report ─────────────────────────────────────────────────────────────
+
+/// What the optimized plan reads from the source, to show that early filters
+/// and selections pay off.
+#[derive(Debug, Clone, Default)]
+pub struct ScanReport {
+    /// (columns read, columns in the source).
+    pub columns_read: (usize, usize),
+    /// A filter was pushed into the scan (Parquet can then skip row groups by
+    /// their statistics; CSV rows are dropped while reading).
+    pub predicate_pushed: bool,
+}
+
+/// Inspect the optimized plan of the pipeline. Polars does not report row
+/// groups or bytes actually read, so this reads the scan node of
+/// `describe_optimized_plan`: the projected column count and whether a
+/// predicate reached the scan. Fails if the plan has no scan node it
+/// recognizes, rather than reporting a full unfiltered read.
+pub fn scan_report(source: &DataSource, operations: &[Operation]) -> Result<ScanReport> {
+    let plan = build_lazy(source, operations)?
+        .describe_optimized_plan()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let n_source = source
+        .scan()
+        .and_then(|mut lf| lf.collect_schema())
+        .map(|schema| schema.len())
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    // File scans print "<format> SCAN [path]", in-memory frames "DF [columns]";
+    // the node's PROJECT and SELECTION lines follow it
+    let lines: Vec<&str> = plan.lines().map(str::trim).collect();
+    let scan = lines
+        .iter()
+        .position(|line| line.contains(" SCAN ") || line.starts_with("DF ["))
+        .ok_or_else(|| anyhow::anyhow!("No scan node in the optimized plan"))?;
+    let node = &lines[scan..];
+
+    // e.g. "PROJECT 3/12 COLUMNS"; "*" or no projection line means all columns
+    let n_read = node
+        .iter()
+        .find_map(|line| {
+            let (_, rest) = line.split_once("PROJECT ")?;
+            let (read, _) = rest.split_once('/')?;
+            Some(read.trim().parse().unwrap_or(n_source))
+        })
+        .unwrap_or(n_source);
+    let predicate_pushed = node.iter().any(|line| {
+        line.strip_prefix("SELECTION:").is_some_and(|sel| sel.trim() != "None")
+    });
+
+    Ok(ScanReport {
+        columns_read: (n_read, n_source),
+        predicate_pushed,
+    })
+}
+
+// ─── Drill-Down ──────────────────────────────────────────────────────────────
+
+/// Transient query for inspecting problem rows: the pipeline followed by
+/// `extra` steps (usually a filter), never added to the pipeline itself.
+/// Returns the first `n` matching rows and the total number of matches.
+pub fn drill_down(
+    source: &DataSource,
+    operations: &[Operation],
+    extra: &[Operation],
+    n: u32,
+) -> Result<(DataFrame, usize)> {
+    guarded(|| {
+        let lf = extra
+            .iter()
+            .try_fold(build_lazy(source, operations)?, apply_operation)?;
+        let counted = lf
+            .clone()
+            .select([len()])
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let total = counted
+            .get_columns()
+            .first()
+            .and_then(|c| c.get(0).ok())
+            .and_then(|v| v.extract::<u64>())
+            .unwrap_or(0) as usize;
+        let rows = lf
+            .limit(n)
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok((rows, total))
+    })
+}
+
+// ─── Observed Values ─────────────────────────────────────────────────────────
+
+/// Most distinct values offered by the filter builder's value picker.
+pub const OBSERVED_VALUES_CAP: usize = 50;
+
+/// Distinct non-null values of `column` in the pipeline result, in sort
+/// order and formatted as they are typed in a filter. `None` if there are more than `cap` (the
+/// column is not low-cardinality); only `cap + 1` uniques are collected.
+pub fn observed_values(
+    source: &DataSource,
+    operations: &[Operation],
+    column: &str,
+    cap: usize,
+) -> Result<Option<Vec<String>>> {
+    guarded(|| {
+        let df = build_lazy(source, operations)?
+            .select([col(column)
+                .drop_nulls()
+                .unique()
+                .sort(SortOptions::default())
+                .head(Some(cap + 1))])
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let Some(values) = df.get_columns().first() else {
+            return Ok(None);
+        };
+        if values.len() > cap {
+            return Ok(None);
+        }
+        Ok(Some(
+            (0..values.len())
+                .filter_map(|i| values.get(i).ok())
+                .map(|v| crate::execution::format_any_value(&v))
+                .collect(),
+        ))
+    })
+}
+
+// ─── Failure Diagnosis ───────────────────────────────────────────────────────
+
+/// The pipeline step a failing query traces back to.
+#[derive(Debug, Clone)]
+pub struct StepFailure {
+    /// Position in the pipeline (0-based).
+    pub index: usize,
+    /// The step as shown in the pipeline list.
+    pub step: String,
+    /// Column the step reads that the error is about, if one can be named.
+    pub column: Option<String>,
+    /// First line of the Polars error, plus the count of offending values when known.
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for StepFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Step {} ({}) failed: {}",
+            self.index + 1,
+            self.step,
+            self.message
+        )
+    }
+}
+
+/// Find the first step whose pipeline prefix fails to run (planning errors
+/// such as a missing column first, then errors raised while collecting `n`
+/// rows), and explain it in terms of that step. `None` if the source itself
+/// fails or every prefix runs.
+pub fn diagnose_failure(
+    source: &DataSource,
+    operations: &[Operation],
+    n: u32,
+) -> Option<StepFailure> {
+    guarded(|| {
+        let mut prefixes = vec![source.scan().map_err(|e| anyhow::anyhow!("{}", e))?];
+        for (i, op) in operations.iter().enumerate() {
+            let before = prefixes[i].clone();
+            let planned = apply_operation(before.clone(), op)
+                .and_then(|mut lf| lf.collect_schema().map(|_| lf).map_err(Into::into));
+            match planned {
+                Ok(lf) => prefixes.push(lf),
+                Err(e) => return Ok(Some(explain_failure(before, i, op, &e.to_string()))),
+            }
+        }
+
+        // Every step plans: walk the prefixes for the first one that fails to
+        // collect. Not a bisection: a later Filter, Limit or Skip can drop the
+        // rows a failing step chokes on, so failures are not monotonic.
+        let fails = |k: usize| prefixes[k].clone().limit(n).collect_with_engine(engine()).err();
+        if fails(operations.len()).is_none() || fails(0).is_some() {
+            return Ok(None);
+        }
+        for index in 0..operations.len() {
+            if let Some(error) = fails(index + 1) {
+                return Ok(Some(explain_failure(
+                    prefixes[index].clone(),
+                    index,
+                    &operations[index],
+                    &error.to_string(),
+                )));
+            }
+        }
+        Ok(None)
+    })
+    .ok()
+    .flatten()
+}
+
+/// Describe `op` failing with `error` on top of the frame `before` it.
+fn explain_failure(
+    mut before: LazyFrame,
+    index: usize,
+    op: &Operation,
+    error: &str,
+) -> StepFailure {
+    let schema = before.collect_schema().ok();
+    let available: Vec<String> = schema
+        .iter()
+        .flat_map(|s| s.iter_names().map(|name| name.to_string()))
+        .collect();
+    let reads: Vec<String> = op
+        .clone()
+        .column_refs_mut()
+        .into_iter()
+        .map(|c| c.clone())
+        .collect();
+    let missing = reads.iter().find(|c| !available.contains(c)).cloned();
+    let column = missing.clone().or_else(|| reads.first().cloned());
+    let dtype = column
+        .as_deref()
+        .and_then(|c| schema.as_ref()?.get(c).cloned());
+
+    let mut message = error.lines().next().unwrap_or(error).trim().to_string();
+    if let (Operation::CastColumn { column, dtype, .. }, Some(failed)) =
+        (op, count_cast_failures(&before, op))
+    {
+        message = format!(
+            "conversion error on {} value{} of {} to {} ({})",
+            failed,
+            if failed == 1 { "" } else { "s" },
+            column,
+            dtype,
+            message
+        );
+    }
+
+    let lower = message.to_lowercase();
+    let suggestion = if let Some(missing) = &missing {
+        Some(match crate::mapping::suggest_column(missing, &available) {
+            Some(close) => format!(
+                "Column '{}' does not exist at this step. Did you mean '{}'?",
+                missing, close
+            ),
+            None => format!(
+                "Column '{}' does not exist at this step; an earlier step may have \
+                 dropped or renamed it",
+                missing
+            ),
+        })
+    } else if let (Operation::CastColumn { .. }, Some(_)) = (op, &dtype) {
+        Some(
+            "Set a quarantine column on the cast to keep the failing values, \
+             or clean them with a filter first"
+                .to_string(),
+        )
+    } else if let (Operation::Filter { column, value, .. }, Some(dtype)) = (op, &dtype) {
+        (lower.contains("compare") || lower.contains("cannot") || lower.contains("cast")).then(
+            || {
+                format!(
+                    "'{}' is {}; check that the value '{}' is of that type, \
+                     or cast the column first",
+                    column, dtype, value
+                )
+            },
+        )
+    } else {
+        dtype.map(|dtype| {
+            format!(
+                "'{}' is {} at this step; check that the operation supports that type",
+                column.clone().unwrap_or_default(),
+                dtype
+            )
+        })
+    };
+
+    StepFailure {
+        index,
+        step: op.to_string(),
+        column,
+        message,
+        suggestion,
+    }
+}
+
+/// Values of the column a cast reads that are not null but would not convert.
+fn count_cast_failures(before: &LazyFrame, op: &Operation) -> Option<usize> {
+    let Operation::CastColumn { column, dtype, .. } = op else {
+        return None;
+    };
+    let c = col(column.as_str());
+    let failed = c
+        .clone()
+        .is_not_null()
+        .and(c.cast(dtype.to_polars()).is_null())
+        .sum();
+    let df = before.clone().select([failed]).collect_with_engine(engine()).ok()?;
+    let count = df
+        .get_columns()
+        .first()
+        .and_then(|c| c.get(0).ok())
+        .and_then(|v| v.extract::<u64>())? as usize;
+    (count > 0).then_some(count)
+}
+
+// ─── Cast Check ──────────────────────────────────────────────────────────────
+//
+// A numeric cast to a narrower type nulls out values it cannot hold and drops
+// fractional parts when going from float to integer, without failing. The check
+// scans the column before the cast is applied so the user sees what would be
+// lost.
+
+/// Values a numeric cast would lose or change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastRisk {
+    pub target: DTypeTag,
+    /// Non-null values outside the target's range (or NaN/infinite); the cast
+    /// turns them into nulls.
+    pub out_of_range: usize,
+    /// Values with a fractional part, truncated by a float → integer cast.
+    pub truncated: usize,
+    /// Smallest and largest value of the column.
+    pub min: String,
+    pub max: String,
+}
+
+impl std::fmt::Display for CastRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if self.out_of_range > 0 {
+            parts.push(format!(
+                "{} values outside the {} range become null (min {}, max {})",
+                self.out_of_range, self.target, self.min, self.max
+            ));
+        }
+        if self.truncated > 0 {
+            parts.push(format!("{} values lose their fractional part", self.truncated));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+/// Range of values a numeric target type holds, `None` for other targets.
+fn numeric_range(target: &DTypeTag) -> Option<(f64, f64)> {
+    match target {
+        DTypeTag::Int32 => Some((i32::MIN as f64, i32::MAX as f64)),
+        DTypeTag::Int64 => Some((i64::MIN as f64, i64::MAX as f64)),
+        DTypeTag::UInt32 => Some((0.0, u32::MAX as f64)),
+        DTypeTag::UInt64 => Some((0.0, u64::MAX as f64)),
+        DTypeTag::Float32 => Some((f32::MIN as f64, f32::MAX as f64)),
+        _ => None,
+    }
+}
+
+/// Scan `column` of the pipeline result for values a cast to `target` would
+/// null out or truncate. `None` when the column is not numeric, the target is
+/// not a narrower numeric type, or nothing would be lost.
+pub fn check_cast(
+    source: &DataSource,
+    operations: &[Operation],
+    column: &str,
+    target: &DTypeTag,
+) -> Result<Option<CastRisk>> {
+    guarded(|| {
+        let Some((lo, hi)) = numeric_range(target) else {
+            return Ok(None);
+        };
+        let mut lf = build_lazy(source, operations)?;
+        let schema = lf.collect_schema().map_err(|e| anyhow::anyhow!("{}", e))?;
+        let Some(dtype) = schema.get(column) else {
+            return Ok(None);
+        };
+        if !(dtype.is_integer() || dtype.is_float()) || *dtype == target.to_polars() {
+            return Ok(None);
+        }
+        let integer_target = !matches!(target, DTypeTag::Float32);
+        // Float targets keep NaN/infinite values, integer targets cannot
+        let x = col(column).cast(DataType::Float64);
+        let in_range = x.clone().gt_eq(lit(lo)).and(x.clone().lt_eq(lit(hi)));
+        let in_range = if integer_target {
+            in_range
+        } else {
+            in_range.or(x.clone().is_finite().not())
+        };
+        let out_of_range = x.clone().is_not_null().and(in_range.clone().not()).sum();
+        let truncated = if integer_target && dtype.is_float() {
+            in_range.and(x.clone().neq(x.floor())).sum()
+        } else {
+            lit(0u32)
+        };
+        let df = lf
+            .select([
+                out_of_range.alias("out_of_range"),
+                truncated.alias("truncated"),
+                col(column).min().alias("min"),
+                col(column).max().alias("max"),
+            ])
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let count = |name: &str| {
+            df.column(name)
+                .ok()
+                .and_then(|c| c.get(0).ok())
+                .and_then(|v| v.extract::<u64>())
+                .unwrap_or(0) as usize
+        };
+        let value = |name: &str| {
+            df.column(name)
+                .ok()
+                .and_then(|c| c.get(0).ok())
+                .map(|v| crate::execution::format_any_value(&v))
+                .unwrap_or_default()
+        };
+        let risk = CastRisk {
+            target: target.clone(),
+            out_of_range: count("out_of_range"),
+            truncated: count("truncated"),
+            min: value("min"),
+            max: value("max"),
+        };
+        Ok((risk.out_of_range > 0 || risk.truncated > 0).then_some(risk))
+    })
+}
+
+// ─── Dry Run ─────────────────────────────────────────────────────────────────
+
+/// Rows in the pipeline result with `extra` steps appended (e.g. a filter not
+/// yet applied). Only the count is collected, so projections and predicates
+/// are pushed down as in any other query.
+pub fn count_rows(
+    source: &DataSource,
+    operations: &[Operation],
+    extra: &[Operation],
+) -> Result<usize> {
+    guarded(|| {
+        let lf = extra
+            .iter()
+            .try_fold(build_lazy(source, operations)?, apply_operation)?;
+        let counted = lf
+            .select([len()])
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(counted
+            .get_columns()
+            .first()
+            .and_then(|c| c.get(0).ok())
+            .and_then(|v| v.extract::<u64>())
+            .unwrap_or(0) as usize)
+    })
+}
+
+// ─── Quick Calc ──────────────────────────────────────────────────────────────
+
+/// Aggregate functions accepted by `quick_calc`.
+pub const QUICK_CALC_FUNCTIONS: &[&str] = &[
+    "mean",
+    "sum",
+    "min",
+    "max",
+    "median",
+    "std",
+    "count",
+    "n_unique",
+    "null_count",
+];
+
+/// Evaluate a one-off aggregate such as `mean(temp) where site == 'A' and temp > 0`
+/// on top of the pipeline (lazily; nothing is added to the pipeline).
+///
+/// Conditions use the filter operators `==`/`=`, `!=`, `>`, `>=`, `<`, `<=`
+/// and `contains`, joined with `and`. `count(*)` counts rows.
+pub fn quick_calc(source: &DataSource, operations: &[Operation], query: &str) -> Result<String> {
+    guarded(|| {
+        let (agg, filters) = parse_quick_calc(query)?;
+        let mut lf = build_lazy(source, operations)?;
+        for filter in &filters {
+            lf = apply_operation(lf, filter)?;
+        }
+        let df = lf
+            .select([agg])
+            .collect_with_engine(engine())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let value = df
+            .get_columns()
+            .first()
+            .and_then(|c| c.get(0).ok())
+            .map(|v| match v {
+                AnyValue::Null => "null".to_string(),
+                v => v.to_string(),
+            })
+            .unwrap_or_default();
+        Ok(value)
+    })
+}
+
+/// Split a quick-calc query into its aggregate expression and `Filter` operations.
+fn parse_quick_calc(query: &str) -> Result<(Expr, Vec<Operation>)> {
+    let (head, conditions) = match find_keyword(query, " where ") {
+        Some(pos) => (&query[..pos], Some(&query[pos + 7..])),
+        None => (query, None),
+    };
+
+    let head = head.trim();
+    let (func, column) = head
+        .strip_suffix(')')
+        .and_then(|h| h.split_once('('))
+        .ok_or_else(|| anyhow::anyhow!("Expected func(column), e.g. mean(temp)"))?;
+    let func = func.trim().to_lowercase();
+    let column = unquote(column.trim());
+    let c = col(column);
+    let agg = match func.as_str() {
+        "count" if column == "*" => len(),
+        "mean" => c.mean(),
+        "sum" => c.sum(),
+        "min" => c.min(),
+        "max" => c.max(),
+        "median" => c.median(),
+        "std" => c.std(1),
+        "count" => c.count(),
+        "n_unique" => c.n_unique(),
+        "null_count" => c.null_count(),
+        other => anyhow::bail!(
+            "Unknown function '{}' (use {})",
+            other,
+            QUICK_CALC_FUNCTIONS.join(", ")
+        ),
+    };
+
+    let mut filters = Vec::new();
+    if let Some(conditions) = conditions {
+        for condition in split_keyword(conditions, " and ") {
+            filters.push(parse_condition(condition.trim())?);
+        }
+    }
+    Ok((agg, filters))
+}
+
+/// One `column op value` condition as a `Filter` operation.
+fn parse_condition(condition: &str) -> Result<Operation> {
+    // `contains` first (its value may hold symbols), then two-character
+    // operators so `>=` is not read as `>`
+    const OPERATORS: &[(&str, FilterOp)] = &[
+        (" contains ", FilterOp::Contains),
+        ("==", FilterOp::Eq),
+        ("!=", FilterOp::Neq),
+        (">=", FilterOp::Gte),
+        ("<=", FilterOp::Lte),
+        ("=", FilterOp::Eq),
+        (">", FilterOp::Gt),
+        ("<", FilterOp::Lt),
+    ];
+    for (token, op) in OPERATORS {
+        if let Some(pos) = find_keyword(condition, token) {
+            let column = unquote(condition[..pos].trim());
+            let value = unquote(condition[pos + token.len()..].trim());
+            return Ok(Operation::Filter {
+                column: column.to_string(),
+                op: op.clone(),
+                value: value.to_string(),
+                options: FilterOptions::default(),
+            });
+        }
+    }
+    anyhow::bail!("Cannot parse condition '{}'", condition)
+}
+
+/// Filter typed in a table's quick-filter cell: an optional leading operator
+/// (`>5`, `<=2024-01-01`, `!=x`); without one, text columns match by
+/// case-insensitive `contains` and other columns by `=`.
+pub fn quick_filter(column: &str, text: &str, is_text: bool) -> Operation {
+    const PREFIXES: &[(&str, FilterOp)] = &[
+        (">=", FilterOp::Gte),
+        ("<=", FilterOp::Lte),
+        ("!=", FilterOp::Neq),
+        ("==", FilterOp::Eq),
+        ("=", FilterOp::Eq),
+        (">", FilterOp::Gt),
+        ("<", FilterOp::Lt),
+    ];
+    let text = text.trim();
+    let (op, value) = PREFIXES
+        .iter()
+        .find_map(|(prefix, op)| Some((op.clone(), text.strip_prefix(prefix)?.trim())))
+        .unwrap_or(if is_text {
+            (FilterOp::Contains, text)
+        } else {
+            (FilterOp::Eq, text)
+        });
+    Operation::Filter {
+        column: column.to_string(),
+        op,
+        value: unquote(value).to_string(),
+        options: FilterOptions {
+            case_insensitive: is_text,
+            ..FilterOptions::default()
+        },
+    }
+}
+
+/// Split on a keyword, ignoring case (e.g. `AND` / `and`) and quoted spans.
+fn split_keyword<'a>(text: &'a str, keyword: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while let Some(pos) = find_keyword(&text[start..], keyword) {
+        parts.push(&text[start..start + pos]);
+        start += pos + keyword.len();
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Byte offset of the first `keyword` (lowercase) in `text` outside quotes or
+/// backticks, ignoring case, so `'Smith and Sons'` is not split.
+fn find_keyword(text: &str, keyword: &str) -> Option<usize> {
+    let lower = text.to_ascii_lowercase();
+    let mut quote = None;
+    for (i, c) in lower.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if matches!(c, '\'' | '"' | '`') => quote = Some(c),
+            None if lower[i..].starts_with(keyword) => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+/// Strip one pair of surrounding quotes or backticks.
+fn unquote(text: &str) -> &str {
+    for q in ['\'', '"', '`'] {
+        if let Some(inner) = text.strip_prefix(q).and_then(|t| t.strip_suffix(q)) {
+            return inner;
+        }
+    }
+    text
 }
 
 // ─── Operation Application ───────────────────────────────────────────────────
@@ -52,15 +995,27 @@ fn apply_operation(lf: LazyFrame, op: &Operation) -> Result<LazyFrame> {
             column,
             op: filter_op,
             value,
+            options,
         } => {
-            let expr = build_filter_expr(column, filter_op, value);
+            // Column type decides how the value is parsed (Date/Datetime literals)
+            let dtype = lf
+                .clone()
+                .collect_schema()
+                .ok()
+                .and_then(|schema| schema.get(column.as_str()).cloned());
+            let expr = build_filter_expr(column, filter_op, value, dtype.as_ref(), options);
             Ok(lf.filter(expr))
         }
 
-        Operation::Sort { column, descending } => Ok(lf.sort(
+        Operation::Sort {
+            column,
+            descending,
+            stable,
+        } => Ok(lf.sort(
             [column.as_str()],
             SortMultipleOptions {
                 descending: vec![*descending],
+                maintain_order: *stable,
                 ..Default::default()
             },
         )),
@@ -91,10 +1046,16 @@ fn apply_operation(lf: LazyFrame, op: &Operation) -> Result<LazyFrame> {
 
         Operation::Limit(n) => Ok(lf.limit(*n)),
 
+        Operation::Skip(n) => Ok(lf.slice(*n as i64, IdxSize::MAX)),
+
+        // A negative offset counts from the end
+        Operation::Tail(n) => Ok(lf.slice(-(*n as i64), *n)),
+
         Operation::FillNull {
             column,
             strategy,
             value,
+            over,
         } => {
             let fill_expr = match strategy {
                 FillNullStrategy::Forward => {
@@ -117,12 +1078,28 @@ fn apply_operation(lf: LazyFrame, op: &Operation) -> Result<LazyFrame> {
                 FillNullStrategy::Min => col(column.as_str()).fill_null(col(column.as_str()).min()),
                 FillNullStrategy::Max => col(column.as_str()).fill_null(col(column.as_str()).max()),
             };
-            Ok(lf.with_columns([fill_expr]))
+            Ok(lf.with_columns([over_groups(fill_expr, over)]))
         }
 
-        Operation::CastColumn { column, dtype } => {
+        Operation::CastColumn {
+            column,
+            dtype,
+            quarantine,
+        } => {
             let target = dtype.to_polars();
-            Ok(lf.with_columns([col(column.as_str()).cast(target)]))
+            let c = col(column.as_str());
+            let cast = c.clone().cast(target);
+            match quarantine {
+                // Both expressions read the original column (with_columns is parallel)
+                Some(quarantine) => Ok(lf.with_columns([
+                    when(c.clone().is_not_null().and(cast.clone().is_null()))
+                        .then(c.cast(DataType::String))
+                        .otherwise(lit(NULL).cast(DataType::String))
+                        .alias(quarantine.as_str()),
+                    cast,
+                ])),
+                None => Ok(lf.with_columns([cast])),
+            }
         }
 
         Operation::ParseDatetime { column, format } => {
@@ -141,13 +1118,120 @@ fn apply_operation(lf: LazyFrame, op: &Operation) -> Result<LazyFrame> {
                 lit("null"),
             )]))
         }
+
+        Operation::JsonExtract {
+            column,
+            json_path,
+            output,
+            dtype,
+        } => {
+            // Rows that are not valid JSON or lack the path become null;
+            // non-strict cast so unconvertible values are null too.
+            let extracted = col(column.as_str())
+                .str()
+                .json_path_match(lit(json_path.clone()))
+                .cast(dtype.to_polars())
+                .alias(output.as_str());
+            Ok(lf.with_columns([extracted]))
+        }
+
+        Operation::Round {
+            column,
+            decimals,
+            mode,
+        } => {
+            let c = col(column.as_str());
+            let rounded = match mode {
+                RoundMode::HalfToEven => c.round(*decimals, polars::prelude::RoundMode::HalfToEven),
+                RoundMode::HalfAwayFromZero => {
+                    c.round(*decimals, polars::prelude::RoundMode::HalfAwayFromZero)
+                }
+                // Floor/ceil at a decimal place: scale, round, scale back
+                RoundMode::Floor | RoundMode::Ceil => {
+                    let scale = lit(10f64.powi(*decimals as i32));
+                    let scaled = c.cast(DataType::Float64) * scale.clone();
+                    let whole = if *mode == RoundMode::Floor {
+                        scaled.floor()
+                    } else {
+                        scaled.ceil()
+                    };
+                    whole / scale
+                }
+            };
+            Ok(lf.with_columns([rounded]))
+        }
+
+        Operation::Normalize {
+            column,
+            method,
+            output,
+        } => {
+            // Aggregates are lazy expressions over the whole column
+            let c = col(column.as_str()).cast(DataType::Float64);
+            let (centered, spread) = match method {
+                NormalizeMethod::ZScore => (c.clone() - c.clone().mean(), c.std(1)),
+                NormalizeMethod::MinMax => (c.clone() - c.clone().min(), c.clone().max() - c.min()),
+                NormalizeMethod::Robust => {
+                    let q = |p: f64| c.clone().quantile(lit(p), QuantileMethod::Linear);
+                    (c.clone() - c.clone().median(), q(0.75) - q(0.25))
+                }
+            };
+            // A constant column has no spread: null rather than NaN or ±inf
+            let scaled = when(spread.clone().eq(lit(0.0)))
+                .then(lit(NULL))
+                .otherwise(centered / spread);
+            Ok(lf.with_columns([scaled.alias(output.as_str())]))
+        }
+
+        Operation::Window {
+            column,
+            function,
+            over,
+            output,
+        } => {
+            let c = col(column.as_str());
+            let rank = |descending| {
+                c.clone().rank(
+                    RankOptions {
+                        method: RankMethod::Min,
+                        descending,
+                    },
+                    None,
+                )
+            };
+            let expr = match function {
+                WindowFunction::CumSum => c.cum_sum(false),
+                WindowFunction::CumMin => c.cum_min(false),
+                WindowFunction::CumMax => c.cum_max(false),
+                WindowFunction::Diff => c.clone() - c.shift(lit(1)),
+                WindowFunction::Rank => rank(false),
+                WindowFunction::RankDescending => rank(true),
+            };
+            Ok(lf.with_columns([over_groups(expr, over).alias(output.as_str())]))
+        }
+
+        Operation::Coalesce { columns, output } => {
+            if columns.is_empty() {
+                return Err(anyhow::anyhow!("Coalesce needs at least one column"));
+            }
+            let exprs: Vec<Expr> = columns.iter().map(|c| col(c.as_str())).collect();
+            Ok(lf.with_columns([coalesce(&exprs).alias(output.as_str())]))
+        }
+
+        Operation::Macro { operations, .. } => operations.iter().try_fold(lf, apply_operation),
     }
 }
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
 /// Build a Polars filter expression from a column name, operator, and value string.
-fn build_filter_expr(column: &str, op: &FilterOp, value: &str) -> Expr {
+fn build_filter_expr(
+    column: &str,
+    op: &FilterOp,
+    value: &str,
+    dtype: Option<&DataType>,
+    options: &FilterOptions,
+) -> Expr {
     let c = col(column);
 
     match op {
@@ -156,20 +1240,98 @@ fn build_filter_expr(column: &str, op: &FilterOp, value: &str) -> Expr {
         _ => {}
     }
 
-    let lit_val = parse_literal(value);
+    // Right-hand side: another column, or a typed literal
+    // (datetime for temporal columns, else int/float/bool/string)
+    let rhs = |value: &str| {
+        if options.value_is_column {
+            col(value)
+        } else {
+            dtype
+                .and_then(|dtype| temporal_literal(value, dtype))
+                .unwrap_or_else(|| parse_literal(value))
+        }
+    };
+
+    if let FilterOp::Between {
+        low,
+        high,
+        inclusive,
+    } = op
+    {
+        return if *inclusive {
+            c.clone().gt_eq(rhs(low)).and(c.lt_eq(rhs(high)))
+        } else {
+            c.clone().gt(rhs(low)).and(c.lt(rhs(high)))
+        };
+    }
+
+    // Case-insensitive: compare lowercased strings (String columns only)
+    let (c, lit_val) = if options.case_insensitive && dtype == Some(&DataType::String) {
+        let lowered = if options.value_is_column {
+            col(value).str().to_lowercase()
+        } else {
+            lit(value.to_lowercase())
+        };
+        (c.str().to_lowercase(), lowered)
+    } else {
+        (c, rhs(value))
+    };
 
     match op {
+        FilterOp::Eq if options.null_safe => c.eq_missing(lit_val),
+        FilterOp::Neq if options.null_safe => c.neq_missing(lit_val),
         FilterOp::Eq => c.eq(lit_val),
         FilterOp::Neq => c.neq(lit_val),
         FilterOp::Gt => c.gt(lit_val),
         FilterOp::Gte => c.gt_eq(lit_val),
         FilterOp::Lt => c.lt(lit_val),
         FilterOp::Lte => c.lt_eq(lit_val),
+        FilterOp::Contains if options.value_is_column => c.str().contains_literal(lit_val),
+        FilterOp::Contains if options.case_insensitive => {
+            c.str().contains(lit(format!("(?i){}", value)), false)
+        }
         FilterOp::Contains => c.str().contains(lit(value.to_string()), false),
-        FilterOp::IsNull | FilterOp::IsNotNull => unreachable!(),
+        FilterOp::IsNull | FilterOp::IsNotNull | FilterOp::Between { .. } => unreachable!(),
     }
 }
 
+/// Typed literal for comparisons against Date/Datetime columns, so ISO strings
+/// compare chronologically instead of lexically. Times are taken as UTC.
+/// Returns `None` for other column types or unparsable values.
+fn temporal_literal(value: &str, dtype: &DataType) -> Option<Expr> {
+    let datetime = parse_iso_datetime(value)?;
+    match dtype {
+        DataType::Date => {
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?;
+            let days = datetime.date().signed_duration_since(epoch).num_days();
+            Some(lit(days as i32).cast(DataType::Date))
+        }
+        DataType::Datetime(tu, tz) => {
+            let utc = datetime.and_utc();
+            let ts = match tu {
+                TimeUnit::Nanoseconds => utc.timestamp_nanos_opt()?,
+                TimeUnit::Microseconds => utc.timestamp_micros(),
+                TimeUnit::Milliseconds => utc.timestamp_millis(),
+            };
+            Some(lit(ts).cast(DataType::Datetime(*tu, tz.clone())))
+        }
+        _ => None,
+    }
+}
+
+/// Parse `YYYY-MM-DD`, `YYYY-MM-DD HH:MM[:SS]` or the `T`-separated ISO form.
+fn parse_iso_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(datetime);
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)
+}
+
 /// Parse a string value into a Polars literal expression.
 /// Tries integer → float → bool → string (in that order).
 fn parse_literal(value: &str) -> Expr {
@@ -183,3 +1345,14 @@ fn parse_literal(value: &str) -> Expr {
         lit(value.to_string())
     }
 }
+
+/// Evaluate `expr` per group of the `over` columns (a window expression);
+/// returns it unchanged when no groups are given.
+fn over_groups(expr: Expr, over: &[String]) -> Expr {
+    if over.is_empty() {
+        expr
+    } else {
+        let partition: Vec<Expr> = over.iter().map(|c| col(c.as_str())).collect();
+        expr.over(partition)
+    }
+}
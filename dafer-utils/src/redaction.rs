@@ -0,0 +1,133 @@
+//! Redaction module: reusable export profiles that drop, hash or mask columns.
+//!
+//! Profiles are kept in the app config and applied to the pipeline result at
+//! export time only, after every operation, so the pipeline itself (and the
+//! preview) is unchanged. What was applied is recorded in the export's provenance.
+
+use std::fmt;
+
+use anyhow::Result;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Characters left readable at the end of a masked value (`****1234`).
+pub const MASK_VISIBLE: usize = 4;
+
+/// What happens to a column.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RedactAction {
+    /// Remove the column from the output.
+    #[default]
+    Drop,
+    /// Replace each value with the SHA-256 (hex) of the profile salt + value.
+    /// Equal values still hash equally, so joins and counts keep working.
+    Hash,
+    /// Replace all but the last `MASK_VISIBLE` characters with `*`.
+    Mask,
+}
+
+impl RedactAction {
+    pub fn all() -> [RedactAction; 3] {
+        [RedactAction::Drop, RedactAction::Hash, RedactAction::Mask]
+    }
+}
+
+impl fmt::Display for RedactAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedactAction::Drop => write!(f, "Drop"),
+            RedactAction::Hash => write!(f, "Hash"),
+            RedactAction::Mask => write!(f, "Mask"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RedactionRule {
+    pub column: String,
+    pub action: RedactAction,
+}
+
+/// A named, reusable set of rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RedactionProfile {
+    pub name: String,
+    pub rules: Vec<RedactionRule>,
+    /// Prepended to values before hashing, so short values (IDs, postcodes)
+    /// cannot be recovered by hashing every candidate.
+    #[serde(default)]
+    pub salt: String,
+}
+
+/// Record of a profile applied to one export, stored in its provenance.
+/// The salt is deliberately left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionAudit {
+    pub profile: String,
+    /// Rules that matched a column of the result.
+    pub applied: Vec<RedactionRule>,
+    /// Rule columns not present in the result (nothing to redact).
+    pub missing_columns: Vec<String>,
+}
+
+impl RedactionProfile {
+    /// Apply the rules to an export frame.
+    pub fn apply(&self, mut df: DataFrame) -> Result<(DataFrame, RedactionAudit)> {
+        let mut audit = RedactionAudit {
+            profile: self.name.clone(),
+            applied: Vec::new(),
+            missing_columns: Vec::new(),
+        };
+        for rule in &self.rules {
+            let Some(col) = df.column(&rule.column).ok().cloned() else {
+                audit.missing_columns.push(rule.column.clone());
+                continue;
+            };
+            match rule.action {
+                RedactAction::Drop => {
+                    df = df
+                        .drop(&rule.column)
+                        .map_err(|e| anyhow::anyhow!("{}", e))?;
+                }
+                RedactAction::Hash | RedactAction::Mask => {
+                    let strings = col
+                        .as_materialized_series()
+                        .cast(&DataType::String)
+                        .map_err(|e| anyhow::anyhow!("{}", e))?;
+                    let values: Vec<Option<String>> = strings
+                        .str()
+                        .map_err(|e| anyhow::anyhow!("{}", e))?
+                        .into_iter()
+                        .map(|v| {
+                            v.map(|s| match rule.action {
+                                RedactAction::Hash => self.hash(s),
+                                _ => mask(s),
+                            })
+                        })
+                        .collect();
+                    df.with_column(Column::new(rule.column.as_str().into(), values))
+                        .map_err(|e| anyhow::anyhow!("{}", e))?;
+                }
+            }
+            audit.applied.push(rule.clone());
+        }
+        Ok((df, audit))
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update(value.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+fn mask(value: &str) -> String {
+    let n = value.chars().count();
+    if n <= MASK_VISIBLE {
+        return "*".repeat(n);
+    }
+    let visible: String = value.chars().skip(n - MASK_VISIBLE).collect();
+    format!("{}{}", "*".repeat(n - MASK_VISIBLE), visible)
+}
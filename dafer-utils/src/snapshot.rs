@@ -0,0 +1,246 @@
+//! Snapshot module: the collected result of a pipeline kept under a name, so
+//! the live result can be compared with it while the pipeline is edited.
+//!
+//! A result snapshot is held in memory, or spilled to a temporary Parquet file
+//! for large results (removed when the snapshot is dropped). Comparing reports
+//! the row counts, added and removed columns, changed column statistics and the
+//! first differing cells.
+
+use anyhow::Result;
+use chrono::Local;
+use polars::prelude::*;
+
+use crate::data_loader::{self, ColumnStats};
+use crate::datasource::DataSource;
+use crate::execution::format_any_value;
+use crate::operations::Operation;
+use crate::query_engine;
+
+/// Rows compared cell by cell (by position) for the sample differences.
+pub const SAMPLE_ROWS: usize = 200;
+
+/// Cell differences listed before the rest are summarized.
+const MAX_CELL_DIFFERENCES: usize = 20;
+
+/// Where the snapshot's rows are kept.
+#[derive(Debug)]
+enum Storage {
+    Memory(DataFrame),
+    #[cfg(feature = "parquet")]
+    Parquet(std::path::PathBuf),
+}
+
+/// Fully collected pipeline result under a name.
+#[derive(Debug)]
+pub struct ResultSnapshot {
+    pub name: String,
+    /// Pipeline that produced the result.
+    pub operations: Vec<Operation>,
+    pub row_count: usize,
+    pub stats: Vec<ColumnStats>,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`.
+    pub taken_at: String,
+    storage: Storage,
+}
+
+impl ResultSnapshot {
+    /// Run the whole pipeline and keep its result. With `spill`, the rows are
+    /// written to a temporary Parquet file instead of held in memory.
+    pub fn take(
+        name: &str,
+        source: &DataSource,
+        operations: &[Operation],
+        spill: bool,
+    ) -> Result<Self> {
+        let df = query_engine::execute(source, operations)?;
+        let stats = data_loader::column_stats(&df);
+        let row_count = df.height();
+        let storage = if spill {
+            spill_to_parquet(df)?
+        } else {
+            Storage::Memory(df)
+        };
+        Ok(Self {
+            name: name.to_string(),
+            operations: operations.to_vec(),
+            row_count,
+            stats,
+            taken_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            storage,
+        })
+    }
+
+    /// True if the rows live in a temporary Parquet file.
+    pub fn is_spilled(&self) -> bool {
+        !matches!(self.storage, Storage::Memory(_))
+    }
+
+    /// The snapshot's rows (read back from disk when spilled).
+    pub fn frame(&self) -> Result<DataFrame> {
+        match &self.storage {
+            Storage::Memory(df) => Ok(df.clone()),
+            #[cfg(feature = "parquet")]
+            Storage::Parquet(path) => data_loader::scan_parquet(&path.to_string_lossy())
+                .and_then(|lf| lf.collect())
+                .map_err(|e| anyhow::anyhow!("{}", e)),
+        }
+    }
+
+    /// Compare the snapshot (before) with `current` (after).
+    pub fn compare(&self, current: &DataFrame) -> Result<SnapshotDiff> {
+        let before = self.frame()?;
+        let before_columns = column_names(&before);
+        let after_columns = column_names(current);
+
+        let after_stats = data_loader::column_stats(current);
+        let mut stat_changes = Vec::new();
+        for old in &self.stats {
+            let Some(new) = after_stats.iter().find(|s| s.name == old.name) else {
+                continue;
+            };
+            let mut change = |stat: &'static str, before: String, after: String| {
+                if before != after {
+                    stat_changes.push(StatChange {
+                        column: old.name.clone(),
+                        stat,
+                        before,
+                        after,
+                    });
+                }
+            };
+            change("type", old.dtype.clone(), new.dtype.clone());
+            change("nulls", old.null_count.to_string(), new.null_count.to_string());
+            change("min", optional(&old.min), optional(&new.min));
+            change("max", optional(&old.max), optional(&new.max));
+            change(
+                "distinct",
+                optional(&old.distinct.map(|d| d.to_string())),
+                optional(&new.distinct.map(|d| d.to_string())),
+            );
+        }
+
+        // First rows by position, common columns by name
+        let rows_compared = before.height().min(current.height()).min(SAMPLE_ROWS);
+        let mut cell_differences = Vec::new();
+        let mut total = 0;
+        for name in before_columns.iter().filter(|c| after_columns.contains(c)) {
+            let (Ok(old), Ok(new)) = (before.column(name), current.column(name)) else {
+                continue;
+            };
+            for i in 0..rows_compared {
+                let cell = |c: &Column| c.get(i).map(|v| format_any_value(&v)).unwrap_or_default();
+                let (was, is) = (cell(old), cell(new));
+                if was != is {
+                    total += 1;
+                    if total <= MAX_CELL_DIFFERENCES {
+                        cell_differences.push(format!(
+                            "Row {}, {}: {:?} → {:?}",
+                            i + 1,
+                            name,
+                            was,
+                            is
+                        ));
+                    }
+                }
+            }
+        }
+        if total > MAX_CELL_DIFFERENCES {
+            cell_differences.push(format!(
+                "... and {} more cell differences",
+                total - MAX_CELL_DIFFERENCES
+            ));
+        }
+
+        Ok(SnapshotDiff {
+            rows: (before.height(), current.height()),
+            added_columns: after_columns
+                .iter()
+                .filter(|c| !before_columns.contains(c))
+                .cloned()
+                .collect(),
+            removed_columns: before_columns
+                .iter()
+                .filter(|c| !after_columns.contains(c))
+                .cloned()
+                .collect(),
+            stat_changes,
+            rows_compared,
+            cell_differences,
+        })
+    }
+}
+
+impl Drop for ResultSnapshot {
+    fn drop(&mut self) {
+        #[cfg(feature = "parquet")]
+        if let Storage::Parquet(path) = &self.storage {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Differences between a snapshot and the live pipeline result.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    /// (snapshot, current) row counts.
+    pub rows: (usize, usize),
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    /// Statistics that changed in columns present in both.
+    pub stat_changes: Vec<StatChange>,
+    /// Leading rows compared cell by cell (at most `SAMPLE_ROWS`).
+    pub rows_compared: usize,
+    pub cell_differences: Vec<String>,
+}
+
+impl SnapshotDiff {
+    pub fn is_identical(&self) -> bool {
+        self.rows.0 == self.rows.1
+            && self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.stat_changes.is_empty()
+            && self.cell_differences.is_empty()
+    }
+}
+
+/// One column statistic that differs (e.g. nulls 12 → 0).
+#[derive(Debug, Clone)]
+pub struct StatChange {
+    pub column: String,
+    pub stat: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+#[cfg(feature = "parquet")]
+fn spill_to_parquet(mut df: DataFrame) -> Result<Storage> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let path = std::env::temp_dir().join(format!(
+        "dafer-snapshot-{}-{}.parquet",
+        std::process::id(),
+        nanos
+    ));
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .finish(&mut df)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(Storage::Parquet(path))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn spill_to_parquet(_df: DataFrame) -> Result<Storage> {
+    anyhow::bail!("Spilling snapshots to disk needs the parquet feature")
+}
+
+fn column_names(df: &DataFrame) -> Vec<String> {
+    df.get_column_names()
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn optional(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| "—".to_string())
+}
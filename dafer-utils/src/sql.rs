@@ -0,0 +1,648 @@
+//! SQL module: the pipeline as a SQL `SELECT` (or dbt model) for a warehouse.
+//!
+//! Each operation becomes one CTE reading the previous one. Columns are
+//! tracked here (starting from the source schema) so drops, renames and new
+//! columns can be written out explicitly. Row order is not a property of SQL
+//! tables, so the last Sort is carried along: order-dependent steps (Limit,
+//! Skip, cumulative windows, Diff) use it, and the final SELECT applies it. A
+//! Limit or Skip with no Sort before it is commented as non-deterministic.
+//! Steps without a translation are skipped, commented in the SQL and listed
+//! in `SqlModel::unsupported`.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::datasource::{DataSource, DataSourceType};
+use crate::operations::{
+    DTypeTag, FillNullStrategy, FilterOp, FilterOptions, LiteralKind, NormalizeMethod, Operation,
+    RoundMode, WindowFunction,
+};
+
+/// Target SQL dialect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+    #[default]
+    DuckDb,
+    Postgres,
+}
+
+impl SqlDialect {
+    pub fn all() -> [SqlDialect; 2] {
+        [SqlDialect::DuckDb, SqlDialect::Postgres]
+    }
+}
+
+impl fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SqlDialect::DuckDb => write!(f, "DuckDB"),
+            SqlDialect::Postgres => write!(f, "PostgreSQL"),
+        }
+    }
+}
+
+/// Generated SQL plus the steps that could not be translated.
+#[derive(Debug, Clone, Default)]
+pub struct SqlModel {
+    pub sql: String,
+    /// (1-based step number, reason).
+    pub unsupported: Vec<(usize, String)>,
+}
+
+/// Quote an identifier (`"name"`).
+pub fn ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quote a string literal (`'text'`).
+pub fn literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// A plain decimal number (`-12`, `3.5`, `1e-3`): safe to write unquoted.
+/// Excludes what Rust parses as a float but SQL reads as an identifier
+/// (`inf`, `nan`) or not at all (`.5`, `+1`).
+fn is_plain_number(value: &str) -> bool {
+    let digits = |d: &str| !d.is_empty() && d.bytes().all(|b| b.is_ascii_digit());
+    let unsigned = value.strip_prefix('-').unwrap_or(value);
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((m, e)) => (m, Some(e)),
+        None => (unsigned, None),
+    };
+    let mantissa_ok = match mantissa.split_once('.') {
+        Some((int, frac)) => digits(int) && digits(frac),
+        None => digits(mantissa),
+    };
+    mantissa_ok && exponent.is_none_or(|e| digits(e.strip_prefix(['+', '-']).unwrap_or(e)))
+}
+
+/// Table name for a source: its file stem.
+pub fn table_name(source: &DataSource) -> String {
+    source
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "source".to_string())
+}
+
+/// `FROM` reading the source file directly with DuckDB's table functions.
+pub fn duckdb_reader(source: &DataSource) -> String {
+    let path = literal(&source.path.display().to_string());
+    match source.source_type {
+        DataSourceType::Csv => format!(
+            "read_csv({}, delim = {}, header = {}, skip = {})",
+            path,
+            literal(&source.csv.delimiter),
+            source.csv.has_header,
+            source.csv.skip_rows
+        ),
+        DataSourceType::Parquet => format!("read_parquet({})", path),
+    }
+}
+
+/// Translate `operations` applied to `from` (a table name or table function,
+/// used verbatim) whose columns are `source_columns` (name, dtype as Polars
+/// displays it).
+pub fn to_sql(
+    operations: &[Operation],
+    source_columns: &[(String, String)],
+    from: &str,
+    dialect: SqlDialect,
+) -> SqlModel {
+    let mut builder = Builder {
+        dialect,
+        columns: source_columns.iter().map(|(name, _)| name.clone()).collect(),
+        numeric: source_columns
+            .iter()
+            .filter(|(_, dtype)| {
+                matches!(
+                    LiteralKind::of_dtype(dtype),
+                    LiteralKind::Integer { .. } | LiteralKind::Float
+                )
+            })
+            .map(|(name, _)| name.clone())
+            .collect(),
+        order: None,
+        ctes: vec![format!("source AS (\n    SELECT * FROM {}\n)", from)],
+        unsupported: Vec::new(),
+        step: 0,
+    };
+    for op in operations {
+        builder.operation(op);
+    }
+
+    let last = builder.previous();
+    let mut sql = String::new();
+    for (step, reason) in &builder.unsupported {
+        sql.push_str(&format!("-- step {} not translated: {}\n", step, reason));
+    }
+    sql.push_str("WITH ");
+    sql.push_str(&builder.ctes.join(",\n"));
+    sql.push_str(&format!("\nSELECT * FROM {}", last));
+    if let Some(order) = builder.order_clause() {
+        sql.push_str(&format!("\n{}", order));
+    }
+    sql.push('\n');
+    SqlModel {
+        sql,
+        unsupported: builder.unsupported,
+    }
+}
+
+/// The same SQL as a dbt model reading `{{ source(source_name, table) }}`.
+pub fn to_dbt_model(
+    operations: &[Operation],
+    source_columns: &[(String, String)],
+    source_name: &str,
+    table: &str,
+    dialect: SqlDialect,
+) -> SqlModel {
+    let from = format!(
+        "{{{{ source({}, {}) }}}}",
+        literal(source_name),
+        literal(table)
+    );
+    let mut model = to_sql(operations, source_columns, &from, dialect);
+    model.sql = format!(
+        "-- Generated by dafer-utils {}\n{{{{ config(materialized='table') }}}}\n\n{}",
+        env!("CARGO_PKG_VERSION"),
+        model.sql
+    );
+    model
+}
+
+struct Builder {
+    dialect: SqlDialect,
+    columns: Vec<String>,
+    /// Columns known to be numeric, whose values are compared unquoted.
+    numeric: HashSet<String>,
+    /// Column and descending flag of the last Sort.
+    order: Option<(String, bool)>,
+    ctes: Vec<String>,
+    unsupported: Vec<(usize, String)>,
+    /// 1-based number of the operation being translated.
+    step: usize,
+}
+
+impl Builder {
+    fn previous(&self) -> String {
+        match self.ctes.len() {
+            1 => "source".to_string(),
+            n => format!("step_{}", n - 1),
+        }
+    }
+
+    fn push(&mut self, body: String) {
+        let name = format!("step_{}", self.ctes.len());
+        self.ctes.push(format!("{} AS (\n    {}\n)", name, body));
+    }
+
+    fn skip(&mut self, reason: impl Into<String>) {
+        self.unsupported.push((self.step, reason.into()));
+    }
+
+    fn order_clause(&self) -> Option<String> {
+        let (column, descending) = self.order.as_ref()?;
+        Some(format!(
+            "ORDER BY {}{}",
+            ident(column),
+            if *descending { " DESC" } else { "" }
+        ))
+    }
+
+    /// `SELECT` of the previous step cut by `clause` (`LIMIT n`, `OFFSET n`)
+    /// in the order of the carried Sort. Without one the database may keep
+    /// any rows, which the SQL says in a comment.
+    fn slice(&mut self, clause: String) {
+        let body = match self.order_clause() {
+            Some(order) => format!("SELECT * FROM {} {} {}", self.previous(), order, clause),
+            None => format!(
+                "-- no Sort before this step: which rows are kept is not deterministic\n    \
+                 SELECT * FROM {} {}",
+                self.previous(),
+                clause
+            ),
+        };
+        self.push(body);
+    }
+
+    /// `SELECT` of all current columns with `name` computed by `expr`
+    /// (replacing the column, or appended if it is new).
+    fn select_with(&mut self, name: &str, expr: String) {
+        let mut items: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| {
+                if c == name {
+                    format!("{} AS {}", expr, ident(c))
+                } else {
+                    ident(c)
+                }
+            })
+            .collect();
+        if !self.columns.iter().any(|c| c == name) {
+            items.push(format!("{} AS {}", expr, ident(name)));
+            self.columns.push(name.to_string());
+        }
+        let body = format!("SELECT {} FROM {}", items.join(", "), self.previous());
+        self.push(body);
+    }
+
+    fn select_columns(&mut self, items: Vec<String>) {
+        let body = format!("SELECT {} FROM {}", items.join(", "), self.previous());
+        self.push(body);
+    }
+
+    /// `OVER (PARTITION BY <over> <order>)`.
+    fn window(over: &[String], order: Option<String>) -> String {
+        let mut parts = Vec::new();
+        if !over.is_empty() {
+            let groups: Vec<String> = over.iter().map(|c| ident(c)).collect();
+            parts.push(format!("PARTITION BY {}", groups.join(", ")));
+        }
+        parts.extend(order);
+        format!("OVER ({})", parts.join(" "))
+    }
+
+    fn operation(&mut self, op: &Operation) {
+        if let Operation::Macro { operations, .. } = op {
+            for inner in operations {
+                self.operation(inner);
+            }
+            return;
+        }
+        self.step += 1;
+        match op {
+            Operation::Filter {
+                column,
+                op,
+                value,
+                options,
+            } => {
+                let condition = self.filter_condition(column, op, value, options);
+                let body = format!("SELECT * FROM {} WHERE {}", self.previous(), condition);
+                self.push(body);
+            }
+            Operation::Sort {
+                column, descending, ..
+            } => {
+                self.order = Some((column.clone(), *descending));
+            }
+            Operation::DropColumn(name) => {
+                self.columns.retain(|c| c != name);
+                self.forget_order(name);
+                let items = self.columns.iter().map(|c| ident(c)).collect();
+                self.select_columns(items);
+            }
+            Operation::RenameColumn { from, to } => {
+                let items = self
+                    .columns
+                    .iter()
+                    .map(|c| {
+                        if c == from {
+                            format!("{} AS {}", ident(c), ident(to))
+                        } else {
+                            ident(c)
+                        }
+                    })
+                    .collect();
+                self.select_columns(items);
+                for c in &mut self.columns {
+                    if c == from {
+                        *c = to.clone();
+                    }
+                }
+                if self.numeric.remove(from) {
+                    self.numeric.insert(to.clone());
+                }
+                if let Some((order, _)) = self.order.as_mut().filter(|(c, _)| c == from) {
+                    *order = to.clone();
+                }
+            }
+            Operation::SelectColumns(names) => {
+                self.columns = names.clone();
+                if let Some((order, _)) = self.order.clone() {
+                    self.forget_order(&order);
+                }
+                let items = names.iter().map(|c| ident(c)).collect();
+                self.select_columns(items);
+            }
+            Operation::Limit(n) => self.slice(format!("LIMIT {}", n)),
+            Operation::Skip(n) => self.slice(format!("OFFSET {}", n)),
+            Operation::Tail(_) => self.skip("tail (needs the row count of the previous step)"),
+            Operation::FillNull {
+                column,
+                strategy,
+                value,
+                over,
+            } => {
+                let c = ident(column);
+                let fill = match strategy {
+                    FillNullStrategy::WithValue => {
+                        self.literal_for(column, value.as_deref().unwrap_or_default())
+                    }
+                    FillNullStrategy::Mean => format!("AVG({}) {}", c, Self::window(over, None)),
+                    FillNullStrategy::Min => format!("MIN({}) {}", c, Self::window(over, None)),
+                    FillNullStrategy::Max => format!("MAX({}) {}", c, Self::window(over, None)),
+                    FillNullStrategy::Forward | FillNullStrategy::Backward => {
+                        self.skip(format!("{} (needs IGNORE NULLS over row order)", strategy));
+                        return;
+                    }
+                };
+                self.select_with(column, format!("COALESCE({}, {})", c, fill));
+            }
+            Operation::CastColumn {
+                column,
+                dtype,
+                quarantine,
+            } => {
+                if quarantine.is_some() {
+                    self.skip("cast with a quarantine column");
+                    return;
+                }
+                let Some(sql_type) = sql_type(dtype) else {
+                    self.skip(format!("cast to {}", dtype));
+                    return;
+                };
+                // Polars casts non-strictly (failures become null)
+                let cast = match self.dialect {
+                    SqlDialect::DuckDb => "TRY_CAST",
+                    SqlDialect::Postgres => "CAST",
+                };
+                self.select_with(
+                    column,
+                    format!("{}({} AS {})", cast, ident(column), sql_type),
+                );
+                if matches!(
+                    dtype,
+                    DTypeTag::Int32 | DTypeTag::Int64 | DTypeTag::Float32 | DTypeTag::Float64
+                ) {
+                    self.numeric.insert(column.clone());
+                } else {
+                    self.numeric.remove(column);
+                }
+            }
+            Operation::ParseDatetime { column, format } => match self.dialect {
+                SqlDialect::DuckDb => self.select_with(
+                    column,
+                    format!("try_strptime({}, {})", ident(column), literal(format)),
+                ),
+                SqlDialect::Postgres => {
+                    self.skip("parse datetime (strftime formats have no PostgreSQL equivalent)")
+                }
+            },
+            Operation::JsonExtract {
+                column,
+                json_path,
+                output,
+                dtype,
+            } => {
+                let extracted = match self.dialect {
+                    SqlDialect::DuckDb => {
+                        format!(
+                            "json_extract_string({}, {})",
+                            ident(column),
+                            literal(json_path)
+                        )
+                    }
+                    SqlDialect::Postgres => format!(
+                        "jsonb_path_query_first({}::jsonb, {}) #>> '{{}}'",
+                        ident(column),
+                        literal(json_path)
+                    ),
+                };
+                let expr = match sql_type(dtype) {
+                    Some(t) if *dtype != DTypeTag::Utf8String => {
+                        format!("CAST({} AS {})", extracted, t)
+                    }
+                    _ => extracted,
+                };
+                self.select_with(output, expr);
+            }
+            Operation::Round {
+                column,
+                decimals,
+                mode,
+            } => {
+                let c = ident(column);
+                let scale = format!("1e{}", decimals);
+                let expr = match (mode, self.dialect) {
+                    (RoundMode::HalfAwayFromZero, _) => format!("ROUND({}, {})", c, decimals),
+                    (RoundMode::HalfToEven, SqlDialect::DuckDb) => {
+                        format!("ROUND_EVEN({}, {})", c, decimals)
+                    }
+                    (RoundMode::HalfToEven, SqlDialect::Postgres) => {
+                        self.skip("round half to even");
+                        return;
+                    }
+                    (RoundMode::Floor, _) => format!("FLOOR({} * {}) / {}", c, scale, scale),
+                    (RoundMode::Ceil, _) => format!("CEIL({} * {}) / {}", c, scale, scale),
+                };
+                self.select_with(column, expr);
+            }
+            Operation::Normalize {
+                column,
+                method,
+                output,
+            } => {
+                let c = ident(column);
+                let expr = match (method, self.dialect) {
+                    // NULLIF: a constant column gives null, as in Polars, not an error
+                    (NormalizeMethod::ZScore, _) => format!(
+                        "({c} - AVG({c}) OVER ()) / NULLIF(STDDEV_SAMP({c}) OVER (), 0)",
+                        c = c
+                    ),
+                    (NormalizeMethod::MinMax, _) => format!(
+                        "({c} - MIN({c}) OVER ()) / NULLIF(MAX({c}) OVER () - MIN({c}) OVER (), 0)",
+                        c = c
+                    ),
+                    (NormalizeMethod::Robust, SqlDialect::DuckDb) => format!(
+                        "({c} - MEDIAN({c}) OVER ()) / NULLIF(\
+                         QUANTILE_CONT({c}, 0.75) OVER () - QUANTILE_CONT({c}, 0.25) OVER (), 0)",
+                        c = c
+                    ),
+                    (NormalizeMethod::Robust, SqlDialect::Postgres) => {
+                        self.skip("robust normalize (no windowed percentiles)");
+                        return;
+                    }
+                };
+                self.select_with(output, expr);
+            }
+            Operation::Window {
+                column,
+                function,
+                over,
+                output,
+            } => {
+                let c = ident(column);
+                let needs_order = !matches!(
+                    function,
+                    WindowFunction::Rank | WindowFunction::RankDescending
+                );
+                if needs_order && self.order.is_none() {
+                    self.skip(format!(
+                        "{} (row order is undefined; add a Sort first)",
+                        function
+                    ));
+                    return;
+                }
+                // Cumulative frames end at the current row (not its peers)
+                let running = self
+                    .order_clause()
+                    .map(|o| format!("{} ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW", o));
+                let expr = match function {
+                    WindowFunction::CumSum => format!("SUM({}) {}", c, Self::window(over, running)),
+                    WindowFunction::CumMin => format!("MIN({}) {}", c, Self::window(over, running)),
+                    WindowFunction::CumMax => format!("MAX({}) {}", c, Self::window(over, running)),
+                    WindowFunction::Diff => format!(
+                        "{} - LAG({}) {}",
+                        c,
+                        c,
+                        Self::window(over, self.order_clause())
+                    ),
+                    WindowFunction::Rank => {
+                        format!(
+                            "RANK() {}",
+                            Self::window(over, Some(format!("ORDER BY {}", c)))
+                        )
+                    }
+                    WindowFunction::RankDescending => format!(
+                        "RANK() {}",
+                        Self::window(over, Some(format!("ORDER BY {} DESC", c)))
+                    ),
+                };
+                self.select_with(output, expr);
+            }
+            Operation::Coalesce { columns, output } => {
+                let args: Vec<String> = columns.iter().map(|c| ident(c)).collect();
+                self.select_with(output, format!("COALESCE({})", args.join(", ")));
+            }
+            Operation::Macro { .. } => unreachable!("expanded above"),
+        }
+    }
+
+    /// Drop the carried sort if its column is gone.
+    fn forget_order(&mut self, removed: &str) {
+        let gone = self
+            .order
+            .as_ref()
+            .is_some_and(|(c, _)| c == removed || !self.columns.contains(c));
+        if gone {
+            self.order = None;
+            self.skip(format!(
+                "sort on {} (column removed before the final SELECT)",
+                removed
+            ));
+        }
+    }
+
+    fn filter_condition(
+        &self,
+        column: &str,
+        op: &FilterOp,
+        value: &str,
+        options: &FilterOptions,
+    ) -> String {
+        let rhs = |v: &str| {
+            if options.value_is_column {
+                ident(v)
+            } else {
+                self.literal_for(column, v)
+            }
+        };
+        let (lhs, value_sql) = if options.case_insensitive && !options.value_is_column {
+            (
+                format!("LOWER({})", ident(column)),
+                format!("LOWER({})", literal(value)),
+            )
+        } else {
+            (ident(column), rhs(value))
+        };
+        match op {
+            FilterOp::Eq if options.null_safe => {
+                format!("{} IS NOT DISTINCT FROM {}", lhs, value_sql)
+            }
+            FilterOp::Neq if options.null_safe => format!("{} IS DISTINCT FROM {}", lhs, value_sql),
+            FilterOp::Eq => format!("{} = {}", lhs, value_sql),
+            FilterOp::Neq => format!("{} <> {}", lhs, value_sql),
+            FilterOp::Gt => format!("{} > {}", ident(column), rhs(value)),
+            FilterOp::Gte => format!("{} >= {}", ident(column), rhs(value)),
+            FilterOp::Lt => format!("{} < {}", ident(column), rhs(value)),
+            FilterOp::Lte => format!("{} <= {}", ident(column), rhs(value)),
+            FilterOp::Contains if options.value_is_column => {
+                let like = if options.case_insensitive {
+                    "ILIKE"
+                } else {
+                    "LIKE"
+                };
+                format!("{} {} '%' || {} || '%'", ident(column), like, ident(value))
+            }
+            FilterOp::Contains => self.contains_condition(column, value, options.case_insensitive),
+            FilterOp::IsNull => format!("{} IS NULL", ident(column)),
+            FilterOp::IsNotNull => format!("{} IS NOT NULL", ident(column)),
+            FilterOp::Between {
+                low,
+                high,
+                inclusive,
+            } => {
+                if *inclusive {
+                    format!("{} BETWEEN {} AND {}", ident(column), rhs(low), rhs(high))
+                } else {
+                    format!(
+                        "{c} > {} AND {c} < {}",
+                        rhs(low),
+                        rhs(high),
+                        c = ident(column)
+                    )
+                }
+            }
+        }
+    }
+
+    /// `value` as a literal for `column`: bare if the column is numeric and the
+    /// value a plain number, quoted otherwise (so text columns keep `007`).
+    fn literal_for(&self, column: &str, value: &str) -> String {
+        if self.numeric.contains(column) && is_plain_number(value.trim()) {
+            value.trim().to_string()
+        } else {
+            literal(value)
+        }
+    }
+
+    /// `column` contains `pattern`, which Polars reads as a regex: `LIKE` when
+    /// it is plain text, a regex match otherwise. A leading `(?i)` ignores case
+    /// like the case-insensitive option (`ILIKE`, `~*`).
+    fn contains_condition(&self, column: &str, pattern: &str, case_insensitive: bool) -> String {
+        let (pattern, case_insensitive) = match pattern.strip_prefix("(?i)") {
+            Some(rest) => (rest, true),
+            None => (pattern, case_insensitive),
+        };
+        let c = ident(column);
+        if pattern.contains(|ch: char| ".^$*+?()[]{}|\\".contains(ch)) {
+            let p = literal(pattern);
+            return match (self.dialect, case_insensitive) {
+                (SqlDialect::DuckDb, false) => format!("regexp_matches({}, {})", c, p),
+                (SqlDialect::DuckDb, true) => format!("regexp_matches({}, {}, 'i')", c, p),
+                (SqlDialect::Postgres, false) => format!("{} ~ {}", c, p),
+                (SqlDialect::Postgres, true) => format!("{} ~* {}", c, p),
+            };
+        }
+        let like = if case_insensitive { "ILIKE" } else { "LIKE" };
+        let escaped = pattern.replace('%', "\\%").replace('_', "\\_");
+        format!("{} {} {} ESCAPE '\\'", c, like, literal(&format!("%{}%", escaped)))
+    }
+}
+
+/// SQL type for a cast target (`None` if there is no portable one).
+fn sql_type(dtype: &DTypeTag) -> Option<&'static str> {
+    match dtype {
+        DTypeTag::Int32 => Some("INTEGER"),
+        DTypeTag::Int64 => Some("BIGINT"),
+        DTypeTag::Float32 => Some("REAL"),
+        DTypeTag::Float64 => Some("DOUBLE PRECISION"),
+        DTypeTag::Utf8String => Some("VARCHAR"),
+        DTypeTag::Boolean => Some("BOOLEAN"),
+        DTypeTag::Date => Some("DATE"),
+        DTypeTag::UInt32 | DTypeTag::UInt64 | DTypeTag::Binary => None,
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::Read;
+
+use dafer_utils::bundle::{self, BundleOptions};
+use dafer_utils::datasource::DataSource;
+use dafer_utils::operations::Operation;
+use dafer_utils::persistence::PersistentState;
+
+#[test]
+fn bundle_is_a_readable_zip() {
+    let dir = std::env::temp_dir().join(format!("dafer-utils-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv = dir.join("input.csv");
+    std::fs::write(&csv, "id,name\n1,<a>\n2,b\n3,c\n").unwrap();
+    let state = PersistentState {
+        source: DataSource::from_path(csv),
+        operations: vec![Operation::Limit(2)],
+        ..PersistentState::default()
+    };
+    let out = dir.join("bundle.zip");
+    let options = BundleOptions {
+        sample_rows: Some(10),
+    };
+    let names = bundle::export_bundle(&state, &out, &options).expect("bundle exports");
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&out).unwrap()).unwrap();
+    let mut entries: Vec<String> = archive.file_names().map(String::from).collect();
+    entries.sort();
+    let mut expected = names.clone();
+    expected.sort();
+    assert_eq!(entries, expected);
+    assert!(names.contains(&"input_sample.csv".to_string()));
+
+    let mut report = String::new();
+    archive
+        .by_name("report.html")
+        .unwrap()
+        .read_to_string(&mut report)
+        .unwrap();
+    assert!(report.contains("&lt;a&gt;"), "cells are escaped: {}", report);
+
+    let mut pipeline = String::new();
+    archive
+        .by_name("pipeline.dfr")
+        .unwrap()
+        .read_to_string(&mut pipeline)
+        .unwrap();
+    let reloaded = PersistentState::decode(pipeline.as_bytes()).expect("state file loads");
+    assert!(matches!(reloaded.operations[..], [Operation::Limit(2)]));
+
+    assert!(!dir.join("bundle.zip.partial").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
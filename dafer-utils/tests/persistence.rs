@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use dafer_utils::datasource::DataSourceType;
+use dafer_utils::operations::{DTypeTag, FillNullStrategy, FilterOp, Operation};
+use dafer_utils::persistence::{self, PersistentState, SNIPPET_PREFIX};
+
+/// State file in the original bincode layout (source + five operations).
+fn baseline_state() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/baseline_state.dfr")
+}
+
+fn assert_baseline(state: &PersistentState) {
+    let source = state.source.as_ref().expect("baseline state has a source");
+    assert_eq!(source.path, Path::new("data/Iris.csv"));
+    assert_eq!(source.source_type, DataSourceType::Csv);
+    assert_eq!(source.auto_numeric_cols, ["SepalWidthCm"]);
+
+    let ops = &state.operations;
+    assert_eq!(ops.len(), 5);
+    assert!(matches!(
+        &ops[0],
+        Operation::Filter { column, op: FilterOp::Gt, value, options }
+            if column == "SepalLengthCm" && value == "5.0" && !options.case_insensitive
+    ));
+    assert!(matches!(
+        &ops[1],
+        Operation::Sort { column, descending: true, stable: false } if column == "Species"
+    ));
+    assert!(matches!(
+        &ops[2],
+        Operation::FillNull { column, strategy: FillNullStrategy::WithValue, value: Some(v), over }
+            if column == "PetalWidthCm" && v == "0" && over.is_empty()
+    ));
+    assert!(matches!(
+        &ops[3],
+        Operation::CastColumn { column, dtype: DTypeTag::Int32, quarantine: None } if column == "Id"
+    ));
+    assert!(matches!(ops[4], Operation::Limit(10)));
+}
+
+#[test]
+fn loads_baseline_bincode_state() {
+    let state = PersistentState::load(&baseline_state()).expect("baseline state loads");
+    assert_baseline(&state);
+    assert!(state.expected_schema.is_empty());
+    assert!(state.fixture.is_none());
+}
+
+#[test]
+fn baseline_state_round_trips_as_json() {
+    let state = PersistentState::load(&baseline_state()).expect("baseline state loads");
+    let dir = std::env::temp_dir().join(format!("dafer-utils-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("migrated.dfr");
+    state.save(&path).expect("state saves");
+
+    let saved = std::fs::read(&path).unwrap();
+    assert_eq!(saved.first(), Some(&b'{'));
+    let reloaded = PersistentState::load(&path).expect("migrated state loads");
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_baseline(&reloaded);
+}
+
+#[test]
+fn json_state_without_newer_fields_loads() {
+    let json = r#"{"source": null, "operations": [{"Limit": 5}]}"#;
+    let state = PersistentState::decode(json.as_bytes()).expect("older JSON state loads");
+    assert!(state.source.is_none());
+    assert!(state.engine.is_none());
+    assert!(matches!(state.operations[..], [Operation::Limit(5)]));
+}
+
+#[test]
+fn base64_snippets_round_trip_with_every_padding() {
+    // Compact JSON of 13, 14 and 15 bytes: two, one and no padding characters
+    for n in [1, 10, 100] {
+        let snippet = persistence::pipeline_to_snippet(&[Operation::Limit(n)], true).unwrap();
+        let encoded = snippet.strip_prefix(SNIPPET_PREFIX).expect("prefixed");
+        assert_eq!(encoded.len() % 4, 0);
+        let ops = persistence::pipeline_from_snippet(&snippet).expect("snippet decodes");
+        assert!(matches!(ops[..], [Operation::Limit(m)] if m == n));
+    }
+}
+
+#[test]
+fn base64_snippets_ignore_wrapped_whitespace() {
+    let ops = [Operation::Limit(5), Operation::DropColumn("notes".to_string())];
+    let snippet = persistence::pipeline_to_snippet(&ops, true).unwrap();
+    let encoded = snippet.strip_prefix(SNIPPET_PREFIX).unwrap();
+    let wrapped: Vec<String> = encoded
+        .as_bytes()
+        .chunks(8)
+        .map(|c| String::from_utf8(c.to_vec()).unwrap())
+        .collect();
+    let pasted = format!("  {}{}\n", SNIPPET_PREFIX, wrapped.join("\n  "));
+    let decoded = persistence::pipeline_from_snippet(&pasted).expect("wrapped snippet decodes");
+    assert_eq!(decoded.len(), 2);
+    assert!(matches!(&decoded[1], Operation::DropColumn(c) if c == "notes"));
+}
+
+#[test]
+fn base64_snippets_reject_invalid_characters() {
+    let text = format!("{}not*base64", SNIPPET_PREFIX);
+    assert!(persistence::pipeline_from_snippet(&text).is_err());
+}
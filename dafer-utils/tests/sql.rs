@@ -0,0 +1,45 @@
+use dafer_utils::operations::{FilterOp, FilterOptions, Operation};
+use dafer_utils::sql::{self, SqlDialect};
+
+fn filter(column: &str, value: &str) -> Operation {
+    Operation::Filter {
+        column: column.to_string(),
+        op: FilterOp::Eq,
+        value: value.to_string(),
+        options: FilterOptions::default(),
+    }
+}
+
+fn translate(operations: &[Operation]) -> String {
+    let columns = [
+        ("code".to_string(), "str".to_string()),
+        ("amount".to_string(), "f64".to_string()),
+    ];
+    sql::to_sql(operations, &columns, "t", SqlDialect::Postgres).sql
+}
+
+#[test]
+fn text_columns_keep_quoted_numbers() {
+    let sql = translate(&[filter("code", "007")]);
+    assert!(sql.contains(r#""code" = '007'"#), "{}", sql);
+}
+
+#[test]
+fn numeric_columns_get_bare_numbers() {
+    let sql = translate(&[filter("amount", "-1.5e3")]);
+    assert!(sql.contains(r#""amount" = -1.5e3"#), "{}", sql);
+}
+
+#[test]
+fn special_floats_are_never_bare() {
+    for value in ["inf", "nan", "infinity", ".5"] {
+        let sql = translate(&[filter("amount", value)]);
+        assert!(sql.contains(&format!(r#""amount" = '{}'"#, value)), "{}", sql);
+    }
+}
+
+#[test]
+fn limit_without_sort_is_flagged() {
+    let sql = translate(&[Operation::Limit(10)]);
+    assert!(sql.contains("not deterministic"), "{}", sql);
+}
@@ -0,0 +1,27 @@
+#![no_main]
+
+use dafer_utils::data_loader;
+use dafer_utils::datasource::{CsvOptions, DataSource};
+use dafer_utils::query_engine;
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary file contents and import options: loading may fail, never panic.
+// Delimiters longer than one byte go through `data_loader::read_delimited`.
+fuzz_target!(|input: (String, bool, u8, Vec<u8>)| {
+    let (delimiter, has_header, skip_rows, data) = input;
+    let path = std::env::temp_dir().join(format!("dafer-fuzz-{}.csv", std::process::id()));
+    if std::fs::write(&path, &data).is_err() {
+        return;
+    }
+    let _ = data_loader::sniff_delimiter(&path);
+    let Some(mut source) = DataSource::from_path(path) else {
+        return;
+    };
+    source.csv = CsvOptions {
+        delimiter: delimiter.chars().take(3).collect(),
+        has_header,
+        skip_rows: usize::from(skip_rows % 8),
+        ..CsvOptions::default()
+    };
+    let _ = query_engine::preview(&source, &[], 100);
+});
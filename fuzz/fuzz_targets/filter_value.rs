@@ -0,0 +1,42 @@
+#![no_main]
+
+use dafer_utils::operations::{FilterOp, FilterOptions, Operation};
+use dafer_utils::query_engine;
+use libfuzzer_sys::fuzz_target;
+use polars::prelude::*;
+
+// Filter values as typed by a user (parsed as literals, dates or bounds)
+// against columns of every common type: errors are fine, panics are not.
+fuzz_target!(|input: (u8, [bool; 3], String, String)| {
+    let (op_index, [case_insensitive, null_safe, value_is_column], value, high) = input;
+    let ops = FilterOp::all();
+    let op = match ops[usize::from(op_index) % ops.len()].clone() {
+        FilterOp::Between { inclusive, .. } => FilterOp::Between {
+            low: value.clone(),
+            high,
+            inclusive,
+        },
+        op => op,
+    };
+    let Ok(df) = df!(
+        "int" => [Some(1i64), None, Some(-3)],
+        "float" => [Some(1.5f64), Some(f64::NAN), None],
+        "text" => [Some("a"), Some("Ünïcode"), None],
+        "flag" => [Some(true), None, Some(false)],
+    ) else {
+        return;
+    };
+    for column in ["int", "float", "text", "flag", "missing"] {
+        let filter = Operation::Filter {
+            column: column.to_string(),
+            op: op.clone(),
+            value: value.clone(),
+            options: FilterOptions {
+                case_insensitive,
+                null_safe,
+                value_is_column,
+            },
+        };
+        let _ = query_engine::apply_to_frame(&df, &[filter]);
+    }
+});
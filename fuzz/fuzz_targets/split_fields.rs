@@ -0,0 +1,11 @@
+#![no_main]
+
+use dafer_utils::data_loader::split_fields;
+use libfuzzer_sys::fuzz_target;
+
+// Any line and delimiter, including multi-byte characters and unbalanced quotes.
+fuzz_target!(|input: (String, String)| {
+    let (line, delimiter) = input;
+    let fields = split_fields(&line, &delimiter);
+    assert!(!fields.is_empty());
+});